@@ -3,3 +3,9 @@ pub mod uniswap_v2_library_tests;
 
 #[cfg(test)]
 pub mod uniswap_v2_library_instance;
+
+#[cfg(test)]
+pub mod uniswap_v2_math_vectors;
+
+#[cfg(test)]
+pub mod uniswap_v2_library_abi;
@@ -0,0 +1,22 @@
+use std::{env, fs, path::PathBuf};
+
+use uniswap_v2_library::{abi, config::error::ErrorCode, entry_points};
+
+/// Checked-in ABI, relative to this crate. Regenerate it with `make abi`.
+const ABI_FILE: &str = "../abi/uniswap-v2-library.json";
+
+#[test]
+fn test_abi_is_up_to_date() {
+    let generated = abi::to_json("uniswap-v2-library", &entry_points::abi(), ErrorCode::ABI);
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(ABI_FILE);
+    if env::var_os("UPDATE_ABI").is_some() {
+        fs::write(&path, &generated).unwrap();
+        return;
+    }
+    let checked_in = fs::read_to_string(&path).unwrap_or_default();
+    assert!(
+        generated == checked_in,
+        "{} is out of date, run `make abi` to regenerate it",
+        path.display()
+    );
+}
@@ -159,6 +159,89 @@ impl LibraryInstance {
         );
     }
 
+    pub fn try_get_amounts_out(
+        &self,
+        sender: AccountHash,
+        factory: Key,
+        amount_in: U256,
+        path: Vec<Key>,
+    ) {
+        self.0.call_contract(
+            sender,
+            "try_get_amounts_out",
+            runtime_args! {
+                "factory" => factory,
+                "amount_in" => amount_in,
+                "path" => path
+            },
+            0
+        );
+    }
+
+    pub fn try_get_amounts_in(
+        &self,
+        sender: AccountHash,
+        factory: Key,
+        amount_out: U256,
+        path: Vec<Key>,
+    ) {
+        self.0.call_contract(
+            sender,
+            "try_get_amounts_in",
+            runtime_args! {
+                "factory" => factory,
+                "amount_out" => amount_out,
+                "path" => path
+            },
+            0
+        );
+    }
+
+    pub fn all_pairs_length(&self, sender: AccountHash, factory: Key) {
+        self.0.call_contract(
+            sender,
+            "all_pairs_length",
+            runtime_args! {
+                "factory" => factory
+            },
+            0
+        );
+    }
+
+    pub fn all_pairs(&self, sender: AccountHash, factory: Key, offset: u64, limit: u64) {
+        self.0.call_contract(
+            sender,
+            "all_pairs",
+            runtime_args! {
+                "factory" => factory,
+                "offset" => offset,
+                "limit" => limit
+            },
+            0
+        );
+    }
+
+    pub fn pairs_for_token(
+        &self,
+        sender: AccountHash,
+        factory: Key,
+        token: Key,
+        offset: u64,
+        limit: u64,
+    ) {
+        self.0.call_contract(
+            sender,
+            "pairs_for_token",
+            runtime_args! {
+                "factory" => factory,
+                "token" => token,
+                "offset" => offset,
+                "limit" => limit
+            },
+            0
+        );
+    }
+
     pub fn add_liquidity(
         &self,
         sender: AccountHash,
@@ -184,7 +267,8 @@ impl LibraryInstance {
                 "amount_b_min" => amount_b_min,
                 "to" => to,
                 "deadline" => deadline,
-                "pair" => pair
+                "pair" => pair,
+                "min_liquidity_out" => None::<U256>
             },
             0
         );
@@ -224,6 +308,42 @@ impl LibraryInstance {
     pub fn package_hash_result(&self) -> ContractPackageHash {
         self.0.query_named_key("package_hash".to_string())
     }
+
+    pub fn quote_result(&self) -> U256 {
+        self.0.query_named_key("quote_result".to_string())
+    }
+
+    pub fn get_amount_out_result(&self) -> U256 {
+        self.0.query_named_key("get_amount_out_result".to_string())
+    }
+
+    pub fn get_amount_in_result(&self) -> U256 {
+        self.0.query_named_key("get_amount_in_result".to_string())
+    }
+
+    pub fn add_liquidity_result(&self) -> (U256, U256, U256) {
+        self.0.query_named_key("add_liquidity_result".to_string())
+    }
+
+    pub fn try_get_amounts_out_result(&self) -> Result<Vec<U256>, (u32, u32)> {
+        self.0.query_named_key("try_get_amounts_out_result".to_string())
+    }
+
+    pub fn try_get_amounts_in_result(&self) -> Result<Vec<U256>, (u32, u32)> {
+        self.0.query_named_key("try_get_amounts_in_result".to_string())
+    }
+
+    pub fn all_pairs_length_result(&self) -> u64 {
+        self.0.query_named_key("all_pairs_length_result".to_string())
+    }
+
+    pub fn all_pairs_result(&self) -> Vec<(Key, Key, Key)> {
+        self.0.query_named_key("all_pairs_result".to_string())
+    }
+
+    pub fn pairs_for_token_result(&self) -> Vec<(Key, Key, Key)> {
+        self.0.query_named_key("pairs_for_token_result".to_string())
+    }
 }
 
 pub fn key_to_str(key: &Key) -> String {
@@ -6,8 +6,9 @@ use std::time::{SystemTime, UNIX_EPOCH};
 use casperlabs_test_env::{TestContract, TestEnv};
 
 use crate::uniswap_v2_library_instance::LibraryInstance;
+use uniswap_v2_library::{config::error::ErrorCode, math};
 
-fn deploy_dummy_tokens(
+pub fn deploy_dummy_tokens(
     env: &TestEnv,
     owner: Option<AccountHash>,
 ) -> (TestContract, TestContract, TestContract) {
@@ -74,7 +75,7 @@ fn deploy_dummy_tokens(
     (token1_contract, token2_contract, token3_contract)
 }
 
-fn deploy_library() -> (
+pub fn deploy_library() -> (
     TestEnv,
     AccountHash,
     LibraryInstance,
@@ -205,6 +206,14 @@ fn deploy_library() -> (
         0
     );
 
+    // allow the router to register the pair with the factory
+    router_contract.call_contract(
+        owner,
+        "set_pair_allowed",
+        runtime_args! {"pair" => Key::Hash(pair_contract.package_hash()), "allowed" => true},
+        0
+    );
+
     (
         env,
         owner,
@@ -456,3 +465,147 @@ fn test_uniswap_get_amounts_in() {
 
     test_contract.get_amounts_in(owner, Key::Hash(factory.package_hash()), 100.into(), path);
 }
+
+#[test]
+fn test_uniswap_try_get_amounts() {
+    let (env, owner, test_contract, _, factory, pair, _router_contract) = deploy_library();
+    let (token1, token2, token3) = deploy_dummy_tokens(&env, Some(owner));
+
+    let token_a = Key::Hash(token1.package_hash());
+    let token_b = Key::Hash(token2.package_hash());
+    let token_c = Key::Hash(token3.package_hash());
+    let factory = Key::Hash(factory.package_hash());
+
+    let deadline: u128 = match SystemTime::now().duration_since(UNIX_EPOCH) {
+        Ok(n) => n.as_millis() + (1000 * (30 * 60)), // current epoch time in milisecond + 30 minutes
+        Err(_) => 0,
+    };
+
+    // Minting to library
+    for token in [&token1, &token2] {
+        token.call_contract(
+            owner,
+            "mint",
+            runtime_args! {
+                "to" => Key::from(test_contract.package_hash_result()),
+                "amount" => U256::from("100000000000")
+            },
+            0
+        );
+    }
+
+    test_contract.add_liquidity(
+        owner,
+        token_a,
+        token_b,
+        U256::from("10000000000"),
+        U256::from("10000000000"),
+        U256::from("1000000"),
+        U256::from("1000000"),
+        token_c,
+        deadline.into(),
+        Some(Key::Hash(pair.package_hash())),
+    );
+    let reserves = [(U256::from("10000000000"), U256::from("10000000000"))];
+
+    test_contract.try_get_amounts_out(owner, factory, 100.into(), vec![token_a, token_b]);
+    assert_eq!(
+        test_contract.try_get_amounts_out_result(),
+        Ok(math::get_amounts_out(100.into(), &reserves).unwrap())
+    );
+
+    // token_b and token_c have no pair, which is the second one on the path
+    test_contract.try_get_amounts_out(owner, factory, 100.into(), vec![token_a, token_b, token_c]);
+    assert_eq!(
+        test_contract.try_get_amounts_out_result(),
+        Err((1, ErrorCode::InsufficientLiquidity as u32))
+    );
+
+    test_contract.try_get_amounts_out(owner, factory, 0.into(), vec![token_a, token_b]);
+    assert_eq!(
+        test_contract.try_get_amounts_out_result(),
+        Err((0, ErrorCode::InsufficientInputAmount as u32))
+    );
+
+    // amounts in are worked out from the end of the path, the pair of token_c fails last
+    test_contract.try_get_amounts_in(owner, factory, 100.into(), vec![token_c, token_a, token_b]);
+    assert_eq!(
+        test_contract.try_get_amounts_in_result(),
+        Err((0, ErrorCode::InsufficientLiquidity as u32))
+    );
+
+    test_contract.try_get_amounts_in(owner, factory, 100.into(), vec![token_a]);
+    assert_eq!(
+        test_contract.try_get_amounts_in_result(),
+        Err((0, ErrorCode::InvalidPath as u32))
+    );
+}
+
+#[test]
+fn test_uniswap_enumerate_pairs() {
+    let (env, owner, test_contract, _, factory, pair, _router_contract) = deploy_library();
+    let (token1, token2, token3) = deploy_dummy_tokens(&env, Some(owner));
+
+    let token_a = Key::Hash(token1.package_hash());
+    let token_b = Key::Hash(token2.package_hash());
+    let token_c = Key::Hash(token3.package_hash());
+    let factory = Key::Hash(factory.package_hash());
+
+    let deadline: u128 = match SystemTime::now().duration_since(UNIX_EPOCH) {
+        Ok(n) => n.as_millis() + (1000 * (30 * 60)), // current epoch time in milisecond + 30 minutes
+        Err(_) => 0,
+    };
+
+    test_contract.all_pairs_length(owner, factory);
+    assert_eq!(test_contract.all_pairs_length_result(), 0);
+
+    // Minting to library
+    for token in [&token1, &token2] {
+        token.call_contract(
+            owner,
+            "mint",
+            runtime_args! {
+                "to" => Key::from(test_contract.package_hash_result()),
+                "amount" => U256::from("100000000000")
+            },
+            0
+        );
+    }
+
+    // the first deposit has the factory register the pair
+    test_contract.add_liquidity(
+        owner,
+        token_a,
+        token_b,
+        U256::from("10000000000"),
+        U256::from("10000000000"),
+        U256::from("1000000"),
+        U256::from("1000000"),
+        token_c,
+        deadline.into(),
+        Some(Key::Hash(pair.package_hash())),
+    );
+
+    test_contract.all_pairs_length(owner, factory);
+    assert_eq!(test_contract.all_pairs_length_result(), 1);
+
+    test_contract.all_pairs(owner, factory, 0, 10);
+    let pairs: Vec<(Key, Key, Key)> = test_contract.all_pairs_result();
+    assert_eq!(pairs.len(), 1);
+    let (pair_key, token0, token1) = pairs[0];
+    assert_eq!(pair_key, Key::Hash(pair.package_hash()));
+    let mut tokens = [token0, token1];
+    tokens.sort();
+    let mut expected = [token_a, token_b];
+    expected.sort();
+    assert_eq!(tokens, expected);
+
+    test_contract.all_pairs(owner, factory, 1, 10);
+    assert!(test_contract.all_pairs_result().is_empty());
+
+    test_contract.pairs_for_token(owner, factory, token_b, 0, 10);
+    assert_eq!(test_contract.pairs_for_token_result(), pairs);
+
+    test_contract.pairs_for_token(owner, factory, token_c, 0, 10);
+    assert!(test_contract.pairs_for_token_result().is_empty());
+}
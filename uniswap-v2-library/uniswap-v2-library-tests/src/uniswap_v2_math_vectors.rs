@@ -0,0 +1,324 @@
+//! Differential test vectors for the constant product math.
+//!
+//! The expected values in `vectors/uniswap_v2_math.json` are what the Ethereum
+//! `UniswapV2Library` / `UniswapV2Router02` contracts return for the same inputs, so these
+//! tests pin the Casper port to the reference implementation, rounding included. Reverts are
+//! pinned to the port's error codes.
+//!
+//! A few `add_liquidity` vectors record where the port's minimum amount checks differ from
+//! `UniswapV2Router02`. They pin the port's current outcome and explain the difference in their
+//! `divergence` field.
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use casper_types::{account::AccountHash, runtime_args, Key, RuntimeArgs, U128, U256};
+use casperlabs_test_env::TestContract;
+use serde::Deserialize;
+
+use crate::uniswap_v2_library_instance::LibraryInstance;
+use crate::uniswap_v2_library_tests::{deploy_dummy_tokens, deploy_library};
+
+const VECTORS: &str = include_str!("../vectors/uniswap_v2_math.json");
+
+#[derive(Deserialize)]
+struct Vectors {
+    quote: Vec<QuoteVector>,
+    get_amount_out: Vec<AmountOutVector>,
+    get_amount_in: Vec<AmountInVector>,
+    add_liquidity: Vec<AddLiquidityVector>,
+}
+
+#[derive(Deserialize)]
+struct Revert {
+    // `None` when the reference implementation reverts without a reason (SafeMath) or the port
+    // reverts outside the router, as when a deposit below `amount_a_min` mints nothing
+    code: Option<u16>,
+}
+
+#[derive(Deserialize)]
+struct QuoteVector {
+    name: String,
+    amount_a: String,
+    reserve_a: String,
+    reserve_b: String,
+    expected: Option<String>,
+    revert: Option<Revert>,
+}
+
+#[derive(Deserialize)]
+struct AmountOutVector {
+    name: String,
+    amount_in: String,
+    reserve_in: String,
+    reserve_out: String,
+    expected: Option<String>,
+    revert: Option<Revert>,
+}
+
+#[derive(Deserialize)]
+struct AmountInVector {
+    name: String,
+    amount_out: String,
+    reserve_in: String,
+    reserve_out: String,
+    expected: Option<String>,
+    revert: Option<Revert>,
+}
+
+#[derive(Deserialize)]
+struct AddLiquidityVector {
+    name: String,
+    reserve_a: String,
+    reserve_b: String,
+    amount_a_desired: String,
+    amount_b_desired: String,
+    amount_a_min: String,
+    amount_b_min: String,
+    expected: Option<(String, String)>,
+    revert: Option<Revert>,
+    // how the port's outcome differs from the reference implementation, if it does
+    #[serde(default)]
+    divergence: Option<String>,
+}
+
+fn vectors() -> Vectors {
+    serde_json::from_str(VECTORS).expect("malformed uniswap_v2_math.json")
+}
+
+fn u256(value: &str) -> U256 {
+    U256::from_dec_str(value).unwrap()
+}
+
+fn u128(value: &str) -> U128 {
+    U128::from_dec_str(value).unwrap()
+}
+
+fn deadline() -> U256 {
+    let deadline: u128 = match SystemTime::now().duration_since(UNIX_EPOCH) {
+        Ok(n) => n.as_millis() + (1000 * (30 * 60)), // current epoch time in milisecond + 30 minutes
+        Err(_) => 0,
+    };
+    deadline.into()
+}
+
+// Runs `f` and asserts it reverts, matching the user error code when the vector names one.
+fn assert_reverts<F: FnOnce()>(name: &str, revert: &Revert, f: F) {
+    let err = catch_unwind(AssertUnwindSafe(f))
+        .err()
+        .unwrap_or_else(|| panic!("vector '{}' did not revert", name));
+    if let Some(code) = revert.code {
+        let message = err
+            .downcast_ref::<String>()
+            .cloned()
+            .or_else(|| err.downcast_ref::<&str>().map(|s| s.to_string()))
+            .unwrap_or_default();
+        assert!(
+            message.contains(&format!("User({})", code)),
+            "vector '{}' expected User({}), got: {}",
+            name,
+            code,
+            message
+        );
+    }
+}
+
+fn mint(token: &TestContract, owner: AccountHash, to: Key, amount: U256) {
+    token.call_contract(
+        owner,
+        "mint",
+        runtime_args! {
+            "to" => to,
+            "amount" => amount
+        },
+        0,
+    );
+}
+
+// Deploys a fresh environment and, unless both reserves are zero, seeds the pair with them
+// through the router so that the following add_liquidity sees exactly those reserves.
+fn add_liquidity_with_reserves(vector: &AddLiquidityVector) -> (U256, U256) {
+    let (env, owner, test_contract, _, _, pair, _) = deploy_library();
+    let (token1, token2, token3) = deploy_dummy_tokens(&env, Some(owner));
+
+    let token_a = Key::Hash(token1.package_hash());
+    let token_b = Key::Hash(token2.package_hash());
+    let to = Key::Hash(token3.package_hash());
+    let pair = Some(Key::Hash(pair.package_hash()));
+    let test_contract_key = Key::from(test_contract.package_hash_result());
+
+    mint(&token1, owner, test_contract_key, u256("100000000000"));
+    mint(&token2, owner, test_contract_key, u256("100000000000"));
+
+    let (reserve_a, reserve_b) = (u256(&vector.reserve_a), u256(&vector.reserve_b));
+    if !reserve_a.is_zero() || !reserve_b.is_zero() {
+        test_contract.add_liquidity(
+            owner,
+            token_a,
+            token_b,
+            reserve_a,
+            reserve_b,
+            0.into(),
+            0.into(),
+            to,
+            deadline(),
+            pair,
+        );
+    }
+
+    test_contract.add_liquidity(
+        owner,
+        token_a,
+        token_b,
+        u256(&vector.amount_a_desired),
+        u256(&vector.amount_b_desired),
+        u256(&vector.amount_a_min),
+        u256(&vector.amount_b_min),
+        to,
+        deadline(),
+        pair,
+    );
+    let (amount_a, amount_b, _) = test_contract.add_liquidity_result();
+    (amount_a, amount_b)
+}
+
+fn library() -> (AccountHash, LibraryInstance) {
+    let (_, owner, test_contract, _, _, _, _) = deploy_library();
+    (owner, test_contract)
+}
+
+#[test]
+fn quote_vectors() {
+    let (owner, test_contract) = library();
+    for vector in vectors().quote {
+        let call = |test_contract: &LibraryInstance, owner: AccountHash| {
+            test_contract.quote(
+                owner,
+                u256(&vector.amount_a),
+                u128(&vector.reserve_a),
+                u128(&vector.reserve_b),
+            )
+        };
+        match (&vector.expected, &vector.revert) {
+            (Some(expected), None) => {
+                call(&test_contract, owner);
+                assert_eq!(
+                    test_contract.quote_result(),
+                    u256(expected),
+                    "{}",
+                    vector.name
+                );
+            }
+            (None, Some(revert)) => assert_reverts(&vector.name, revert, || {
+                let (owner, test_contract) = library();
+                call(&test_contract, owner);
+            }),
+            _ => panic!(
+                "vector '{}' needs exactly one of expected/revert",
+                vector.name
+            ),
+        }
+    }
+}
+
+#[test]
+fn get_amount_out_vectors() {
+    let (owner, test_contract) = library();
+    for vector in vectors().get_amount_out {
+        let call = |test_contract: &LibraryInstance, owner: AccountHash| {
+            test_contract.get_amount_out(
+                owner,
+                u256(&vector.amount_in),
+                u256(&vector.reserve_in),
+                u256(&vector.reserve_out),
+            )
+        };
+        match (&vector.expected, &vector.revert) {
+            (Some(expected), None) => {
+                call(&test_contract, owner);
+                assert_eq!(
+                    test_contract.get_amount_out_result(),
+                    u256(expected),
+                    "{}",
+                    vector.name
+                );
+            }
+            (None, Some(revert)) => assert_reverts(&vector.name, revert, || {
+                let (owner, test_contract) = library();
+                call(&test_contract, owner);
+            }),
+            _ => panic!(
+                "vector '{}' needs exactly one of expected/revert",
+                vector.name
+            ),
+        }
+    }
+}
+
+#[test]
+fn get_amount_in_vectors() {
+    let (owner, test_contract) = library();
+    for vector in vectors().get_amount_in {
+        let call = |test_contract: &LibraryInstance, owner: AccountHash| {
+            test_contract.get_amount_in(
+                owner,
+                u256(&vector.amount_out),
+                u256(&vector.reserve_in),
+                u256(&vector.reserve_out),
+            )
+        };
+        match (&vector.expected, &vector.revert) {
+            (Some(expected), None) => {
+                call(&test_contract, owner);
+                assert_eq!(
+                    test_contract.get_amount_in_result(),
+                    u256(expected),
+                    "{}",
+                    vector.name
+                );
+            }
+            (None, Some(revert)) => assert_reverts(&vector.name, revert, || {
+                let (owner, test_contract) = library();
+                call(&test_contract, owner);
+            }),
+            _ => panic!(
+                "vector '{}' needs exactly one of expected/revert",
+                vector.name
+            ),
+        }
+    }
+}
+
+#[test]
+fn add_liquidity_vectors() {
+    for vector in vectors().add_liquidity {
+        match (&vector.expected, &vector.revert) {
+            (Some((amount_a, amount_b)), None) => assert_eq!(
+                add_liquidity_with_reserves(&vector),
+                (u256(amount_a), u256(amount_b)),
+                "{}",
+                vector.name
+            ),
+            (None, Some(revert)) => assert_reverts(&vector.name, revert, || {
+                add_liquidity_with_reserves(&vector);
+            }),
+            _ => panic!(
+                "vector '{}' needs exactly one of expected/revert",
+                vector.name
+            ),
+        }
+    }
+}
+
+#[test]
+fn vectors_are_well_formed() {
+    let vectors = vectors();
+    assert!(!vectors.quote.is_empty());
+    assert!(!vectors.get_amount_out.is_empty());
+    assert!(!vectors.get_amount_in.is_empty());
+    assert!(!vectors.add_liquidity.is_empty());
+    for vector in vectors.add_liquidity {
+        if let Some(divergence) = vector.divergence {
+            assert!(!divergence.is_empty(), "{}", vector.name);
+        }
+    }
+}
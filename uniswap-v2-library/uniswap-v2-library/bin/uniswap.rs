@@ -3,18 +3,16 @@
 #![feature(slice_range)]
 
 extern crate alloc;
-use crate::vec::Vec;
-use alloc::{boxed::Box, collections::BTreeSet, format, vec};
+use alloc::{collections::BTreeSet, format, vec::Vec};
 use casper_contract::{
     contract_api::{runtime, storage},
     unwrap_or_revert::UnwrapOrRevert,
 };
 use casper_types::{
-    runtime_args, CLType, CLTyped, CLValue, ContractHash, ContractPackageHash, EntryPoint,
-    EntryPointAccess, EntryPointType, EntryPoints, Group, Key, Parameter, RuntimeArgs, URef, U128,
-    U256,
+    runtime_args, CLValue, ContractHash, ContractPackageHash, Key, RuntimeArgs, URef, U256,
 };
 use casperlabs_contract_utils::{ContractContext, OnChainContractStorage};
+use uniswap_v2_library::entry_points::{self, get_entry_points};
 use uniswap_v2_library::{self, UniswapV2Library};
 
 #[derive(Default)]
@@ -33,15 +31,19 @@ impl Uniswap {
 
 #[no_mangle]
 fn constructor() {
-    let contract_hash: ContractHash = runtime::get_named_arg("contract_hash");
-    let package_hash: ContractPackageHash = runtime::get_named_arg("package_hash");
+    let entry_points::constructor::Args {
+        contract_hash,
+        package_hash,
+    } = entry_points::constructor::Args::get();
     Uniswap::default().constructor(contract_hash, package_hash);
 }
 
 #[no_mangle]
 fn sort_tokens() {
-    let _token_a: Key = runtime::get_named_arg("token_a");
-    let _token_b: Key = runtime::get_named_arg("token_b");
+    let entry_points::sort_tokens::Args {
+        token_a: _token_a,
+        token_b: _token_b,
+    } = entry_points::sort_tokens::Args::get();
 
     let token_a: ContractPackageHash = _token_a.into_hash().unwrap_or_default().into();
     let token_b: ContractPackageHash = _token_b.into_hash().unwrap_or_default().into();
@@ -52,9 +54,11 @@ fn sort_tokens() {
 
 #[no_mangle]
 fn get_reserves() {
-    let _factory: Key = runtime::get_named_arg("factory");
-    let _token_a: Key = runtime::get_named_arg("token_a");
-    let _token_b: Key = runtime::get_named_arg("token_b");
+    let entry_points::get_reserves::Args {
+        factory: _factory,
+        token_a: _token_a,
+        token_b: _token_b,
+    } = entry_points::get_reserves::Args::get();
 
     let token_a: ContractPackageHash = _token_a.into_hash().unwrap_or_default().into();
     let token_b: ContractPackageHash = _token_b.into_hash().unwrap_or_default().into();
@@ -67,9 +71,11 @@ fn get_reserves() {
 #[no_mangle]
 // given some amount of an asset and pair reserves, returns an equivalent amount of the other asset
 fn quote() {
-    let amount_a: U256 = runtime::get_named_arg("amount_a");
-    let reserve_a: U128 = runtime::get_named_arg("reserve_a");
-    let reserve_b: U128 = runtime::get_named_arg("reserve_b");
+    let entry_points::quote::Args {
+        amount_a,
+        reserve_a,
+        reserve_b,
+    } = entry_points::quote::Args::get();
 
     let amount_b: U256 = Uniswap::default().quote(amount_a, reserve_a, reserve_b);
     runtime::ret(CLValue::from_t(amount_b).unwrap_or_revert())
@@ -78,9 +84,11 @@ fn quote() {
 #[no_mangle]
 // given an input amount of an asset and pair reserves, returns the maximum output amount of the other asset
 fn get_amount_out() {
-    let amount_in: U256 = runtime::get_named_arg("amount_in");
-    let reserve_in: U256 = runtime::get_named_arg("reserve_in");
-    let reserve_out: U256 = runtime::get_named_arg("reserve_out");
+    let entry_points::get_amount_out::Args {
+        amount_in,
+        reserve_in,
+        reserve_out,
+    } = entry_points::get_amount_out::Args::get();
 
     let amount_out: U256 = Uniswap::default().get_amount_out(amount_in, reserve_in, reserve_out);
     runtime::ret(CLValue::from_t(amount_out).unwrap_or_revert())
@@ -89,9 +97,11 @@ fn get_amount_out() {
 #[no_mangle]
 // given an output amount of an asset and pair reserves, returns a required input amount of the other asset
 fn get_amount_in() {
-    let amount_out: U256 = runtime::get_named_arg("amount_out");
-    let reserve_in: U256 = runtime::get_named_arg("reserve_in");
-    let reserve_out: U256 = runtime::get_named_arg("reserve_out");
+    let entry_points::get_amount_in::Args {
+        amount_out,
+        reserve_in,
+        reserve_out,
+    } = entry_points::get_amount_in::Args::get();
 
     let amount_in: U256 = Uniswap::default().get_amount_in(amount_out, reserve_in, reserve_out);
     runtime::ret(CLValue::from_t(amount_in).unwrap_or_revert())
@@ -100,9 +110,11 @@ fn get_amount_in() {
 #[no_mangle]
 // performs chained getAmountOut calculations on any number of pairs
 fn get_amounts_out() {
-    let _factory: Key = runtime::get_named_arg("factory");
-    let amount_in: U256 = runtime::get_named_arg("amount_in");
-    let _path: Vec<Key> = runtime::get_named_arg("path");
+    let entry_points::get_amounts_out::Args {
+        factory: _factory,
+        amount_in,
+        path: _path,
+    } = entry_points::get_amounts_out::Args::get();
 
     let factory: ContractPackageHash = _factory.into_hash().unwrap_or_default().into();
     let mut path: Vec<ContractPackageHash> = Vec::new();
@@ -117,9 +129,11 @@ fn get_amounts_out() {
 #[no_mangle]
 // performs chained getAmountIn calculations on any number of pairs
 fn get_amounts_in() {
-    let _factory: Key = runtime::get_named_arg("factory");
-    let amount_out: U256 = runtime::get_named_arg("amount_out");
-    let _path: Vec<Key> = runtime::get_named_arg("path");
+    let entry_points::get_amounts_in::Args {
+        factory: _factory,
+        amount_out,
+        path: _path,
+    } = entry_points::get_amounts_in::Args::get();
 
     let factory: ContractPackageHash = _factory.into_hash().unwrap_or_default().into();
     let mut path: Vec<ContractPackageHash> = Vec::new();
@@ -131,120 +145,131 @@ fn get_amounts_in() {
     runtime::ret(CLValue::from_t(amounts).unwrap_or_revert())
 }
 
+#[no_mangle]
+// performs chained getAmountOut calculations for a batch of input amounts
+fn get_amounts_out_batch() {
+    let entry_points::get_amounts_out_batch::Args {
+        factory,
+        amounts_in,
+        path,
+    } = entry_points::get_amounts_out_batch::Args::get();
+
+    let factory: ContractPackageHash = factory.into_hash().unwrap_or_default().into();
+    let path: Vec<ContractPackageHash> = path
+        .into_iter()
+        .map(|token| token.into_hash().unwrap_or_default().into())
+        .collect();
+
+    let amounts: Vec<Vec<U256>> =
+        Uniswap::default().get_amounts_out_batch(factory, amounts_in, path);
+    runtime::ret(CLValue::from_t(amounts).unwrap_or_revert())
+}
+
+#[no_mangle]
+// performs chained getAmountIn calculations for a batch of output amounts
+fn get_amounts_in_batch() {
+    let entry_points::get_amounts_in_batch::Args {
+        factory,
+        amounts_out,
+        path,
+    } = entry_points::get_amounts_in_batch::Args::get();
+
+    let factory: ContractPackageHash = factory.into_hash().unwrap_or_default().into();
+    let path: Vec<ContractPackageHash> = path
+        .into_iter()
+        .map(|token| token.into_hash().unwrap_or_default().into())
+        .collect();
+
+    let amounts: Vec<Vec<U256>> =
+        Uniswap::default().get_amounts_in_batch(factory, amounts_out, path);
+    runtime::ret(CLValue::from_t(amounts).unwrap_or_revert())
+}
+
+#[no_mangle]
+// performs chained getAmountOut calculations, reporting the pair that fails instead of reverting
+fn try_get_amounts_out() {
+    let entry_points::try_get_amounts_out::Args {
+        factory,
+        amount_in,
+        path,
+    } = entry_points::try_get_amounts_out::Args::get();
+
+    let factory: ContractPackageHash = factory.into_hash().unwrap_or_default().into();
+    let path: Vec<ContractPackageHash> = path
+        .into_iter()
+        .map(|token| token.into_hash().unwrap_or_default().into())
+        .collect();
+
+    let amounts: Result<Vec<U256>, (u32, u32)> =
+        Uniswap::default().try_get_amounts_out(factory, amount_in, path);
+    runtime::ret(CLValue::from_t(amounts).unwrap_or_revert())
+}
+
+#[no_mangle]
+// performs chained getAmountIn calculations, reporting the pair that fails instead of reverting
+fn try_get_amounts_in() {
+    let entry_points::try_get_amounts_in::Args {
+        factory,
+        amount_out,
+        path,
+    } = entry_points::try_get_amounts_in::Args::get();
+
+    let factory: ContractPackageHash = factory.into_hash().unwrap_or_default().into();
+    let path: Vec<ContractPackageHash> = path
+        .into_iter()
+        .map(|token| token.into_hash().unwrap_or_default().into())
+        .collect();
+
+    let amounts: Result<Vec<U256>, (u32, u32)> =
+        Uniswap::default().try_get_amounts_in(factory, amount_out, path);
+    runtime::ret(CLValue::from_t(amounts).unwrap_or_revert())
+}
+
 #[no_mangle]
 fn pair_for() {
-    let factory: Key = runtime::get_named_arg("factory");
-    let token_a: Key = runtime::get_named_arg("token_a");
-    let token_b: Key = runtime::get_named_arg("token_b");
+    let entry_points::pair_for::Args {
+        factory,
+        token_a,
+        token_b,
+    } = entry_points::pair_for::Args::get();
 
     let ret = Uniswap::default().pair_for(factory, token_a, token_b);
     runtime::ret(CLValue::from_t(ret).unwrap_or_revert())
 }
 
-fn get_entry_points() -> EntryPoints {
-    let mut entry_points = EntryPoints::new();
-
-    entry_points.add_entry_point(EntryPoint::new(
-        "constructor",
-        vec![
-            Parameter::new("contract_hash", ContractHash::cl_type()),
-            Parameter::new("package_hash", ContractPackageHash::cl_type()),
-        ],
-        <()>::cl_type(),
-        EntryPointAccess::Groups(vec![Group::new("constructor")]),
-        EntryPointType::Contract,
-    ));
-    entry_points.add_entry_point(EntryPoint::new(
-        "sort_tokens",
-        vec![
-            Parameter::new("token_a", Key::cl_type()),
-            Parameter::new("token_b", Key::cl_type()),
-        ],
-        CLType::Tuple2([
-            Box::new(ContractPackageHash::cl_type()),
-            Box::new(ContractPackageHash::cl_type()),
-        ]),
-        EntryPointAccess::Public,
-        EntryPointType::Contract,
-    ));
-    entry_points.add_entry_point(EntryPoint::new(
-        "get_reserves",
-        vec![
-            Parameter::new("factory", Key::cl_type()),
-            Parameter::new("token_a", Key::cl_type()),
-            Parameter::new("token_b", Key::cl_type()),
-        ],
-        CLType::Tuple2([Box::new(CLType::U128), Box::new(CLType::U128)]),
-        EntryPointAccess::Public,
-        EntryPointType::Contract,
-    ));
-    entry_points.add_entry_point(EntryPoint::new(
-        "quote",
-        vec![
-            Parameter::new("amount_a", U256::cl_type()),
-            Parameter::new("reserve_a", U128::cl_type()),
-            Parameter::new("reserve_b", U128::cl_type()),
-        ],
-        U256::cl_type(),
-        EntryPointAccess::Public,
-        EntryPointType::Contract,
-    ));
-    entry_points.add_entry_point(EntryPoint::new(
-        "get_amount_out",
-        vec![
-            Parameter::new("amount_in", U256::cl_type()),
-            Parameter::new("reserve_in", U256::cl_type()),
-            Parameter::new("reserve_out", U256::cl_type()),
-        ],
-        U256::cl_type(),
-        EntryPointAccess::Public,
-        EntryPointType::Contract,
-    ));
-    entry_points.add_entry_point(EntryPoint::new(
-        "get_amount_in",
-        vec![
-            Parameter::new("amount_out", U256::cl_type()),
-            Parameter::new("reserve_in", U256::cl_type()),
-            Parameter::new("reserve_out", U256::cl_type()),
-        ],
-        U256::cl_type(),
-        EntryPointAccess::Public,
-        EntryPointType::Contract,
-    ));
-    entry_points.add_entry_point(EntryPoint::new(
-        "get_amounts_out",
-        vec![
-            Parameter::new("factory", Key::cl_type()),
-            Parameter::new("amount_in", U256::cl_type()),
-            Parameter::new("path", CLType::List(Box::new(Key::cl_type()))),
-        ],
-        CLType::List(Box::new(U256::cl_type())),
-        EntryPointAccess::Public,
-        EntryPointType::Contract,
-    ));
-    entry_points.add_entry_point(EntryPoint::new(
-        "get_amounts_in",
-        vec![
-            Parameter::new("factory", Key::cl_type()),
-            Parameter::new("amount_out", U256::cl_type()),
-            Parameter::new("path", CLType::List(Box::new(Key::cl_type()))),
-        ],
-        CLType::List(Box::new(U256::cl_type())),
-        EntryPointAccess::Public,
-        EntryPointType::Contract,
-    ));
-    entry_points.add_entry_point(EntryPoint::new(
-        "pair_for",
-        vec![
-            Parameter::new("factory", Key::cl_type()),
-            Parameter::new("token_a", Key::cl_type()),
-            Parameter::new("token_b", Key::cl_type()),
-        ],
-        CLType::Key,
-        EntryPointAccess::Public,
-        EntryPointType::Contract,
-    ));
-    entry_points
+#[no_mangle]
+fn all_pairs_length() {
+    let entry_points::all_pairs_length::Args { factory } =
+        entry_points::all_pairs_length::Args::get();
+
+    let ret: u64 = Uniswap::default().all_pairs_length(factory);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert())
+}
+
+#[no_mangle]
+fn all_pairs() {
+    let entry_points::all_pairs::Args {
+        factory,
+        offset,
+        limit,
+    } = entry_points::all_pairs::Args::get();
+
+    let ret: Vec<(Key, Key, Key)> = Uniswap::default().all_pairs(factory, offset, limit);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert())
+}
+
+#[no_mangle]
+fn pairs_for_token() {
+    let entry_points::pairs_for_token::Args {
+        factory,
+        token,
+        offset,
+        limit,
+    } = entry_points::pairs_for_token::Args::get();
+
+    let ret: Vec<(Key, Key, Key)> =
+        Uniswap::default().pairs_for_token(factory, token, offset, limit);
+    runtime::ret(CLValue::from_t(ret).unwrap_or_revert())
 }
 
 #[no_mangle]
@@ -0,0 +1,297 @@
+//! Declarative entry point definitions and the JSON ABI written from them.
+//!
+//! A contract lists its entry points once in [`entry_points!`]. The same table gives the
+//! `EntryPoints` installed with the contract, a typed `Args::get()` reading the named arguments of
+//! each entry point, and the metadata [`to_json`] turns into the ABI published next to the wasm.
+//! Error enums declared through [`error_codes!`] add their codes to the ABI the same way.
+
+use alloc::{boxed::Box, format, string::String, vec::Vec};
+
+use casper_contract::{contract_api::runtime, ext_ffi, unwrap_or_revert::UnwrapOrRevert};
+use casper_types::{
+    api_error,
+    bytesrepr::{self, FromBytes, OPTION_NONE_TAG},
+    ApiError, CLType, CLTyped, EntryPointAccess, Group,
+};
+
+#[doc(hidden)]
+pub mod __private {
+    pub use super::get_named_arg;
+    pub use alloc::vec::Vec;
+    pub use casper_types::{CLTyped, EntryPoint, EntryPointType, EntryPoints, Parameter};
+}
+
+/// Whether the caller passed the named argument `name` to the current call.
+pub fn has_named_arg(name: &str) -> bool {
+    let mut arg_size: usize = 0;
+    let ret = unsafe {
+        ext_ffi::casper_get_named_arg_size(
+            name.as_bytes().as_ptr(),
+            name.len(),
+            &mut arg_size as *mut usize,
+        )
+    };
+    match api_error::result_from(ret) {
+        Ok(()) => true,
+        Err(ApiError::MissingArgument) => false,
+        Err(error) => runtime::revert(error),
+    }
+}
+
+/// Reads the named argument `name` of the current call. An `Option` argument the caller left out
+/// reads as `None`, so entry points can take new optional arguments without breaking existing
+/// callers. Any other missing argument reverts.
+pub fn get_named_arg<T: CLTyped + FromBytes>(name: &str) -> T {
+    if let CLType::Option(_) = T::cl_type() {
+        if !has_named_arg(name) {
+            return bytesrepr::deserialize(Vec::from([OPTION_NONE_TAG])).unwrap_or_revert();
+        }
+    }
+    runtime::get_named_arg(name)
+}
+
+pub struct ParamAbi {
+    pub name: &'static str,
+    pub cl_type: CLType,
+}
+
+pub struct EntryPointAbi {
+    pub name: &'static str,
+    pub doc: &'static str,
+    pub params: Vec<ParamAbi>,
+    pub ret: CLType,
+    pub groups: &'static [&'static str],
+}
+
+pub struct ErrorAbi {
+    pub name: &'static str,
+    pub code: u16,
+}
+
+/// Entry points without a group are public, the others are restricted to the listed groups.
+pub fn access(groups: &[&str]) -> EntryPointAccess {
+    if groups.is_empty() {
+        EntryPointAccess::Public
+    } else {
+        EntryPointAccess::Groups(groups.iter().map(|group| Group::new(*group)).collect())
+    }
+}
+
+/// Defines the entry points of a contract.
+///
+/// Each `fn` becomes a module of the same name holding `NAME`, the `Args` struct with its
+/// `get()` reader, `entry_point()` and `abi()`. The invoking module also gets
+/// `get_entry_points()` and `abi()` covering the whole table. Types are resolved in the invoking
+/// module, and `#[group = "…"]` restricts an entry point to a contract user group.
+#[macro_export]
+macro_rules! entry_points {
+    ($(
+        $(#[doc = $doc:literal])*
+        $(#[group = $group:literal])?
+        fn $name:ident($($arg:ident: $ty:ty),* $(,)?) -> $ret:ty;
+    )*) => {
+        $(
+            $(#[doc = $doc])*
+            pub mod $name {
+                #[allow(unused_imports)]
+                use super::*;
+                #[allow(unused_imports)]
+                use $crate::abi::__private::{
+                    get_named_arg, CLTyped, EntryPoint, EntryPointType, Parameter, Vec,
+                };
+
+                pub const NAME: &str = stringify!($name);
+                const GROUPS: &[&str] = &[$($group)?];
+
+                /// Named arguments of the entry point.
+                pub struct Args {
+                    $(pub $arg: $ty,)*
+                }
+
+                impl Args {
+                    /// Reads the arguments of the current call, reverting if one is missing or has
+                    /// another type. Missing `Option` arguments read as `None`.
+                    pub fn get() -> Args {
+                        Args {
+                            $($arg: get_named_arg(stringify!($arg)),)*
+                        }
+                    }
+                }
+
+                pub fn entry_point() -> EntryPoint {
+                    EntryPoint::new(
+                        NAME,
+                        Vec::from([$(Parameter::new(stringify!($arg), <$ty>::cl_type())),*]),
+                        <$ret>::cl_type(),
+                        $crate::abi::access(GROUPS),
+                        EntryPointType::Contract,
+                    )
+                }
+
+                pub fn abi() -> $crate::abi::EntryPointAbi {
+                    $crate::abi::EntryPointAbi {
+                        name: NAME,
+                        doc: concat!($($doc, "\n"),*),
+                        params: Vec::from([$($crate::abi::ParamAbi {
+                            name: stringify!($arg),
+                            cl_type: <$ty>::cl_type(),
+                        }),*]),
+                        ret: <$ret>::cl_type(),
+                        groups: GROUPS,
+                    }
+                }
+            }
+        )*
+
+        /// Entry points the contract registers on install and on every upgrade.
+        pub fn get_entry_points() -> $crate::abi::__private::EntryPoints {
+            let mut entry_points = $crate::abi::__private::EntryPoints::new();
+            $(entry_points.add_entry_point($name::entry_point());)*
+            entry_points
+        }
+
+        /// Description of every entry point, in definition order.
+        pub fn abi() -> $crate::abi::__private::Vec<$crate::abi::EntryPointAbi> {
+            $crate::abi::__private::Vec::from([$($name::abi()),*])
+        }
+    };
+}
+
+/// Declares a `#[repr(u16)]` error enum, with `ABI` listing the name and code of every variant.
+#[macro_export]
+macro_rules! error_codes {
+    (
+        $(#[$meta:meta])*
+        pub enum $name:ident {
+            $($(#[doc = $doc:literal])* $variant:ident = $code:literal,)*
+        }
+    ) => {
+        $(#[$meta])*
+        #[repr(u16)]
+        pub enum $name {
+            $($(#[doc = $doc])* $variant = $code,)*
+        }
+
+        impl $name {
+            pub const ABI: &'static [$crate::abi::ErrorAbi] = &[
+                $($crate::abi::ErrorAbi { name: stringify!($variant), code: $code },)*
+            ];
+        }
+    };
+}
+
+fn string_json(value: &str) -> String {
+    let mut json = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            c if (c as u32) < 0x20 => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+/// Joins the lines of a doc comment, keeping blank lines as paragraph breaks.
+fn doc_text(doc: &str) -> String {
+    let mut paragraphs: Vec<String> = Vec::new();
+    let mut paragraph: Vec<&str> = Vec::new();
+    for line in doc.lines().map(str::trim).chain(core::iter::once("")) {
+        if !line.is_empty() {
+            paragraph.push(line);
+        } else if !paragraph.is_empty() {
+            paragraphs.push(paragraph.join(" "));
+            paragraph.clear();
+        }
+    }
+    paragraphs.join("\n\n")
+}
+
+/// CLType in the JSON form casper-client and the node RPC use.
+pub fn cl_type_json(cl_type: &CLType) -> String {
+    let list = |types: &[Box<CLType>]| {
+        let types: Vec<String> = types.iter().map(|cl_type| cl_type_json(cl_type)).collect();
+        format!("[{}]", types.join(", "))
+    };
+    match cl_type {
+        CLType::Option(inner) => format!("{{\"Option\": {}}}", cl_type_json(inner)),
+        CLType::List(inner) => format!("{{\"List\": {}}}", cl_type_json(inner)),
+        CLType::ByteArray(length) => format!("{{\"ByteArray\": {}}}", length),
+        CLType::Result { ok, err } => format!(
+            "{{\"Result\": {{\"ok\": {}, \"err\": {}}}}}",
+            cl_type_json(ok),
+            cl_type_json(err)
+        ),
+        CLType::Map { key, value } => format!(
+            "{{\"Map\": {{\"key\": {}, \"value\": {}}}}}",
+            cl_type_json(key),
+            cl_type_json(value)
+        ),
+        CLType::Tuple1(types) => format!("{{\"Tuple1\": {}}}", list(types)),
+        CLType::Tuple2(types) => format!("{{\"Tuple2\": {}}}", list(types)),
+        CLType::Tuple3(types) => format!("{{\"Tuple3\": {}}}", list(types)),
+        simple => format!("\"{:?}\"", simple),
+    }
+}
+
+/// The ABI of a contract as pretty-printed JSON.
+///
+/// Error codes are listed as defined and as the `ApiError::User` value a failed deploy reports.
+pub fn to_json(contract: &str, entry_points: &[EntryPointAbi], errors: &[ErrorAbi]) -> String {
+    let entry_points: Vec<String> = entry_points
+        .iter()
+        .map(|entry_point| {
+            let params: Vec<String> = entry_point
+                .params
+                .iter()
+                .map(|param| {
+                    format!(
+                        "        {{\"name\": {}, \"cl_type\": {}}}",
+                        string_json(param.name),
+                        cl_type_json(&param.cl_type)
+                    )
+                })
+                .collect();
+            let params = if params.is_empty() {
+                String::from("[]")
+            } else {
+                format!("[\n{}\n      ]", params.join(",\n"))
+            };
+            let access = if entry_point.groups.is_empty() {
+                String::from("\"Public\"")
+            } else {
+                let groups: Vec<String> =
+                    entry_point.groups.iter().map(|group| string_json(group)).collect();
+                format!("{{\"Groups\": [{}]}}", groups.join(", "))
+            };
+            format!(
+                "    {{\n      \"name\": {},\n      \"doc\": {},\n      \"params\": {},\n      \"ret\": {},\n      \"access\": {}\n    }}",
+                string_json(entry_point.name),
+                string_json(&doc_text(entry_point.doc)),
+                params,
+                cl_type_json(&entry_point.ret),
+                access
+            )
+        })
+        .collect();
+    let errors: Vec<String> = errors
+        .iter()
+        .map(|error| {
+            format!(
+                "    {{\"name\": {}, \"code\": {}, \"api_error\": {}}}",
+                string_json(error.name),
+                error.code,
+                u32::from(error.code) + 65_536
+            )
+        })
+        .collect();
+    format!(
+        "{{\n  \"contract\": {},\n  \"entry_points\": [\n{}\n  ],\n  \"errors\": [\n{}\n  ]\n}}\n",
+        string_json(contract),
+        entry_points.join(",\n"),
+        errors.join(",\n")
+    )
+}
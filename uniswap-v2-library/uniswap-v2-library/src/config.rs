@@ -1,17 +1,21 @@
 pub mod error {
 
     use casper_types::api_error::ApiError;
-    #[repr(u16)]
-    pub enum ErrorCode {
-        Zero = 0, // Overflow
-        One,      // Underflow
-        ZeroAddress,
-        IdenticalAddresses,
-        InsufficientAmount,
-        InsufficientInputAmount,
-        InsufficientOutputAmount,
-        InvalidPath,
-        InsufficientLiquidity,
+
+    crate::error_codes! {
+        pub enum ErrorCode {
+            /// Overflow
+            Zero = 0,
+            /// Underflow
+            One = 1,
+            ZeroAddress = 2,
+            IdenticalAddresses = 3,
+            InsufficientAmount = 4,
+            InsufficientInputAmount = 5,
+            InsufficientOutputAmount = 6,
+            InvalidPath = 7,
+            InsufficientLiquidity = 8,
+        }
     }
 
     impl From<ErrorCode> for ApiError {
@@ -0,0 +1,82 @@
+//! Entry points of the library contract.
+
+use alloc::vec::Vec;
+use casper_types::{ContractHash, ContractPackageHash, Key, U128, U256};
+
+crate::entry_points! {
+    #[group = "constructor"]
+    fn constructor(contract_hash: ContractHash, package_hash: ContractPackageHash) -> ();
+
+    /// Sorts two tokens by hash, the order pairs store them in.
+    fn sort_tokens(token_a: Key, token_b: Key) -> (ContractPackageHash, ContractPackageHash);
+
+    /// Fetches the reserves of a pair, ordered as `token_a`, `token_b`.
+    fn get_reserves(factory: Key, token_a: Key, token_b: Key) -> (U128, U128);
+
+    /// Given some amount of an asset and pair reserves, returns an equivalent amount of the other
+    /// asset.
+    fn quote(amount_a: U256, reserve_a: U128, reserve_b: U128) -> U256;
+
+    /// Given an input amount of an asset and pair reserves, returns the maximum output amount of
+    /// the other asset.
+    fn get_amount_out(amount_in: U256, reserve_in: U256, reserve_out: U256) -> U256;
+
+    /// Given an output amount of an asset and pair reserves, returns a required input amount of
+    /// the other asset.
+    fn get_amount_in(amount_out: U256, reserve_in: U256, reserve_out: U256) -> U256;
+
+    /// Performs chained `get_amount_out` calculations on any number of pairs.
+    fn get_amounts_out(factory: Key, amount_in: U256, path: Vec<Key>) -> Vec<U256>;
+
+    /// Performs chained `get_amount_in` calculations on any number of pairs.
+    fn get_amounts_in(factory: Key, amount_out: U256, path: Vec<Key>) -> Vec<U256>;
+
+    /// Performs chained `get_amount_out` calculations for every amount in `amounts_in`, reading
+    /// the reserves of each pair once. Row `i` holds the amounts along `path` for `amounts_in[i]`.
+    fn get_amounts_out_batch(
+        factory: Key,
+        amounts_in: Vec<U256>,
+        path: Vec<Key>,
+    ) -> Vec<Vec<U256>>;
+
+    /// Performs chained `get_amount_in` calculations for every amount in `amounts_out`, reading
+    /// the reserves of each pair once. Row `i` holds the amounts along `path` for `amounts_out[i]`.
+    fn get_amounts_in_batch(
+        factory: Key,
+        amounts_out: Vec<U256>,
+        path: Vec<Key>,
+    ) -> Vec<Vec<U256>>;
+
+    /// Like `get_amounts_out`, but returns an error instead of reverting when the amount cannot go
+    /// through the path. The error holds the index in `path` of the token the failing pair starts
+    /// at and the `ErrorCode` the call would have reverted with, so route scanners can tell which
+    /// pair is illiquid.
+    fn try_get_amounts_out(
+        factory: Key,
+        amount_in: U256,
+        path: Vec<Key>,
+    ) -> Result<Vec<U256>, (u32, u32)>;
+
+    /// Like `get_amounts_in`, but returns an error instead of reverting when the amount cannot go
+    /// through the path, as `try_get_amounts_out` does.
+    fn try_get_amounts_in(
+        factory: Key,
+        amount_out: U256,
+        path: Vec<Key>,
+    ) -> Result<Vec<U256>, (u32, u32)>;
+
+    /// Number of pairs the factory created.
+    fn all_pairs_length(factory: Key) -> u64;
+
+    /// Up to `limit` of the pairs the factory created, in order of creation and starting at
+    /// `offset`, each as `(pair, token0, token1)`.
+    fn all_pairs(factory: Key, offset: u64, limit: u64) -> Vec<(Key, Key, Key)>;
+
+    /// The pairs holding `token` among the page of pairs `all_pairs` returns for the same
+    /// `offset` and `limit`, each as `(pair, token0, token1)`. Pages may come back short or empty
+    /// before `all_pairs_length` is reached.
+    fn pairs_for_token(factory: Key, token: Key, offset: u64, limit: u64) -> Vec<(Key, Key, Key)>;
+
+    /// Package hash of the pair of two tokens, as registered with the factory.
+    fn pair_for(factory: Key, token_a: Key, token_b: Key) -> Key;
+}
@@ -2,8 +2,12 @@
 
 extern crate alloc;
 
+pub mod abi;
 pub mod config;
 pub mod data;
+pub mod entry_points;
+pub mod math;
+pub mod oracle;
 pub mod uniswap_v2_library;
 
 pub use uniswap_v2_library::UniswapV2Library;
@@ -0,0 +1,195 @@
+//! Pricing formulas, free of contract calls so off-chain tools can link them too.
+//!
+//! Reverts of the contract entry points are returned as errors here. Overflow maps to
+//! `ErrorCode::Zero` and underflow to `ErrorCode::One`.
+
+use alloc::{vec, vec::Vec};
+
+use casper_types::{api_error::ApiError, U128, U256};
+
+use crate::config::error::ErrorCode;
+
+/// LP tokens a pair mints to nobody on its first deposit, so its supply can never drain to zero.
+pub const MINIMUM_LIQUIDITY: u64 = 1000;
+
+fn overflow() -> ApiError {
+    ErrorCode::Zero.into()
+}
+
+// given some amount of an asset and pair reserves, returns an equivalent amount of the other asset
+pub fn quote(amount_a: U256, reserve_a: U128, reserve_b: U128) -> Result<U256, ApiError> {
+    if amount_a.is_zero() {
+        return Err(ErrorCode::InsufficientAmount.into());
+    }
+    if reserve_a.is_zero() || reserve_b.is_zero() {
+        return Err(ErrorCode::InsufficientLiquidity.into());
+    }
+    let amount_b: U256 = amount_a
+        .checked_mul(U256::from(reserve_b.as_u128()))
+        .ok_or_else(overflow)?
+        / U256::from(reserve_a.as_u128());
+    Ok(amount_b)
+}
+
+// given an input amount of an asset and pair reserves, returns the maximum output amount of the other asset
+pub fn get_amount_out(
+    amount_in: U256,
+    reserve_in: U256,
+    reserve_out: U256,
+) -> Result<U256, ApiError> {
+    if amount_in.is_zero() {
+        return Err(ErrorCode::InsufficientInputAmount.into());
+    }
+    if reserve_in.is_zero() || reserve_out.is_zero() {
+        return Err(ErrorCode::InsufficientLiquidity.into());
+    }
+    let amount_in_with_fee: U256 = amount_in
+        .checked_mul(U256::from(997))
+        .ok_or_else(overflow)?;
+    let numerator: U256 = amount_in_with_fee
+        .checked_mul(reserve_out)
+        .ok_or_else(overflow)?;
+    let denominator: U256 = reserve_in
+        .checked_mul(U256::from(1000))
+        .and_then(|denominator| denominator.checked_add(amount_in_with_fee))
+        .ok_or_else(overflow)?;
+    Ok(numerator / denominator)
+}
+
+// given an output amount of an asset and pair reserves, returns a required input amount of the other asset
+pub fn get_amount_in(
+    amount_out: U256,
+    reserve_in: U256,
+    reserve_out: U256,
+) -> Result<U256, ApiError> {
+    if amount_out.is_zero() {
+        return Err(ErrorCode::InsufficientOutputAmount.into());
+    }
+    if reserve_in.is_zero() || reserve_out.is_zero() {
+        return Err(ErrorCode::InsufficientLiquidity.into());
+    }
+    let numerator: U256 = reserve_in
+        .checked_mul(amount_out)
+        .and_then(|numerator| numerator.checked_mul(U256::from(1000)))
+        .ok_or_else(overflow)?;
+    let remaining: U256 = reserve_out
+        .checked_sub(amount_out)
+        .ok_or_else(|| ApiError::from(ErrorCode::One))?;
+    // The whole reserve can never be bought.
+    if remaining.is_zero() {
+        return Err(ErrorCode::InsufficientLiquidity.into());
+    }
+    let denominator: U256 = remaining
+        .checked_mul(U256::from(997))
+        .ok_or_else(overflow)?;
+    (numerator / denominator)
+        .checked_add(U256::from(1))
+        .ok_or_else(overflow)
+}
+
+// performs chained getAmountOut calculations over the (reserve_in, reserve_out) of each hop
+pub fn get_amounts_out(amount_in: U256, reserves: &[(U256, U256)]) -> Result<Vec<U256>, ApiError> {
+    if reserves.is_empty() {
+        return Err(ErrorCode::InvalidPath.into());
+    }
+    let mut amounts: Vec<U256> = vec![amount_in];
+    for (reserve_in, reserve_out) in reserves {
+        let amount_out = get_amount_out(amounts[amounts.len() - 1], *reserve_in, *reserve_out)?;
+        amounts.push(amount_out);
+    }
+    Ok(amounts)
+}
+
+// performs chained getAmountIn calculations over the (reserve_in, reserve_out) of each hop
+pub fn get_amounts_in(amount_out: U256, reserves: &[(U256, U256)]) -> Result<Vec<U256>, ApiError> {
+    if reserves.is_empty() {
+        return Err(ErrorCode::InvalidPath.into());
+    }
+    let mut amounts: Vec<U256> = vec![0.into(); reserves.len() + 1];
+    amounts[reserves.len()] = amount_out;
+    for i in (0..reserves.len()).rev() {
+        let (reserve_in, reserve_out) = reserves[i];
+        amounts[i] = get_amount_in(amounts[i + 1], reserve_in, reserve_out)?;
+    }
+    Ok(amounts)
+}
+
+// given amounts deposited into a pair, its reserves and LP supply, returns the LP tokens it mints
+pub fn liquidity_minted(
+    amount_a: U256,
+    amount_b: U256,
+    reserve_a: U128,
+    reserve_b: U128,
+    total_supply: U256,
+) -> Result<U256, ApiError> {
+    let liquidity: U256 = if total_supply.is_zero() {
+        sqrt(amount_a.checked_mul(amount_b).ok_or_else(overflow)?)
+            .saturating_sub(U256::from(MINIMUM_LIQUIDITY))
+    } else {
+        if reserve_a.is_zero() || reserve_b.is_zero() {
+            return Err(ErrorCode::InsufficientLiquidity.into());
+        }
+        let liquidity_a: U256 = amount_a.checked_mul(total_supply).ok_or_else(overflow)?
+            / U256::from(reserve_a.as_u128());
+        let liquidity_b: U256 = amount_b.checked_mul(total_supply).ok_or_else(overflow)?
+            / U256::from(reserve_b.as_u128());
+        liquidity_a.min(liquidity_b)
+    };
+    if liquidity.is_zero() {
+        return Err(ErrorCode::InsufficientLiquidity.into());
+    }
+    Ok(liquidity)
+}
+
+// given LP tokens burnt in a pair, its reserves and LP supply, returns the amounts paid out
+pub fn liquidity_amounts(
+    liquidity: U256,
+    reserve_a: U128,
+    reserve_b: U128,
+    total_supply: U256,
+) -> Result<(U256, U256), ApiError> {
+    if liquidity.is_zero() || liquidity > total_supply {
+        return Err(ErrorCode::InsufficientLiquidity.into());
+    }
+    let amount_a: U256 = liquidity
+        .checked_mul(U256::from(reserve_a.as_u128()))
+        .ok_or_else(overflow)?
+        / total_supply;
+    let amount_b: U256 = liquidity
+        .checked_mul(U256::from(reserve_b.as_u128()))
+        .ok_or_else(overflow)?
+        / total_supply;
+    if amount_a.is_zero() || amount_b.is_zero() {
+        return Err(ErrorCode::InsufficientLiquidity.into());
+    }
+    Ok((amount_a, amount_b))
+}
+
+// given an amount borrowed from a pair in a flash swap, returns the smallest fee on top of it
+// that keeps the pair's invariant when both are paid back in the borrowed token
+pub fn flash_swap_fee(amount: U256) -> Result<U256, ApiError> {
+    if amount.is_zero() {
+        return Err(ErrorCode::InsufficientOutputAmount.into());
+    }
+    // (amount + fee) * 997 >= amount * 1000
+    let fee: U256 = amount
+        .checked_mul(U256::from(3))
+        .and_then(|fee| fee.checked_add(U256::from(996)))
+        .ok_or_else(overflow)?
+        / 997;
+    Ok(fee)
+}
+
+// babylonian method, rounding down
+fn sqrt(y: U256) -> U256 {
+    if y <= U256::from(3) {
+        return if y.is_zero() { y } else { U256::one() };
+    }
+    let mut z: U256 = y;
+    let mut x: U256 = y / 2 + 1;
+    while x < z {
+        z = x;
+        x = (y / x + x) / 2;
+    }
+    z
+}
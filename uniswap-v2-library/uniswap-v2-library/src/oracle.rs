@@ -0,0 +1,70 @@
+//! Time-weighted average prices from the cumulative prices pairs keep, free of contract calls like
+//! [`math`](crate::math).
+//!
+//! Prices are UQ112x112 fixed point numbers, `price0` is token1 per token0 and `price1` token0 per
+//! token1. A cumulative price is the sum of the price times the block time it held for and wraps
+//! around on overflow, so only the difference between two of them means anything.
+
+use casper_types::{api_error::ApiError, U128, U256, U512};
+
+use crate::config::error::ErrorCode;
+
+const RESOLUTION: usize = 112;
+
+// encodes reserve_numerator / reserve_denominator as a UQ112x112, the denominator must not be zero
+pub fn encode_price(reserve_numerator: U128, reserve_denominator: U128) -> U256 {
+    (U256::from(reserve_numerator.as_u128()) << RESOLUTION)
+        / U256::from(reserve_denominator.as_u128())
+}
+
+// cumulative prices of a pair at `timestamp`, including the time since its last update that the
+// pair itself only accounts for on its next swap, mint, burn or sync
+pub fn current_cumulative_prices(
+    reserve0: U128,
+    reserve1: U128,
+    block_timestamp_last: u64,
+    price0_cumulative_last: U256,
+    price1_cumulative_last: U256,
+    timestamp: u64,
+) -> (U256, U256) {
+    let time_elapsed: u64 = timestamp.saturating_sub(block_timestamp_last);
+    if time_elapsed == 0 || reserve0.is_zero() || reserve1.is_zero() {
+        return (price0_cumulative_last, price1_cumulative_last);
+    }
+    let time_elapsed: U256 = U256::from(time_elapsed);
+    let (price0_elapsed, _) = encode_price(reserve1, reserve0).overflowing_mul(time_elapsed);
+    let (price1_elapsed, _) = encode_price(reserve0, reserve1).overflowing_mul(time_elapsed);
+    (
+        price0_cumulative_last.overflowing_add(price0_elapsed).0,
+        price1_cumulative_last.overflowing_add(price1_elapsed).0,
+    )
+}
+
+// given an input amount of an asset, returns the amount of the other asset it is worth at the
+// average price between two cumulative prices taken `time_elapsed` apart
+pub fn compute_amount_out(
+    price_cumulative_start: U256,
+    price_cumulative_end: U256,
+    time_elapsed: u64,
+    amount_in: U256,
+) -> Result<U256, ApiError> {
+    // an empty interval has no average price
+    if time_elapsed == 0 {
+        return Err(ErrorCode::InsufficientAmount.into());
+    }
+    let (price_cumulative_delta, _) = price_cumulative_end.overflowing_sub(price_cumulative_start);
+    let price_average: U256 = price_cumulative_delta / U256::from(time_elapsed);
+    let amount_out: U512 = (to_u512(price_average) * to_u512(amount_in)) >> RESOLUTION;
+    let mut bytes = [0u8; 64];
+    amount_out.to_little_endian(&mut bytes);
+    if bytes[32..].iter().any(|byte| *byte != 0) {
+        return Err(ErrorCode::Zero.into());
+    }
+    Ok(U256::from_little_endian(&bytes[..32]))
+}
+
+fn to_u512(value: U256) -> U512 {
+    let mut bytes = [0u8; 32];
+    value.to_little_endian(&mut bytes);
+    U512::from_little_endian(&bytes)
+}
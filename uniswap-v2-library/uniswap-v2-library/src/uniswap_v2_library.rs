@@ -14,6 +14,15 @@ use casperlabs_contract_utils::{ContractContext, ContractStorage};
 
 use crate::config::error::ErrorCode;
 use crate::data::{self};
+use crate::math;
+
+// the code an error is listed under in the ABI, without the offset of `ApiError::User`
+fn error_code(error: ApiError) -> u32 {
+    match error {
+        ApiError::User(code) => u32::from(code),
+        other => u32::from(other),
+    }
+}
 
 pub trait UniswapV2Library<Storage: ContractStorage>: ContractContext<Storage> {
     // Will be called by constructor
@@ -90,55 +99,17 @@ pub trait UniswapV2Library<Storage: ContractStorage>: ContractContext<Storage> {
 
     // given some amount of an asset and pair reserves, returns an equivalent amount of the other asset
     fn quote(&mut self, amount_a: U256, reserve_a: U128, reserve_b: U128) -> U256 {
-        if amount_a <= 0.into() {
-            runtime::revert(ApiError::User(ErrorCode::InsufficientAmount as u16));
-        }
-        if reserve_a <= 0.into() || reserve_b <= 0.into() {
-            runtime::revert(ApiError::User(ErrorCode::InsufficientLiquidity as u16));
-        }
-        let amount_b: U256 =
-            (amount_a * U256::from(reserve_b.as_u128())) / U256::from(reserve_a.as_u128());
-        amount_b
+        math::quote(amount_a, reserve_a, reserve_b).unwrap_or_revert()
     }
 
     // given an input amount of an asset and pair reserves, returns the maximum output amount of the other asset
     fn get_amount_out(&mut self, amount_in: U256, reserve_in: U256, reserve_out: U256) -> U256 {
-        if amount_in <= 0.into() {
-            runtime::revert(ApiError::User(ErrorCode::InsufficientInputAmount as u16));
-        }
-        if reserve_in <= 0.into() || reserve_out <= 0.into() {
-            runtime::revert(ApiError::User(ErrorCode::InsufficientLiquidity as u16));
-        }
-
-        let amount_in_with_fee: U256 = amount_in * 997;
-        let numerator: U256 = amount_in_with_fee * reserve_out;
-        let denominator: U256 = reserve_in
-            .checked_mul(U256::from(1000))
-            .unwrap_or_revert()
-            .checked_add(amount_in_with_fee)
-            .ok_or(ApiError::User(ErrorCode::Zero as u16))
-            .unwrap_or_revert();
-        let amount_out: U256 = numerator / denominator;
-
-        amount_out
+        math::get_amount_out(amount_in, reserve_in, reserve_out).unwrap_or_revert()
     }
 
     // given an output amount of an asset and pair reserves, returns a required input amount of the other asset
     fn get_amount_in(&mut self, amount_out: U256, reserve_in: U256, reserve_out: U256) -> U256 {
-        if amount_out <= 0.into() {
-            runtime::revert(ApiError::User(ErrorCode::InsufficientOutputAmount as u16));
-        }
-        if reserve_in <= 0.into() || reserve_out <= 0.into() {
-            runtime::revert(ApiError::User(ErrorCode::InsufficientLiquidity as u16));
-        }
-        let numerator: U256 = reserve_in * amount_out * 1000;
-        let denominator: U256 = (reserve_out - amount_out) * 997;
-        let amount_in: U256 = (numerator / denominator)
-            .checked_add(U256::from(1))
-            .ok_or(ApiError::User(ErrorCode::Zero as u16))
-            .unwrap_or_revert();
-
-        amount_in
+        math::get_amount_in(amount_out, reserve_in, reserve_out).unwrap_or_revert()
     }
 
     // performs chained getAmountOut calculations on any number of pairs
@@ -190,6 +161,182 @@ pub trait UniswapV2Library<Storage: ContractStorage>: ContractContext<Storage> {
         amounts
     }
 
+    // performs chained getAmountOut calculations for every input amount, reading the reserves of
+    // each pair only once
+    fn get_amounts_out_batch(
+        &mut self,
+        factory: ContractPackageHash,
+        amounts_in: Vec<U256>,
+        path: Vec<ContractPackageHash>,
+    ) -> Vec<Vec<U256>> {
+        let reserves: Vec<(U256, U256)> = self.hop_reserves(factory, &path);
+        amounts_in
+            .into_iter()
+            .map(|amount_in| math::get_amounts_out(amount_in, &reserves).unwrap_or_revert())
+            .collect()
+    }
+
+    // performs chained getAmountIn calculations for every output amount, reading the reserves of
+    // each pair only once
+    fn get_amounts_in_batch(
+        &mut self,
+        factory: ContractPackageHash,
+        amounts_out: Vec<U256>,
+        path: Vec<ContractPackageHash>,
+    ) -> Vec<Vec<U256>> {
+        let reserves: Vec<(U256, U256)> = self.hop_reserves(factory, &path);
+        amounts_out
+            .into_iter()
+            .map(|amount_out| math::get_amounts_in(amount_out, &reserves).unwrap_or_revert())
+            .collect()
+    }
+
+    // like get_amounts_out, but instead of reverting returns the index in the path of the first
+    // pair the amounts cannot go through, together with the error code it would have reverted with
+    fn try_get_amounts_out(
+        &mut self,
+        factory: ContractPackageHash,
+        amount_in: U256,
+        path: Vec<ContractPackageHash>,
+    ) -> Result<Vec<U256>, (u32, u32)> {
+        if path.len() < 2 {
+            return Err((0, ErrorCode::InvalidPath as u32));
+        }
+        let mut amounts: Vec<U256> = vec![amount_in];
+        for (hop, tokens) in path.windows(2).enumerate() {
+            let amount_out: U256 = self
+                .try_get_reserves(factory, tokens[0], tokens[1])
+                .and_then(|(reserve_in, reserve_out)| {
+                    math::get_amount_out(amounts[hop], reserve_in, reserve_out)
+                })
+                .map_err(|error| (hop as u32, error_code(error)))?;
+            amounts.push(amount_out);
+        }
+        Ok(amounts)
+    }
+
+    // like get_amounts_in, but instead of reverting returns the index in the path of the last
+    // pair the amounts cannot go through, together with the error code it would have reverted with
+    fn try_get_amounts_in(
+        &mut self,
+        factory: ContractPackageHash,
+        amount_out: U256,
+        path: Vec<ContractPackageHash>,
+    ) -> Result<Vec<U256>, (u32, u32)> {
+        if path.len() < 2 {
+            return Err((0, ErrorCode::InvalidPath as u32));
+        }
+        let mut amounts: Vec<U256> = vec![0.into(); path.len()];
+        amounts[path.len() - 1] = amount_out;
+        for hop in (0..path.len() - 1).rev() {
+            amounts[hop] = self
+                .try_get_reserves(factory, path[hop], path[hop + 1])
+                .and_then(|(reserve_in, reserve_out)| {
+                    math::get_amount_in(amounts[hop + 1], reserve_in, reserve_out)
+                })
+                .map_err(|error| (hop as u32, error_code(error)))?;
+        }
+        Ok(amounts)
+    }
+
+    // (reserve_in, reserve_out) of the pair of two tokens, or the error reading them would revert
+    // with. A pair the factory does not know has no liquidity either.
+    fn try_get_reserves(
+        &mut self,
+        factory: ContractPackageHash,
+        token_in: ContractPackageHash,
+        token_out: ContractPackageHash,
+    ) -> Result<(U256, U256), ApiError> {
+        if token_in == token_out {
+            return Err(ErrorCode::IdenticalAddresses.into());
+        }
+        let pair: Key = self.pair_for(
+            Key::from(factory),
+            Key::from(token_in),
+            Key::from(token_out),
+        );
+        if pair == Key::Hash([0u8; 32]) {
+            return Err(ErrorCode::InsufficientLiquidity.into());
+        }
+        let (reserve_in, reserve_out): (U128, U128) =
+            self.get_reserves(factory, token_in, token_out);
+        Ok((
+            U256::from(reserve_in.as_u128()),
+            U256::from(reserve_out.as_u128()),
+        ))
+    }
+
+    // number of pairs the factory created
+    fn all_pairs_length(&mut self, factory: Key) -> u64 {
+        self.factory_pairs(factory).len() as u64
+    }
+
+    // up to `limit` pairs the factory created, starting at `offset`, as (pair, token0, token1)
+    fn all_pairs(&mut self, factory: Key, offset: u64, limit: u64) -> Vec<(Key, Key, Key)> {
+        self.factory_pairs(factory)
+            .into_iter()
+            .skip(offset as usize)
+            .take(limit as usize)
+            .map(|pair| self.pair_tokens(pair))
+            .collect()
+    }
+
+    // the pairs holding `token` among the same page of pairs `all_pairs` returns, so a page may
+    // hold fewer than `limit` of them before the end of the list is reached
+    fn pairs_for_token(
+        &mut self,
+        factory: Key,
+        token: Key,
+        offset: u64,
+        limit: u64,
+    ) -> Vec<(Key, Key, Key)> {
+        self.all_pairs(factory, offset, limit)
+            .into_iter()
+            .filter(|(_, token0, token1)| *token0 == token || *token1 == token)
+            .collect()
+    }
+
+    // every pair the factory created, in order of creation
+    fn factory_pairs(&mut self, factory: Key) -> Vec<Key> {
+        runtime::call_versioned_contract(
+            ContractPackageHash::from(factory.into_hash().unwrap_or_default()),
+            None,
+            "all_pairs",
+            runtime_args! {},
+        )
+    }
+
+    fn pair_tokens(&mut self, pair: Key) -> (Key, Key, Key) {
+        let pair_hash: ContractPackageHash =
+            ContractPackageHash::from(pair.into_hash().unwrap_or_default());
+        let token0: Key =
+            runtime::call_versioned_contract(pair_hash, None, "token0", runtime_args! {});
+        let token1: Key =
+            runtime::call_versioned_contract(pair_hash, None, "token1", runtime_args! {});
+        (pair, token0, token1)
+    }
+
+    // (reserve_in, reserve_out) of every pair along the path
+    fn hop_reserves(
+        &mut self,
+        factory: ContractPackageHash,
+        path: &[ContractPackageHash],
+    ) -> Vec<(U256, U256)> {
+        if path.len() < 2 {
+            runtime::revert(ApiError::User(ErrorCode::InvalidPath as u16));
+        }
+        path.windows(2)
+            .map(|hop| {
+                let (reserve_in, reserve_out): (U128, U128) =
+                    self.get_reserves(factory, hop[0], hop[1]);
+                (
+                    U256::from(reserve_in.as_u128()),
+                    U256::from(reserve_out.as_u128()),
+                )
+            })
+            .collect()
+    }
+
     fn call_versioned_contract<T: CLTyped + FromBytes>(
         package_hash_str: &str,
         method: &str,
@@ -79,6 +79,7 @@ fn add_liquidity() {
     let to: Key = runtime::get_named_arg("to");
     let deadline: U256 = runtime::get_named_arg("deadline");
     let pair: Option<Key> = runtime::get_named_arg("pair");
+    let min_liquidity_out: Option<U256> = runtime::get_named_arg("min_liquidity_out");
 
     let router_package_hash: ContractPackageHash =
         runtime::call_versioned_contract(router_address, None, "package_hash", runtime_args! {});
@@ -113,7 +114,8 @@ fn add_liquidity() {
         "amount_b_min" => amount_b_min,
         "to" => to,
         "deadline" => deadline,
-        "pair" => pair
+        "pair" => pair,
+        "min_liquidity_out" => min_liquidity_out
     };
 
     let (amount_a, amount_b, liquidity): (U256, U256, U256) =
@@ -152,6 +154,7 @@ fn add_liquidity_cspr() {
     let to: Key = runtime::get_named_arg("to");
     let deadline: U256 = runtime::get_named_arg("deadline");
     let pair: Option<Key> = runtime::get_named_arg("pair");
+    let min_liquidity_out: Option<U256> = runtime::get_named_arg("min_liquidity_out");
 
     let router_package_hash: ContractPackageHash =
         runtime::call_versioned_contract(router_address, None, "package_hash", runtime_args! {});
@@ -179,6 +182,7 @@ fn add_liquidity_cspr() {
         "to" => to,
         "deadline" => deadline,
         "pair" => pair,
+        "min_liquidity_out" => min_liquidity_out,
         "purse" => self_purse
     };
 
@@ -423,6 +427,7 @@ fn swap_exact_tokens_for_tokens() {
     let _path: Vec<String> = runtime::get_named_arg("path");
     let to: Key = runtime::get_named_arg("to");
     let deadline: U256 = runtime::get_named_arg("deadline");
+    let max_price_impact_bps: Option<u32> = runtime::get_named_arg("max_price_impact_bps");
 
     let router_package_hash: ContractPackageHash =
         runtime::call_versioned_contract(router_address, None, "package_hash", runtime_args! {});
@@ -447,7 +452,8 @@ fn swap_exact_tokens_for_tokens() {
         "amount_out_min" => amount_out_min,
         "path" => _path,
         "to" => to,
-        "deadline" => deadline
+        "deadline" => deadline,
+        "max_price_impact_bps" => max_price_impact_bps
     };
 
     let amounts: Vec<U256> = runtime::call_versioned_contract(
@@ -468,6 +474,7 @@ fn swap_tokens_for_exact_tokens() {
     let _path: Vec<String> = runtime::get_named_arg("path");
     let to: Key = runtime::get_named_arg("to");
     let deadline: U256 = runtime::get_named_arg("deadline");
+    let max_price_impact_bps: Option<u32> = runtime::get_named_arg("max_price_impact_bps");
 
     let router_package_hash: ContractPackageHash =
         runtime::call_versioned_contract(router_address, None, "package_hash", runtime_args! {});
@@ -492,7 +499,8 @@ fn swap_tokens_for_exact_tokens() {
         "amount_in_max" => amount_in_max,
         "path" => _path,
         "to" => to,
-        "deadline" => deadline
+        "deadline" => deadline,
+        "max_price_impact_bps" => max_price_impact_bps
     };
 
     let amounts: Vec<U256> = runtime::call_versioned_contract(
@@ -515,6 +523,7 @@ fn swap_exact_cspr_for_tokens() {
     let _path: Vec<String> = runtime::get_named_arg("path");
     let to: Key = runtime::get_named_arg("to");
     let deadline: U256 = runtime::get_named_arg("deadline");
+    let max_price_impact_bps: Option<u32> = runtime::get_named_arg("max_price_impact_bps");
     let mut path: Vec<Key> = Vec::new();
     for i in 0..(_path.len()) {
         path.push(Key::from_formatted_str(&_path[i]).unwrap());
@@ -529,6 +538,7 @@ fn swap_exact_cspr_for_tokens() {
         "path" => _path,
         "to" => to,
         "deadline" => deadline,
+        "max_price_impact_bps" => max_price_impact_bps,
         "purse" => purse
     };
 
@@ -545,6 +555,7 @@ fn swap_tokens_for_exact_cspr() {
     let amount_in_max: U256 = runtime::get_named_arg("amount_in_max");
     let _path: Vec<String> = runtime::get_named_arg("path");
     let deadline: U256 = runtime::get_named_arg("deadline");
+    let max_price_impact_bps: Option<u32> = runtime::get_named_arg("max_price_impact_bps");
     let router_package_hash: ContractPackageHash =
         runtime::call_versioned_contract(router_address, None, "package_hash", runtime_args! {});
 
@@ -572,7 +583,8 @@ fn swap_tokens_for_exact_cspr() {
         "amount_in_max" => amount_in_max,
         "path" => _path,
         "to" => to_purse,
-        "deadline" => deadline
+        "deadline" => deadline,
+        "max_price_impact_bps" => max_price_impact_bps
     };
 
     let amounts: Vec<U256> =
@@ -588,6 +600,7 @@ fn swap_exact_tokens_for_cspr() {
     let amount_out_min: U256 = runtime::get_named_arg("amount_out_min");
     let _path: Vec<String> = runtime::get_named_arg("path");
     let deadline: U256 = runtime::get_named_arg("deadline");
+    let max_price_impact_bps: Option<u32> = runtime::get_named_arg("max_price_impact_bps");
     let router_package_hash: ContractPackageHash =
         runtime::call_versioned_contract(router_address, None, "package_hash", runtime_args! {});
 
@@ -615,7 +628,8 @@ fn swap_exact_tokens_for_cspr() {
         "amount_out_min" => amount_out_min,
         "path" => _path,
         "to" => to_purse,
-        "deadline" => deadline
+        "deadline" => deadline,
+        "max_price_impact_bps" => max_price_impact_bps
     };
 
     let amounts: Vec<U256> =
@@ -632,6 +646,7 @@ fn swap_cspr_for_exact_tokens() {
     let _path: Vec<String> = runtime::get_named_arg("path");
     let to: Key = runtime::get_named_arg("to");
     let deadline: U256 = runtime::get_named_arg("deadline");
+    let max_price_impact_bps: Option<u32> = runtime::get_named_arg("max_price_impact_bps");
     let router_package_hash: ContractPackageHash =
         runtime::call_versioned_contract(router_address, None, "package_hash", runtime_args! {});
 
@@ -660,6 +675,7 @@ fn swap_cspr_for_exact_tokens() {
         "path" => _path,
         "to" => to,
         "deadline" => deadline,
+        "max_price_impact_bps" => max_price_impact_bps,
         "purse" => caller_purse
     };
 
@@ -682,8 +698,9 @@ fn get_reserves() {
         "token_b" => token_b
     };
 
-    let (_reserve_1, _reserve_2): (U128, U128) =
+    let (reserve_a, reserve_b): (U128, U128) =
         runtime::call_versioned_contract(library_address, None, "get_reserves", args);
+    mappings::set_key(&mappings::get_reserves_key(), (reserve_a, reserve_b));
 }
 
 #[no_mangle]
@@ -700,7 +717,8 @@ fn quote() {
         "reserve_b" => reserve_b
     };
 
-    let _quote: U256 = runtime::call_versioned_contract(library_address, None, "quote", args);
+    let quote: U256 = runtime::call_versioned_contract(library_address, None, "quote", args);
+    mappings::set_key(&mappings::quote_key(), quote);
 }
 
 #[no_mangle]
@@ -717,8 +735,9 @@ fn get_amount_out() {
         "reserve_out" => reserve_out
     };
 
-    let _amount_out: U256 =
+    let amount_out: U256 =
         runtime::call_versioned_contract(library_address, None, "get_amount_out", args);
+    mappings::set_key(&mappings::get_amount_out_key(), amount_out);
 }
 
 #[no_mangle]
@@ -735,8 +754,9 @@ fn get_amount_in() {
         "reserve_out" => reserve_out
     };
 
-    let _amount_in: U256 =
+    let amount_in: U256 =
         runtime::call_versioned_contract(library_address, None, "get_amount_in", args);
+    mappings::set_key(&mappings::get_amount_in_key(), amount_in);
 }
 
 #[no_mangle]
@@ -774,6 +794,229 @@ fn get_amounts_in() {
         runtime::call_versioned_contract(library_address, None, "get_amounts_in", args);
 }
 
+#[no_mangle]
+fn try_get_amounts_out() {
+    let library_address: ContractPackageHash = mappings::get_key(&mappings::library_key());
+
+    let factory: Key = runtime::get_named_arg("factory");
+    let amount_in: U256 = runtime::get_named_arg("amount_in");
+    let path: Vec<Key> = runtime::get_named_arg("path");
+
+    let args: RuntimeArgs = runtime_args! {
+        "factory" => factory,
+        "amount_in" => amount_in,
+        "path" => path
+    };
+
+    let amounts: Result<Vec<U256>, (u32, u32)> =
+        runtime::call_versioned_contract(library_address, None, "try_get_amounts_out", args);
+    mappings::set_key(&mappings::try_get_amounts_out_key(), amounts);
+}
+
+#[no_mangle]
+fn try_get_amounts_in() {
+    let library_address: ContractPackageHash = mappings::get_key(&mappings::library_key());
+
+    let factory: Key = runtime::get_named_arg("factory");
+    let amount_out: U256 = runtime::get_named_arg("amount_out");
+    let path: Vec<Key> = runtime::get_named_arg("path");
+
+    let args: RuntimeArgs = runtime_args! {
+        "factory" => factory,
+        "amount_out" => amount_out,
+        "path" => path
+    };
+
+    let amounts: Result<Vec<U256>, (u32, u32)> =
+        runtime::call_versioned_contract(library_address, None, "try_get_amounts_in", args);
+    mappings::set_key(&mappings::try_get_amounts_in_key(), amounts);
+}
+
+#[no_mangle]
+fn all_pairs_length() {
+    let library_address: ContractPackageHash = mappings::get_key(&mappings::library_key());
+
+    let factory: Key = runtime::get_named_arg("factory");
+
+    let args: RuntimeArgs = runtime_args! {
+        "factory" => factory
+    };
+
+    let length: u64 =
+        runtime::call_versioned_contract(library_address, None, "all_pairs_length", args);
+    mappings::set_key(&mappings::all_pairs_length_key(), length);
+}
+
+#[no_mangle]
+fn all_pairs() {
+    let library_address: ContractPackageHash = mappings::get_key(&mappings::library_key());
+
+    let factory: Key = runtime::get_named_arg("factory");
+    let offset: u64 = runtime::get_named_arg("offset");
+    let limit: u64 = runtime::get_named_arg("limit");
+
+    let args: RuntimeArgs = runtime_args! {
+        "factory" => factory,
+        "offset" => offset,
+        "limit" => limit
+    };
+
+    let pairs: Vec<(Key, Key, Key)> =
+        runtime::call_versioned_contract(library_address, None, "all_pairs", args);
+    mappings::set_key(&mappings::all_pairs_key(), pairs);
+}
+
+#[no_mangle]
+fn pairs_for_token() {
+    let library_address: ContractPackageHash = mappings::get_key(&mappings::library_key());
+
+    let factory: Key = runtime::get_named_arg("factory");
+    let token: Key = runtime::get_named_arg("token");
+    let offset: u64 = runtime::get_named_arg("offset");
+    let limit: u64 = runtime::get_named_arg("limit");
+
+    let args: RuntimeArgs = runtime_args! {
+        "factory" => factory,
+        "token" => token,
+        "offset" => offset,
+        "limit" => limit
+    };
+
+    let pairs: Vec<(Key, Key, Key)> =
+        runtime::call_versioned_contract(library_address, None, "pairs_for_token", args);
+    mappings::set_key(&mappings::pairs_for_token_key(), pairs);
+}
+
+#[no_mangle]
+fn consult() {
+    let router_address: ContractPackageHash = mappings::get_key(&mappings::router_key());
+
+    let token_in: Key = runtime::get_named_arg("token_in");
+    let amount_in: U256 = runtime::get_named_arg("amount_in");
+    let token_out: Key = runtime::get_named_arg("token_out");
+    let window: u64 = runtime::get_named_arg("window");
+
+    let args: RuntimeArgs = runtime_args! {
+        "token_in" => token_in,
+        "amount_in" => amount_in,
+        "token_out" => token_out,
+        "window" => window
+    };
+
+    let amount_out: U256 = runtime::call_versioned_contract(router_address, None, "consult", args);
+    mappings::set_key(&mappings::consult_key(), amount_out);
+}
+
+#[no_mangle]
+fn quote_add_liquidity() {
+    let router_address: ContractPackageHash = mappings::get_key(&mappings::router_key());
+
+    let token_a: Key = runtime::get_named_arg("token_a");
+    let token_b: Key = runtime::get_named_arg("token_b");
+    let amount_a_desired: U256 = runtime::get_named_arg("amount_a_desired");
+    let amount_b_desired: U256 = runtime::get_named_arg("amount_b_desired");
+
+    let args: RuntimeArgs = runtime_args! {
+        "token_a" => token_a,
+        "token_b" => token_b,
+        "amount_a_desired" => amount_a_desired,
+        "amount_b_desired" => amount_b_desired
+    };
+
+    let quote: (U256, U256, U256) =
+        runtime::call_versioned_contract(router_address, None, "quote_add_liquidity", args);
+    mappings::set_key(&mappings::quote_add_liquidity_key(), quote);
+}
+
+#[no_mangle]
+fn quote_remove_liquidity() {
+    let router_address: ContractPackageHash = mappings::get_key(&mappings::router_key());
+
+    let token_a: Key = runtime::get_named_arg("token_a");
+    let token_b: Key = runtime::get_named_arg("token_b");
+    let liquidity: U256 = runtime::get_named_arg("liquidity");
+
+    let args: RuntimeArgs = runtime_args! {
+        "token_a" => token_a,
+        "token_b" => token_b,
+        "liquidity" => liquidity
+    };
+
+    let amounts: (U256, U256) =
+        runtime::call_versioned_contract(router_address, None, "quote_remove_liquidity", args);
+    mappings::set_key(&mappings::quote_remove_liquidity_key(), amounts);
+}
+
+#[no_mangle]
+fn get_pair_info() {
+    let router_address: ContractPackageHash = mappings::get_key(&mappings::router_key());
+
+    let token_a: Key = runtime::get_named_arg("token_a");
+    let token_b: Key = runtime::get_named_arg("token_b");
+
+    let args: RuntimeArgs = runtime_args! {
+        "token_a" => token_a,
+        "token_b" => token_b
+    };
+
+    let info: ((Key, U256), (Key, Key), (U128, U128, u64)) =
+        runtime::call_versioned_contract(router_address, None, "get_pair_info", args);
+    mappings::set_key(&mappings::get_pair_info_key(), info);
+}
+
+#[no_mangle]
+fn get_amounts_out_batch() {
+    let router_address: ContractPackageHash = mappings::get_key(&mappings::router_key());
+
+    let amounts_in: Vec<U256> = runtime::get_named_arg("amounts_in");
+    let path: Vec<Key> = runtime::get_named_arg("path");
+
+    let args: RuntimeArgs = runtime_args! {
+        "amounts_in" => amounts_in,
+        "path" => path
+    };
+
+    let amounts: Vec<Vec<U256>> =
+        runtime::call_versioned_contract(router_address, None, "get_amounts_out_batch", args);
+    mappings::set_key(&mappings::get_amounts_out_batch_key(), amounts);
+}
+
+#[no_mangle]
+fn get_amounts_in_batch() {
+    let router_address: ContractPackageHash = mappings::get_key(&mappings::router_key());
+
+    let amounts_out: Vec<U256> = runtime::get_named_arg("amounts_out");
+    let path: Vec<Key> = runtime::get_named_arg("path");
+
+    let args: RuntimeArgs = runtime_args! {
+        "amounts_out" => amounts_out,
+        "path" => path
+    };
+
+    let amounts: Vec<Vec<U256>> =
+        runtime::call_versioned_contract(router_address, None, "get_amounts_in_batch", args);
+    mappings::set_key(&mappings::get_amounts_in_batch_key(), amounts);
+}
+
+// Flash swap callback that pays back the loan without the fee, which the router must refuse.
+#[no_mangle]
+fn flash_swap_call() {
+    let token: Key = runtime::get_named_arg("token");
+    let amount: U256 = runtime::get_named_arg("amount");
+    let pair: Key = runtime::get_named_arg("pair");
+
+    let result: Result<(), u32> = runtime::call_versioned_contract(
+        ContractPackageHash::from(token.into_hash().unwrap_or_revert()),
+        None,
+        "transfer",
+        runtime_args! {
+            "recipient" => pair,
+            "amount" => amount
+        },
+    );
+    result.unwrap_or_revert();
+}
+
 #[no_mangle]
 fn approve() {
     let token: Key = runtime::get_named_arg("token");
@@ -819,6 +1062,7 @@ fn get_entry_points() -> EntryPoints {
             Parameter::new("to", CLType::Key),
             Parameter::new("deadline", CLType::U256),
             Parameter::new("pair", CLType::Option(Box::new(CLType::Key))),
+            Parameter::new("min_liquidity_out", CLType::Option(Box::new(CLType::U256))),
         ],
         <()>::cl_type(),
         EntryPointAccess::Public,
@@ -836,6 +1080,7 @@ fn get_entry_points() -> EntryPoints {
             Parameter::new("to", Key::cl_type()),
             Parameter::new("deadline", CLType::U256),
             Parameter::new("pair", CLType::Option(Box::new(CLType::Key))),
+            Parameter::new("min_liquidity_out", CLType::Option(Box::new(CLType::U256))),
             Parameter::new("router_hash", CLType::Key),
             Parameter::new("self_hash", CLType::Key),
         ],
@@ -978,6 +1223,7 @@ fn get_entry_points() -> EntryPoints {
             Parameter::new("path", CLType::List(Box::new(String::cl_type()))),
             Parameter::new("to", CLType::Key),
             Parameter::new("deadline", CLType::U256),
+            Parameter::new("max_price_impact_bps", CLType::Option(Box::new(CLType::U32))),
         ],
         <()>::cl_type(),
         EntryPointAccess::Public,
@@ -992,6 +1238,7 @@ fn get_entry_points() -> EntryPoints {
             Parameter::new("path", CLType::List(Box::new(String::cl_type()))),
             Parameter::new("to", CLType::Key),
             Parameter::new("deadline", CLType::U256),
+            Parameter::new("max_price_impact_bps", CLType::Option(Box::new(CLType::U32))),
         ],
         CLType::List(Box::new(CLType::U256)),
         EntryPointAccess::Public,
@@ -1006,6 +1253,7 @@ fn get_entry_points() -> EntryPoints {
             Parameter::new("path", CLType::List(Box::new(String::cl_type()))),
             Parameter::new("to", CLType::Key),
             Parameter::new("deadline", CLType::U256),
+            Parameter::new("max_price_impact_bps", CLType::Option(Box::new(CLType::U32))),
             Parameter::new("router_hash", CLType::Key),
         ],
         CLType::List(Box::new(CLType::U256)),
@@ -1020,6 +1268,7 @@ fn get_entry_points() -> EntryPoints {
             Parameter::new("amount_in_max", CLType::U256),
             Parameter::new("path", CLType::List(Box::new(String::cl_type()))),
             Parameter::new("deadline", CLType::U256),
+            Parameter::new("max_price_impact_bps", CLType::Option(Box::new(CLType::U32))),
         ],
         CLType::List(Box::new(CLType::U256)),
         EntryPointAccess::Public,
@@ -1033,6 +1282,7 @@ fn get_entry_points() -> EntryPoints {
             Parameter::new("amount_out_min", CLType::U256),
             Parameter::new("path", CLType::List(Box::new(String::cl_type()))),
             Parameter::new("deadline", CLType::U256),
+            Parameter::new("max_price_impact_bps", CLType::Option(Box::new(CLType::U32))),
         ],
         CLType::List(Box::new(CLType::U256)),
         EntryPointAccess::Public,
@@ -1047,6 +1297,7 @@ fn get_entry_points() -> EntryPoints {
             Parameter::new("path", CLType::List(Box::new(String::cl_type()))),
             Parameter::new("to", CLType::Key),
             Parameter::new("deadline", CLType::U256),
+            Parameter::new("max_price_impact_bps", CLType::Option(Box::new(CLType::U32))),
         ],
         CLType::List(Box::new(CLType::U256)),
         EntryPointAccess::Public,
@@ -1149,6 +1400,149 @@ fn get_entry_points() -> EntryPoints {
         EntryPointType::Contract,
     ));
 
+    entry_points.add_entry_point(EntryPoint::new(
+        "try_get_amounts_out",
+        vec![
+            Parameter::new("factory", Key::cl_type()),
+            Parameter::new("amount_in", U256::cl_type()),
+            Parameter::new("path", CLType::List(Box::new(Key::cl_type()))),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "try_get_amounts_in",
+        vec![
+            Parameter::new("factory", Key::cl_type()),
+            Parameter::new("amount_out", U256::cl_type()),
+            Parameter::new("path", CLType::List(Box::new(Key::cl_type()))),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "all_pairs_length",
+        vec![Parameter::new("factory", Key::cl_type())],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "all_pairs",
+        vec![
+            Parameter::new("factory", Key::cl_type()),
+            Parameter::new("offset", u64::cl_type()),
+            Parameter::new("limit", u64::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "pairs_for_token",
+        vec![
+            Parameter::new("factory", Key::cl_type()),
+            Parameter::new("token", Key::cl_type()),
+            Parameter::new("offset", u64::cl_type()),
+            Parameter::new("limit", u64::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "consult",
+        vec![
+            Parameter::new("token_in", Key::cl_type()),
+            Parameter::new("amount_in", U256::cl_type()),
+            Parameter::new("token_out", Key::cl_type()),
+            Parameter::new("window", u64::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "quote_add_liquidity",
+        vec![
+            Parameter::new("token_a", Key::cl_type()),
+            Parameter::new("token_b", Key::cl_type()),
+            Parameter::new("amount_a_desired", U256::cl_type()),
+            Parameter::new("amount_b_desired", U256::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "quote_remove_liquidity",
+        vec![
+            Parameter::new("token_a", Key::cl_type()),
+            Parameter::new("token_b", Key::cl_type()),
+            Parameter::new("liquidity", U256::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "get_pair_info",
+        vec![
+            Parameter::new("token_a", Key::cl_type()),
+            Parameter::new("token_b", Key::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "get_amounts_out_batch",
+        vec![
+            Parameter::new("amounts_in", CLType::List(Box::new(U256::cl_type()))),
+            Parameter::new("path", CLType::List(Box::new(Key::cl_type()))),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "get_amounts_in_batch",
+        vec![
+            Parameter::new("amounts_out", CLType::List(Box::new(U256::cl_type()))),
+            Parameter::new("path", CLType::List(Box::new(Key::cl_type()))),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
+    entry_points.add_entry_point(EntryPoint::new(
+        "flash_swap_call",
+        vec![
+            Parameter::new("initiator", Key::cl_type()),
+            Parameter::new("token", Key::cl_type()),
+            Parameter::new("amount", U256::cl_type()),
+            Parameter::new("fee", U256::cl_type()),
+            Parameter::new("pair", Key::cl_type()),
+            Parameter::new("data", String::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+
     entry_points.add_entry_point(EntryPoint::new(
         "approve",
         vec![
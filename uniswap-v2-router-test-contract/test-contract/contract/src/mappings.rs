@@ -101,6 +101,66 @@ pub fn swap_cspr_for_exact_tokens() -> String {
     format!("swap_cspr_for_exact_tokens")
 }
 
+pub fn get_reserves_key() -> String {
+    format!("get_reserves_result")
+}
+
+pub fn quote_key() -> String {
+    format!("quote_result")
+}
+
+pub fn get_amount_out_key() -> String {
+    format!("get_amount_out_result")
+}
+
+pub fn get_amount_in_key() -> String {
+    format!("get_amount_in_result")
+}
+
+pub fn try_get_amounts_out_key() -> String {
+    format!("try_get_amounts_out_result")
+}
+
+pub fn try_get_amounts_in_key() -> String {
+    format!("try_get_amounts_in_result")
+}
+
+pub fn all_pairs_length_key() -> String {
+    format!("all_pairs_length_result")
+}
+
+pub fn all_pairs_key() -> String {
+    format!("all_pairs_result")
+}
+
+pub fn pairs_for_token_key() -> String {
+    format!("pairs_for_token_result")
+}
+
+pub fn consult_key() -> String {
+    format!("consult_result")
+}
+
+pub fn quote_add_liquidity_key() -> String {
+    format!("quote_add_liquidity_result")
+}
+
+pub fn quote_remove_liquidity_key() -> String {
+    format!("quote_remove_liquidity_result")
+}
+
+pub fn get_pair_info_key() -> String {
+    format!("get_pair_info_result")
+}
+
+pub fn get_amounts_out_batch_key() -> String {
+    format!("get_amounts_out_batch_result")
+}
+
+pub fn get_amounts_in_batch_key() -> String {
+    format!("get_amounts_in_batch_result")
+}
+
 pub fn purse_balance() -> String {
     format!("purse_balance")
 }
@@ -0,0 +1,110 @@
+//! A token stand-in whose transfers report failure instead of reverting, used to pin the router's
+//! `TransferFailed` errors. The dummy erc20 reverts on its own when a transfer cannot happen, which
+//! shadows the router's checks of the transfer result.
+
+#![no_main]
+#![no_std]
+
+extern crate alloc;
+use alloc::{format, string::String, vec};
+
+use casper_contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{
+    contracts::{ContractHash, NamedKeys},
+    CLTyped, CLValue, EntryPoint, EntryPointAccess, EntryPointType, EntryPoints, Key, Parameter,
+    U256,
+};
+
+// Returned by every transfer.
+const TRANSFER_ERROR: u32 = 1;
+
+fn fail() {
+    let result: Result<(), u32> = Err(TRANSFER_ERROR);
+    runtime::ret(CLValue::from_t(result).unwrap_or_revert());
+}
+
+/// Accepts any allowance.
+#[no_mangle]
+fn approve() {}
+
+#[no_mangle]
+fn transfer() {
+    fail();
+}
+
+#[no_mangle]
+fn transfer_from() {
+    fail();
+}
+
+#[no_mangle]
+fn balance_of() {
+    runtime::ret(CLValue::from_t(U256::zero()).unwrap_or_revert());
+}
+
+fn get_entry_points() -> EntryPoints {
+    let mut entry_points = EntryPoints::new();
+    entry_points.add_entry_point(EntryPoint::new(
+        "approve",
+        vec![
+            Parameter::new("spender", Key::cl_type()),
+            Parameter::new("amount", U256::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "transfer",
+        vec![
+            Parameter::new("recipient", Key::cl_type()),
+            Parameter::new("amount", U256::cl_type()),
+        ],
+        <Result<(), u32>>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "transfer_from",
+        vec![
+            Parameter::new("owner", Key::cl_type()),
+            Parameter::new("recipient", Key::cl_type()),
+            Parameter::new("amount", U256::cl_type()),
+        ],
+        <Result<(), u32>>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "balance_of",
+        vec![Parameter::new("owner", Key::cl_type())],
+        U256::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let (package_hash, access_token) = storage::create_contract_package_at_hash();
+    let (contract_hash, _): (ContractHash, _) =
+        storage::add_contract_version(package_hash, get_entry_points(), NamedKeys::new());
+
+    let contract_name: String = runtime::get_named_arg("contract_name");
+    runtime::put_key(
+        &format!("{}_package_hash", contract_name),
+        package_hash.into(),
+    );
+    runtime::put_key(
+        &format!("{}_contract_hash", contract_name),
+        contract_hash.into(),
+    );
+    runtime::put_key(
+        &format!("{}_package_access_token", contract_name),
+        access_token.into(),
+    );
+}
@@ -0,0 +1,182 @@
+//! A reference borrower for the router's `flash_swap`: borrows tokens from a pair through the
+//! router and pays them back with the fee from its own balance in the same call.
+//!
+//! `borrow` starts a flash swap with the borrower as callback. The router then calls back
+//! `flash_swap_call`, which only accepts loans the borrower started itself through the router it
+//! was installed with. Anybody can name any contract as callback, so a borrower that skipped
+//! these checks would pay fees for flash swaps of strangers. Each loan it repays is kept in the
+//! `last_flash_swap` named key as `(token, amount, fee)`.
+
+#![no_main]
+#![no_std]
+
+extern crate alloc;
+use alloc::{format, string::String, vec};
+
+use casper_contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{
+    contracts::{ContractHash, NamedKeys},
+    runtime_args, ApiError, CLType, CLTyped, ContractPackageHash, EntryPoint, EntryPointAccess,
+    EntryPointType, EntryPoints, Key, Parameter, RuntimeArgs, U256,
+};
+
+const ROUTER: &str = "router";
+const PACKAGE_HASH: &str = "package_hash";
+const LAST_FLASH_SWAP: &str = "last_flash_swap";
+
+#[repr(u32)]
+pub enum Error {
+    NotRouter = 0,
+    NotInitiator = 1,
+    RepaymentFailed = 2,
+}
+
+impl From<Error> for ApiError {
+    fn from(error: Error) -> ApiError {
+        ApiError::User(error as u16)
+    }
+}
+
+fn read_key(name: &str) -> Key {
+    let uref = runtime::get_key(name)
+        .unwrap_or_revert()
+        .into_uref()
+        .unwrap_or_revert();
+    storage::read(uref).unwrap_or_revert().unwrap_or_revert()
+}
+
+fn package(key: Key) -> ContractPackageHash {
+    ContractPackageHash::from(key.into_hash().unwrap_or_revert())
+}
+
+/// Borrows `amount` of `token` from its pair with `other_token` through the router.
+#[no_mangle]
+fn borrow() {
+    let token: Key = runtime::get_named_arg("token");
+    let other_token: Key = runtime::get_named_arg("other_token");
+    let amount: U256 = runtime::get_named_arg("amount");
+    let data: String = runtime::get_named_arg("data");
+    let deadline: U256 = runtime::get_named_arg("deadline");
+    let _: U256 = runtime::call_versioned_contract(
+        package(read_key(ROUTER)),
+        None,
+        "flash_swap",
+        runtime_args! {
+            "token" => token,
+            "other_token" => other_token,
+            "amount" => amount,
+            "callback" => read_key(PACKAGE_HASH),
+            "data" => data,
+            "deadline" => deadline
+        },
+    );
+}
+
+/// Pays the loan of a flash swap the borrower started back to the pair, together with the fee.
+#[no_mangle]
+fn flash_swap_call() {
+    let initiator: Key = runtime::get_named_arg("initiator");
+    let token: Key = runtime::get_named_arg("token");
+    let amount: U256 = runtime::get_named_arg("amount");
+    let fee: U256 = runtime::get_named_arg("fee");
+    let pair: Key = runtime::get_named_arg("pair");
+    let caller: Key = runtime::get_call_stack()
+        .iter()
+        .nth_back(1)
+        .and_then(|caller| caller.contract_package_hash())
+        .map(|package_hash| Key::from(*package_hash))
+        .unwrap_or_revert_with(Error::NotRouter);
+    if caller != read_key(ROUTER) {
+        runtime::revert(Error::NotRouter);
+    }
+    if initiator != read_key(PACKAGE_HASH) {
+        runtime::revert(Error::NotInitiator);
+    }
+
+    let result: Result<(), u32> = runtime::call_versioned_contract(
+        package(token),
+        None,
+        "transfer",
+        runtime_args! {
+            "recipient" => pair,
+            "amount" => amount + fee
+        },
+    );
+    if result.is_err() {
+        runtime::revert(Error::RepaymentFailed);
+    }
+    let last_flash_swap = runtime::get_key(LAST_FLASH_SWAP)
+        .unwrap_or_revert()
+        .into_uref()
+        .unwrap_or_revert();
+    storage::write(last_flash_swap, (token, amount, fee));
+}
+
+fn get_entry_points() -> EntryPoints {
+    let mut entry_points = EntryPoints::new();
+    entry_points.add_entry_point(EntryPoint::new(
+        "borrow",
+        vec![
+            Parameter::new("token", Key::cl_type()),
+            Parameter::new("other_token", Key::cl_type()),
+            Parameter::new("amount", U256::cl_type()),
+            Parameter::new("data", String::cl_type()),
+            Parameter::new("deadline", U256::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "flash_swap_call",
+        vec![
+            Parameter::new("initiator", Key::cl_type()),
+            Parameter::new("token", Key::cl_type()),
+            Parameter::new("amount", U256::cl_type()),
+            Parameter::new("fee", U256::cl_type()),
+            Parameter::new("pair", Key::cl_type()),
+            Parameter::new("data", String::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let router: Key = runtime::get_named_arg(ROUTER);
+    let (package_hash, access_token) = storage::create_contract_package_at_hash();
+
+    let mut named_keys = NamedKeys::new();
+    named_keys.insert(ROUTER.into(), storage::new_uref(router).into());
+    named_keys.insert(
+        PACKAGE_HASH.into(),
+        storage::new_uref(Key::from(package_hash)).into(),
+    );
+    named_keys.insert(
+        LAST_FLASH_SWAP.into(),
+        storage::new_uref((Key::from(package_hash), U256::zero(), U256::zero())).into(),
+    );
+
+    let (contract_hash, _): (ContractHash, _) =
+        storage::add_contract_version(package_hash, get_entry_points(), named_keys);
+
+    let contract_name: String = runtime::get_named_arg("contract_name");
+    runtime::put_key(
+        &format!("{}_package_hash", contract_name),
+        package_hash.into(),
+    );
+    runtime::put_key(
+        &format!("{}_contract_hash", contract_name),
+        contract_hash.into(),
+    );
+    runtime::put_key(
+        &format!("{}_package_access_token", contract_name),
+        access_token.into(),
+    );
+}
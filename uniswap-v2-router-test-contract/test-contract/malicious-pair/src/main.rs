@@ -0,0 +1,224 @@
+//! A pair stand-in that lets the factory initialize it but reports whatever tokens and factory it
+//! was installed with, used to check that the router refuses pairs it cannot trust. It accepts
+//! deposits without minting and reports every burn as paying out all the recipient holds, so a
+//! router trusting it would pay out of its own balance. Its swaps call back no callee, like a
+//! pair deployed with a callee other than the router.
+
+#![no_main]
+#![no_std]
+
+extern crate alloc;
+use alloc::{format, string::String, vec};
+
+use casper_contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{
+    bytesrepr::FromBytes,
+    contracts::{ContractHash, ContractPackageHash, NamedKeys},
+    runtime_args, CLType, CLTyped, CLValue, EntryPoint, EntryPointAccess, EntryPointType,
+    EntryPoints, Key, Parameter, RuntimeArgs, U128, U256,
+};
+
+const TOKEN0: &str = "token0";
+const TOKEN1: &str = "token1";
+const FACTORY: &str = "factory";
+const PACKAGE_HASH: &str = "package_hash";
+
+fn read<T: CLTyped + FromBytes>(name: &str) -> T {
+    let uref = runtime::get_key(name)
+        .unwrap_or_revert()
+        .into_uref()
+        .unwrap_or_revert();
+    storage::read(uref).unwrap_or_revert().unwrap_or_revert()
+}
+
+fn balance_of(token: Key, owner: Key) -> U256 {
+    runtime::call_versioned_contract(
+        ContractPackageHash::from(token.into_hash().unwrap_or_revert()),
+        None,
+        "balance_of",
+        runtime_args! {
+            "owner" => owner
+        },
+    )
+}
+
+/// Accepts the factory's initialization and keeps the tokens it was installed with.
+#[no_mangle]
+fn initialize() {}
+
+#[no_mangle]
+fn token0() {
+    runtime::ret(CLValue::from_t(read::<Key>(TOKEN0)).unwrap_or_revert());
+}
+
+#[no_mangle]
+fn token1() {
+    runtime::ret(CLValue::from_t(read::<Key>(TOKEN1)).unwrap_or_revert());
+}
+
+#[no_mangle]
+fn factory() {
+    runtime::ret(CLValue::from_t(read::<Key>(FACTORY)).unwrap_or_revert());
+}
+
+#[no_mangle]
+fn package_hash() {
+    let package_hash: ContractPackageHash = read(PACKAGE_HASH);
+    runtime::ret(CLValue::from_t(package_hash).unwrap_or_revert());
+}
+
+/// Reports empty reserves, so every deposit is taken at the desired amounts.
+#[no_mangle]
+fn get_reserves() {
+    let reserves: (U128, U128, u64) = (U128::zero(), U128::zero(), 0);
+    runtime::ret(CLValue::from_t(reserves).unwrap_or_revert());
+}
+
+#[no_mangle]
+fn mint() {
+    runtime::ret(CLValue::from_t(U256::from(1000)).unwrap_or_revert());
+}
+
+/// Accepts any liquidity without moving it.
+#[no_mangle]
+fn transfer_from() {
+    let result: Result<(), u32> = Ok(());
+    runtime::ret(CLValue::from_t(result).unwrap_or_revert());
+}
+
+/// Pays nothing but reports what `to` already holds of both tokens as paid out.
+#[no_mangle]
+fn burn() {
+    let to: Key = runtime::get_named_arg("to");
+    let amounts: (U256, U256) = (
+        balance_of(read::<Key>(TOKEN0), to),
+        balance_of(read::<Key>(TOKEN1), to),
+    );
+    runtime::ret(CLValue::from_t(amounts).unwrap_or_revert());
+}
+
+/// Completes without paying out or calling back a callee.
+#[no_mangle]
+fn swap() {}
+
+fn get_entry_points() -> EntryPoints {
+    let mut entry_points = EntryPoints::new();
+    entry_points.add_entry_point(EntryPoint::new(
+        "initialize",
+        vec![
+            Parameter::new("token0", Key::cl_type()),
+            Parameter::new("token1", Key::cl_type()),
+            Parameter::new("factory_hash", Key::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        TOKEN0,
+        vec![],
+        CLType::Key,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        TOKEN1,
+        vec![],
+        CLType::Key,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        FACTORY,
+        vec![],
+        CLType::Key,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        PACKAGE_HASH,
+        vec![],
+        ContractPackageHash::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "get_reserves",
+        vec![],
+        <(U128, U128, u64)>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "mint",
+        vec![Parameter::new("to", Key::cl_type())],
+        U256::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "transfer_from",
+        vec![
+            Parameter::new("owner", Key::cl_type()),
+            Parameter::new("recipient", Key::cl_type()),
+            Parameter::new("amount", U256::cl_type()),
+        ],
+        <Result<(), u32>>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "burn",
+        vec![Parameter::new("to", Key::cl_type())],
+        <(U256, U256)>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "swap",
+        vec![
+            Parameter::new("amount0_out", U256::cl_type()),
+            Parameter::new("amount1_out", U256::cl_type()),
+            Parameter::new("to", Key::cl_type()),
+            Parameter::new("data", String::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let token0: Key = runtime::get_named_arg(TOKEN0);
+    let token1: Key = runtime::get_named_arg(TOKEN1);
+    let factory: Key = runtime::get_named_arg(FACTORY);
+
+    let (package_hash, access_token) = storage::create_contract_package_at_hash();
+    let mut named_keys = NamedKeys::new();
+    named_keys.insert(TOKEN0.into(), storage::new_uref(token0).into());
+    named_keys.insert(TOKEN1.into(), storage::new_uref(token1).into());
+    named_keys.insert(FACTORY.into(), storage::new_uref(factory).into());
+    named_keys.insert(PACKAGE_HASH.into(), storage::new_uref(package_hash).into());
+
+    let (contract_hash, _): (ContractHash, _) =
+        storage::add_contract_version(package_hash, get_entry_points(), named_keys);
+
+    let contract_name: String = runtime::get_named_arg("contract_name");
+    runtime::put_key(
+        &format!("{}_package_hash", contract_name),
+        package_hash.into(),
+    );
+    runtime::put_key(
+        &format!("{}_contract_hash", contract_name),
+        contract_hash.into(),
+    );
+    runtime::put_key(
+        &format!("{}_package_access_token", contract_name),
+        access_token.into(),
+    );
+}
@@ -0,0 +1,143 @@
+//! Session code that approves the router, calls it and revokes the approval in one deploy.
+//!
+//! It calls `destination_entrypoint` on the router package `router_hash`, forwarding the runtime
+//! arguments named after the parameters of that entry point like the purse-proxy does, including
+//! `Option` arguments left out as `None`. Before the call the router is approved for exactly the
+//! tokens the entry point may pull:
+//!
+//! - `add_liquidity`: `amount_a_desired` of `token_a` and `amount_b_desired` of `token_b`.
+//! - `remove_liquidity`, `remove_liquidity_cspr` and `remove_liquidity_cspr_to_key`: `liquidity` of
+//!   the `pair` runtime argument.
+//! - Exact input swaps: `amount_in` of the first token of `path`.
+//! - Exact output swaps: `amount_in_max` of the first token of `path`.
+//!
+//! Every approval is reset to zero after the call, so the router keeps no allowance whatever it
+//! spent. An allowance the account gave the router before is replaced as well. Purse parameters
+//! are paid into the main purse, which the router only gets add access to, and the amounts the
+//! router returns are written to the `<destination_entrypoint>_result` named key of the account.
+
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+use alloc::{string::String, vec, vec::Vec};
+use casper_contract::{
+    contract_api::{account, runtime},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{
+    runtime_args, ApiError, CLType, CLValue, ContractPackageHash, Key, RuntimeArgs, URef, U256,
+};
+use uniswap_v2_router::session::{self, Raw};
+
+pub const DESTINATION_ENTRYPOINT_RUNTIME_ARG: &str = "destination_entrypoint";
+pub const ROUTER_HASH_RUNTIME_ARG: &str = "router_hash";
+/// Liquidity token the `remove_liquidity` entry points burn.
+pub const PAIR_RUNTIME_ARG: &str = "pair";
+
+const JS_CLIENT_SUFFIX: &str = "_js_client";
+
+#[repr(u32)]
+pub enum Error {
+    UnknownEntryPoint = 1,
+    UnsupportedResult = 2,
+    /// The entry point spends no tokens, or spends CSPR and has to go through the purse-proxy.
+    NothingToApprove = 3,
+    InvalidPath = 4,
+}
+
+impl From<Error> for ApiError {
+    fn from(error: Error) -> ApiError {
+        ApiError::User(error as u16)
+    }
+}
+
+fn package_hash(key: Key) -> ContractPackageHash {
+    ContractPackageHash::from(key.into_hash().unwrap_or_revert())
+}
+
+/// First token of a swap path, given as formatted key strings.
+fn path_start() -> ContractPackageHash {
+    let path: Vec<String> = runtime::get_named_arg("path");
+    let first: &String = path.first().unwrap_or_revert_with(Error::InvalidPath);
+    package_hash(Key::from_formatted_str(first).unwrap_or_revert_with(Error::InvalidPath))
+}
+
+/// Tokens and amounts the router may pull during `destination`.
+fn approvals(destination: &str) -> Vec<(ContractPackageHash, U256)> {
+    let destination = destination
+        .strip_suffix(JS_CLIENT_SUFFIX)
+        .unwrap_or(destination);
+    match destination {
+        "add_liquidity" => vec![
+            (
+                package_hash(runtime::get_named_arg("token_a")),
+                runtime::get_named_arg("amount_a_desired"),
+            ),
+            (
+                package_hash(runtime::get_named_arg("token_b")),
+                runtime::get_named_arg("amount_b_desired"),
+            ),
+        ],
+        "remove_liquidity" | "remove_liquidity_cspr" | "remove_liquidity_cspr_to_key" => vec![(
+            package_hash(runtime::get_named_arg(PAIR_RUNTIME_ARG)),
+            runtime::get_named_arg("liquidity"),
+        )],
+        "swap_exact_tokens_for_tokens"
+        | "swap_exact_tokens_for_cspr"
+        | "swap_exact_tokens_for_cspr_to_key" => {
+            vec![(path_start(), runtime::get_named_arg("amount_in"))]
+        }
+        "swap_tokens_for_exact_tokens"
+        | "swap_tokens_for_exact_cspr"
+        | "swap_tokens_for_exact_cspr_to_key" => {
+            vec![(path_start(), runtime::get_named_arg("amount_in_max"))]
+        }
+        _ => runtime::revert(Error::NothingToApprove),
+    }
+}
+
+fn approve(token: ContractPackageHash, spender: Key, amount: U256) {
+    let Raw(_) = runtime::call_versioned_contract(
+        token,
+        None,
+        "approve",
+        runtime_args! {
+            "spender" => spender,
+            "amount" => amount
+        },
+    );
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let destination_entrypoint: String = runtime::get_named_arg(DESTINATION_ENTRYPOINT_RUNTIME_ARG);
+    let router_address: ContractPackageHash =
+        package_hash(runtime::get_named_arg(ROUTER_HASH_RUNTIME_ARG));
+    let destination = session::destination(&destination_entrypoint)
+        .unwrap_or_revert_with(Error::UnknownEntryPoint);
+    let approvals = approvals(destination.name);
+
+    let main_purse: URef = account::get_main_purse();
+    let mut args: RuntimeArgs = RuntimeArgs::new();
+    for param in destination.params.iter() {
+        let value: CLValue = match param.cl_type {
+            // purses only receive CSPR, so the router gets no more than add access
+            CLType::URef => CLValue::from_t(main_purse.into_add()).unwrap_or_revert(),
+            _ => session::forward_arg(param.name, param.cl_type.clone()),
+        };
+        args.insert_cl_value(param.name, value);
+    }
+
+    let router = Key::from(router_address);
+    for (token, amount) in approvals.iter() {
+        approve(*token, router, *amount);
+    }
+    let Raw(result) =
+        runtime::call_versioned_contract(router_address, None, destination.name, args);
+    for (token, _) in approvals.iter() {
+        approve(*token, router, U256::zero());
+    }
+
+    session::store_amounts(&destination, result, Error::UnsupportedResult);
+}
@@ -1,3 +1,18 @@
+//! Session code through which an account calls the router entry points that move CSPR.
+//!
+//! Contracts cannot take CSPR out of an account's main purse, so the account runs this wasm in
+//! its own context instead. It calls `destination_entrypoint` on the router package
+//! `router_hash` and forwards the runtime arguments named after the parameters of that entry
+//! point unchanged. `Option` arguments may be left out and are forwarded as `None`. The
+//! parameters are looked up in the router's entry point table, so a new entry point is supported
+//! as soon as the proxy is rebuilt.
+//!
+//! `URef` parameters are filled in by the proxy. `purse` gets a fresh purse holding `amount`
+//! motes from the main purse, any other purse parameter is paid into the main purse, which the
+//! router only gets add access to. CSPR the router leaves in the fresh purse goes back to the
+//! main purse. The amounts the router returns are written to the `<destination_entrypoint>_result`
+//! named key of the account, as a `Vec<U256>` in the order the entry point returns them.
+
 #![no_std]
 #![no_main]
 
@@ -7,27 +22,26 @@
 // We need to explicitly import the std alloc crate and `alloc::string::String` as we're in a
 // `no_std` environment.
 extern crate alloc;
-use alloc::{string::String, vec::Vec};
+use alloc::string::String;
 use casper_contract::{
     contract_api::{account, runtime, system},
     unwrap_or_revert::UnwrapOrRevert,
 };
-use casper_types::{
-    runtime_args, ApiError, ContractPackageHash, Key, RuntimeArgs, URef, U256, U512,
-};
-
-pub const DESTINATION_ADD_LIQUIDITY_CSPR: &str = "add_liquidity_cspr";
-pub const DESTINATION_REMOVE_LIQUIDITY_CSPR: &str = "remove_liquidity_cspr";
-pub const DESTINATION_SWAP_EXACT_CSPR_FOR_TOKENS: &str = "swap_exact_cspr_for_tokens";
-pub const DESTINATION_SWAP_CSPR_FOR_EXACT_TOKENS: &str = "swap_cspr_for_exact_tokens";
-pub const DESTINATION_SWAP_TOKENS_FOR_EXACT_CSPR: &str = "swap_tokens_for_exact_cspr";
-pub const DESTINATION_SWAP_EXACT_TOKENS_FOR_CSPR: &str = "swap_exact_tokens_for_cspr";
+use casper_types::{ApiError, CLType, CLValue, ContractPackageHash, Key, RuntimeArgs, URef, U512};
+use uniswap_v2_router::session::{self, Raw};
 
+pub const DESTINATION_ENTRYPOINT_RUNTIME_ARG: &str = "destination_entrypoint";
+pub const ROUTER_HASH_RUNTIME_ARG: &str = "router_hash";
 pub const AMOUNT_RUNTIME_ARG: &str = "amount";
 
+/// Router parameter taking the purse an entry point spends CSPR from.
+pub const PURSE_PARAMETER: &str = "purse";
+
 #[repr(u32)]
 pub enum Error {
     Abort = 0,
+    UnknownEntryPoint = 1,
+    UnsupportedResult = 2,
 }
 
 impl From<Error> for ApiError {
@@ -38,239 +52,43 @@ impl From<Error> for ApiError {
 
 #[no_mangle]
 pub extern "C" fn call() {
-    let destination_entrypoint: String = runtime::get_named_arg("destination_entrypoint");
-    let main_purse: URef = account::get_main_purse();
-
-    let ret: Result<(), u32> = match destination_entrypoint.as_str() {
-        DESTINATION_ADD_LIQUIDITY_CSPR => {
-            let amount: U512 = runtime::get_named_arg(AMOUNT_RUNTIME_ARG);
-            let secondary_purse: URef = system::create_purse();
-            system::transfer_from_purse_to_purse(main_purse, secondary_purse, amount, None)
-                .unwrap_or_revert();
-
-            let router_address: Key = runtime::get_named_arg("router_hash");
-            let router_address: ContractPackageHash =
-                ContractPackageHash::from(router_address.into_hash().unwrap_or_revert());
-
-            let self_hash: Key = runtime::get_named_arg("self_hash");
-            let self_hash: ContractPackageHash =
-                ContractPackageHash::from(self_hash.into_hash().unwrap_or_revert());
-
-            let token: Key = runtime::get_named_arg("token");
-            let amount_token_desired: U256 = runtime::get_named_arg("amount_token_desired");
-            let amount_cspr_desired: U256 = runtime::get_named_arg("amount_cspr_desired");
-            let amount_token_min: U256 = runtime::get_named_arg("amount_token_min");
-            let amount_cspr_min: U256 = runtime::get_named_arg("amount_cspr_min");
-            let to: Key = runtime::get_named_arg("to");
-            let deadline: U256 = runtime::get_named_arg("deadline");
-            let pair: Option<Key> = runtime::get_named_arg("pair");
-
-            let args: RuntimeArgs = runtime_args! {
-                "token" => token,
-                "amount_token_desired" => amount_token_desired,
-                "amount_cspr_desired" => amount_cspr_desired,
-                "amount_token_min" => amount_token_min,
-                "amount_cspr_min" => amount_cspr_min,
-                "to" => to,
-                "deadline" => deadline,
-                "pair" => pair,
-                "purse" => secondary_purse
-            };
-
-            let (amount_token, amount_cspr, liquidity): (U256, U256, U256) =
-                runtime::call_versioned_contract(
-                    router_address,
-                    None,
-                    DESTINATION_ADD_LIQUIDITY_CSPR,
-                    args,
-                );
-
-            let () = runtime::call_versioned_contract(
-                self_hash,
-                None,
-                "set_liquidity_cspr_keys",
-                runtime_args! { "amount_token" => amount_token, "amount_cspr" => amount_cspr, "liquidity" => liquidity},
-            );
-            Ok(())
-        }
-        DESTINATION_REMOVE_LIQUIDITY_CSPR => {
-            let self_hash: Key = runtime::get_named_arg("self_hash");
-            let self_hash: ContractPackageHash =
-                ContractPackageHash::from(self_hash.into_hash().unwrap_or_revert());
-
-            let router_address: Key = runtime::get_named_arg("router_hash");
-            let router_address: ContractPackageHash =
-                ContractPackageHash::from(router_address.into_hash().unwrap_or_revert());
-
-            let token: Key = runtime::get_named_arg("token");
-            let liquidity: U256 = runtime::get_named_arg("liquidity");
-            let amount_token_min: U256 = runtime::get_named_arg("amount_token_min");
-            let amount_cspr_min: U256 = runtime::get_named_arg("amount_cspr_min");
-            let to: Key = runtime::get_named_arg("to");
-            let deadline: U256 = runtime::get_named_arg("deadline");
-
-            let args: RuntimeArgs = runtime_args! {
-                "token" => token,
-                "liquidity" => liquidity,
-                "amount_token_min" => amount_token_min,
-                "amount_cspr_min" => amount_cspr_min,
-                "to" => to,
-                "deadline" => deadline,
-                "to_purse" => main_purse
-            };
-
-            let (amount_token, amount_cspr): (U256, U256) = runtime::call_versioned_contract(
-                router_address,
-                None,
-                "remove_liquidity_cspr",
-                args,
-            );
-
-            let () = runtime::call_versioned_contract(
-                self_hash,
-                None,
-                "set_remove_liquidity_cspr_keys",
-                runtime_args! { "amount_a" => amount_token, "amount_b" => amount_cspr},
-            );
-            Ok(())
-        }
-
-        DESTINATION_SWAP_EXACT_CSPR_FOR_TOKENS => {
-            let amount: U512 = runtime::get_named_arg(AMOUNT_RUNTIME_ARG);
-            let secondary_purse: URef = system::create_purse();
-            system::transfer_from_purse_to_purse(main_purse, secondary_purse, amount, None)
-                .unwrap_or_revert();
+    let destination_entrypoint: String = runtime::get_named_arg(DESTINATION_ENTRYPOINT_RUNTIME_ARG);
+    let router_address: Key = runtime::get_named_arg(ROUTER_HASH_RUNTIME_ARG);
+    let router_address: ContractPackageHash =
+        ContractPackageHash::from(router_address.into_hash().unwrap_or_revert());
+    let destination = session::destination(&destination_entrypoint)
+        .unwrap_or_revert_with(Error::UnknownEntryPoint);
 
-            let router_address: Key = runtime::get_named_arg("router_hash");
-            let router_address: ContractPackageHash =
-                ContractPackageHash::from(router_address.into_hash().unwrap_or_revert());
-
-            let amount_out_min: U256 = runtime::get_named_arg("amount_out_min");
-            let amount_in: U256 = runtime::get_named_arg("amount_in");
-            let _path: Vec<String> = runtime::get_named_arg("path");
-            let to: Key = runtime::get_named_arg("to");
-            let deadline: U256 = runtime::get_named_arg("deadline");
-            let mut path: Vec<Key> = Vec::new();
-            for i in 0..(_path.len()) {
-                path.push(Key::from_formatted_str(&_path[i]).unwrap());
-            }
-
-            let args: RuntimeArgs = runtime_args! {
-                "amount_out_min" => amount_out_min,
-                "amount_in" => amount_in,
-                "path" => _path,
-                "to" => to,
-                "deadline" => deadline,
-                "purse" => secondary_purse
-            };
-            let _amounts: Vec<U256> = runtime::call_versioned_contract(
-                router_address,
-                None,
-                DESTINATION_SWAP_EXACT_CSPR_FOR_TOKENS,
-                args,
-            );
-            Ok(())
-        }
-        DESTINATION_SWAP_CSPR_FOR_EXACT_TOKENS => {
-            let amount: U512 = runtime::get_named_arg(AMOUNT_RUNTIME_ARG);
-            let secondary_purse: URef = system::create_purse();
-            system::transfer_from_purse_to_purse(main_purse, secondary_purse, amount, None)
-                .unwrap_or_revert();
-
-            let router_address: Key = runtime::get_named_arg("router_hash");
-            let router_address: ContractPackageHash =
-                ContractPackageHash::from(router_address.into_hash().unwrap_or_revert());
-
-            let amount_out: U256 = runtime::get_named_arg("amount_out");
-            let amount_in_max: U256 = runtime::get_named_arg("amount_in_max");
-            let _path: Vec<String> = runtime::get_named_arg("path");
-            let to: Key = runtime::get_named_arg("to");
-            let deadline: U256 = runtime::get_named_arg("deadline");
-
-            let mut path: Vec<Key> = Vec::new();
-            for i in 0..(_path.len()) {
-                path.push(Key::from_formatted_str(&_path[i]).unwrap());
-            }
-
-            let args: RuntimeArgs = runtime_args! {
-                "amount_out" => amount_out,
-                "amount_in_max" => amount_in_max,
-                "path" => _path,
-                "to" => to,
-                "deadline" => deadline,
-                "purse" => secondary_purse
-            };
-            let _amounts: Vec<U256> = runtime::call_versioned_contract(
-                router_address,
-                None,
-                DESTINATION_SWAP_CSPR_FOR_EXACT_TOKENS,
-                args,
-            );
-            Ok(())
-        }
-        DESTINATION_SWAP_TOKENS_FOR_EXACT_CSPR => {
-            let router_address: Key = runtime::get_named_arg("router_hash");
-            let router_address: ContractPackageHash =
-                ContractPackageHash::from(router_address.into_hash().unwrap_or_revert());
-
-            let amount_out: U256 = runtime::get_named_arg("amount_out");
-            let amount_in_max: U256 = runtime::get_named_arg("amount_in_max");
-            let _path: Vec<String> = runtime::get_named_arg("path");
-            let deadline: U256 = runtime::get_named_arg("deadline");
-
-            let mut path: Vec<Key> = Vec::new();
-            for i in 0..(_path.len()) {
-                path.push(Key::from_formatted_str(&_path[i]).unwrap());
-            }
-
-            let args: RuntimeArgs = runtime_args! {
-                "amount_out" => amount_out,
-                "amount_in_max" => amount_in_max,
-                "path" => _path,
-                "to" => main_purse,
-                "deadline" => deadline
-            };
-
-            let _amounts: Vec<U256> = runtime::call_versioned_contract(
-                router_address,
-                None,
-                DESTINATION_SWAP_TOKENS_FOR_EXACT_CSPR,
-                args,
-            );
-            Ok(())
-        }
-        DESTINATION_SWAP_EXACT_TOKENS_FOR_CSPR => {
-            let router_address: Key = runtime::get_named_arg("router_hash");
-            let router_address: ContractPackageHash =
-                ContractPackageHash::from(router_address.into_hash().unwrap_or_revert());
-
-            let amount_in: U256 = runtime::get_named_arg("amount_in");
-            let amount_out_min: U256 = runtime::get_named_arg("amount_out_min");
-            let _path: Vec<String> = runtime::get_named_arg("path");
-            let deadline: U256 = runtime::get_named_arg("deadline");
-            let mut path: Vec<Key> = Vec::new();
-            for i in 0..(_path.len()) {
-                path.push(Key::from_formatted_str(&_path[i]).unwrap());
+    let main_purse: URef = account::get_main_purse();
+    let mut secondary_purse: Option<URef> = None;
+    let mut args: RuntimeArgs = RuntimeArgs::new();
+    for param in destination.params.iter() {
+        let value: CLValue = match param.cl_type {
+            CLType::URef if param.name == PURSE_PARAMETER => {
+                let amount: U512 = runtime::get_named_arg(AMOUNT_RUNTIME_ARG);
+                let purse: URef = system::create_purse();
+                system::transfer_from_purse_to_purse(main_purse, purse, amount, None)
+                    .unwrap_or_revert();
+                secondary_purse = Some(purse);
+                CLValue::from_t(purse).unwrap_or_revert()
             }
+            // other purses only receive CSPR, so the router gets no more than add access
+            CLType::URef => CLValue::from_t(main_purse.into_add()).unwrap_or_revert(),
+            _ => session::forward_arg(param.name, param.cl_type.clone()),
+        };
+        args.insert_cl_value(param.name, value);
+    }
 
-            let args: RuntimeArgs = runtime_args! {
-                "amount_in" => amount_in,
-                "amount_out_min" => amount_out_min,
-                "path" => _path,
-                "to" => main_purse,
-                "deadline" => deadline
-            };
+    let Raw(result) =
+        runtime::call_versioned_contract(router_address, None, destination.name, args);
 
-            let _amounts: Vec<U256> = runtime::call_versioned_contract(
-                router_address,
-                None,
-                DESTINATION_SWAP_EXACT_TOKENS_FOR_CSPR,
-                args,
-            );
-            Ok(())
+    if let Some(purse) = secondary_purse {
+        let unused: U512 = system::get_purse_balance(purse).unwrap_or_revert();
+        if !unused.is_zero() {
+            system::transfer_from_purse_to_purse(purse, main_purse, unused, None)
+                .unwrap_or_revert();
         }
+    }
 
-        _ => Err(ApiError::UnexpectedKeyVariant.into()),
-    };
-    ret.unwrap_or_revert();
+    session::store_amounts(&destination, result, Error::UnsupportedResult);
 }
@@ -0,0 +1,73 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use casper_types::{ContractPackageHash, Key, PublicKey, SecretKey, U512};
+use serde::Deserialize;
+
+/// Contents of the TOML file passed with `--config`.
+///
+/// Contract hashes are package hashes in their formatted `hash-…` form.
+#[derive(Debug, Deserialize)]
+pub struct Config {
+    pub chain_name: String,
+    pub router: String,
+    pub factory: String,
+    pub wcspr: String,
+    /// PEM file holding the secret key deploys are signed with.
+    pub secret_key: PathBuf,
+    /// Motes paid for each deploy.
+    pub payment_amount: u64,
+    /// Compiled purse-proxy wasm, needed for the CSPR flows.
+    pub purse_proxy: Option<PathBuf>,
+}
+
+impl Config {
+    pub fn load(path: &Path) -> Result<Config, String> {
+        let text = fs::read_to_string(path)
+            .map_err(|error| format!("reading {}: {}", path.display(), error))?;
+        toml::from_str(&text).map_err(|error| format!("parsing {}: {}", path.display(), error))
+    }
+
+    pub fn router(&self) -> Result<ContractPackageHash, String> {
+        package_hash("router", &self.router)
+    }
+
+    pub fn factory(&self) -> Result<ContractPackageHash, String> {
+        package_hash("factory", &self.factory)
+    }
+
+    pub fn wcspr(&self) -> Result<Key, String> {
+        package_hash("wcspr", &self.wcspr).map(Key::from)
+    }
+
+    pub fn purse_proxy(&self) -> Result<Vec<u8>, String> {
+        let path = self
+            .purse_proxy
+            .as_ref()
+            .ok_or("purse_proxy is required for CSPR deploys")?;
+        fs::read(path).map_err(|error| format!("reading {}: {}", path.display(), error))
+    }
+
+    pub fn payment_amount(&self) -> U512 {
+        U512::from(self.payment_amount)
+    }
+
+    pub fn secret_key(&self) -> Result<SecretKey, String> {
+        SecretKey::from_file(&self.secret_key)
+            .map_err(|error| format!("reading {}: {}", self.secret_key.display(), error))
+    }
+
+    /// The account the secret key belongs to, the default recipient of every operation.
+    pub fn account(&self) -> Result<Key, String> {
+        let public_key = PublicKey::from(&self.secret_key()?);
+        Ok(Key::from(public_key.to_account_hash()))
+    }
+}
+
+fn package_hash(name: &str, value: &str) -> Result<ContractPackageHash, String> {
+    Key::from_formatted_str(value)
+        .ok()
+        .and_then(|key| key.into_hash())
+        .map(ContractPackageHash::new)
+        .ok_or_else(|| format!("{} must be a formatted hash key, got {}", name, value))
+}
@@ -0,0 +1,669 @@
+//! Offline quotes and deploy construction for the Uniswap V2 Router.
+//!
+//! Quotes are computed from reserve snapshots passed on the command line, nothing is fetched from
+//! a node. Deploy commands print the signed deploy as JSON, ready for
+//! `casper-client send-deploy --input`.
+
+mod config;
+mod quote;
+
+use std::fs;
+use std::path::PathBuf;
+use std::process;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use casper_execution_engine::core::engine_state::ExecutableDeployItem;
+use casper_node::types::Deploy;
+use casper_types::{Key, URef, U256, U512};
+use clap::{Args, Parser, Subcommand};
+use uniswap_v2_sdk::{purse_proxy, router, DeployBuilder, PurseProxy, Router};
+
+use config::Config;
+use quote::{Reserves, MAX_BPS};
+
+#[derive(Parser)]
+#[clap(
+    name = "uniswap-cli",
+    about = "Offline quotes and deploys for the Uniswap V2 Router"
+)]
+struct Cli {
+    #[clap(long, default_value = "uniswap-cli.toml")]
+    config: PathBuf,
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Args)]
+struct DeployArgs {
+    /// Write the deploy JSON here instead of stdout
+    #[clap(long)]
+    output: Option<PathBuf>,
+    /// Minutes until the router rejects the call
+    #[clap(long, default_value = "30")]
+    deadline_minutes: u64,
+    /// Price movement tolerated between the reserve snapshot and execution
+    #[clap(long, default_value = "50")]
+    slippage_bps: u32,
+    /// Recipient, the signing account by default
+    #[clap(long, parse(try_from_str = parse_key))]
+    to: Option<Key>,
+}
+
+#[derive(Args)]
+struct SwapArgs {
+    /// Tokens to swap through, comma separated. WCSPR is added for CSPR swaps.
+    #[clap(long, required = true, use_delimiter = true, parse(try_from_str = parse_key))]
+    path: Vec<Key>,
+    /// `reserve_in:reserve_out` of each hop, in path order
+    #[clap(long, required = true, use_delimiter = true)]
+    reserves: Vec<Reserves>,
+    /// Revert when a hop executes this many basis points or more below its mid-price
+    #[clap(long)]
+    max_price_impact_bps: Option<u32>,
+}
+
+/// Signed approval of the LP tokens, the `*_with_permit` entry points are called when given.
+#[derive(Args)]
+struct PermitArgs {
+    #[clap(long, requires = "signature")]
+    public_key: Option<String>,
+    #[clap(long, requires = "public-key")]
+    signature: Option<String>,
+    #[clap(long, requires = "signature")]
+    approve_max: bool,
+}
+
+impl PermitArgs {
+    fn into_permit(self) -> Option<(bool, String, String)> {
+        Some((self.approve_max, self.public_key?, self.signature?))
+    }
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Check the config and print the hashes it resolves to
+    Config,
+    /// Chained output amounts for an exact input
+    QuoteExactIn {
+        #[clap(long, parse(try_from_str = parse_u256))]
+        amount_in: U256,
+        #[clap(long, required = true, use_delimiter = true)]
+        reserves: Vec<Reserves>,
+    },
+    /// Chained input amounts for an exact output
+    QuoteExactOut {
+        #[clap(long, parse(try_from_str = parse_u256))]
+        amount_out: U256,
+        #[clap(long, required = true, use_delimiter = true)]
+        reserves: Vec<Reserves>,
+    },
+    AddLiquidity {
+        #[clap(long, parse(try_from_str = parse_key))]
+        token_a: Key,
+        #[clap(long, parse(try_from_str = parse_key))]
+        token_b: Key,
+        #[clap(long, parse(try_from_str = parse_u256))]
+        amount_a: U256,
+        #[clap(long, parse(try_from_str = parse_u256))]
+        amount_b: U256,
+        /// `reserve_a:reserve_b`, left out for a new pair
+        #[clap(long)]
+        reserves: Option<Reserves>,
+        #[clap(long, parse(try_from_str = parse_key))]
+        pair: Option<Key>,
+        /// Revert unless at least this much LP token is minted
+        #[clap(long, parse(try_from_str = parse_u256))]
+        min_liquidity_out: Option<U256>,
+        #[clap(long)]
+        js_client: bool,
+        #[clap(flatten)]
+        deploy: DeployArgs,
+    },
+    /// Goes through the purse-proxy
+    AddLiquidityCspr {
+        #[clap(long, parse(try_from_str = parse_key))]
+        token: Key,
+        #[clap(long, parse(try_from_str = parse_u256))]
+        amount_token: U256,
+        /// Motes
+        #[clap(long, parse(try_from_str = parse_u256))]
+        amount_cspr: U256,
+        /// `reserve_token:reserve_cspr`, left out for a new pair
+        #[clap(long)]
+        reserves: Option<Reserves>,
+        #[clap(long, parse(try_from_str = parse_key))]
+        pair: Option<Key>,
+        /// Revert unless at least this much LP token is minted
+        #[clap(long, parse(try_from_str = parse_u256))]
+        min_liquidity_out: Option<U256>,
+        #[clap(flatten)]
+        deploy: DeployArgs,
+    },
+    RemoveLiquidity {
+        #[clap(long, parse(try_from_str = parse_key))]
+        token_a: Key,
+        #[clap(long, parse(try_from_str = parse_key))]
+        token_b: Key,
+        #[clap(long, parse(try_from_str = parse_u256))]
+        liquidity: U256,
+        /// `reserve_a:reserve_b`
+        #[clap(long)]
+        reserves: Reserves,
+        /// LP token supply of the pair
+        #[clap(long, parse(try_from_str = parse_u256))]
+        total_supply: U256,
+        #[clap(long)]
+        js_client: bool,
+        #[clap(flatten)]
+        permit: PermitArgs,
+        #[clap(flatten)]
+        deploy: DeployArgs,
+    },
+    /// Goes through the purse-proxy, or straight to the router with a permit
+    RemoveLiquidityCspr {
+        #[clap(long, parse(try_from_str = parse_key))]
+        token: Key,
+        #[clap(long, parse(try_from_str = parse_u256))]
+        liquidity: U256,
+        /// `reserve_token:reserve_cspr`
+        #[clap(long)]
+        reserves: Reserves,
+        /// LP token supply of the pair
+        #[clap(long, parse(try_from_str = parse_u256))]
+        total_supply: U256,
+        /// Purse the CSPR is paid to, needed with a permit
+        #[clap(long, parse(try_from_str = parse_uref))]
+        to_purse: Option<URef>,
+        #[clap(long)]
+        js_client: bool,
+        #[clap(flatten)]
+        permit: PermitArgs,
+        #[clap(flatten)]
+        deploy: DeployArgs,
+    },
+    SwapExactTokensForTokens {
+        #[clap(long, parse(try_from_str = parse_u256))]
+        amount_in: U256,
+        #[clap(long)]
+        js_client: bool,
+        #[clap(flatten)]
+        swap: SwapArgs,
+        #[clap(flatten)]
+        deploy: DeployArgs,
+    },
+    SwapTokensForExactTokens {
+        #[clap(long, parse(try_from_str = parse_u256))]
+        amount_out: U256,
+        #[clap(long)]
+        js_client: bool,
+        #[clap(flatten)]
+        swap: SwapArgs,
+        #[clap(flatten)]
+        deploy: DeployArgs,
+    },
+    /// Goes through the purse-proxy
+    SwapExactCsprForTokens {
+        #[clap(long, parse(try_from_str = parse_u256))]
+        amount_in: U256,
+        #[clap(flatten)]
+        swap: SwapArgs,
+        #[clap(flatten)]
+        deploy: DeployArgs,
+    },
+    /// Goes through the purse-proxy
+    SwapCsprForExactTokens {
+        #[clap(long, parse(try_from_str = parse_u256))]
+        amount_out: U256,
+        #[clap(flatten)]
+        swap: SwapArgs,
+        #[clap(flatten)]
+        deploy: DeployArgs,
+    },
+    /// Goes through the purse-proxy, the CSPR is paid to the main purse
+    SwapTokensForExactCspr {
+        #[clap(long, parse(try_from_str = parse_u256))]
+        amount_out: U256,
+        #[clap(flatten)]
+        swap: SwapArgs,
+        #[clap(flatten)]
+        deploy: DeployArgs,
+    },
+    /// Goes through the purse-proxy, the CSPR is paid to the main purse
+    SwapExactTokensForCspr {
+        #[clap(long, parse(try_from_str = parse_u256))]
+        amount_in: U256,
+        #[clap(flatten)]
+        swap: SwapArgs,
+        #[clap(flatten)]
+        deploy: DeployArgs,
+    },
+    /// Record a price observation of a pair for the router's oracle
+    UpdateOracle {
+        #[clap(long, parse(try_from_str = parse_key))]
+        token_a: Key,
+        #[clap(long, parse(try_from_str = parse_key))]
+        token_b: Key,
+        #[clap(flatten)]
+        deploy: DeployArgs,
+    },
+    /// Account for the escrow of a batch of the limit orders placed before the router tracked
+    /// escrows
+    MigrateEscrows {
+        /// Orders to go through in this deploy
+        #[clap(long)]
+        limit: u64,
+        #[clap(flatten)]
+        deploy: DeployArgs,
+    },
+}
+
+fn parse_u256(value: &str) -> Result<U256, String> {
+    U256::from_dec_str(value).map_err(|_| format!("invalid amount {}", value))
+}
+
+fn parse_key(value: &str) -> Result<Key, String> {
+    Key::from_formatted_str(value).map_err(|_| format!("invalid key {}", value))
+}
+
+fn parse_uref(value: &str) -> Result<URef, String> {
+    URef::from_formatted_str(value).map_err(|_| format!("invalid uref {}", value))
+}
+
+fn u512(value: U256) -> U512 {
+    let mut bytes = [0u8; 32];
+    value.to_little_endian(&mut bytes);
+    U512::from_little_endian(&bytes)
+}
+
+/// Deploy inputs shared by every command.
+struct Context {
+    config: Config,
+    deadline: U256,
+    slippage_bps: u32,
+    to: Key,
+    output: Option<PathBuf>,
+}
+
+impl Context {
+    fn new(config: Config, args: DeployArgs) -> Result<Context, String> {
+        if args.slippage_bps > MAX_BPS {
+            return Err(format!("slippage_bps can not exceed {}", MAX_BPS));
+        }
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_err(|error| error.to_string())?
+            .as_millis() as u64;
+        let to = match args.to {
+            Some(to) => to,
+            None => config.account()?,
+        };
+        Ok(Context {
+            deadline: U256::from(now + args.deadline_minutes * 60 * 1000),
+            slippage_bps: args.slippage_bps,
+            to,
+            output: args.output,
+            config,
+        })
+    }
+
+    fn min(&self, amount: U256) -> U256 {
+        quote::min_with_slippage(amount, self.slippage_bps)
+    }
+
+    fn max(&self, amount: U256) -> U256 {
+        quote::max_with_slippage(amount, self.slippage_bps)
+    }
+
+    fn router<C: router::RouterCall>(&self, call: C) -> Result<(), String> {
+        let session = Router::new(self.config.router()?).session(&call);
+        self.write(session)
+    }
+
+    fn purse_proxy<C: purse_proxy::PurseProxyCall>(&self, call: C) -> Result<(), String> {
+        let proxy = PurseProxy::new(self.config.purse_proxy()?, self.config.router()?);
+        self.write(proxy.session(&call))
+    }
+
+    fn write(&self, session: ExecutableDeployItem) -> Result<(), String> {
+        let deploy: Deploy =
+            DeployBuilder::new(self.config.chain_name.clone(), self.config.payment_amount())
+                .build(session, &self.config.secret_key()?);
+        let json = serde_json::to_string_pretty(&deploy).map_err(|error| error.to_string())?;
+        match &self.output {
+            Some(path) => fs::write(path, json + "\n")
+                .map_err(|error| format!("writing {}: {}", path.display(), error)),
+            None => {
+                println!("{}", json);
+                Ok(())
+            }
+        }
+    }
+}
+
+fn check_hops(path: &[Key], reserves: &[Reserves]) -> Result<(), String> {
+    if path.len() < 2 || reserves.len() != path.len() - 1 {
+        return Err(format!(
+            "a path of {} tokens needs {} reserve snapshots, got {}",
+            path.len(),
+            path.len().saturating_sub(1),
+            reserves.len()
+        ));
+    }
+    Ok(())
+}
+
+fn print_amounts(amounts: &[U256]) {
+    let amounts: Vec<String> = amounts.iter().map(U256::to_string).collect();
+    eprintln!("amounts: {}", amounts.join(" -> "));
+}
+
+fn run(cli: Cli) -> Result<(), String> {
+    let config = Config::load(&cli.config)?;
+    match cli.command {
+        Command::Config => {
+            println!("chain:   {}", config.chain_name);
+            println!(
+                "router:  {}",
+                Key::from(config.router()?).to_formatted_string()
+            );
+            println!(
+                "factory: {}",
+                Key::from(config.factory()?).to_formatted_string()
+            );
+            println!("wcspr:   {}", config.wcspr()?.to_formatted_string());
+            println!("account: {}", config.account()?.to_formatted_string());
+            Ok(())
+        }
+        Command::QuoteExactIn {
+            amount_in,
+            reserves,
+        } => {
+            print_amounts(&quote::amounts_out(amount_in, &reserves)?);
+            Ok(())
+        }
+        Command::QuoteExactOut {
+            amount_out,
+            reserves,
+        } => {
+            print_amounts(&quote::amounts_in(amount_out, &reserves)?);
+            Ok(())
+        }
+        Command::AddLiquidity {
+            token_a,
+            token_b,
+            amount_a,
+            amount_b,
+            reserves,
+            pair,
+            min_liquidity_out,
+            js_client,
+            deploy,
+        } => {
+            let context = Context::new(config, deploy)?;
+            let (expected_a, expected_b) =
+                quote::add_liquidity_amounts(amount_a, amount_b, reserves)?;
+            print_amounts(&[expected_a, expected_b]);
+            context.router(router::AddLiquidity {
+                token_a,
+                token_b,
+                amount_a_desired: amount_a,
+                amount_b_desired: amount_b,
+                amount_a_min: context.min(expected_a),
+                amount_b_min: context.min(expected_b),
+                to: context.to,
+                deadline: context.deadline,
+                pair,
+                min_liquidity_out,
+                js_client,
+            })
+        }
+        Command::AddLiquidityCspr {
+            token,
+            amount_token,
+            amount_cspr,
+            reserves,
+            pair,
+            min_liquidity_out,
+            deploy,
+        } => {
+            let context = Context::new(config, deploy)?;
+            let (expected_token, expected_cspr) =
+                quote::add_liquidity_amounts(amount_token, amount_cspr, reserves)?;
+            print_amounts(&[expected_token, expected_cspr]);
+            context.purse_proxy(purse_proxy::AddLiquidityCspr {
+                amount: u512(amount_cspr),
+                token,
+                amount_token_desired: amount_token,
+                amount_cspr_desired: amount_cspr,
+                amount_token_min: context.min(expected_token),
+                amount_cspr_min: context.min(expected_cspr),
+                to: context.to,
+                deadline: context.deadline,
+                pair,
+                min_liquidity_out,
+            })
+        }
+        Command::RemoveLiquidity {
+            token_a,
+            token_b,
+            liquidity,
+            reserves,
+            total_supply,
+            js_client,
+            permit,
+            deploy,
+        } => {
+            let context = Context::new(config, deploy)?;
+            let (expected_a, expected_b) =
+                quote::remove_liquidity_amounts(liquidity, reserves, total_supply)?;
+            print_amounts(&[expected_a, expected_b]);
+            match permit.into_permit() {
+                None => context.router(router::RemoveLiquidity {
+                    token_a,
+                    token_b,
+                    liquidity,
+                    amount_a_min: context.min(expected_a),
+                    amount_b_min: context.min(expected_b),
+                    to: context.to,
+                    deadline: context.deadline,
+                    js_client,
+                }),
+                Some((approve_max, public_key, signature)) => {
+                    context.router(router::RemoveLiquidityWithPermit {
+                        token_a,
+                        token_b,
+                        liquidity,
+                        amount_a_min: context.min(expected_a),
+                        amount_b_min: context.min(expected_b),
+                        to: context.to,
+                        deadline: context.deadline,
+                        approve_max,
+                        public_key,
+                        signature,
+                        js_client,
+                    })
+                }
+            }
+        }
+        Command::RemoveLiquidityCspr {
+            token,
+            liquidity,
+            reserves,
+            total_supply,
+            to_purse,
+            js_client,
+            permit,
+            deploy,
+        } => {
+            let context = Context::new(config, deploy)?;
+            let (expected_token, expected_cspr) =
+                quote::remove_liquidity_amounts(liquidity, reserves, total_supply)?;
+            print_amounts(&[expected_token, expected_cspr]);
+            match permit.into_permit() {
+                None => context.purse_proxy(purse_proxy::RemoveLiquidityCspr {
+                    token,
+                    liquidity,
+                    amount_token_min: context.min(expected_token),
+                    amount_cspr_min: context.min(expected_cspr),
+                    to: context.to,
+                    deadline: context.deadline,
+                }),
+                Some((approve_max, public_key, signature)) => {
+                    context.router(router::RemoveLiquidityCsprWithPermit {
+                        token,
+                        liquidity,
+                        amount_token_min: context.min(expected_token),
+                        amount_cspr_min: context.min(expected_cspr),
+                        to: context.to,
+                        deadline: context.deadline,
+                        approve_max,
+                        public_key,
+                        signature,
+                        to_purse: to_purse.ok_or("--to-purse is required with a permit")?,
+                        js_client,
+                    })
+                }
+            }
+        }
+        Command::SwapExactTokensForTokens {
+            amount_in,
+            js_client,
+            swap,
+            deploy,
+        } => {
+            let context = Context::new(config, deploy)?;
+            check_hops(&swap.path, &swap.reserves)?;
+            let amounts = quote::amounts_out(amount_in, &swap.reserves)?;
+            print_amounts(&amounts);
+            context.router(router::SwapExactTokensForTokens {
+                amount_in,
+                amount_out_min: context.min(amounts[amounts.len() - 1]),
+                path: swap.path,
+                to: context.to,
+                deadline: context.deadline,
+                max_price_impact_bps: swap.max_price_impact_bps,
+                js_client,
+            })
+        }
+        Command::SwapTokensForExactTokens {
+            amount_out,
+            js_client,
+            swap,
+            deploy,
+        } => {
+            let context = Context::new(config, deploy)?;
+            check_hops(&swap.path, &swap.reserves)?;
+            let amounts = quote::amounts_in(amount_out, &swap.reserves)?;
+            print_amounts(&amounts);
+            context.router(router::SwapTokensForExactTokens {
+                amount_out,
+                amount_in_max: context.max(amounts[0]),
+                path: swap.path,
+                to: context.to,
+                deadline: context.deadline,
+                max_price_impact_bps: swap.max_price_impact_bps,
+                js_client,
+            })
+        }
+        Command::SwapExactCsprForTokens {
+            amount_in,
+            swap,
+            deploy,
+        } => {
+            let context = Context::new(config, deploy)?;
+            let mut path = vec![context.config.wcspr()?];
+            path.extend(swap.path);
+            check_hops(&path, &swap.reserves)?;
+            let amounts = quote::amounts_out(amount_in, &swap.reserves)?;
+            print_amounts(&amounts);
+            context.purse_proxy(purse_proxy::SwapExactCsprForTokens {
+                amount: u512(amount_in),
+                amount_out_min: context.min(amounts[amounts.len() - 1]),
+                amount_in,
+                path,
+                to: context.to,
+                deadline: context.deadline,
+                max_price_impact_bps: swap.max_price_impact_bps,
+            })
+        }
+        Command::SwapCsprForExactTokens {
+            amount_out,
+            swap,
+            deploy,
+        } => {
+            let context = Context::new(config, deploy)?;
+            let mut path = vec![context.config.wcspr()?];
+            path.extend(swap.path);
+            check_hops(&path, &swap.reserves)?;
+            let amounts = quote::amounts_in(amount_out, &swap.reserves)?;
+            print_amounts(&amounts);
+            let amount_in_max = context.max(amounts[0]);
+            context.purse_proxy(purse_proxy::SwapCsprForExactTokens {
+                amount: u512(amount_in_max),
+                amount_out,
+                amount_in_max,
+                path,
+                to: context.to,
+                deadline: context.deadline,
+                max_price_impact_bps: swap.max_price_impact_bps,
+            })
+        }
+        Command::SwapTokensForExactCspr {
+            amount_out,
+            swap,
+            deploy,
+        } => {
+            let context = Context::new(config, deploy)?;
+            let mut path = swap.path;
+            path.push(context.config.wcspr()?);
+            check_hops(&path, &swap.reserves)?;
+            let amounts = quote::amounts_in(amount_out, &swap.reserves)?;
+            print_amounts(&amounts);
+            context.purse_proxy(purse_proxy::SwapTokensForExactCspr {
+                amount_out,
+                amount_in_max: context.max(amounts[0]),
+                path,
+                deadline: context.deadline,
+                max_price_impact_bps: swap.max_price_impact_bps,
+            })
+        }
+        Command::SwapExactTokensForCspr {
+            amount_in,
+            swap,
+            deploy,
+        } => {
+            let context = Context::new(config, deploy)?;
+            let mut path = swap.path;
+            path.push(context.config.wcspr()?);
+            check_hops(&path, &swap.reserves)?;
+            let amounts = quote::amounts_out(amount_in, &swap.reserves)?;
+            print_amounts(&amounts);
+            context.purse_proxy(purse_proxy::SwapExactTokensForCspr {
+                amount_in,
+                amount_out_min: context.min(amounts[amounts.len() - 1]),
+                path,
+                deadline: context.deadline,
+                max_price_impact_bps: swap.max_price_impact_bps,
+            })
+        }
+        Command::UpdateOracle {
+            token_a,
+            token_b,
+            deploy,
+        } => {
+            let context = Context::new(config, deploy)?;
+            context.router(router::UpdateOracle { token_a, token_b })
+        }
+        Command::MigrateEscrows { limit, deploy } => {
+            let context = Context::new(config, deploy)?;
+            context.router(router::MigrateEscrows { limit })
+        }
+    }
+}
+
+fn main() {
+    if let Err(error) = run(Cli::parse()) {
+        eprintln!("error: {}", error);
+        process::exit(1);
+    }
+}
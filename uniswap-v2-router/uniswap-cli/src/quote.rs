@@ -0,0 +1,167 @@
+//! Quotes from reserve snapshots, using the library's pricing formulas.
+
+use std::str::FromStr;
+
+use casper_types::{api_error::ApiError, U128, U256};
+use uniswap_v2_library::math;
+
+pub const MAX_BPS: u32 = 10_000;
+
+/// Reserves of one pair, `reserve_in:reserve_out` on the command line.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Reserves(pub U128, pub U128);
+
+impl Reserves {
+    fn u256(&self) -> (U256, U256) {
+        (U256::from(self.0.as_u128()), U256::from(self.1.as_u128()))
+    }
+}
+
+impl FromStr for Reserves {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Reserves, String> {
+        let (reserve_in, reserve_out) = value
+            .split_once(':')
+            .ok_or_else(|| format!("reserves must look like 1000:2000, got {}", value))?;
+        let parse = |reserve: &str| {
+            U128::from_dec_str(reserve).map_err(|_| format!("invalid reserve {}", reserve))
+        };
+        Ok(Reserves(parse(reserve_in)?, parse(reserve_out)?))
+    }
+}
+
+/// Names the library error a contract call would have reverted with.
+fn describe(error: ApiError) -> String {
+    let reason = match error {
+        ApiError::User(0) => "overflow",
+        ApiError::User(1) => "underflow",
+        ApiError::User(4) => "insufficient amount",
+        ApiError::User(5) => "insufficient input amount",
+        ApiError::User(6) => "insufficient output amount",
+        ApiError::User(7) => "invalid path",
+        ApiError::User(8) => "insufficient liquidity",
+        _ => "unexpected error",
+    };
+    format!("{} ({:?})", reason, error)
+}
+
+pub fn amounts_out(amount_in: U256, reserves: &[Reserves]) -> Result<Vec<U256>, String> {
+    let reserves: Vec<(U256, U256)> = reserves.iter().map(Reserves::u256).collect();
+    math::get_amounts_out(amount_in, &reserves).map_err(describe)
+}
+
+pub fn amounts_in(amount_out: U256, reserves: &[Reserves]) -> Result<Vec<U256>, String> {
+    let reserves: Vec<(U256, U256)> = reserves.iter().map(Reserves::u256).collect();
+    math::get_amounts_in(amount_out, &reserves).map_err(describe)
+}
+
+/// Lowest amount still accepted when the price may move `slippage_bps` against the caller.
+pub fn min_with_slippage(amount: U256, slippage_bps: u32) -> U256 {
+    amount * U256::from(MAX_BPS - slippage_bps) / U256::from(MAX_BPS)
+}
+
+/// Highest amount still accepted when the price may move `slippage_bps` against the caller.
+pub fn max_with_slippage(amount: U256, slippage_bps: u32) -> U256 {
+    amount * U256::from(MAX_BPS + slippage_bps) / U256::from(MAX_BPS)
+}
+
+/// Amounts the router will take for a deposit, as in its `_add_liquidity`.
+pub fn add_liquidity_amounts(
+    amount_a_desired: U256,
+    amount_b_desired: U256,
+    reserves: Option<Reserves>,
+) -> Result<(U256, U256), String> {
+    let Reserves(reserve_a, reserve_b) = match reserves {
+        Some(reserves) if !reserves.0.is_zero() || !reserves.1.is_zero() => reserves,
+        _ => return Ok((amount_a_desired, amount_b_desired)),
+    };
+    let amount_b_optimal = math::quote(amount_a_desired, reserve_a, reserve_b).map_err(describe)?;
+    if amount_b_optimal <= amount_b_desired {
+        return Ok((amount_a_desired, amount_b_optimal));
+    }
+    let amount_a_optimal = math::quote(amount_b_desired, reserve_b, reserve_a).map_err(describe)?;
+    Ok((amount_a_optimal, amount_b_desired))
+}
+
+/// Amounts paid out for burning `liquidity` of a pair with the given reserves and LP supply.
+pub fn remove_liquidity_amounts(
+    liquidity: U256,
+    reserves: Reserves,
+    total_supply: U256,
+) -> Result<(U256, U256), String> {
+    if total_supply.is_zero() || liquidity > total_supply {
+        return Err(format!(
+            "liquidity {} is not part of a supply of {}",
+            liquidity, total_supply
+        ));
+    }
+    let (reserve_a, reserve_b) = reserves.u256();
+    Ok((
+        liquidity * reserve_a / total_supply,
+        liquidity * reserve_b / total_supply,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reserves(reserve_in: u64, reserve_out: u64) -> Reserves {
+        Reserves(reserve_in.into(), reserve_out.into())
+    }
+
+    #[test]
+    fn parses_reserves() {
+        assert_eq!("1000:2000".parse(), Ok(reserves(1000, 2000)));
+        assert!("1000".parse::<Reserves>().is_err());
+        assert!("a:2000".parse::<Reserves>().is_err());
+    }
+
+    #[test]
+    fn chains_amounts_over_hops() {
+        let hops = [reserves(1000, 1000), reserves(1000, 1000)];
+        let amounts = amounts_out(100.into(), &hops).unwrap();
+        assert_eq!(amounts, vec![100.into(), 90.into(), 82.into()]);
+        let amounts = amounts_in(82.into(), &hops).unwrap();
+        assert_eq!(amounts, vec![100.into(), 90.into(), 82.into()]);
+    }
+
+    #[test]
+    fn reports_library_errors() {
+        let error = amounts_out(0.into(), &[reserves(1000, 1000)]).unwrap_err();
+        assert!(error.starts_with("insufficient input amount"), "{}", error);
+    }
+
+    #[test]
+    fn applies_slippage() {
+        assert_eq!(min_with_slippage(10_000.into(), 50), 9_950.into());
+        assert_eq!(max_with_slippage(10_000.into(), 50), 10_050.into());
+    }
+
+    #[test]
+    fn matches_router_deposit_amounts() {
+        let pool = Some(reserves(1000, 2000));
+        assert_eq!(
+            add_liquidity_amounts(100.into(), 500.into(), pool),
+            Ok((100.into(), 200.into()))
+        );
+        assert_eq!(
+            add_liquidity_amounts(100.into(), 100.into(), pool),
+            Ok((50.into(), 100.into()))
+        );
+        assert_eq!(
+            add_liquidity_amounts(100.into(), 100.into(), None),
+            Ok((100.into(), 100.into()))
+        );
+    }
+
+    #[test]
+    fn splits_removed_liquidity() {
+        assert_eq!(
+            remove_liquidity_amounts(10.into(), reserves(1000, 2000), 100.into()),
+            Ok((100.into(), 200.into()))
+        );
+        assert!(remove_liquidity_amounts(101.into(), reserves(1000, 2000), 100.into()).is_err());
+    }
+}
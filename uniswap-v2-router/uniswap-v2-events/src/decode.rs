@@ -0,0 +1,118 @@
+//! Finds router events among the transforms of an execution result.
+
+use std::collections::BTreeMap;
+
+use casper_types::{CLTyped, CLValue, ExecutionResult, Transform};
+
+use crate::{Error, RouterEvent};
+
+/// Decodes a stored value if it is an event map, `None` for any other value.
+pub fn decode_cl_value(value: &CLValue) -> Option<Result<RouterEvent, Error>> {
+    if *value.cl_type() != BTreeMap::<String, String>::cl_type() {
+        return None;
+    }
+    let fields: BTreeMap<String, String> = value.clone().into_t().ok()?;
+    if !fields.contains_key("event_type") {
+        return None;
+    }
+    Some(RouterEvent::from_fields(fields))
+}
+
+/// Decodes the events written to new URefs by a successful execution.
+///
+/// Since schema version 1 the router also copies each event into its `events` dictionary. Those
+/// writes are skipped so every event is returned once. Events of other contracts using the same
+/// map layout, like the pairs, are returned too. Their `contract_package_hash` tells them apart.
+pub fn decode_execution_result(result: &ExecutionResult) -> Result<Vec<RouterEvent>, Error> {
+    let effect = match result {
+        ExecutionResult::Success { effect, .. } => effect,
+        ExecutionResult::Failure { .. } => return Ok(Vec::new()),
+    };
+    effect
+        .transforms
+        .iter()
+        .filter(|entry| entry.key.starts_with("uref-"))
+        .filter_map(|entry| match &entry.transform {
+            Transform::WriteCLValue(value) => decode_cl_value(value),
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use casper_types::{ExecutionEffect, TransformEntry, U512};
+
+    use crate::event::ADD_RESERVES;
+
+    fn event_fields(event_type: &str) -> BTreeMap<String, String> {
+        let mut fields = BTreeMap::new();
+        fields.insert("contract_package_hash".into(), "11".repeat(32));
+        fields.insert("event_type".into(), event_type.into());
+        fields.insert("user".into(), format!("Key::Account({})", "33".repeat(32)));
+        fields.insert("reserve0".into(), "10".into());
+        fields.insert("reserve1".into(), "20".into());
+        fields.insert("pair_contract_hash".into(), "22".repeat(32));
+        fields
+    }
+
+    fn write(key: &str, value: CLValue) -> TransformEntry {
+        TransformEntry {
+            key: key.into(),
+            transform: Transform::WriteCLValue(value),
+        }
+    }
+
+    fn success(transforms: Vec<TransformEntry>) -> ExecutionResult {
+        ExecutionResult::Success {
+            effect: ExecutionEffect {
+                operations: Vec::new(),
+                transforms,
+            },
+            transfers: Vec::new(),
+            cost: U512::from(1),
+        }
+    }
+
+    #[test]
+    fn decodes_event_urefs_only() {
+        let event = CLValue::from_t(event_fields(ADD_RESERVES)).unwrap();
+        let result = success(vec![
+            write("uref-01-007", event.clone()),
+            write("dictionary-02", event),
+            write("uref-03-007", CLValue::from_t(5u32).unwrap()),
+            write(
+                "uref-04-007",
+                CLValue::from_t(BTreeMap::<String, String>::new()).unwrap(),
+            ),
+        ]);
+        let events = decode_execution_result(&result).unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event_type(), ADD_RESERVES);
+    }
+
+    #[test]
+    fn failed_executions_have_no_events() {
+        let event = CLValue::from_t(event_fields(ADD_RESERVES)).unwrap();
+        let result = ExecutionResult::Failure {
+            effect: ExecutionEffect {
+                operations: Vec::new(),
+                transforms: vec![write("uref-01-007", event)],
+            },
+            transfers: Vec::new(),
+            cost: U512::from(1),
+            error_message: "User error: 1".into(),
+        };
+        assert_eq!(decode_execution_result(&result), Ok(Vec::new()));
+    }
+
+    #[test]
+    fn survives_json_round_trip() {
+        let event = CLValue::from_t(event_fields("swap")).unwrap();
+        let json = serde_json::to_string(&success(vec![write("uref-01-007", event)])).unwrap();
+        let result: ExecutionResult = serde_json::from_str(&json).unwrap();
+        let events = decode_execution_result(&result).unwrap();
+        assert_eq!(events[0].event_type(), "swap");
+    }
+}
@@ -0,0 +1,241 @@
+//! Typed router events, parsed from the string maps written by `UniswapV2Router::emit`.
+//!
+//! Values are written with `to_string`, so keys look like `Key::Account(<hex>)`, package hashes
+//! are bare hex and amounts are decimal.
+
+use std::collections::BTreeMap;
+
+use casper_types::{account::AccountHash, ContractPackageHash, Key, U256};
+
+use crate::Error;
+
+pub const ADD_RESERVES: &str = "addreserves";
+pub const REMOVE_RESERVES: &str = "removereserves";
+
+/// Reserves a liquidity provider added to or removed from a pair.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReservesEvent {
+    pub contract_package_hash: ContractPackageHash,
+    pub user: Key,
+    pub reserve0: U256,
+    pub reserve1: U256,
+    pub pair_contract_hash: ContractPackageHash,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RouterEvent {
+    AddReserves(ReservesEvent),
+    RemoveReserves(ReservesEvent),
+    /// An event type this crate does not know yet, kept with its raw fields.
+    Other {
+        event_type: String,
+        fields: BTreeMap<String, String>,
+    },
+}
+
+impl RouterEvent {
+    pub fn from_fields(fields: BTreeMap<String, String>) -> Result<RouterEvent, Error> {
+        let event_type = match fields.get("event_type") {
+            Some(event_type) => event_type.clone(),
+            None => {
+                return Err(Error::MissingField {
+                    event_type: "unnamed".into(),
+                    field: "event_type",
+                })
+            }
+        };
+        match event_type.as_str() {
+            ADD_RESERVES => Ok(RouterEvent::AddReserves(ReservesEvent::from_fields(
+                &event_type,
+                &fields,
+            )?)),
+            REMOVE_RESERVES => Ok(RouterEvent::RemoveReserves(ReservesEvent::from_fields(
+                &event_type,
+                &fields,
+            )?)),
+            _ => Ok(RouterEvent::Other { event_type, fields }),
+        }
+    }
+
+    pub fn event_type(&self) -> &str {
+        match self {
+            RouterEvent::AddReserves(_) => ADD_RESERVES,
+            RouterEvent::RemoveReserves(_) => REMOVE_RESERVES,
+            RouterEvent::Other { event_type, .. } => event_type,
+        }
+    }
+
+    /// Package of the contract that emitted the event.
+    pub fn contract_package_hash(&self) -> Option<ContractPackageHash> {
+        match self {
+            RouterEvent::AddReserves(event) | RouterEvent::RemoveReserves(event) => {
+                Some(event.contract_package_hash)
+            }
+            RouterEvent::Other { fields, .. } => fields
+                .get("contract_package_hash")
+                .and_then(|value| parse_package_hash("contract_package_hash", value).ok()),
+        }
+    }
+
+    /// The account or contract the event is about, if it names one.
+    pub fn user(&self) -> Option<Key> {
+        match self {
+            RouterEvent::AddReserves(event) | RouterEvent::RemoveReserves(event) => {
+                Some(event.user)
+            }
+            RouterEvent::Other { fields, .. } => fields
+                .get("user")
+                .and_then(|value| parse_key("user", value).ok()),
+        }
+    }
+
+    /// The pair the event is about, if it names one.
+    pub fn pair(&self) -> Option<ContractPackageHash> {
+        match self {
+            RouterEvent::AddReserves(event) | RouterEvent::RemoveReserves(event) => {
+                Some(event.pair_contract_hash)
+            }
+            RouterEvent::Other { fields, .. } => fields
+                .get("pair_contract_hash")
+                .and_then(|value| parse_package_hash("pair_contract_hash", value).ok()),
+        }
+    }
+}
+
+impl ReservesEvent {
+    fn from_fields(event_type: &str, fields: &BTreeMap<String, String>) -> Result<Self, Error> {
+        let field = |field: &'static str| {
+            fields.get(field).ok_or_else(|| Error::MissingField {
+                event_type: event_type.into(),
+                field,
+            })
+        };
+        Ok(ReservesEvent {
+            contract_package_hash: parse_package_hash(
+                "contract_package_hash",
+                field("contract_package_hash")?,
+            )?,
+            user: parse_key("user", field("user")?)?,
+            reserve0: parse_u256("reserve0", field("reserve0")?)?,
+            reserve1: parse_u256("reserve1", field("reserve1")?)?,
+            pair_contract_hash: parse_package_hash(
+                "pair_contract_hash",
+                field("pair_contract_hash")?,
+            )?,
+        })
+    }
+}
+
+fn invalid(field: &'static str, value: &str) -> Error {
+    Error::InvalidField {
+        field,
+        value: value.into(),
+    }
+}
+
+fn parse_hash(value: &str) -> Option<[u8; 32]> {
+    if value.len() != 64 || !value.is_ascii() {
+        return None;
+    }
+    let mut hash = [0u8; 32];
+    for (i, byte) in hash.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&value[2 * i..2 * i + 2], 16).ok()?;
+    }
+    Some(hash)
+}
+
+fn parse_package_hash(field: &'static str, value: &str) -> Result<ContractPackageHash, Error> {
+    parse_hash(value)
+        .map(ContractPackageHash::new)
+        .ok_or_else(|| invalid(field, value))
+}
+
+/// Reads a key in its `Display` form, falling back to the formatted `account-hash-…` form.
+fn parse_key(field: &'static str, value: &str) -> Result<Key, Error> {
+    let inner = |prefix: &str| {
+        value
+            .strip_prefix(prefix)
+            .and_then(|rest| rest.strip_suffix(')'))
+            .and_then(parse_hash)
+    };
+    if let Some(hash) = inner("Key::Account(") {
+        return Ok(Key::Account(AccountHash::new(hash)));
+    }
+    if let Some(hash) = inner("Key::Hash(") {
+        return Ok(Key::Hash(hash));
+    }
+    Key::from_formatted_str(value).map_err(|_| invalid(field, value))
+}
+
+fn parse_u256(field: &'static str, value: &str) -> Result<U256, Error> {
+    U256::from_dec_str(value).map_err(|_| invalid(field, value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PACKAGE: &str = "1111111111111111111111111111111111111111111111111111111111111111";
+    const PAIR: &str = "2222222222222222222222222222222222222222222222222222222222222222";
+
+    fn add_reserves_fields(user: Key, pair: &str) -> BTreeMap<String, String> {
+        let mut fields = BTreeMap::new();
+        fields.insert("contract_package_hash".into(), PACKAGE.into());
+        fields.insert("event_type".into(), ADD_RESERVES.into());
+        fields.insert("user".into(), user.to_string());
+        fields.insert("reserve0".into(), "1000".into());
+        fields.insert("reserve1".into(), "2000".into());
+        fields.insert("pair_contract_hash".into(), pair.into());
+        fields
+    }
+
+    #[test]
+    fn decodes_fields_written_by_the_router() {
+        let user = Key::Account(AccountHash::new([3; 32]));
+        let event = RouterEvent::from_fields(add_reserves_fields(user, PAIR)).unwrap();
+        assert_eq!(
+            event,
+            RouterEvent::AddReserves(ReservesEvent {
+                contract_package_hash: ContractPackageHash::new([0x11; 32]),
+                user,
+                reserve0: 1000.into(),
+                reserve1: 2000.into(),
+                pair_contract_hash: ContractPackageHash::new([0x22; 32]),
+            })
+        );
+        let contract = Key::Hash([4; 32]);
+        let event = RouterEvent::from_fields(add_reserves_fields(contract, PAIR)).unwrap();
+        assert_eq!(event.user(), Some(contract));
+    }
+
+    #[test]
+    fn keeps_unknown_event_types() {
+        let mut fields = add_reserves_fields(Key::Hash([4; 32]), PAIR);
+        fields.insert("event_type".into(), "swap".into());
+        let event = RouterEvent::from_fields(fields).unwrap();
+        assert_eq!(event.event_type(), "swap");
+        assert_eq!(event.user(), Some(Key::Hash([4; 32])));
+        assert_eq!(event.pair(), Some(ContractPackageHash::new([0x22; 32])));
+    }
+
+    #[test]
+    fn rejects_malformed_fields() {
+        let fields = add_reserves_fields(Key::Hash([4; 32]), "not a hash");
+        assert_eq!(
+            RouterEvent::from_fields(fields),
+            Err(Error::InvalidField {
+                field: "pair_contract_hash",
+                value: "not a hash".into()
+            })
+        );
+        let mut fields = add_reserves_fields(Key::Hash([4; 32]), PAIR);
+        fields.remove("reserve1");
+        assert_eq!(
+            RouterEvent::from_fields(fields),
+            Err(Error::MissingField {
+                event_type: ADD_RESERVES.into(),
+                field: "reserve1"
+            })
+        );
+    }
+}
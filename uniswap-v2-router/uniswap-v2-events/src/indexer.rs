@@ -0,0 +1,343 @@
+//! Collects router events from execution results saved to disk.
+//!
+//! Accepted documents are the output of `casper-client get-deploy`, its bare `result` object, a
+//! `DeployProcessed` event from a node's event stream, or a JSON array of any of these.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::Path;
+
+use casper_types::{ContractPackageHash, ExecutionResult, Key};
+use serde::Deserialize;
+
+use crate::{decode_execution_result, Error, RouterEvent};
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Document {
+    Many(Vec<Document>),
+    Rpc {
+        result: DeployInfo,
+    },
+    Processed {
+        #[serde(rename = "DeployProcessed")]
+        processed: DeployProcessed,
+    },
+    Info(DeployInfo),
+}
+
+#[derive(Deserialize)]
+struct DeployInfo {
+    deploy: Deploy,
+    execution_results: Vec<BlockExecutionResult>,
+}
+
+#[derive(Deserialize)]
+struct Deploy {
+    hash: String,
+    header: DeployHeader,
+}
+
+#[derive(Deserialize)]
+struct DeployHeader {
+    timestamp: String,
+}
+
+#[derive(Deserialize)]
+struct BlockExecutionResult {
+    block_hash: String,
+    result: ExecutionResult,
+}
+
+#[derive(Deserialize)]
+struct DeployProcessed {
+    deploy_hash: String,
+    timestamp: String,
+    block_hash: String,
+    execution_result: ExecutionResult,
+}
+
+/// An event with the deploy that emitted it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexedEvent {
+    pub deploy_hash: String,
+    pub block_hash: String,
+    /// Deploy timestamp as the node formats it.
+    pub timestamp: String,
+    pub event: RouterEvent,
+}
+
+/// Router events of the ingested deploys, in the order they were ingested.
+#[derive(Debug, Default)]
+pub struct Indexer {
+    router: Option<ContractPackageHash>,
+    deploys: BTreeSet<String>,
+    events: Vec<IndexedEvent>,
+    by_user: BTreeMap<Key, Vec<usize>>,
+    by_pair: BTreeMap<ContractPackageHash, Vec<usize>>,
+}
+
+impl Indexer {
+    pub fn new() -> Indexer {
+        Indexer::default()
+    }
+
+    /// Keeps only the events emitted by the given router package.
+    pub fn for_router(router: ContractPackageHash) -> Indexer {
+        Indexer {
+            router: Some(router),
+            ..Indexer::default()
+        }
+    }
+
+    /// Ingests one document and returns the number of events added. Deploys seen before are
+    /// skipped, so a file can safely be ingested again.
+    pub fn ingest_str(&mut self, json: &str) -> Result<usize, Error> {
+        let document: Document =
+            serde_json::from_str(json).map_err(|error| Error::Document(error.to_string()))?;
+        self.ingest_document(document)
+    }
+
+    pub fn ingest_file(&mut self, path: &Path) -> Result<usize, Error> {
+        let json = fs::read_to_string(path)
+            .map_err(|error| Error::Document(format!("{}: {}", path.display(), error)))?;
+        self.ingest_str(&json)
+            .map_err(|error| Error::Document(format!("{}: {}", path.display(), error)))
+    }
+
+    /// Ingests every `.json` file of a directory, in file name order.
+    pub fn ingest_dir(&mut self, dir: &Path) -> Result<usize, Error> {
+        let entries = fs::read_dir(dir)
+            .map_err(|error| Error::Document(format!("{}: {}", dir.display(), error)))?;
+        let mut paths = Vec::new();
+        for entry in entries {
+            let path = entry
+                .map_err(|error| Error::Document(format!("{}: {}", dir.display(), error)))?
+                .path();
+            if path
+                .extension()
+                .map_or(false, |extension| extension == "json")
+            {
+                paths.push(path);
+            }
+        }
+        paths.sort();
+        let mut added = 0;
+        for path in paths {
+            added += self.ingest_file(&path)?;
+        }
+        Ok(added)
+    }
+
+    pub fn events(&self) -> &[IndexedEvent] {
+        &self.events
+    }
+
+    /// Events naming `user`, oldest first.
+    pub fn user_history(&self, user: &Key) -> Vec<&IndexedEvent> {
+        self.history(self.by_user.get(user))
+    }
+
+    /// Events about `pair`, oldest first.
+    pub fn pair_history(&self, pair: &ContractPackageHash) -> Vec<&IndexedEvent> {
+        self.history(self.by_pair.get(pair))
+    }
+
+    pub fn users(&self) -> impl Iterator<Item = &Key> {
+        self.by_user.keys()
+    }
+
+    pub fn pairs(&self) -> impl Iterator<Item = &ContractPackageHash> {
+        self.by_pair.keys()
+    }
+
+    fn history(&self, indices: Option<&Vec<usize>>) -> Vec<&IndexedEvent> {
+        indices
+            .map(|indices| indices.iter().map(|&index| &self.events[index]).collect())
+            .unwrap_or_default()
+    }
+
+    fn ingest_document(&mut self, document: Document) -> Result<usize, Error> {
+        match document {
+            Document::Many(documents) => {
+                let mut added = 0;
+                for document in documents {
+                    added += self.ingest_document(document)?;
+                }
+                Ok(added)
+            }
+            Document::Rpc { result: info } | Document::Info(info) => {
+                let mut added = 0;
+                for result in &info.execution_results {
+                    added += self.ingest_result(
+                        &info.deploy.hash,
+                        &result.block_hash,
+                        &info.deploy.header.timestamp,
+                        &result.result,
+                    )?;
+                }
+                Ok(added)
+            }
+            Document::Processed { processed } => self.ingest_result(
+                &processed.deploy_hash,
+                &processed.block_hash,
+                &processed.timestamp,
+                &processed.execution_result,
+            ),
+        }
+    }
+
+    fn ingest_result(
+        &mut self,
+        deploy_hash: &str,
+        block_hash: &str,
+        timestamp: &str,
+        result: &ExecutionResult,
+    ) -> Result<usize, Error> {
+        if self.deploys.contains(deploy_hash) {
+            return Ok(0);
+        }
+        let events = decode_execution_result(result)?;
+        self.deploys.insert(deploy_hash.into());
+        let mut added = 0;
+        for event in events {
+            if self.router.is_some() && event.contract_package_hash() != self.router {
+                continue;
+            }
+            let index = self.events.len();
+            if let Some(user) = event.user() {
+                self.by_user.entry(user).or_default().push(index);
+            }
+            if let Some(pair) = event.pair() {
+                self.by_pair.entry(pair).or_default().push(index);
+            }
+            self.events.push(IndexedEvent {
+                deploy_hash: deploy_hash.into(),
+                block_hash: block_hash.into(),
+                timestamp: timestamp.into(),
+                event,
+            });
+            added += 1;
+        }
+        Ok(added)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use casper_types::{CLValue, ExecutionEffect, Transform, TransformEntry, U512};
+    use serde_json::{json, Value};
+
+    const ROUTER: [u8; 32] = [0x11; 32];
+    const PAIR: [u8; 32] = [0x22; 32];
+    const USER: [u8; 32] = [0x33; 32];
+
+    fn event(package: [u8; 32], event_type: &str, user: [u8; 32]) -> CLValue {
+        let mut fields: BTreeMap<String, String> = BTreeMap::new();
+        fields.insert(
+            "contract_package_hash".into(),
+            ContractPackageHash::new(package).to_string(),
+        );
+        fields.insert("event_type".into(), event_type.into());
+        fields.insert("user".into(), Key::Hash(user).to_string());
+        fields.insert("reserve0".into(), "10".into());
+        fields.insert("reserve1".into(), "20".into());
+        fields.insert(
+            "pair_contract_hash".into(),
+            ContractPackageHash::new(PAIR).to_string(),
+        );
+        CLValue::from_t(fields).unwrap()
+    }
+
+    fn result(events: Vec<CLValue>) -> Value {
+        let transforms = events
+            .into_iter()
+            .enumerate()
+            .map(|(i, event)| TransformEntry {
+                key: format!("uref-{:064x}-007", i),
+                transform: Transform::WriteCLValue(event),
+            })
+            .collect();
+        serde_json::to_value(ExecutionResult::Success {
+            effect: ExecutionEffect {
+                operations: Vec::new(),
+                transforms,
+            },
+            transfers: Vec::new(),
+            cost: U512::from(1),
+        })
+        .unwrap()
+    }
+
+    fn get_deploy(hash: &str, events: Vec<CLValue>) -> String {
+        json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "result": {
+                "api_version": "1.4.5",
+                "deploy": {"hash": hash, "header": {"timestamp": "2022-03-01T10:00:00.000Z"}},
+                "execution_results": [{"block_hash": "b1", "result": result(events)}]
+            }
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn builds_user_and_pair_histories() {
+        let mut indexer = Indexer::for_router(ContractPackageHash::new(ROUTER));
+        let added = indexer
+            .ingest_str(&get_deploy(
+                "d1",
+                vec![
+                    event(ROUTER, "addreserves", USER),
+                    event([0x44; 32], "transfer", USER),
+                ],
+            ))
+            .unwrap();
+        assert_eq!(added, 1);
+        let processed = json!({
+            "DeployProcessed": {
+                "deploy_hash": "d2",
+                "account": "01",
+                "timestamp": "2022-03-01T11:00:00.000Z",
+                "block_hash": "b2",
+                "execution_result": result(vec![event(ROUTER, "removereserves", [0x55; 32])])
+            }
+        });
+        assert_eq!(indexer.ingest_str(&processed.to_string()), Ok(1));
+
+        let user = indexer.user_history(&Key::Hash(USER));
+        assert_eq!(user.len(), 1);
+        assert_eq!(user[0].deploy_hash, "d1");
+        assert_eq!(user[0].event.event_type(), "addreserves");
+        let pair = indexer.pair_history(&ContractPackageHash::new(PAIR));
+        let deploys: Vec<&str> = pair
+            .iter()
+            .map(|event| event.deploy_hash.as_str())
+            .collect();
+        assert_eq!(deploys, vec!["d1", "d2"]);
+        assert_eq!(indexer.users().count(), 2);
+    }
+
+    #[test]
+    fn skips_deploys_already_ingested() {
+        let mut indexer = Indexer::new();
+        let document = format!(
+            "[{}, {}]",
+            get_deploy("d1", vec![event(ROUTER, "addreserves", USER)]),
+            get_deploy("d1", vec![event(ROUTER, "addreserves", USER)])
+        );
+        assert_eq!(indexer.ingest_str(&document), Ok(1));
+        assert_eq!(indexer.events().len(), 1);
+    }
+
+    #[test]
+    fn rejects_unknown_documents() {
+        let mut indexer = Indexer::new();
+        assert!(matches!(
+            indexer.ingest_str("{\"deploy_hash\": \"d1\"}"),
+            Err(Error::Document(_))
+        ));
+    }
+}
@@ -0,0 +1,43 @@
+//! Host-side decoding and indexing of Uniswap V2 Router events.
+//!
+//! The router emits each event as a `BTreeMap<String, String>` written to a fresh URef. [`decode`]
+//! finds those writes in the transforms of an execution result and turns them into
+//! [`RouterEvent`]s, and [`Indexer`] keeps the decoded events of many deploys with per-user and
+//! per-pair histories.
+
+pub mod decode;
+pub mod event;
+pub mod indexer;
+
+pub use decode::{decode_cl_value, decode_execution_result};
+pub use event::{ReservesEvent, RouterEvent};
+pub use indexer::{IndexedEvent, Indexer};
+
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// An event map lacks a field its event type needs.
+    MissingField {
+        event_type: String,
+        field: &'static str,
+    },
+    /// An event field holds something other than what the router writes there.
+    InvalidField { field: &'static str, value: String },
+    /// An execution results document could not be read or parsed.
+    Document(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::MissingField { event_type, field } => {
+                write!(f, "{} event has no {} field", event_type, field)
+            }
+            Error::InvalidField { field, value } => write!(f, "invalid {}: {}", field, value),
+            Error::Document(reason) => write!(f, "invalid execution results: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
@@ -3,3 +3,15 @@ pub mod uniswap_tests;
 
 #[cfg(test)]
 pub mod uniswap_instance;
+
+#[cfg(test)]
+pub mod uniswap_revert_tests;
+
+#[cfg(test)]
+pub mod uniswap_upgrade_tests;
+
+#[cfg(test)]
+pub mod uniswap_gas_benchmarks;
+
+#[cfg(test)]
+pub mod uniswap_abi_tests;
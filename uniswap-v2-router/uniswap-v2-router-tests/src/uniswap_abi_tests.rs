@@ -0,0 +1,33 @@
+use std::{env, fs, path::PathBuf};
+
+use uniswap_v2_library::abi;
+use uniswap_v2_router::{config::ErrorCodes, entry_points};
+
+/// Checked-in ABI, relative to this crate. Regenerate it with `make abi`.
+const ABI_FILE: &str = "../abi/uniswap-v2-router.json";
+
+#[test]
+fn test_abi_is_up_to_date() {
+    let generated = abi::to_json("uniswap-v2-router", &entry_points::abi(), ErrorCodes::ABI);
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(ABI_FILE);
+    if env::var_os("UPDATE_ABI").is_some() {
+        fs::write(&path, &generated).unwrap();
+        return;
+    }
+    let checked_in = fs::read_to_string(&path).unwrap_or_default();
+    assert!(
+        generated == checked_in,
+        "{} is out of date, run `make abi` to regenerate it",
+        path.display()
+    );
+}
+
+#[test]
+fn test_abi_lists_every_installed_entry_point() {
+    let installed = entry_points::get_entry_points();
+    let described = entry_points::abi();
+    assert_eq!(installed.keys().count(), described.len());
+    for entry_point in described {
+        assert!(installed.has_entry_point(entry_point.name));
+    }
+}
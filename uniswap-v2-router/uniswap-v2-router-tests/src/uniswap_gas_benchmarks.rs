@@ -0,0 +1,877 @@
+//! Gas benchmarks for the router entry points.
+//!
+//! `gas_report` is ignored by default, run it with `make bench-gas`. It installs the router and
+//! its dependencies on an in-memory builder, seeds a chain of pairs, then calls every entry point
+//! once and the token swaps and amount views over 1 to 4 hops. The gas of each call is written to
+//! `GAS_REPORT` (`gas-report.json` by default) as key-sorted JSON so two reports diff cleanly
+//! between commits.
+//!
+//! CSPR paying entry points go through the purse-proxy session wasm, so their figures include the
+//! proxy's purse creation and transfer.
+
+use casper_engine_test_support::{
+    ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNT_ADDR,
+    DEFAULT_RUN_GENESIS_REQUEST,
+};
+use casper_types::{
+    bytesrepr::FromBytes, runtime_args, CLTyped, ContractHash, ContractPackageHash, Key,
+    RuntimeArgs, URef, U256, U512,
+};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use uniswap_v2_router::config::ORACLE_PERIOD;
+
+use crate::uniswap_instance::{calculate_signature, PURSE_PROXY_WASM_SRC};
+
+const BLOCK_TIME: u64 = 1_000;
+const DEADLINE: u64 = BLOCK_TIME + 1000 * (30 * 60);
+const TOKENS: usize = 5;
+const MAX_HOPS: usize = TOKENS - 1;
+const LIQUIDITY: u64 = 1_000_000_000_000;
+const AMOUNT: u64 = 1_000_000;
+const GAS_REPORT: &str = "gas-report.json";
+
+#[derive(Default, Serialize)]
+struct GasReport {
+    gas: BTreeMap<String, u64>,
+    skipped: BTreeMap<String, String>,
+}
+
+struct Bench {
+    builder: InMemoryWasmTestBuilder,
+    report: GasReport,
+}
+
+impl Bench {
+    fn new() -> Bench {
+        let mut builder = InMemoryWasmTestBuilder::default();
+        builder.run_genesis(&*DEFAULT_RUN_GENESIS_REQUEST);
+        Bench {
+            builder,
+            report: GasReport::default(),
+        }
+    }
+
+    fn owner(&self) -> Key {
+        Key::from(*DEFAULT_ACCOUNT_ADDR)
+    }
+
+    fn main_purse(&self) -> URef {
+        self.builder
+            .get_account(*DEFAULT_ACCOUNT_ADDR)
+            .expect("default account")
+            .main_purse()
+    }
+
+    fn named_key(&self, name: &str) -> Key {
+        *self
+            .builder
+            .get_account(*DEFAULT_ACCOUNT_ADDR)
+            .expect("default account")
+            .named_keys()
+            .get(name)
+            .unwrap_or_else(|| panic!("missing named key {}", name))
+    }
+
+    /// Install `wasm` under `name` and return the package and contract hash it stored.
+    fn install(
+        &mut self,
+        wasm: &str,
+        name: &str,
+        mut args: RuntimeArgs,
+    ) -> (ContractPackageHash, ContractHash) {
+        args.insert("contract_name", name.to_string()).unwrap();
+        self.session(wasm, args);
+        let package_hash = self.named_key(&format!("{}_package_hash", name));
+        let contract_hash = self.named_key(&format!("{}_contract_hash", name));
+        (
+            ContractPackageHash::new(package_hash.into_hash().unwrap()),
+            ContractHash::new(contract_hash.into_hash().unwrap()),
+        )
+    }
+
+    fn session(&mut self, wasm: &str, args: RuntimeArgs) -> u64 {
+        let request = ExecuteRequestBuilder::standard(*DEFAULT_ACCOUNT_ADDR, wasm, args)
+            .with_block_time(BLOCK_TIME)
+            .build();
+        self.builder.exec(request).expect_success().commit();
+        self.builder.last_exec_gas_cost().value().as_u64()
+    }
+
+    fn call(&mut self, package: ContractPackageHash, entry_point: &str, args: RuntimeArgs) -> u64 {
+        self.call_at(package, entry_point, args, BLOCK_TIME)
+    }
+
+    fn call_at(
+        &mut self,
+        package: ContractPackageHash,
+        entry_point: &str,
+        args: RuntimeArgs,
+        block_time: u64,
+    ) -> u64 {
+        let request = ExecuteRequestBuilder::versioned_contract_call_by_hash(
+            *DEFAULT_ACCOUNT_ADDR,
+            package,
+            None,
+            entry_point,
+            args,
+        )
+        .with_block_time(block_time)
+        .build();
+        self.builder.exec(request).expect_success().commit();
+        self.builder.last_exec_gas_cost().value().as_u64()
+    }
+
+    fn query<T: CLTyped + FromBytes>(&self, contract: ContractHash, name: &str) -> T {
+        self.builder
+            .query(None, Key::from(contract), &[name.to_string()])
+            .unwrap_or_else(|error| panic!("missing {}: {}", name, error))
+            .as_cl_value()
+            .cloned()
+            .unwrap()
+            .into_t()
+            .unwrap()
+    }
+
+    fn record(&mut self, label: &str, gas: u64) {
+        self.report.gas.insert(label.to_string(), gas);
+    }
+
+    fn skip(&mut self, label: &str, reason: &str) {
+        self.report
+            .skipped
+            .insert(label.to_string(), reason.to_string());
+    }
+
+    /// Sign a permit letting the router pull `liquidity` of `pair` from the default account.
+    fn permit(
+        &self,
+        pair: ContractHash,
+        router: ContractPackageHash,
+        liquidity: U256,
+        nonce: U256,
+    ) -> (String, String) {
+        let permit_type_hash: String = self.query(pair, "permit_type_hash");
+        let domain_separator: String = self.query(pair, "domain_separator");
+        let data: String = format!(
+            "{}{}{}{}{}{}",
+            permit_type_hash,
+            self.owner(),
+            Key::from(router),
+            liquidity,
+            nonce,
+            U256::from(DEADLINE)
+        );
+        calculate_signature(&data, &domain_separator)
+    }
+}
+
+fn hops_label(entry_point: &str, hops: usize) -> String {
+    if hops == 1 {
+        entry_point.to_string()
+    } else {
+        format!("{}/{}_hops", entry_point, hops)
+    }
+}
+
+fn token_path(tokens: &[ContractPackageHash]) -> Vec<String> {
+    tokens
+        .iter()
+        .map(|token| Key::from(*token).to_formatted_string())
+        .collect()
+}
+
+#[test]
+#[ignore]
+fn gas_report() {
+    let mut bench = Bench::new();
+    let owner = bench.owner();
+    let decimals: u8 = 18;
+
+    // Contracts the router depends on, laid out as in `deploy_uniswap_router`.
+    let tokens: Vec<ContractPackageHash> = (1..=TOKENS)
+        .map(|i| {
+            bench
+                .install(
+                    "erc20-token.wasm",
+                    &format!("token{}", i),
+                    runtime_args! {
+                        "initial_supply" => U256::from(LIQUIDITY) * U256::from(LIQUIDITY),
+                        "name" => format!("token{}", i),
+                        "symbol" => format!("tk{}", i),
+                        "decimals" => decimals
+                    },
+                )
+                .0
+        })
+        .collect();
+    let (factory, _) = bench.install(
+        "factory.wasm",
+        "factory",
+        runtime_args! { "fee_to_setter" => Key::from(tokens[TOKENS - 1]) },
+    );
+    let (wcspr, _) = bench.install(
+        "wcspr-token.wasm",
+        "wcspr",
+        runtime_args! { "name" => "wcspr", "symbol" => "ERC", "decimals" => decimals },
+    );
+    let (dai, _) = bench.install(
+        "wcspr-token.wasm",
+        "dai",
+        runtime_args! { "name" => "dai", "symbol" => "dai", "decimals" => decimals },
+    );
+    let (flash_swapper, _) = bench.install(
+        "flashswapper-token.wasm",
+        "flash_swapper",
+        runtime_args! {
+            "uniswap_v2_factory" => Key::from(factory),
+            "wcspr" => Key::from(wcspr),
+            "dai" => Key::from(dai)
+        },
+    );
+    let (library, _) = bench.install("uniswap-v2-library.wasm", "library", runtime_args! {});
+    let (router, _) = bench.install(
+        "uniswap-v2-router.wasm",
+        "uniswap_router",
+        runtime_args! {
+            "factory" => Key::from(factory),
+            "wcspr" => Key::from(wcspr),
+            "library" => Key::from(library)
+        },
+    );
+    let install = bench.builder.last_exec_gas_cost().value().as_u64();
+    bench.record("install", install);
+    bench.call(
+        factory,
+        "set_white_list",
+        runtime_args! { "white_list" => Key::from(router) },
+    );
+    // One pair per neighbouring token and one against wcspr, each allowed by the owner.
+    let install_pair = |bench: &mut Bench, name: &str| {
+        let pair = bench.install(
+            "pair-token.wasm",
+            name,
+            runtime_args! {
+                "name" => "erc20",
+                "symbol" => "ERC",
+                "decimals" => decimals,
+                "initial_supply" => U256::zero(),
+                "factory_hash" => Key::from(factory),
+                "callee_package_hash" => Key::from(flash_swapper)
+            },
+        );
+        bench.call(
+            router,
+            "set_pair_allowed",
+            runtime_args! { "pair" => Key::from(pair.0), "allowed" => true },
+        );
+        pair
+    };
+    let pairs: Vec<(ContractPackageHash, ContractHash)> = (0..MAX_HOPS)
+        .map(|i| install_pair(&mut bench, &format!("pair{}", i)))
+        .collect();
+    let cspr_pair = install_pair(&mut bench, "pair_wcspr");
+
+    for token in tokens.iter() {
+        bench.call(
+            *token,
+            "approve",
+            runtime_args! {
+                "spender" => Key::from(router),
+                "amount" => U256::from(LIQUIDITY) * U256::from(LIQUIDITY)
+            },
+        );
+    }
+    for (i, (pair, _)) in pairs.iter().enumerate() {
+        bench.call(
+            router,
+            "add_liquidity",
+            runtime_args! {
+                "token_a" => Key::from(tokens[i]),
+                "token_b" => Key::from(tokens[i + 1]),
+                "amount_a_desired" => U256::from(LIQUIDITY),
+                "amount_b_desired" => U256::from(LIQUIDITY),
+                "amount_a_min" => U256::zero(),
+                "amount_b_min" => U256::zero(),
+                "to" => owner,
+                "deadline" => U256::from(DEADLINE),
+                "pair" => Some(Key::from(*pair)),
+                "min_liquidity_out" => None::<U256>
+            },
+        );
+    }
+    let add_liquidity_cspr = runtime_args! {
+        "amount" => U512::from(LIQUIDITY),
+        "destination_entrypoint" => "add_liquidity_cspr",
+        "token" => Key::from(tokens[0]),
+        "amount_token_desired" => U256::from(LIQUIDITY),
+        "amount_cspr_desired" => U256::from(LIQUIDITY),
+        "amount_token_min" => U256::zero(),
+        "amount_cspr_min" => U256::zero(),
+        "to" => owner,
+        "deadline" => U256::from(DEADLINE),
+        "pair" => Some(Key::from(cspr_pair.0)),
+        "min_liquidity_out" => None::<U256>,
+        "router_hash" => Key::from(router)
+    };
+    bench.session(PURSE_PROXY_WASM_SRC, add_liquidity_cspr.clone());
+    for (pair, _) in pairs.iter().chain(Some(&cspr_pair)) {
+        bench.call(
+            *pair,
+            "approve",
+            runtime_args! {
+                "spender" => Key::from(router),
+                "amount" => U256::from(LIQUIDITY)
+            },
+        );
+    }
+
+    // Liquidity, against pairs that already hold reserves.
+    for entry_point in ["add_liquidity", "add_liquidity_js_client"] {
+        let gas = bench.call(
+            router,
+            entry_point,
+            runtime_args! {
+                "token_a" => Key::from(tokens[0]),
+                "token_b" => Key::from(tokens[1]),
+                "amount_a_desired" => U256::from(AMOUNT),
+                "amount_b_desired" => U256::from(AMOUNT),
+                "amount_a_min" => U256::zero(),
+                "amount_b_min" => U256::zero(),
+                "to" => owner,
+                "deadline" => U256::from(DEADLINE),
+                "pair" => Some(Key::from(pairs[0].0)),
+                "min_liquidity_out" => None::<U256>
+            },
+        );
+        bench.record(entry_point, gas);
+    }
+    let gas = bench.session(PURSE_PROXY_WASM_SRC, add_liquidity_cspr.clone());
+    bench.record("add_liquidity_cspr", gas);
+    let mut add_liquidity_cspr_js_client = add_liquidity_cspr;
+    add_liquidity_cspr_js_client
+        .insert("destination_entrypoint", "add_liquidity_cspr_js_client")
+        .unwrap();
+    let gas = bench.session(PURSE_PROXY_WASM_SRC, add_liquidity_cspr_js_client);
+    bench.record("add_liquidity_cspr_js_client", gas);
+
+    for entry_point in ["remove_liquidity", "remove_liquidity_js_client"] {
+        let gas = bench.call(
+            router,
+            entry_point,
+            runtime_args! {
+                "token_a" => Key::from(tokens[0]),
+                "token_b" => Key::from(tokens[1]),
+                "liquidity" => U256::from(AMOUNT),
+                "amount_a_min" => U256::zero(),
+                "amount_b_min" => U256::zero(),
+                "to" => owner,
+                "deadline" => U256::from(DEADLINE)
+            },
+        );
+        bench.record(entry_point, gas);
+    }
+    for entry_point in ["remove_liquidity_cspr", "remove_liquidity_cspr_js_client"] {
+        let to_purse = bench.main_purse();
+        let gas = bench.call(
+            router,
+            entry_point,
+            runtime_args! {
+                "token" => Key::from(tokens[0]),
+                "liquidity" => U256::from(AMOUNT),
+                "amount_token_min" => U256::zero(),
+                "amount_cspr_min" => U256::zero(),
+                "to" => owner,
+                "deadline" => U256::from(DEADLINE),
+                "to_purse" => to_purse
+            },
+        );
+        bench.record(entry_point, gas);
+    }
+    let gas = bench.call(
+        router,
+        "remove_liquidity_cspr_to_key",
+        runtime_args! {
+            "token" => Key::from(tokens[0]),
+            "liquidity" => U256::from(AMOUNT),
+            "amount_token_min" => U256::zero(),
+            "amount_cspr_min" => U256::zero(),
+            "to" => owner,
+            "deadline" => U256::from(DEADLINE)
+        },
+    );
+    bench.record("remove_liquidity_cspr_to_key", gas);
+
+    for (nonce, entry_point) in [
+        "remove_liquidity_with_permit",
+        "remove_liquidity_with_permit_js_client",
+    ]
+    .iter()
+    .enumerate()
+    {
+        let (signature, public_key) =
+            bench.permit(pairs[0].1, router, U256::from(AMOUNT), U256::from(nonce));
+        let gas = bench.call(
+            router,
+            entry_point,
+            runtime_args! {
+                "token_a" => Key::from(tokens[0]),
+                "token_b" => Key::from(tokens[1]),
+                "liquidity" => U256::from(AMOUNT),
+                "amount_a_min" => U256::zero(),
+                "amount_b_min" => U256::zero(),
+                "to" => owner,
+                "approve_max" => false,
+                "public_key" => public_key,
+                "signature" => signature,
+                "deadline" => U256::from(DEADLINE)
+            },
+        );
+        bench.record(entry_point, gas);
+    }
+    for (nonce, entry_point) in [
+        "remove_liquidity_cspr_with_permit",
+        "remove_liquidity_cspr_with_permit_js_client",
+    ]
+    .iter()
+    .enumerate()
+    {
+        let (signature, public_key) =
+            bench.permit(cspr_pair.1, router, U256::from(AMOUNT), U256::from(nonce));
+        let to_purse = bench.main_purse();
+        let gas = bench.call(
+            router,
+            entry_point,
+            runtime_args! {
+                "token" => Key::from(tokens[0]),
+                "liquidity" => U256::from(AMOUNT),
+                "amount_token_min" => U256::zero(),
+                "amount_cspr_min" => U256::zero(),
+                "to" => owner,
+                "approve_max" => false,
+                "public_key" => public_key,
+                "signature" => signature,
+                "deadline" => U256::from(DEADLINE),
+                "to_purse" => to_purse
+            },
+        );
+        bench.record(entry_point, gas);
+    }
+
+    // Token swaps and amount views along token1 -> token2 -> ... -> token5.
+    for hops in 1..=MAX_HOPS {
+        let path = &tokens[..=hops];
+        for entry_point in [
+            "swap_exact_tokens_for_tokens",
+            "swap_exact_tokens_for_tokens_js_client",
+        ] {
+            let gas = bench.call(
+                router,
+                entry_point,
+                runtime_args! {
+                    "amount_in" => U256::from(AMOUNT),
+                    "amount_out_min" => U256::zero(),
+                    "path" => token_path(path),
+                    "to" => owner,
+                    "deadline" => U256::from(DEADLINE),
+                    "max_price_impact_bps" => None::<u32>
+                },
+            );
+            bench.record(&hops_label(entry_point, hops), gas);
+        }
+        for entry_point in [
+            "swap_tokens_for_exact_tokens",
+            "swap_tokens_for_exact_tokens_js_client",
+        ] {
+            let gas = bench.call(
+                router,
+                entry_point,
+                runtime_args! {
+                    "amount_out" => U256::from(AMOUNT),
+                    "amount_in_max" => U256::from(LIQUIDITY),
+                    "path" => token_path(path),
+                    "to" => owner,
+                    "deadline" => U256::from(DEADLINE),
+                    "max_price_impact_bps" => None::<u32>
+                },
+            );
+            bench.record(&hops_label(entry_point, hops), gas);
+        }
+        let keys: Vec<Key> = path.iter().map(|token| Key::from(*token)).collect();
+        let gas = bench.call(
+            router,
+            "get_amounts_out",
+            runtime_args! { "amount_in" => U256::from(AMOUNT), "path" => keys.clone() },
+        );
+        bench.record(&hops_label("get_amounts_out", hops), gas);
+        let gas = bench.call(
+            router,
+            "get_amounts_in",
+            runtime_args! { "amount_out" => U256::from(AMOUNT), "path" => keys.clone() },
+        );
+        bench.record(&hops_label("get_amounts_in", hops), gas);
+        // a depth chart's worth of amounts
+        let amounts: Vec<U256> = (1..=10).map(|step| U256::from(AMOUNT * step)).collect();
+        let gas = bench.call(
+            router,
+            "get_amounts_out_batch",
+            runtime_args! { "amounts_in" => amounts.clone(), "path" => keys.clone() },
+        );
+        bench.record(&hops_label("get_amounts_out_batch", hops), gas);
+        let gas = bench.call(
+            router,
+            "get_amounts_in_batch",
+            runtime_args! { "amounts_out" => amounts, "path" => keys },
+        );
+        bench.record(&hops_label("get_amounts_in_batch", hops), gas);
+    }
+
+    // CSPR swaps against the wcspr pair.
+    for entry_point in [
+        "swap_exact_cspr_for_tokens",
+        "swap_exact_cspr_for_tokens_js_client",
+    ] {
+        let gas = bench.session(
+            PURSE_PROXY_WASM_SRC,
+            runtime_args! {
+                "amount" => U512::from(AMOUNT),
+                "destination_entrypoint" => entry_point,
+                "amount_out_min" => U256::zero(),
+                "amount_in" => U256::from(AMOUNT),
+                "path" => token_path(&[wcspr, tokens[0]]),
+                "to" => owner,
+                "deadline" => U256::from(DEADLINE),
+                "max_price_impact_bps" => None::<u32>,
+                "router_hash" => Key::from(router)
+            },
+        );
+        bench.record(entry_point, gas);
+    }
+    for entry_point in [
+        "swap_cspr_for_exact_tokens",
+        "swap_cspr_for_exact_tokens_js_client",
+    ] {
+        let gas = bench.session(
+            PURSE_PROXY_WASM_SRC,
+            runtime_args! {
+                "amount" => U512::from(2 * AMOUNT),
+                "destination_entrypoint" => entry_point,
+                "amount_out" => U256::from(AMOUNT),
+                "amount_in_max" => U256::from(2 * AMOUNT),
+                "path" => token_path(&[wcspr, tokens[0]]),
+                "to" => owner,
+                "deadline" => U256::from(DEADLINE),
+                "max_price_impact_bps" => None::<u32>,
+                "router_hash" => Key::from(router)
+            },
+        );
+        bench.record(entry_point, gas);
+    }
+    for entry_point in [
+        "swap_tokens_for_exact_cspr",
+        "swap_tokens_for_exact_cspr_js_client",
+    ] {
+        let to = bench.main_purse();
+        let gas = bench.call(
+            router,
+            entry_point,
+            runtime_args! {
+                "amount_out" => U256::from(AMOUNT),
+                "amount_in_max" => U256::from(LIQUIDITY),
+                "path" => token_path(&[tokens[0], wcspr]),
+                "to" => to,
+                "deadline" => U256::from(DEADLINE),
+                "max_price_impact_bps" => None::<u32>
+            },
+        );
+        bench.record(entry_point, gas);
+    }
+    for entry_point in [
+        "swap_exact_tokens_for_cspr",
+        "swap_exact_tokens_for_cspr_js_client",
+    ] {
+        let to = bench.main_purse();
+        let gas = bench.call(
+            router,
+            entry_point,
+            runtime_args! {
+                "amount_in" => U256::from(AMOUNT),
+                "amount_out_min" => U256::zero(),
+                "path" => token_path(&[tokens[0], wcspr]),
+                "to" => to,
+                "deadline" => U256::from(DEADLINE),
+                "max_price_impact_bps" => None::<u32>
+            },
+        );
+        bench.record(entry_point, gas);
+    }
+    let gas = bench.call(
+        router,
+        "swap_tokens_for_exact_cspr_to_key",
+        runtime_args! {
+            "amount_out" => U256::from(AMOUNT),
+            "amount_in_max" => U256::from(LIQUIDITY),
+            "path" => token_path(&[tokens[0], wcspr]),
+            "to" => owner,
+            "deadline" => U256::from(DEADLINE),
+            "max_price_impact_bps" => None::<u32>
+        },
+    );
+    bench.record("swap_tokens_for_exact_cspr_to_key", gas);
+    let gas = bench.call(
+        router,
+        "swap_exact_tokens_for_cspr_to_key",
+        runtime_args! {
+            "amount_in" => U256::from(AMOUNT),
+            "amount_out_min" => U256::zero(),
+            "path" => token_path(&[tokens[0], wcspr]),
+            "to" => owner,
+            "deadline" => U256::from(DEADLINE),
+            "max_price_impact_bps" => None::<u32>
+        },
+    );
+    bench.record("swap_exact_tokens_for_cspr_to_key", gas);
+
+    // Flash swaps, through a pair that calls back the router. The figure includes the reference
+    // borrower starting the flash swap and repaying it. The pair closes the token chain into a
+    // cycle and prices the last token at two of the first.
+    let (flash_pair, _) = bench.install(
+        "pair-token.wasm",
+        "pair_flash",
+        runtime_args! {
+            "name" => "erc20",
+            "symbol" => "ERC",
+            "decimals" => decimals,
+            "initial_supply" => U256::zero(),
+            "factory_hash" => Key::from(factory),
+            "callee_package_hash" => Key::from(router)
+        },
+    );
+    bench.call(
+        router,
+        "set_pair_allowed",
+        runtime_args! { "pair" => Key::from(flash_pair), "allowed" => true },
+    );
+    bench.call(
+        router,
+        "add_liquidity",
+        runtime_args! {
+            "token_a" => Key::from(tokens[0]),
+            "token_b" => Key::from(tokens[MAX_HOPS]),
+            "amount_a_desired" => U256::from(LIQUIDITY),
+            "amount_b_desired" => U256::from(LIQUIDITY / 2),
+            "amount_a_min" => U256::zero(),
+            "amount_b_min" => U256::zero(),
+            "to" => owner,
+            "deadline" => U256::from(DEADLINE),
+            "pair" => Some(Key::from(flash_pair)),
+            "min_liquidity_out" => None::<U256>
+        },
+    );
+    let (borrower, _) = bench.install(
+        "flash-borrower.wasm",
+        "flash_borrower",
+        runtime_args! { "router" => Key::from(router) },
+    );
+    bench.call(
+        tokens[0],
+        "transfer",
+        runtime_args! {
+            "recipient" => Key::from(borrower),
+            "amount" => U256::from(AMOUNT)
+        },
+    );
+    let gas = bench.call(
+        borrower,
+        "borrow",
+        runtime_args! {
+            "token" => Key::from(tokens[0]),
+            "other_token" => Key::from(tokens[MAX_HOPS]),
+            "amount" => U256::from(AMOUNT),
+            "data" => "bench",
+            "deadline" => U256::from(DEADLINE)
+        },
+    );
+    bench.record("flash_swap", gas);
+    bench.skip(
+        "uniswap_v2_call",
+        "only accepted from the pair of a flash swap in progress, its gas is part of flash_swap",
+    );
+
+    // Arbitrage along token1 -> ... -> token5 at one price and back to token1 at two.
+    let mut cycle: Vec<ContractPackageHash> = tokens.clone();
+    cycle.push(tokens[0]);
+    let gas = bench.call(
+        router,
+        "swap_cycle",
+        runtime_args! {
+            "amount_in" => U256::from(AMOUNT),
+            "min_profit" => U256::zero(),
+            "path" => token_path(&cycle),
+            "to" => owner,
+            "deadline" => U256::from(DEADLINE),
+            "max_price_impact_bps" => None::<u32>
+        },
+    );
+    bench.record(&hops_label("swap_cycle", cycle.len() - 1), gas);
+
+    // Limit orders, executed through the first pair. Order 0 is executed and order 1 cancelled.
+    let place_order = runtime_args! {
+        "path" => vec![Key::from(tokens[0]), Key::from(tokens[1])],
+        "amount_in" => U256::from(AMOUNT),
+        "amount_out_min" => U256::one(),
+        "tip" => U256::from(AMOUNT)
+    };
+    let gas = bench.call(router, "place_order", place_order.clone());
+    bench.record("place_order", gas);
+    let gas = bench.call(router, "get_order", runtime_args! { "order_id" => 0u64 });
+    bench.record("get_order", gas);
+    let gas = bench.call(
+        router,
+        "execute_order",
+        runtime_args! { "order_id" => 0u64 },
+    );
+    bench.record("execute_order", gas);
+    bench.call(router, "place_order", place_order);
+    let gas = bench.call(router, "cancel_order", runtime_args! { "order_id" => 1u64 });
+    bench.record("cancel_order", gas);
+    // a router installed with escrow tracking has no orders left to account for
+    let gas = bench.call(
+        router,
+        "migrate_escrows",
+        runtime_args! { "limit" => 100u64 },
+    );
+    bench.record("migrate_escrows", gas);
+    for entry_point in ["constructor", "migrate", "export_state"] {
+        bench.skip(
+            entry_point,
+            "group guarded, only reachable while installing",
+        );
+    }
+
+    // Pure views.
+    let gas = bench.call(
+        router,
+        "quote",
+        runtime_args! {
+            "amount_a" => U256::from(AMOUNT),
+            "reserve_a" => U256::from(LIQUIDITY),
+            "reserve_b" => U256::from(LIQUIDITY)
+        },
+    );
+    bench.record("quote", gas);
+    let gas = bench.call(
+        router,
+        "get_amount_out",
+        runtime_args! {
+            "amount_in" => U256::from(AMOUNT),
+            "reserve_in" => U256::from(LIQUIDITY),
+            "reserve_out" => U256::from(LIQUIDITY)
+        },
+    );
+    bench.record("get_amount_out", gas);
+    let gas = bench.call(
+        router,
+        "get_amount_in",
+        runtime_args! {
+            "amount_out" => U256::from(AMOUNT),
+            "reserve_in" => U256::from(LIQUIDITY),
+            "reserve_out" => U256::from(LIQUIDITY)
+        },
+    );
+    bench.record("get_amount_in", gas);
+    let gas = bench.call(router, "package_hash", runtime_args! {});
+    bench.record("package_hash", gas);
+    let gas = bench.call(
+        router,
+        "quote_add_liquidity",
+        runtime_args! {
+            "token_a" => Key::from(tokens[0]),
+            "token_b" => Key::from(tokens[1]),
+            "amount_a_desired" => U256::from(AMOUNT),
+            "amount_b_desired" => U256::from(AMOUNT)
+        },
+    );
+    bench.record("quote_add_liquidity", gas);
+    let gas = bench.call(
+        router,
+        "quote_remove_liquidity",
+        runtime_args! {
+            "token_a" => Key::from(tokens[0]),
+            "token_b" => Key::from(tokens[1]),
+            "liquidity" => U256::from(AMOUNT)
+        },
+    );
+    bench.record("quote_remove_liquidity", gas);
+    let gas = bench.call(
+        router,
+        "get_reserves",
+        runtime_args! {
+            "token_a" => Key::from(tokens[0]),
+            "token_b" => Key::from(tokens[1])
+        },
+    );
+    bench.record("get_reserves", gas);
+    let gas = bench.call(
+        router,
+        "pair_for",
+        runtime_args! {
+            "token_a" => Key::from(tokens[0]),
+            "token_b" => Key::from(tokens[1])
+        },
+    );
+    bench.record("pair_for", gas);
+    let gas = bench.call(
+        router,
+        "get_pair_info",
+        runtime_args! {
+            "token_a" => Key::from(tokens[0]),
+            "token_b" => Key::from(tokens[1])
+        },
+    );
+    bench.record("get_pair_info", gas);
+
+    // Oracle, the first observation of a pair in a period is the one that writes.
+    let gas = bench.call(
+        router,
+        "update_oracle",
+        runtime_args! {
+            "token_a" => Key::from(tokens[0]),
+            "token_b" => Key::from(tokens[1])
+        },
+    );
+    bench.record("update_oracle", gas);
+    let gas = bench.call(
+        router,
+        "set_oracle_guard",
+        runtime_args! {
+            "token_a" => Key::from(tokens[0]),
+            "token_b" => Key::from(tokens[1]),
+            "max_deviation_bps" => Some(100u32),
+            "window" => ORACLE_PERIOD
+        },
+    );
+    bench.record("set_oracle_guard", gas);
+    // one period after the observation update_oracle recorded
+    let gas = bench.call_at(
+        router,
+        "consult",
+        runtime_args! {
+            "token_in" => Key::from(tokens[0]),
+            "amount_in" => U256::from(AMOUNT),
+            "token_out" => Key::from(tokens[1]),
+            "window" => ORACLE_PERIOD
+        },
+        BLOCK_TIME + ORACLE_PERIOD,
+    );
+    bench.record("consult", gas);
+
+    for (label, gas) in bench.report.gas.iter() {
+        println!("{:<50} {:>16}", label, gas);
+    }
+    let path = env::var("GAS_REPORT").unwrap_or_else(|_| GAS_REPORT.to_string());
+    let json = serde_json::to_string_pretty(&bench.report).unwrap();
+    fs::write(&path, json + "\n").unwrap_or_else(|error| panic!("{}: {}", path, error));
+}
@@ -1,5 +1,12 @@
+use blake2::{
+    digest::{Update, VariableOutput},
+    VarBlake2b,
+};
 use casper_types::account::AccountHash;
-use casper_types::{runtime_args, ContractHash, ContractPackageHash, Key, RuntimeArgs, U256, U512};
+use casper_types::{
+    bytesrepr::ToBytes, runtime_args, ContractHash, ContractPackageHash, Key, RuntimeArgs, U128,
+    U256, U512,
+};
 use casperlabs_test_env::{TestContract, TestEnv};
 
 use cryptoxide::ed25519;
@@ -7,6 +14,7 @@ use renvm_sig::hash_message;
 use renvm_sig::keccak256;
 
 pub const PURSE_PROXY_WASM_SRC: &str = "purse-proxy.wasm";
+pub const APPROVE_AND_SWAP_WASM_SRC: &str = "approve-and-swap.wasm";
 
 pub struct UniswapInstance(TestContract);
 impl UniswapInstance {
@@ -26,7 +34,7 @@ impl UniswapInstance {
                 "library_address" => library_address
                 // contract_name is passed seperately, so we don't need to pass it here.
             },
-            0
+            0,
         ))
     }
 
@@ -47,7 +55,7 @@ impl UniswapInstance {
                 "symbol" => symbol,
                 "decimals" => decimals
             },
-            0
+            0,
         );
     }
 
@@ -63,6 +71,7 @@ impl UniswapInstance {
         to: Key,
         deadline: U256,
         pair: Option<Key>,
+        min_liquidity_out: Option<U256>,
     ) {
         self.0.call_contract(
             sender,
@@ -76,9 +85,10 @@ impl UniswapInstance {
                 "amount_b_min" => amount_b_min,
                 "to" => to,
                 "deadline" => deadline,
-                "pair" => pair
+                "pair" => pair,
+                "min_liquidity_out" => min_liquidity_out
             },
-            0
+            0,
         );
     }
 
@@ -93,6 +103,7 @@ impl UniswapInstance {
         to: Key,
         deadline: U256,
         pair: Option<Key>,
+        min_liquidity_out: Option<U256>,
         router: Key,
         test_contract_hash: Key,
     ) {
@@ -108,10 +119,11 @@ impl UniswapInstance {
                 "to" => to,
                 "deadline" => deadline,
                 "pair" => pair,
+                "min_liquidity_out" => min_liquidity_out,
                 "router_hash" => router,
                 "self_hash" => test_contract_hash
             },
-            0
+            0,
         );
     }
 
@@ -142,7 +154,7 @@ impl UniswapInstance {
                 "pair" => pair,
                 "self_hash" => test_contract_hash
             },
-            0
+            0,
         );
     }
 
@@ -169,7 +181,7 @@ impl UniswapInstance {
                 "deadline" => deadline,
                 "pair" => pair,
             },
-            0
+            0,
         );
     }
 
@@ -202,7 +214,7 @@ impl UniswapInstance {
                 "public_key" => public_key,
                 "signature" => signature
             },
-            0
+            0,
         );
     }
 
@@ -233,7 +245,7 @@ impl UniswapInstance {
                 "public_key" => public_key,
                 "signature" => signature
             },
-            0
+            0,
         );
     }
 
@@ -245,6 +257,7 @@ impl UniswapInstance {
         path: Vec<String>,
         to: Key,
         deadline: U256,
+        max_price_impact_bps: Option<u32>,
     ) {
         self.0.call_contract(
             sender,
@@ -254,9 +267,10 @@ impl UniswapInstance {
                 "amount_out_min" => amount_out_min,
                 "path" => path,
                 "to" => to,
-                "deadline" => deadline
+                "deadline" => deadline,
+                "max_price_impact_bps" => max_price_impact_bps
             },
-            0
+            0,
         );
     }
 
@@ -268,6 +282,7 @@ impl UniswapInstance {
         path: Vec<String>,
         to: Key,
         deadline: U256,
+        max_price_impact_bps: Option<u32>,
     ) {
         self.0.call_contract(
             sender,
@@ -277,9 +292,10 @@ impl UniswapInstance {
                 "amount_in_max" => amount_in_max,
                 "path" => path,
                 "to" => to,
-                "deadline" => deadline
+                "deadline" => deadline,
+                "max_price_impact_bps" => max_price_impact_bps
             },
-            0
+            0,
         );
     }
 
@@ -291,6 +307,7 @@ impl UniswapInstance {
         path: Vec<String>,
         to: Key,
         deadline: U256,
+        max_price_impact_bps: Option<u32>,
         router: Key,
     ) {
         self.0.call_contract(
@@ -302,9 +319,10 @@ impl UniswapInstance {
                 "path" => path,
                 "to" => to,
                 "deadline" => deadline,
+                "max_price_impact_bps" => max_price_impact_bps,
                 "router_hash" => router
             },
-            0
+            0,
         );
     }
 
@@ -315,6 +333,7 @@ impl UniswapInstance {
         amount_in_max: U256,
         path: Vec<String>,
         deadline: U256,
+        max_price_impact_bps: Option<u32>,
     ) {
         self.0.call_contract(
             sender,
@@ -323,9 +342,10 @@ impl UniswapInstance {
                 "amount_out" => amount_out,
                 "amount_in_max" => amount_in_max,
                 "path" => path,
-                "deadline" => deadline
+                "deadline" => deadline,
+                "max_price_impact_bps" => max_price_impact_bps
             },
-            0
+            0,
         );
     }
 
@@ -336,6 +356,7 @@ impl UniswapInstance {
         amount_out_min: U256,
         path: Vec<String>,
         deadline: U256,
+        max_price_impact_bps: Option<u32>,
     ) {
         self.0.call_contract(
             sender,
@@ -344,9 +365,10 @@ impl UniswapInstance {
                 "amount_in" => amount_in,
                 "amount_out_min" => amount_out_min,
                 "path" => path,
-                "deadline" => deadline
+                "deadline" => deadline,
+                "max_price_impact_bps" => max_price_impact_bps
             },
-            0
+            0,
         );
     }
 
@@ -358,6 +380,7 @@ impl UniswapInstance {
         path: Vec<String>,
         to: Key,
         deadline: U256,
+        max_price_impact_bps: Option<u32>,
     ) {
         self.0.call_contract(
             sender,
@@ -367,9 +390,132 @@ impl UniswapInstance {
                 "amount_out" => amount_out,
                 "path" => path,
                 "to" => to,
-                "deadline" => deadline
+                "deadline" => deadline,
+                "max_price_impact_bps" => max_price_impact_bps
+            },
+            0,
+        );
+    }
+
+    pub fn get_reserves(&self, sender: AccountHash, factory: Key, token_a: Key, token_b: Key) {
+        self.0.call_contract(
+            sender,
+            "get_reserves",
+            runtime_args! {
+                "factory" => factory,
+                "token_a" => token_a,
+                "token_b" => token_b
+            },
+            0,
+        );
+    }
+
+    /// Consults the router's oracle at block time `time`, the result is kept by the test contract.
+    pub fn consult(
+        &self,
+        sender: AccountHash,
+        token_in: Key,
+        amount_in: U256,
+        token_out: Key,
+        window: u64,
+        time: u64,
+    ) {
+        self.0.call_contract(
+            sender,
+            "consult",
+            runtime_args! {
+                "token_in" => token_in,
+                "amount_in" => amount_in,
+                "token_out" => token_out,
+                "window" => window
+            },
+            time,
+        );
+    }
+
+    pub fn quote_add_liquidity(
+        &self,
+        sender: AccountHash,
+        token_a: Key,
+        token_b: Key,
+        amount_a_desired: U256,
+        amount_b_desired: U256,
+    ) {
+        self.0.call_contract(
+            sender,
+            "quote_add_liquidity",
+            runtime_args! {
+                "token_a" => token_a,
+                "token_b" => token_b,
+                "amount_a_desired" => amount_a_desired,
+                "amount_b_desired" => amount_b_desired
             },
-            0
+            0,
+        );
+    }
+
+    pub fn quote_remove_liquidity(
+        &self,
+        sender: AccountHash,
+        token_a: Key,
+        token_b: Key,
+        liquidity: U256,
+    ) {
+        self.0.call_contract(
+            sender,
+            "quote_remove_liquidity",
+            runtime_args! {
+                "token_a" => token_a,
+                "token_b" => token_b,
+                "liquidity" => liquidity
+            },
+            0,
+        );
+    }
+
+    pub fn get_pair_info(&self, sender: AccountHash, token_a: Key, token_b: Key) {
+        self.0.call_contract(
+            sender,
+            "get_pair_info",
+            runtime_args! {
+                "token_a" => token_a,
+                "token_b" => token_b
+            },
+            0,
+        );
+    }
+
+    pub fn get_amounts_out_batch(
+        &self,
+        sender: AccountHash,
+        amounts_in: Vec<U256>,
+        path: Vec<Key>,
+    ) {
+        self.0.call_contract(
+            sender,
+            "get_amounts_out_batch",
+            runtime_args! {
+                "amounts_in" => amounts_in,
+                "path" => path
+            },
+            0,
+        );
+    }
+
+    pub fn get_amounts_in_batch(
+        &self,
+        sender: AccountHash,
+        amounts_out: Vec<U256>,
+        path: Vec<Key>,
+    ) {
+        self.0.call_contract(
+            sender,
+            "get_amounts_in_batch",
+            runtime_args! {
+                "amounts_out" => amounts_out,
+                "path" => path
+            },
+            0,
         );
     }
 
@@ -381,7 +527,7 @@ impl UniswapInstance {
                 "self_hash" => test_contract_hash,
                 "amount" => amount
             },
-            0
+            0,
         );
     }
 
@@ -393,7 +539,7 @@ impl UniswapInstance {
                 "spender" => spender,
                 "amount" => amount
             },
-            0
+            0,
         );
     }
 
@@ -419,28 +565,7 @@ impl UniswapInstance {
     }
 
     pub fn calculate_signature(&self, data: &String, domainseparator: &String) -> (String, String) {
-        let hash = keccak256(data.as_bytes());
-        let hashstring = hex::encode(hash);
-        let data2: String = format!("{}{}", domainseparator, hashstring);
-        let geteip191standard_hash = hash_message(data2);
-
-        let secret = "MC4CAQAwBQYDK2VwBCIEIPPGVic1+UO0UJJJRTHaBkpH/05oaDQacEinXQnKoaIu".as_bytes();
-        let public = ed25519::to_public(secret);
-        let signature = ed25519::signature_extended(&geteip191standard_hash, &secret);
-
-        let signature = signature.to_vec();
-        let public = public.to_vec();
-
-        let signature_str = format!("{:?}", &signature);
-        let public_str = format!("{:?}", &public);
-
-        let mut signature_str = signature_str.replace("[", "");
-        signature_str = signature_str.replace("]", "");
-
-        let mut public_str = public_str.replace("[", "");
-        public_str = public_str.replace("]", "");
-
-        (signature_str, public_str)
+        calculate_signature(data, domainseparator)
     }
 
     // Result methods
@@ -478,6 +603,40 @@ impl UniswapInstance {
         (amount_a, amount_b)
     }
 
+    pub fn get_reserves_result(&self) -> (U128, U128) {
+        let (reserve_a, reserve_b): (U128, U128) =
+            self.0.query_named_key("get_reserves_result".to_string());
+        (reserve_a, reserve_b)
+    }
+
+    pub fn consult_result(&self) -> U256 {
+        self.0.query_named_key("consult_result".to_string())
+    }
+
+    pub fn quote_add_liquidity_result(&self) -> (U256, U256, U256) {
+        self.0
+            .query_named_key("quote_add_liquidity_result".to_string())
+    }
+
+    pub fn quote_remove_liquidity_result(&self) -> (U256, U256) {
+        self.0
+            .query_named_key("quote_remove_liquidity_result".to_string())
+    }
+
+    pub fn get_pair_info_result(&self) -> ((Key, U256), (Key, Key), (U128, U128, u64)) {
+        self.0.query_named_key("get_pair_info_result".to_string())
+    }
+
+    pub fn get_amounts_out_batch_result(&self) -> Vec<Vec<U256>> {
+        self.0
+            .query_named_key("get_amounts_out_batch_result".to_string())
+    }
+
+    pub fn get_amounts_in_batch_result(&self) -> Vec<Vec<U256>> {
+        self.0
+            .query_named_key("get_amounts_in_batch_result".to_string())
+    }
+
     pub fn remove_liquidity_cspr_with_permit_result(&self) -> (U256, U256) {
         let (amount_a, amount_b): (U256, U256) = self
             .0
@@ -486,6 +645,31 @@ impl UniswapInstance {
     }
 }
 
+pub fn calculate_signature(data: &String, domainseparator: &String) -> (String, String) {
+    let hash = keccak256(data.as_bytes());
+    let hashstring = hex::encode(hash);
+    let data2: String = format!("{}{}", domainseparator, hashstring);
+    let geteip191standard_hash = hash_message(data2);
+
+    let secret = "MC4CAQAwBQYDK2VwBCIEIPPGVic1+UO0UJJJRTHaBkpH/05oaDQacEinXQnKoaIu".as_bytes();
+    let public = ed25519::to_public(secret);
+    let signature = ed25519::signature_extended(&geteip191standard_hash, &secret);
+
+    let signature = signature.to_vec();
+    let public = public.to_vec();
+
+    let signature_str = format!("{:?}", &signature);
+    let public_str = format!("{:?}", &public);
+
+    let mut signature_str = signature_str.replace("[", "");
+    signature_str = signature_str.replace("]", "");
+
+    let mut public_str = public_str.replace("[", "");
+    public_str = public_str.replace("]", "");
+
+    (signature_str, public_str)
+}
+
 pub fn key_to_str(key: &Key) -> String {
     match key {
         Key::Account(account) => account.to_string(),
@@ -494,6 +678,44 @@ pub fn key_to_str(key: &Key) -> String {
     }
 }
 
+fn hashed_key<T: ToBytes>(value: &T) -> String {
+    let mut hasher = VarBlake2b::new(32).unwrap();
+    hasher.update(value.to_bytes().unwrap());
+    let mut ret = [0u8; 32];
+    hasher.finalize_variable(|hash| ret.clone_from_slice(hash));
+    hex::encode(ret)
+}
+
+/// Item key of the number of results the router recorded for `owner` in `result_nonces`.
+pub fn result_nonce_key(owner: Key) -> String {
+    hashed_key(&owner)
+}
+
+/// Item key of the result `nonce` of `owner` in the router's `results` dictionary.
+pub fn result_key(owner: Key, nonce: u64) -> String {
+    hashed_key(&(owner, nonce))
+}
+
+/// Item key of `token` in the router's `escrows` dictionary.
+pub fn escrow_key(token: Key) -> String {
+    hashed_key(&token)
+}
+
+/// Item key of `pair` in the router's `allowed_pairs` dictionary.
+pub fn allowed_pair_key(pair: Key) -> String {
+    hashed_key(&pair)
+}
+
+/// Amounts the purse-proxy or approve-and-swap stored on `account` for the last call to
+/// `destination_entrypoint`.
+pub fn session_result(
+    env: &TestEnv,
+    account: AccountHash,
+    destination_entrypoint: &str,
+) -> Vec<U256> {
+    env.query_account_named_key(account, &[format!("{}_result", destination_entrypoint)])
+}
+
 pub fn session_add_liquidity_cspr(
     env: &TestEnv,
     sender: AccountHash,
@@ -506,8 +728,8 @@ pub fn session_add_liquidity_cspr(
     to: Key,
     deadline: U256,
     pair: Option<Key>,
+    min_liquidity_out: Option<U256>,
     router: Key,
-    test_contract_hash: Key,
 ) -> TestContract {
     TestContract::new(
         env,
@@ -525,10 +747,10 @@ pub fn session_add_liquidity_cspr(
             "to" => to,
             "deadline" => deadline,
             "pair" => pair,
-            "router_hash" => router,
-            "self_hash" => test_contract_hash
+            "min_liquidity_out" => min_liquidity_out,
+            "router_hash" => router
         },
-        0
+        0,
     )
 }
 
@@ -542,7 +764,6 @@ pub fn session_remove_liquidity_cspr(
     to: Key,
     deadline: U256,
     router: Key,
-    test_contract_hash: Key,
 ) -> TestContract {
     TestContract::new(
         env,
@@ -557,10 +778,9 @@ pub fn session_remove_liquidity_cspr(
             "amount_cspr_min" => amount_cspr_min,
             "to" => to,
             "deadline" => deadline,
-            "router_hash" => router,
-            "self_hash" => test_contract_hash
+            "router_hash" => router
         },
-        0
+        0,
     )
 }
 
@@ -573,6 +793,7 @@ pub fn session_swap_exact_cspr_for_tokens(
     path: Vec<String>,
     to: Key,
     deadline: U256,
+    max_price_impact_bps: Option<u32>,
     router: Key,
 ) -> TestContract {
     TestContract::new(
@@ -588,9 +809,10 @@ pub fn session_swap_exact_cspr_for_tokens(
             "path" => path,
             "to" => to,
             "deadline" => deadline,
+            "max_price_impact_bps" => max_price_impact_bps,
             "router_hash" => router
         },
-        0
+        0,
     )
 }
 
@@ -603,6 +825,7 @@ pub fn session_swap_cspr_for_exact_tokens(
     path: Vec<String>,
     to: Key,
     deadline: U256,
+    max_price_impact_bps: Option<u32>,
     router: Key,
 ) -> TestContract {
     TestContract::new(
@@ -618,9 +841,10 @@ pub fn session_swap_cspr_for_exact_tokens(
             "path" => path,
             "to" => to,
             "deadline" => deadline,
+            "max_price_impact_bps" => max_price_impact_bps,
             "router_hash" => router
         },
-        0
+        0,
     )
 }
 
@@ -631,6 +855,7 @@ pub fn session_swap_tokens_for_exact_cspr(
     amount_in_max: U256,
     path: Vec<String>,
     deadline: U256,
+    max_price_impact_bps: Option<u32>,
     router: Key,
 ) -> TestContract {
     TestContract::new(
@@ -644,9 +869,10 @@ pub fn session_swap_tokens_for_exact_cspr(
             "amount_in_max" => amount_in_max,
             "path" => path,
             "deadline" => deadline,
+            "max_price_impact_bps" => max_price_impact_bps,
             "router_hash" => router
         },
-        0
+        0,
     )
 }
 
@@ -657,6 +883,7 @@ pub fn session_swap_exact_tokens_for_cspr(
     amount_out_min: U256,
     path: Vec<String>,
     deadline: U256,
+    max_price_impact_bps: Option<u32>,
     router: Key,
 ) -> TestContract {
     TestContract::new(
@@ -670,8 +897,29 @@ pub fn session_swap_exact_tokens_for_cspr(
             "amount_out_min" => amount_out_min,
             "path" => path,
             "deadline" => deadline,
+            "max_price_impact_bps" => max_price_impact_bps,
             "router_hash" => router
         },
-        0
+        0,
+    )
+}
+
+pub fn session_approve_and_swap(
+    env: &TestEnv,
+    sender: AccountHash,
+    router: Key,
+    destination_entrypoint: &str,
+    mut args: RuntimeArgs,
+) -> TestContract {
+    args.insert("destination_entrypoint", destination_entrypoint)
+        .unwrap();
+    args.insert("router_hash", router).unwrap();
+    TestContract::new(
+        env,
+        APPROVE_AND_SWAP_WASM_SRC,
+        "approve-and-swap",
+        sender,
+        args,
+        0,
     )
 }
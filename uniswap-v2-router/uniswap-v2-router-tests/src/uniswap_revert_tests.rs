@@ -0,0 +1,1825 @@
+//! Negative-path tests for the router.
+//!
+//! Every test triggers a single failure path and pins the exact user error the router reverts
+//! with, printed by the engine as `User(code) [65536 + code]`. Renumbering or reusing a variant of
+//! `config::ErrorCodes` therefore fails the matching test instead of silently changing behavior.
+//!
+//! `_add_liquidity` has no error of its own for a deposit below `amount_a_min`: it returns zero
+//! amounts and the pair's `mint` reverts with an error of the pair, so that path is not pinned
+//! here.
+use casper_types::{account::AccountHash, runtime_args, Key, RuntimeArgs, U256, U512};
+use casperlabs_test_env::{TestContract, TestEnv};
+use std::time::{SystemTime, UNIX_EPOCH};
+use uniswap_v2_router::config::ORACLE_PERIOD;
+
+use crate::uniswap_instance::*;
+use crate::uniswap_tests::{
+    allow_pair, deploy_flash_borrower, deploy_pair_contract, deploy_uniswap_router,
+};
+
+fn deadline() -> U256 {
+    let deadline: u128 = match SystemTime::now().duration_since(UNIX_EPOCH) {
+        Ok(n) => n.as_millis() + (1000 * (30 * 60)), // current epoch time in milisecond + 30 minutes
+        Err(_) => 0,
+    };
+    deadline.into()
+}
+
+fn path(keys: &[&TestContract]) -> Vec<String> {
+    keys.iter()
+        .map(|contract| Key::Hash(contract.package_hash()).to_formatted_string())
+        .collect()
+}
+
+// Calls `entry_point` with a deadline that is one millisecond behind the block time. Every
+// deadline-guarded entry point reads `deadline` first, so no other argument is needed.
+fn call_after_deadline(entry_point: &str) {
+    let (_, _, owner, router_contract, _, _, _, _, _, _, _) = deploy_uniswap_router();
+    router_contract.call_contract(
+        owner,
+        entry_point,
+        runtime_args! {
+            "deadline" => U256::from(1)
+        },
+        2,
+    );
+}
+
+// Adds 10000000 of both token1 and token2 to a fresh pair, liquidity is kept by the test contract.
+fn add_token_liquidity(
+    env: &TestEnv,
+    uniswap: &UniswapInstance,
+    owner: AccountHash,
+    router_contract: &TestContract,
+    factory: &TestContract,
+    flash_swapper: &TestContract,
+    token1: &TestContract,
+    token2: &TestContract,
+) -> TestContract {
+    let pair: TestContract = deploy_pair_contract(
+        env,
+        owner,
+        Key::Hash(factory.package_hash()),
+        Key::Hash(flash_swapper.package_hash()),
+    );
+    allow_pair(router_contract, owner, &pair);
+    uniswap.add_liquidity(
+        owner,
+        Key::Hash(token1.package_hash()),
+        Key::Hash(token2.package_hash()),
+        10000000.into(),
+        10000000.into(),
+        0.into(),
+        0.into(),
+        uniswap.test_contract_package_hash(),
+        deadline(),
+        Some(Key::Hash(pair.package_hash())),
+        None,
+    );
+    pair
+}
+
+// Adds 10000000 token1 and 100 motes to a fresh token1 / wcspr pair.
+fn add_cspr_liquidity(
+    env: &TestEnv,
+    owner: AccountHash,
+    router_contract: &TestContract,
+    factory: &TestContract,
+    flash_swapper: &TestContract,
+    token1: &TestContract,
+) {
+    let pair: TestContract = deploy_pair_contract(
+        env,
+        owner,
+        Key::Hash(factory.package_hash()),
+        Key::Hash(flash_swapper.package_hash()),
+    );
+    allow_pair(router_contract, owner, &pair);
+    let amount_token_desired: U256 = 10000000.into();
+    token1.call_contract(
+        owner,
+        "approve",
+        runtime_args! {
+            "spender" => Key::Hash(router_contract.package_hash()),
+            "amount" => amount_token_desired
+        },
+        0,
+    );
+    let _ = session_add_liquidity_cspr(
+        env,
+        owner,
+        U512::from(1000),
+        Key::Hash(token1.package_hash()),
+        amount_token_desired,
+        100.into(),
+        0.into(),
+        0.into(),
+        Key::from(owner),
+        deadline(),
+        Some(Key::Hash(pair.package_hash())),
+        None,
+        Key::Hash(router_contract.package_hash()),
+    );
+}
+
+#[test]
+#[should_panic(expected = "User(106) [65642]")]
+fn add_liquidity_after_deadline() {
+    call_after_deadline("add_liquidity");
+}
+
+#[test]
+#[should_panic(expected = "User(107) [65643]")]
+fn add_liquidity_js_client_after_deadline() {
+    call_after_deadline("add_liquidity_js_client");
+}
+
+#[test]
+#[should_panic(expected = "User(108) [65644]")]
+fn add_liquidity_cspr_after_deadline() {
+    call_after_deadline("add_liquidity_cspr");
+}
+
+#[test]
+#[should_panic(expected = "User(109) [65645]")]
+fn add_liquidity_cspr_js_client_after_deadline() {
+    call_after_deadline("add_liquidity_cspr_js_client");
+}
+
+#[test]
+#[should_panic(expected = "User(110) [65646]")]
+fn remove_liquidity_after_deadline() {
+    call_after_deadline("remove_liquidity");
+}
+
+#[test]
+#[should_panic(expected = "User(111) [65647]")]
+fn remove_liquidity_js_client_after_deadline() {
+    call_after_deadline("remove_liquidity_js_client");
+}
+
+#[test]
+#[should_panic(expected = "User(112) [65648]")]
+fn remove_liquidity_cspr_after_deadline() {
+    call_after_deadline("remove_liquidity_cspr");
+}
+
+#[test]
+#[should_panic(expected = "User(113) [65649]")]
+fn remove_liquidity_cspr_js_client_after_deadline() {
+    call_after_deadline("remove_liquidity_cspr_js_client");
+}
+
+#[test]
+#[should_panic(expected = "User(114) [65650]")]
+fn swap_exact_tokens_for_tokens_after_deadline() {
+    call_after_deadline("swap_exact_tokens_for_tokens");
+}
+
+#[test]
+#[should_panic(expected = "User(115) [65651]")]
+fn swap_exact_tokens_for_tokens_js_client_after_deadline() {
+    call_after_deadline("swap_exact_tokens_for_tokens_js_client");
+}
+
+#[test]
+#[should_panic(expected = "User(116) [65652]")]
+fn swap_tokens_for_exact_tokens_after_deadline() {
+    call_after_deadline("swap_tokens_for_exact_tokens");
+}
+
+#[test]
+#[should_panic(expected = "User(117) [65653]")]
+fn swap_tokens_for_exact_tokens_js_client_after_deadline() {
+    call_after_deadline("swap_tokens_for_exact_tokens_js_client");
+}
+
+#[test]
+#[should_panic(expected = "User(118) [65654]")]
+fn swap_exact_cspr_for_tokens_after_deadline() {
+    call_after_deadline("swap_exact_cspr_for_tokens");
+}
+
+#[test]
+#[should_panic(expected = "User(119) [65655]")]
+fn swap_exact_cspr_for_tokens_js_client_after_deadline() {
+    call_after_deadline("swap_exact_cspr_for_tokens_js_client");
+}
+
+#[test]
+#[should_panic(expected = "User(120) [65656]")]
+fn swap_tokens_for_exact_cspr_after_deadline() {
+    call_after_deadline("swap_tokens_for_exact_cspr");
+}
+
+#[test]
+#[should_panic(expected = "User(121) [65657]")]
+fn swap_tokens_for_exact_cspr_js_client_after_deadline() {
+    call_after_deadline("swap_tokens_for_exact_cspr_js_client");
+}
+
+#[test]
+#[should_panic(expected = "User(122) [65658]")]
+fn swap_exact_tokens_for_cspr_after_deadline() {
+    call_after_deadline("swap_exact_tokens_for_cspr");
+}
+
+#[test]
+#[should_panic(expected = "User(123) [65659]")]
+fn swap_exact_tokens_for_cspr_js_client_after_deadline() {
+    call_after_deadline("swap_exact_tokens_for_cspr_js_client");
+}
+
+#[test]
+#[should_panic(expected = "User(124) [65660]")]
+fn swap_cspr_for_exact_tokens_after_deadline() {
+    call_after_deadline("swap_cspr_for_exact_tokens");
+}
+
+#[test]
+#[should_panic(expected = "User(125) [65661]")]
+fn swap_cspr_for_exact_tokens_js_client_after_deadline() {
+    call_after_deadline("swap_cspr_for_exact_tokens_js_client");
+}
+
+#[test]
+#[should_panic(expected = "User(128) [65664]")]
+fn remove_liquidity_cspr_to_key_after_deadline() {
+    call_after_deadline("remove_liquidity_cspr_to_key");
+}
+
+#[test]
+#[should_panic(expected = "User(129) [65665]")]
+fn swap_tokens_for_exact_cspr_to_key_after_deadline() {
+    call_after_deadline("swap_tokens_for_exact_cspr_to_key");
+}
+
+#[test]
+#[should_panic(expected = "User(130) [65666]")]
+fn swap_exact_tokens_for_cspr_to_key_after_deadline() {
+    call_after_deadline("swap_exact_tokens_for_cspr_to_key");
+}
+
+#[test]
+#[should_panic(expected = "User(139) [65675]")]
+fn flash_swap_after_deadline() {
+    call_after_deadline("flash_swap");
+}
+
+#[test]
+#[should_panic(expected = "User(142) [65678]")]
+fn swap_cycle_after_deadline() {
+    call_after_deadline("swap_cycle");
+}
+
+#[test]
+#[should_panic(expected = "User(67) [65603]")]
+fn add_liquidity_zero_amount_a_desired() {
+    let (env, uniswap, owner, _, flash_swapper, _, token1, token2, _, _, factory) =
+        deploy_uniswap_router();
+    let pair: TestContract = deploy_pair_contract(
+        &env,
+        owner,
+        Key::Hash(factory.package_hash()),
+        Key::Hash(flash_swapper.package_hash()),
+    );
+    uniswap.add_liquidity(
+        owner,
+        Key::Hash(token1.package_hash()),
+        Key::Hash(token2.package_hash()),
+        0.into(),
+        10000000.into(),
+        0.into(),
+        0.into(),
+        uniswap.test_contract_package_hash(),
+        deadline(),
+        Some(Key::Hash(pair.package_hash())),
+        None,
+    );
+}
+
+#[test]
+#[should_panic(expected = "User(68) [65604]")]
+fn add_liquidity_zero_amount_b_desired() {
+    let (env, uniswap, owner, _, flash_swapper, _, token1, token2, _, _, factory) =
+        deploy_uniswap_router();
+    let pair: TestContract = deploy_pair_contract(
+        &env,
+        owner,
+        Key::Hash(factory.package_hash()),
+        Key::Hash(flash_swapper.package_hash()),
+    );
+    uniswap.add_liquidity(
+        owner,
+        Key::Hash(token1.package_hash()),
+        Key::Hash(token2.package_hash()),
+        10000000.into(),
+        0.into(),
+        0.into(),
+        0.into(),
+        uniswap.test_contract_package_hash(),
+        deadline(),
+        Some(Key::Hash(pair.package_hash())),
+        None,
+    );
+}
+
+#[test]
+#[should_panic(expected = "User(69) [65605]")]
+fn add_liquidity_cspr_zero_amount_token() {
+    let (env, _, owner, router_contract, flash_swapper, _, token1, _, _, _, factory) =
+        deploy_uniswap_router();
+    let pair: TestContract = deploy_pair_contract(
+        &env,
+        owner,
+        Key::Hash(factory.package_hash()),
+        Key::Hash(flash_swapper.package_hash()),
+    );
+    let _ = session_add_liquidity_cspr(
+        &env,
+        owner,
+        U512::from(1000),
+        Key::Hash(token1.package_hash()),
+        0.into(),
+        100.into(),
+        0.into(),
+        0.into(),
+        Key::from(owner),
+        deadline(),
+        Some(Key::Hash(pair.package_hash())),
+        None,
+        Key::Hash(router_contract.package_hash()),
+    );
+}
+
+#[test]
+#[should_panic(expected = "User(104) [65640]")]
+fn add_liquidity_without_pair() {
+    let (_, uniswap, owner, _, _, _, token1, token2, _, _, _) = deploy_uniswap_router();
+    uniswap.add_liquidity(
+        owner,
+        Key::Hash(token1.package_hash()),
+        Key::Hash(token2.package_hash()),
+        10000000.into(),
+        10000000.into(),
+        0.into(),
+        0.into(),
+        uniswap.test_contract_package_hash(),
+        deadline(),
+        None,
+        None,
+    );
+}
+
+#[test]
+#[should_panic(expected = "User(105) [65641]")]
+fn add_liquidity_below_amount_b_min() {
+    let (env, uniswap, owner, router_contract, flash_swapper, _, token1, token2, _, _, factory) =
+        deploy_uniswap_router();
+    let pair = add_token_liquidity(
+        &env,
+        &uniswap,
+        owner,
+        &router_contract,
+        &factory,
+        &flash_swapper,
+        &token1,
+        &token2,
+    );
+    // below amount_b_min the router falls back to amount_a_optimal, which exceeds amount_a_desired
+    uniswap.add_liquidity(
+        owner,
+        Key::Hash(token1.package_hash()),
+        Key::Hash(token2.package_hash()),
+        1000000.into(),
+        2000000.into(),
+        0.into(),
+        1500000.into(),
+        uniswap.test_contract_package_hash(),
+        deadline(),
+        Some(Key::Hash(pair.package_hash())),
+        None,
+    );
+}
+
+// Installs a pair stand-in that accepts the factory's initialization but reports `token0`,
+// `token1` and `factory` as given.
+fn deploy_malicious_pair(
+    env: &TestEnv,
+    owner: AccountHash,
+    token0: Key,
+    token1: Key,
+    factory: Key,
+) -> TestContract {
+    TestContract::new(
+        env,
+        "malicious-pair.wasm",
+        "malicious_pair",
+        owner,
+        runtime_args! {
+            "token0" => token0,
+            "token1" => token1,
+            "factory" => factory
+        },
+        0,
+    )
+}
+
+#[test]
+#[should_panic(expected = "User(131) [65667]")]
+fn add_liquidity_with_pair_reporting_other_tokens() {
+    let (env, uniswap, owner, router_contract, _, _, token1, token2, token3, _, factory) =
+        deploy_uniswap_router();
+    let pair: TestContract = deploy_malicious_pair(
+        &env,
+        owner,
+        Key::Hash(token1.package_hash()),
+        Key::Hash(token3.package_hash()),
+        Key::Hash(factory.package_hash()),
+    );
+    // even an allowed pair has to report the tokens it is registered for
+    allow_pair(&router_contract, owner, &pair);
+    uniswap.add_liquidity(
+        owner,
+        Key::Hash(token1.package_hash()),
+        Key::Hash(token2.package_hash()),
+        10000000.into(),
+        10000000.into(),
+        0.into(),
+        0.into(),
+        uniswap.test_contract_package_hash(),
+        deadline(),
+        Some(Key::Hash(pair.package_hash())),
+        None,
+    );
+}
+
+#[test]
+#[should_panic(expected = "User(131) [65667]")]
+fn add_liquidity_with_pair_not_allowed() {
+    let (env, uniswap, owner, router_contract, _, _, token1, token2, _, _, factory) =
+        deploy_uniswap_router();
+    let token_a = Key::Hash(token1.package_hash());
+    let token_b = Key::Hash(token2.package_hash());
+    let (pair_token0, pair_token1) = if token1.package_hash() < token2.package_hash() {
+        (token_a, token_b)
+    } else {
+        (token_b, token_a)
+    };
+    router_contract.call_contract(
+        owner,
+        "set_pair_allowlist",
+        runtime_args! {
+            "enabled" => true
+        },
+        0,
+    );
+    // the stand-in reports the right tokens and factory, only its code is not a vetted pair
+    let pair: TestContract = deploy_malicious_pair(
+        &env,
+        owner,
+        pair_token0,
+        pair_token1,
+        Key::Hash(factory.package_hash()),
+    );
+    uniswap.add_liquidity(
+        owner,
+        token_a,
+        token_b,
+        10000000.into(),
+        10000000.into(),
+        0.into(),
+        0.into(),
+        uniswap.test_contract_package_hash(),
+        deadline(),
+        Some(Key::Hash(pair.package_hash())),
+        None,
+    );
+}
+
+#[test]
+#[should_panic(expected = "User(131) [65667]")]
+fn add_liquidity_with_pair_of_other_factory() {
+    let (env, uniswap, owner, router_contract, _, _, token1, token2, token3, _, _) =
+        deploy_uniswap_router();
+    let token_a = Key::Hash(token1.package_hash());
+    let token_b = Key::Hash(token2.package_hash());
+    let (pair_token0, pair_token1) = if token1.package_hash() < token2.package_hash() {
+        (token_a, token_b)
+    } else {
+        (token_b, token_a)
+    };
+    // the stand-in reports the right tokens but was made for another factory
+    let pair: TestContract = deploy_malicious_pair(
+        &env,
+        owner,
+        pair_token0,
+        pair_token1,
+        Key::Hash(token3.package_hash()),
+    );
+    // being allowed does not make up for it
+    allow_pair(&router_contract, owner, &pair);
+    uniswap.add_liquidity(
+        owner,
+        token_a,
+        token_b,
+        10000000.into(),
+        10000000.into(),
+        0.into(),
+        0.into(),
+        uniswap.test_contract_package_hash(),
+        deadline(),
+        Some(Key::Hash(pair.package_hash())),
+        None,
+    );
+}
+
+#[test]
+#[should_panic(expected = "User(131) [65667]")]
+fn add_liquidity_cspr_with_pair_reporting_other_tokens() {
+    let (env, _, owner, router_contract, _, _, token1, token2, _, _, factory) =
+        deploy_uniswap_router();
+    let pair: TestContract = deploy_malicious_pair(
+        &env,
+        owner,
+        Key::Hash(token1.package_hash()),
+        Key::Hash(token2.package_hash()),
+        Key::Hash(factory.package_hash()),
+    );
+    allow_pair(&router_contract, owner, &pair);
+    let _ = session_add_liquidity_cspr(
+        &env,
+        owner,
+        U512::from(1000),
+        Key::Hash(token1.package_hash()),
+        10000000.into(),
+        100.into(),
+        0.into(),
+        0.into(),
+        Key::from(owner),
+        deadline(),
+        Some(Key::Hash(pair.package_hash())),
+        None,
+        Key::Hash(router_contract.package_hash()),
+    );
+}
+
+#[test]
+#[should_panic(expected = "User(132) [65668]")]
+fn set_min_initial_liquidity_not_owner() {
+    let (env, _, _, router_contract, _, _, _, _, _, _, _) = deploy_uniswap_router();
+    router_contract.call_contract(
+        env.next_user(),
+        "set_min_initial_liquidity",
+        runtime_args! {
+            "min_initial_liquidity" => U256::from(1000)
+        },
+        0,
+    );
+}
+
+#[test]
+#[should_panic(expected = "User(132) [65668]")]
+fn set_pair_allowlist_not_owner() {
+    let (env, _, _, router_contract, _, _, _, _, _, _, _) = deploy_uniswap_router();
+    router_contract.call_contract(
+        env.next_user(),
+        "set_pair_allowlist",
+        runtime_args! {
+            "enabled" => false
+        },
+        0,
+    );
+}
+
+#[test]
+#[should_panic(expected = "User(132) [65668]")]
+fn set_pair_allowed_not_owner() {
+    let (env, _, _, router_contract, _, pair_contract, _, _, _, _, _) = deploy_uniswap_router();
+    allow_pair(&router_contract, env.next_user(), &pair_contract);
+}
+
+// 10000000 of both tokens into a fresh pair mints 9999000 liquidity.
+#[test]
+#[should_panic(expected = "User(133) [65669]")]
+fn add_liquidity_below_min_liquidity_out() {
+    let (env, uniswap, owner, router_contract, flash_swapper, _, token1, token2, _, _, factory) =
+        deploy_uniswap_router();
+    let pair: TestContract = deploy_pair_contract(
+        &env,
+        owner,
+        Key::Hash(factory.package_hash()),
+        Key::Hash(flash_swapper.package_hash()),
+    );
+    allow_pair(&router_contract, owner, &pair);
+    uniswap.add_liquidity(
+        owner,
+        Key::Hash(token1.package_hash()),
+        Key::Hash(token2.package_hash()),
+        10000000.into(),
+        10000000.into(),
+        0.into(),
+        0.into(),
+        uniswap.test_contract_package_hash(),
+        deadline(),
+        Some(Key::Hash(pair.package_hash())),
+        Some(10000000.into()),
+    );
+}
+
+#[test]
+#[should_panic(expected = "User(134) [65670]")]
+fn add_liquidity_below_min_initial_liquidity() {
+    let (env, uniswap, owner, router_contract, flash_swapper, _, token1, token2, _, _, factory) =
+        deploy_uniswap_router();
+    let pair: TestContract = deploy_pair_contract(
+        &env,
+        owner,
+        Key::Hash(factory.package_hash()),
+        Key::Hash(flash_swapper.package_hash()),
+    );
+    allow_pair(&router_contract, owner, &pair);
+    router_contract.call_contract(
+        owner,
+        "set_min_initial_liquidity",
+        runtime_args! {
+            "min_initial_liquidity" => U256::from(10000000)
+        },
+        0,
+    );
+    uniswap.add_liquidity(
+        owner,
+        Key::Hash(token1.package_hash()),
+        Key::Hash(token2.package_hash()),
+        10000000.into(),
+        10000000.into(),
+        0.into(),
+        0.into(),
+        uniswap.test_contract_package_hash(),
+        deadline(),
+        Some(Key::Hash(pair.package_hash())),
+        None,
+    );
+}
+
+// The dummy erc20 reverts on its own when a transfer cannot happen, so the router's check of the
+// transfer result is pinned with a token whose transfers report failure instead.
+#[test]
+#[should_panic(expected = "User(83) [65619]")]
+fn add_liquidity_failed_transfer_from() {
+    let (env, uniswap, owner, router_contract, flash_swapper, _, _, token2, _, _, factory) =
+        deploy_uniswap_router();
+    let failing_token = TestContract::new(
+        &env,
+        "failing-token.wasm",
+        "failing_token",
+        owner,
+        runtime_args! {},
+        0,
+    );
+    let pair: TestContract = deploy_pair_contract(
+        &env,
+        owner,
+        Key::Hash(factory.package_hash()),
+        Key::Hash(flash_swapper.package_hash()),
+    );
+    allow_pair(&router_contract, owner, &pair);
+    uniswap.add_liquidity(
+        owner,
+        Key::Hash(failing_token.package_hash()),
+        Key::Hash(token2.package_hash()),
+        10000000.into(),
+        10000000.into(),
+        0.into(),
+        0.into(),
+        uniswap.test_contract_package_hash(),
+        deadline(),
+        Some(Key::Hash(pair.package_hash())),
+        None,
+    );
+}
+
+#[test]
+#[should_panic(expected = "User(70) [65606]")]
+fn remove_liquidity_below_amount_a_min() {
+    let (env, uniswap, owner, router_contract, flash_swapper, _, token1, token2, _, _, factory) =
+        deploy_uniswap_router();
+    let pair = add_token_liquidity(
+        &env,
+        &uniswap,
+        owner,
+        &router_contract,
+        &factory,
+        &flash_swapper,
+        &token1,
+        &token2,
+    );
+    let (_, _, liquidity): (U256, U256, U256) = uniswap.add_liquidity_result();
+
+    // MINIMUM_LIQUIDITY stays locked in the pair, so the full deposit can never be withdrawn
+    uniswap.remove_liquidity(
+        owner,
+        Key::Hash(token1.package_hash()),
+        Key::Hash(token2.package_hash()),
+        liquidity,
+        10000000.into(),
+        0.into(),
+        uniswap.test_contract_package_hash(),
+        deadline(),
+        Key::Hash(pair.package_hash()),
+        uniswap.test_contract_package_hash(),
+    );
+}
+
+#[test]
+#[should_panic(expected = "User(71) [65607]")]
+fn remove_liquidity_with_permit_without_pair() {
+    let (_, uniswap, owner, _, _, _, token1, token2, _, _, _) = deploy_uniswap_router();
+    uniswap.remove_liquidity_with_permit(
+        owner,
+        Key::Hash(token1.package_hash()),
+        Key::Hash(token2.package_hash()),
+        1000.into(),
+        0.into(),
+        0.into(),
+        uniswap.test_contract_package_hash(),
+        deadline(),
+        false,
+        String::new(),
+        String::new(),
+    );
+}
+
+#[test]
+#[should_panic(expected = "User(72) [65608]")]
+fn remove_liquidity_cspr_with_permit_without_pair() {
+    let (_, uniswap, owner, _, _, _, token1, _, _, _, _) = deploy_uniswap_router();
+    uniswap.remove_liquidity_cspr_with_permit(
+        owner,
+        Key::Hash(token1.package_hash()),
+        1000.into(),
+        0.into(),
+        0.into(),
+        uniswap.test_contract_package_hash(),
+        deadline(),
+        false,
+        String::new(),
+        String::new(),
+    );
+}
+
+#[test]
+#[should_panic(expected = "User(73) [65609]")]
+fn swap_exact_tokens_for_tokens_below_amount_out_min() {
+    let (
+        env,
+        uniswap,
+        owner,
+        router_contract,
+        flash_swapper,
+        _,
+        token1,
+        token2,
+        token3,
+        _,
+        factory,
+    ) = deploy_uniswap_router();
+    add_token_liquidity(
+        &env,
+        &uniswap,
+        owner,
+        &router_contract,
+        &factory,
+        &flash_swapper,
+        &token1,
+        &token2,
+    );
+    // the 0.3% fee keeps the output below the input on a balanced pool
+    uniswap.swap_exact_tokens_for_tokens(
+        owner,
+        100000.into(),
+        100000.into(),
+        path(&[&token1, &token2]),
+        Key::Hash(token3.package_hash()),
+        deadline(),
+        None,
+    );
+}
+
+#[test]
+#[should_panic(expected = "User(74) [65610]")]
+fn swap_tokens_for_exact_tokens_above_amount_in_max() {
+    let (
+        env,
+        uniswap,
+        owner,
+        router_contract,
+        flash_swapper,
+        _,
+        token1,
+        token2,
+        token3,
+        _,
+        factory,
+    ) = deploy_uniswap_router();
+    add_token_liquidity(
+        &env,
+        &uniswap,
+        owner,
+        &router_contract,
+        &factory,
+        &flash_swapper,
+        &token1,
+        &token2,
+    );
+    uniswap.swap_tokens_for_exact_tokens(
+        owner,
+        10000.into(),
+        1.into(),
+        path(&[&token1, &token2]),
+        Key::Hash(token3.package_hash()),
+        deadline(),
+        None,
+    );
+}
+
+#[test]
+#[should_panic(expected = "User(75) [65611]")]
+fn swap_exact_cspr_for_tokens_without_wcspr_first() {
+    let (_, uniswap, owner, router_contract, _, _, token1, token2, _, wcspr, _) =
+        deploy_uniswap_router();
+    uniswap.swap_exact_cspr_for_tokens(
+        owner,
+        0.into(),
+        10.into(),
+        path(&[&token1, &wcspr]),
+        Key::Hash(token2.package_hash()),
+        deadline(),
+        None,
+        Key::Hash(router_contract.package_hash()),
+    );
+}
+
+#[test]
+#[should_panic(expected = "User(76) [65612]")]
+fn swap_exact_cspr_for_tokens_below_amount_out_min() {
+    let (env, uniswap, owner, router_contract, flash_swapper, _, token1, token2, _, wcspr, factory) =
+        deploy_uniswap_router();
+    add_cspr_liquidity(
+        &env,
+        owner,
+        &router_contract,
+        &factory,
+        &flash_swapper,
+        &token1,
+    );
+    // the pool only holds 10000000 token1
+    uniswap.swap_exact_cspr_for_tokens(
+        owner,
+        10000000.into(),
+        10.into(),
+        path(&[&wcspr, &token1]),
+        Key::Hash(token2.package_hash()),
+        deadline(),
+        None,
+        Key::Hash(router_contract.package_hash()),
+    );
+}
+
+#[test]
+#[should_panic(expected = "User(77) [65613]")]
+fn swap_tokens_for_exact_cspr_without_wcspr_last() {
+    let (_, uniswap, owner, _, _, _, token1, _, _, wcspr, _) = deploy_uniswap_router();
+    uniswap.swap_tokens_for_exact_cspr(
+        owner,
+        10.into(),
+        10000000.into(),
+        path(&[&wcspr, &token1]),
+        deadline(),
+        None,
+    );
+}
+
+#[test]
+#[should_panic(expected = "User(78) [65614]")]
+fn swap_tokens_for_exact_cspr_above_amount_in_max() {
+    let (env, uniswap, owner, router_contract, flash_swapper, _, token1, _, _, wcspr, factory) =
+        deploy_uniswap_router();
+    add_cspr_liquidity(
+        &env,
+        owner,
+        &router_contract,
+        &factory,
+        &flash_swapper,
+        &token1,
+    );
+    uniswap.swap_tokens_for_exact_cspr(
+        owner,
+        10.into(),
+        1.into(),
+        path(&[&token1, &wcspr]),
+        deadline(),
+        None,
+    );
+}
+
+#[test]
+#[should_panic(expected = "User(79) [65615]")]
+fn swap_exact_tokens_for_cspr_without_wcspr_last() {
+    let (_, uniswap, owner, _, _, _, token1, _, _, wcspr, _) = deploy_uniswap_router();
+    uniswap.swap_exact_tokens_for_cspr(
+        owner,
+        1000.into(),
+        0.into(),
+        path(&[&wcspr, &token1]),
+        deadline(),
+        None,
+    );
+}
+
+#[test]
+#[should_panic(expected = "User(80) [65616]")]
+fn swap_exact_tokens_for_cspr_below_amount_out_min() {
+    let (env, uniswap, owner, router_contract, flash_swapper, _, token1, _, _, wcspr, factory) =
+        deploy_uniswap_router();
+    add_cspr_liquidity(
+        &env,
+        owner,
+        &router_contract,
+        &factory,
+        &flash_swapper,
+        &token1,
+    );
+    // the pool only holds 100 motes
+    uniswap.swap_exact_tokens_for_cspr(
+        owner,
+        1000.into(),
+        100.into(),
+        path(&[&token1, &wcspr]),
+        deadline(),
+        None,
+    );
+}
+
+#[test]
+#[should_panic(expected = "User(81) [65617]")]
+fn swap_cspr_for_exact_tokens_without_wcspr_first() {
+    let (_, uniswap, owner, _, _, _, token1, token2, _, wcspr, _) = deploy_uniswap_router();
+    uniswap.swap_cspr_for_exact_tokens(
+        owner,
+        10.into(),
+        10.into(),
+        path(&[&token1, &wcspr]),
+        Key::Hash(token2.package_hash()),
+        deadline(),
+        None,
+    );
+}
+
+#[test]
+#[should_panic(expected = "User(82) [65618]")]
+fn swap_cspr_for_exact_tokens_above_amount_in_max() {
+    let (env, uniswap, owner, router_contract, flash_swapper, _, token1, token2, _, wcspr, factory) =
+        deploy_uniswap_router();
+    add_cspr_liquidity(
+        &env,
+        owner,
+        &router_contract,
+        &factory,
+        &flash_swapper,
+        &token1,
+    );
+    uniswap.swap_cspr_for_exact_tokens(
+        owner,
+        1000000.into(),
+        1.into(),
+        path(&[&wcspr, &token1]),
+        Key::Hash(token2.package_hash()),
+        deadline(),
+        None,
+    );
+}
+
+#[test]
+#[should_panic(expected = "User(127) [65663]")]
+fn swap_exact_tokens_for_cspr_to_contract() {
+    let (env, _, owner, router_contract, flash_swapper, _, token1, _, _, wcspr, factory) =
+        deploy_uniswap_router();
+    add_cspr_liquidity(
+        &env,
+        owner,
+        &router_contract,
+        &factory,
+        &flash_swapper,
+        &token1,
+    );
+    let router = Key::Hash(router_contract.package_hash());
+    token1.call_contract(
+        owner,
+        "approve",
+        runtime_args! {
+            "spender" => router,
+            "amount" => U256::from(100000)
+        },
+        0,
+    );
+    // a contract hash has no purse to pay the cspr into
+    router_contract.call_contract(
+        owner,
+        "swap_exact_tokens_for_cspr_to_key",
+        runtime_args! {
+            "amount_in" => U256::from(100000),
+            "amount_out_min" => U256::zero(),
+            "path" => path(&[&token1, &wcspr]),
+            "to" => router,
+            "deadline" => deadline(),
+            "max_price_impact_bps" => None::<u32>
+        },
+        0,
+    );
+}
+
+#[test]
+#[should_panic(expected = "User(135) [65671]")]
+fn swap_exact_tokens_for_tokens_above_max_price_impact() {
+    let (
+        env,
+        uniswap,
+        owner,
+        router_contract,
+        flash_swapper,
+        _,
+        token1,
+        token2,
+        token3,
+        _,
+        factory,
+    ) = deploy_uniswap_router();
+    add_token_liquidity(
+        &env,
+        &uniswap,
+        owner,
+        &router_contract,
+        &factory,
+        &flash_swapper,
+        &token1,
+        &token2,
+    );
+    // half the reserve in moves the price by roughly a third
+    uniswap.swap_exact_tokens_for_tokens(
+        owner,
+        5000000.into(),
+        0.into(),
+        path(&[&token1, &token2]),
+        Key::Hash(token3.package_hash()),
+        deadline(),
+        Some(100),
+    );
+}
+
+#[test]
+#[should_panic(expected = "User(136) [65672]")]
+fn consult_window_below_oracle_period() {
+    let (_, uniswap, owner, _, _, _, token1, token2, _, _, _) = deploy_uniswap_router();
+    uniswap.consult(
+        owner,
+        Key::Hash(token1.package_hash()),
+        1000.into(),
+        Key::Hash(token2.package_hash()),
+        ORACLE_PERIOD - 1,
+        3 * ORACLE_PERIOD,
+    );
+}
+
+#[test]
+#[should_panic(expected = "User(137) [65673]")]
+fn consult_without_observation() {
+    let (env, uniswap, owner, router_contract, flash_swapper, _, token1, token2, _, _, factory) =
+        deploy_uniswap_router();
+    add_token_liquidity(
+        &env,
+        &uniswap,
+        owner,
+        &router_contract,
+        &factory,
+        &flash_swapper,
+        &token1,
+        &token2,
+    );
+    // nobody called update_oracle in the period the window starts in
+    uniswap.consult(
+        owner,
+        Key::Hash(token1.package_hash()),
+        1000.into(),
+        Key::Hash(token2.package_hash()),
+        2 * ORACLE_PERIOD,
+        3 * ORACLE_PERIOD,
+    );
+}
+
+#[test]
+#[should_panic(expected = "User(132) [65668]")]
+fn set_oracle_guard_not_owner() {
+    let (env, _, _, router_contract, _, _, token1, token2, _, _, _) = deploy_uniswap_router();
+    router_contract.call_contract(
+        env.next_user(),
+        "set_oracle_guard",
+        runtime_args! {
+            "token_a" => Key::Hash(token1.package_hash()),
+            "token_b" => Key::Hash(token2.package_hash()),
+            "max_deviation_bps" => Some(100u32),
+            "window" => ORACLE_PERIOD
+        },
+        0,
+    );
+}
+
+#[test]
+#[should_panic(expected = "User(138) [65674]")]
+fn swap_with_spot_price_away_from_average() {
+    let (env, uniswap, owner, router_contract, flash_swapper, _, token1, token2, _, _, factory) =
+        deploy_uniswap_router();
+    add_token_liquidity(
+        &env,
+        &uniswap,
+        owner,
+        &router_contract,
+        &factory,
+        &flash_swapper,
+        &token1,
+        &token2,
+    );
+    let token_a = Key::Hash(token1.package_hash());
+    let token_b = Key::Hash(token2.package_hash());
+    router_contract.call_contract(
+        owner,
+        "set_oracle_guard",
+        runtime_args! {
+            "token_a" => token_a,
+            "token_b" => token_b,
+            "max_deviation_bps" => Some(100u32),
+            "window" => ORACLE_PERIOD
+        },
+        0,
+    );
+    router_contract.call_contract(
+        owner,
+        "update_oracle",
+        runtime_args! {
+            "token_a" => token_a,
+            "token_b" => token_b
+        },
+        ORACLE_PERIOD,
+    );
+    uniswap.approve(
+        &token1,
+        owner,
+        Key::Hash(router_contract.package_hash()),
+        10000000.into(),
+    );
+    let swap = |amount_in: U256, time: u64| {
+        router_contract.call_contract(
+            owner,
+            "swap_exact_tokens_for_tokens",
+            runtime_args! {
+                "amount_in" => amount_in,
+                "amount_out_min" => U256::zero(),
+                "path" => path(&[&token1, &token2]),
+                "to" => Key::from(owner),
+                "deadline" => deadline(),
+                "max_price_impact_bps" => None::<u32>
+            },
+            time,
+        )
+    };
+    // spot and average price still agree, so the guard lets this swap move the price
+    swap(5000000.into(), 2 * ORACLE_PERIOD);
+    // half of the window has passed at the old price since
+    swap(100000.into(), 2 * ORACLE_PERIOD + ORACLE_PERIOD / 2);
+}
+
+// Without an observation the guard has no average to hold the spot price to, so the swap that
+// would move the price is refused rather than let through unchecked.
+#[test]
+#[should_panic(expected = "User(137) [65673]")]
+fn swap_through_guarded_pair_without_observation() {
+    let (env, uniswap, owner, router_contract, flash_swapper, _, token1, token2, _, _, factory) =
+        deploy_uniswap_router();
+    add_token_liquidity(
+        &env,
+        &uniswap,
+        owner,
+        &router_contract,
+        &factory,
+        &flash_swapper,
+        &token1,
+        &token2,
+    );
+    let token_a = Key::Hash(token1.package_hash());
+    let token_b = Key::Hash(token2.package_hash());
+    router_contract.call_contract(
+        owner,
+        "set_oracle_guard",
+        runtime_args! {
+            "token_a" => token_a,
+            "token_b" => token_b,
+            "max_deviation_bps" => Some(100u32),
+            "window" => ORACLE_PERIOD
+        },
+        0,
+    );
+    uniswap.approve(
+        &token1,
+        owner,
+        Key::Hash(router_contract.package_hash()),
+        10000000.into(),
+    );
+    let swap = |amount_in: U256, time: u64| {
+        router_contract.call_contract(
+            owner,
+            "swap_exact_tokens_for_tokens",
+            runtime_args! {
+                "amount_in" => amount_in,
+                "amount_out_min" => U256::zero(),
+                "path" => path(&[&token1, &token2]),
+                "to" => Key::from(owner),
+                "deadline" => deadline(),
+                "max_price_impact_bps" => None::<u32>
+            },
+            time,
+        )
+    };
+    swap(5000000.into(), 2 * ORACLE_PERIOD);
+}
+
+#[test]
+#[should_panic(expected = "User(138) [65674]")]
+fn swap_with_spot_price_away_from_stale_average() {
+    let (env, uniswap, owner, router_contract, flash_swapper, _, token1, token2, _, _, factory) =
+        deploy_uniswap_router();
+    add_token_liquidity(
+        &env,
+        &uniswap,
+        owner,
+        &router_contract,
+        &factory,
+        &flash_swapper,
+        &token1,
+        &token2,
+    );
+    let token_a = Key::Hash(token1.package_hash());
+    let token_b = Key::Hash(token2.package_hash());
+    router_contract.call_contract(
+        owner,
+        "set_oracle_guard",
+        runtime_args! {
+            "token_a" => token_a,
+            "token_b" => token_b,
+            "max_deviation_bps" => Some(100u32),
+            "window" => ORACLE_PERIOD
+        },
+        0,
+    );
+    router_contract.call_contract(
+        owner,
+        "update_oracle",
+        runtime_args! {
+            "token_a" => token_a,
+            "token_b" => token_b
+        },
+        ORACLE_PERIOD,
+    );
+    uniswap.approve(
+        &token1,
+        owner,
+        Key::Hash(router_contract.package_hash()),
+        10000000.into(),
+    );
+    let swap = |amount_in: U256, time: u64| {
+        router_contract.call_contract(
+            owner,
+            "swap_exact_tokens_for_tokens",
+            runtime_args! {
+                "amount_in" => amount_in,
+                "amount_out_min" => U256::zero(),
+                "path" => path(&[&token1, &token2]),
+                "to" => Key::from(owner),
+                "deadline" => deadline(),
+                "max_price_impact_bps" => None::<u32>
+            },
+            time,
+        )
+    };
+    // nobody observed the pair in the period the window starts in, so the guard averages from
+    // the older observation, at which spot and average price still agree
+    swap(5000000.into(), 5 * ORACLE_PERIOD);
+    swap(100000.into(), 5 * ORACLE_PERIOD);
+}
+
+#[test]
+#[should_panic(expected = "User(104) [65640]")]
+fn quote_remove_liquidity_without_pair() {
+    let (_, uniswap, owner, _, _, _, token1, _, token3, _, _) = deploy_uniswap_router();
+    uniswap.quote_remove_liquidity(
+        owner,
+        Key::Hash(token1.package_hash()),
+        Key::Hash(token3.package_hash()),
+        1000.into(),
+    );
+}
+
+#[test]
+#[should_panic(expected = "User(8) [65544]")]
+fn quote_remove_liquidity_above_total_supply() {
+    let (env, uniswap, owner, router_contract, flash_swapper, _, token1, token2, _, _, factory) =
+        deploy_uniswap_router();
+    add_token_liquidity(
+        &env,
+        &uniswap,
+        owner,
+        &router_contract,
+        &factory,
+        &flash_swapper,
+        &token1,
+        &token2,
+    );
+    // the first deposit of 10000000 of both tokens makes a supply of 10000000
+    uniswap.quote_remove_liquidity(
+        owner,
+        Key::Hash(token1.package_hash()),
+        Key::Hash(token2.package_hash()),
+        10000001.into(),
+    );
+}
+
+#[test]
+#[should_panic(expected = "User(104) [65640]")]
+fn get_pair_info_without_pair() {
+    let (_, uniswap, owner, _, _, _, token1, _, token3, _, _) = deploy_uniswap_router();
+    uniswap.get_pair_info(
+        owner,
+        Key::Hash(token1.package_hash()),
+        Key::Hash(token3.package_hash()),
+    );
+}
+
+#[test]
+#[should_panic(expected = "User(104) [65640]")]
+fn get_reserves_without_pair() {
+    let (_, _, owner, router_contract, _, _, token1, _, token3, _, _) = deploy_uniswap_router();
+    router_contract.call_contract(
+        owner,
+        "get_reserves",
+        runtime_args! {
+            "token_a" => Key::Hash(token1.package_hash()),
+            "token_b" => Key::Hash(token3.package_hash())
+        },
+        0,
+    );
+}
+
+#[test]
+#[should_panic(expected = "User(104) [65640]")]
+fn pair_for_without_pair() {
+    let (_, _, owner, router_contract, _, _, token1, _, token3, _, _) = deploy_uniswap_router();
+    router_contract.call_contract(
+        owner,
+        "pair_for",
+        runtime_args! {
+            "token_a" => Key::Hash(token1.package_hash()),
+            "token_b" => Key::Hash(token3.package_hash())
+        },
+        0,
+    );
+}
+
+#[test]
+#[should_panic(expected = "User(7) [65543]")]
+fn get_amounts_out_batch_single_token_path() {
+    let (_, uniswap, owner, _, _, _, token1, _, _, _, _) = deploy_uniswap_router();
+    uniswap.get_amounts_out_batch(
+        owner,
+        vec![1000.into()],
+        vec![Key::Hash(token1.package_hash())],
+    );
+}
+
+#[test]
+#[should_panic(expected = "User(105) [65641]")]
+fn flash_swap_without_data() {
+    let (_, uniswap, owner, router_contract, _, _, token1, token2, _, _, _) =
+        deploy_uniswap_router();
+    // the pair would skip the callback
+    router_contract.call_contract(
+        owner,
+        "flash_swap",
+        runtime_args! {
+            "token" => Key::Hash(token1.package_hash()),
+            "other_token" => Key::Hash(token2.package_hash()),
+            "amount" => U256::from(100000),
+            "callback" => uniswap.test_contract_package_hash(),
+            "data" => "",
+            "deadline" => deadline()
+        },
+        0,
+    );
+}
+
+#[test]
+#[should_panic(expected = "User(141) [65677]")]
+fn flash_swap_without_fee() {
+    let (env, uniswap, owner, router_contract, _, _, token1, token2, _, _, factory) =
+        deploy_uniswap_router();
+    // pairs call back the router during flash swaps
+    add_token_liquidity(
+        &env,
+        &uniswap,
+        owner,
+        &router_contract,
+        &factory,
+        &router_contract,
+        &token1,
+        &token2,
+    );
+    // the test contract pays back only what it borrowed
+    router_contract.call_contract(
+        owner,
+        "flash_swap",
+        runtime_args! {
+            "token" => Key::Hash(token1.package_hash()),
+            "other_token" => Key::Hash(token2.package_hash()),
+            "amount" => U256::from(100000),
+            "callback" => uniswap.test_contract_package_hash(),
+            "data" => "no fee",
+            "deadline" => deadline()
+        },
+        0,
+    );
+}
+
+#[test]
+#[should_panic(expected = "User(1) [65537]")]
+fn flash_swap_into_foreign_borrower() {
+    let (env, uniswap, owner, router_contract, _, _, token1, token2, _, _, factory) =
+        deploy_uniswap_router();
+    add_token_liquidity(
+        &env,
+        &uniswap,
+        owner,
+        &router_contract,
+        &factory,
+        &router_contract,
+        &token1,
+        &token2,
+    );
+    let borrower: TestContract =
+        deploy_flash_borrower(&env, owner, Key::Hash(router_contract.package_hash()));
+    // the borrower only pays fees for flash swaps it started itself
+    router_contract.call_contract(
+        owner,
+        "flash_swap",
+        runtime_args! {
+            "token" => Key::Hash(token1.package_hash()),
+            "other_token" => Key::Hash(token2.package_hash()),
+            "amount" => U256::from(100000),
+            "callback" => Key::Hash(borrower.package_hash()),
+            "data" => "foreign",
+            "deadline" => deadline()
+        },
+        0,
+    );
+}
+
+#[test]
+#[should_panic(expected = "User(140) [65676]")]
+fn flash_swap_through_pair_with_other_callee() {
+    let (env, uniswap, owner, router_contract, _, _, token1, token2, _, _, factory) =
+        deploy_uniswap_router();
+    let token_a = Key::Hash(token1.package_hash());
+    let token_b = Key::Hash(token2.package_hash());
+    let (pair_token0, pair_token1) = if token1.package_hash() < token2.package_hash() {
+        (token_a, token_b)
+    } else {
+        (token_b, token_a)
+    };
+    // the stand-in swaps without calling the router back, as pairs deployed with the flash
+    // swapper as callee do
+    let pair: TestContract = deploy_malicious_pair(
+        &env,
+        owner,
+        pair_token0,
+        pair_token1,
+        Key::Hash(factory.package_hash()),
+    );
+    allow_pair(&router_contract, owner, &pair);
+    uniswap.add_liquidity(
+        owner,
+        token_a,
+        token_b,
+        10000000.into(),
+        10000000.into(),
+        0.into(),
+        0.into(),
+        uniswap.test_contract_package_hash(),
+        deadline(),
+        Some(Key::Hash(pair.package_hash())),
+        None,
+    );
+    router_contract.call_contract(
+        owner,
+        "flash_swap",
+        runtime_args! {
+            "token" => token_a,
+            "other_token" => token_b,
+            "amount" => U256::from(100000),
+            "callback" => uniswap.test_contract_package_hash(),
+            "data" => "other callee",
+            "deadline" => deadline()
+        },
+        0,
+    );
+}
+
+#[test]
+#[should_panic(expected = "User(140) [65676]")]
+fn uniswap_v2_call_outside_flash_swap() {
+    let (_, _, owner, router_contract, _, _, _, _, _, _, _) = deploy_uniswap_router();
+    router_contract.call_contract(
+        owner,
+        "uniswap_v2_call",
+        runtime_args! {
+            "sender" => Key::Hash(router_contract.package_hash()),
+            "amount0" => U256::from(100000),
+            "amount1" => U256::zero(),
+            "data" => "not a pair"
+        },
+        0,
+    );
+}
+
+#[test]
+#[should_panic(expected = "User(144) [65680]")]
+fn swap_cycle_open_path() {
+    let (env, uniswap, owner, router_contract, flash_swapper, _, token1, token2, _, _, factory) =
+        deploy_uniswap_router();
+    add_token_liquidity(
+        &env,
+        &uniswap,
+        owner,
+        &router_contract,
+        &factory,
+        &flash_swapper,
+        &token1,
+        &token2,
+    );
+    router_contract.call_contract(
+        owner,
+        "swap_cycle",
+        runtime_args! {
+            "amount_in" => U256::from(100000),
+            "min_profit" => U256::zero(),
+            "path" => path(&[&token1, &token2]),
+            "to" => Key::from(owner),
+            "deadline" => deadline(),
+            "max_price_impact_bps" => None::<u32>
+        },
+        0,
+    );
+}
+
+#[test]
+#[should_panic(expected = "User(145) [65681]")]
+fn swap_cycle_at_a_loss() {
+    let (env, uniswap, owner, router_contract, flash_swapper, _, token1, token2, _, _, factory) =
+        deploy_uniswap_router();
+    add_token_liquidity(
+        &env,
+        &uniswap,
+        owner,
+        &router_contract,
+        &factory,
+        &flash_swapper,
+        &token1,
+        &token2,
+    );
+    uniswap.approve(
+        &token1,
+        owner,
+        Key::Hash(router_contract.package_hash()),
+        100000.into(),
+    );
+    // there and back through one pair pays its fee twice
+    router_contract.call_contract(
+        owner,
+        "swap_cycle",
+        runtime_args! {
+            "amount_in" => U256::from(100000),
+            "min_profit" => U256::zero(),
+            "path" => path(&[&token1, &token2, &token1]),
+            "to" => Key::from(owner),
+            "deadline" => deadline(),
+            "max_price_impact_bps" => None::<u32>
+        },
+        0,
+    );
+}
+
+// Places order 0, selling 100000 token1 for at least `amount_out_min` token2 without a tip.
+fn place_order(
+    uniswap: &UniswapInstance,
+    owner: AccountHash,
+    router_contract: &TestContract,
+    token1: &TestContract,
+    token2: &TestContract,
+    amount_out_min: U256,
+) {
+    uniswap.approve(
+        token1,
+        owner,
+        Key::Hash(router_contract.package_hash()),
+        100000.into(),
+    );
+    router_contract.call_contract(
+        owner,
+        "place_order",
+        runtime_args! {
+            "path" => vec![Key::Hash(token1.package_hash()), Key::Hash(token2.package_hash())],
+            "amount_in" => U256::from(100000),
+            "amount_out_min" => amount_out_min,
+            "tip" => U256::zero()
+        },
+        0,
+    );
+}
+
+#[test]
+#[should_panic(expected = "User(150) [65686]")]
+fn place_order_zero_amount() {
+    let (_, _, owner, router_contract, _, _, token1, token2, ..) = deploy_uniswap_router();
+    router_contract.call_contract(
+        owner,
+        "place_order",
+        runtime_args! {
+            "path" => vec![Key::Hash(token1.package_hash()), Key::Hash(token2.package_hash())],
+            "amount_in" => U256::zero(),
+            "amount_out_min" => U256::from(1000),
+            "tip" => U256::zero()
+        },
+        0,
+    );
+}
+
+#[test]
+#[should_panic(expected = "User(151) [65687]")]
+fn execute_unknown_order() {
+    let (_, _, owner, router_contract, ..) = deploy_uniswap_router();
+    router_contract.call_contract(
+        owner,
+        "execute_order",
+        runtime_args! {
+            "order_id" => 0u64
+        },
+        0,
+    );
+}
+
+#[test]
+#[should_panic(expected = "User(152) [65688]")]
+fn cancel_order_not_owner() {
+    let (env, uniswap, owner, router_contract, _, _, token1, token2, ..) = deploy_uniswap_router();
+    place_order(
+        &uniswap,
+        owner,
+        &router_contract,
+        &token1,
+        &token2,
+        1000.into(),
+    );
+    router_contract.call_contract(
+        env.next_user(),
+        "cancel_order",
+        runtime_args! {
+            "order_id" => 0u64
+        },
+        0,
+    );
+}
+
+#[test]
+#[should_panic(expected = "User(153) [65689]")]
+fn execute_order_above_limit() {
+    let (env, uniswap, owner, router_contract, flash_swapper, _, token1, token2, _, _, factory) =
+        deploy_uniswap_router();
+    add_token_liquidity(
+        &env,
+        &uniswap,
+        owner,
+        &router_contract,
+        &factory,
+        &flash_swapper,
+        &token1,
+        &token2,
+    );
+    // the pair pays 98715 token2 for 100000 token1
+    place_order(
+        &uniswap,
+        owner,
+        &router_contract,
+        &token1,
+        &token2,
+        100000.into(),
+    );
+    router_contract.call_contract(
+        env.next_user(),
+        "execute_order",
+        runtime_args! {
+            "order_id" => 0u64
+        },
+        0,
+    );
+}
+
+#[test]
+#[should_panic(expected = "User(154) [65690]")]
+fn remove_liquidity_cspr_from_pair_reporting_escrow() {
+    let (env, uniswap, owner, router_contract, _, _, token1, token2, _, wcspr, factory) =
+        deploy_uniswap_router();
+    // an open order leaves its escrow with the router
+    place_order(
+        &uniswap,
+        owner,
+        &router_contract,
+        &token1,
+        &token2,
+        1000.into(),
+    );
+
+    // the owner allowed a pair for token1 and wcspr that turns out to report every burn as paying
+    // out all the router holds, while it pays nothing
+    let token = Key::Hash(token1.package_hash());
+    let (pair_token0, pair_token1) = if token1.package_hash() < wcspr.package_hash() {
+        (token, Key::Hash(wcspr.package_hash()))
+    } else {
+        (Key::Hash(wcspr.package_hash()), token)
+    };
+    let pair: TestContract = deploy_malicious_pair(
+        &env,
+        owner,
+        pair_token0,
+        pair_token1,
+        Key::Hash(factory.package_hash()),
+    );
+    allow_pair(&router_contract, owner, &pair);
+    uniswap.approve(
+        &token1,
+        owner,
+        Key::Hash(router_contract.package_hash()),
+        10000000.into(),
+    );
+    let _ = session_add_liquidity_cspr(
+        &env,
+        owner,
+        U512::from(1000),
+        token,
+        10000000.into(),
+        100.into(),
+        0.into(),
+        0.into(),
+        Key::from(owner),
+        deadline(),
+        Some(Key::Hash(pair.package_hash())),
+        None,
+        Key::Hash(router_contract.package_hash()),
+    );
+
+    // the router would pay the order's escrow out as the token side of the burn
+    let attacker: AccountHash = env.next_user();
+    let _ = session_remove_liquidity_cspr(
+        &env,
+        attacker,
+        token,
+        1000.into(),
+        0.into(),
+        0.into(),
+        Key::from(attacker),
+        deadline(),
+        Key::Hash(router_contract.package_hash()),
+    );
+}
+
+#[test]
+#[should_panic(expected = "User(1) [65537]")]
+fn purse_proxy_unknown_destination() {
+    let (env, _, owner, router_contract, ..) = deploy_uniswap_router();
+    let _ = TestContract::new(
+        &env,
+        PURSE_PROXY_WASM_SRC,
+        "purse-proxy",
+        owner,
+        runtime_args! {
+            "destination_entrypoint" => "transfer",
+            "router_hash" => Key::Hash(router_contract.package_hash())
+        },
+        0,
+    );
+}
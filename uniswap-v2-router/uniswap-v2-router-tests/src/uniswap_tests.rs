@@ -1,14 +1,16 @@
 use casper_types::{
-    account::AccountHash, runtime_args, ContractPackageHash, Key, RuntimeArgs, U256, U512,
+    account::AccountHash, runtime_args, ContractPackageHash, Key, RuntimeArgs, U128, U256, U512,
 };
 use casperlabs_test_env::{TestContract, TestEnv};
+use uniswap_v2_library::math;
+use uniswap_v2_router::config::ORACLE_PERIOD;
 
 use crate::uniswap_instance::*;
 
 use more_asserts;
 use std::time::{SystemTime, UNIX_EPOCH};
 
-const NAME: &str = "uniswap_router";
+pub const NAME: &str = "uniswap_router";
 
 fn deploy_dummy_tokens(
     env: &TestEnv,
@@ -76,11 +78,22 @@ fn deploy_dummy_tokens(
     (token1_contract, token2_contract, token3_contract)
 }
 
-fn deploy_pair_contract(
+pub fn deploy_pair_contract(
     env: &TestEnv,
     owner: AccountHash,
     factory_contract: Key,
     flash_swapper: Key,
+) -> TestContract {
+    deploy_named_pair_contract(env, "pair", owner, factory_contract, flash_swapper)
+}
+
+// Pairs of tests that need more than one pair are installed under different names.
+pub fn deploy_named_pair_contract(
+    env: &TestEnv,
+    name: &str,
+    owner: AccountHash,
+    factory_contract: Key,
+    flash_swapper: Key,
 ) -> TestContract {
     let decimals: u8 = 18;
     let init_total_supply: U256 = 0.into();
@@ -88,7 +101,7 @@ fn deploy_pair_contract(
     let pair_contract = TestContract::new(
         &env,
         "pair-token.wasm",
-        "pair",
+        name,
         owner,
         runtime_args! {
             "name" => "erc20",
@@ -104,7 +117,53 @@ fn deploy_pair_contract(
     pair_contract
 }
 
-fn deploy_uniswap_router() -> (
+// Lets the router register `pair` with the factory, as the owner does once it vetted the pair.
+pub fn allow_pair(router_contract: &TestContract, owner: AccountHash, pair: &TestContract) {
+    router_contract.call_contract(
+        owner,
+        "set_pair_allowed",
+        runtime_args! {
+            "pair" => Key::Hash(pair.package_hash()),
+            "allowed" => true
+        },
+        0,
+    );
+}
+
+// Reference flash swap borrower that borrows through `router` and repays with the fee.
+pub fn deploy_flash_borrower(env: &TestEnv, owner: AccountHash, router: Key) -> TestContract {
+    TestContract::new(
+        env,
+        "flash-borrower.wasm",
+        "flash_borrower",
+        owner,
+        runtime_args! {
+            "router" => router
+        },
+        0,
+    )
+}
+
+pub fn deploy_uniswap_router() -> (
+    TestEnv,         // env
+    UniswapInstance, // token
+    AccountHash,     // owner
+    TestContract,    // router_contract
+    TestContract,    // flash_swapper
+    TestContract,    // pair_contract
+    TestContract,    // token1
+    TestContract,    // token2
+    TestContract,    // token3
+    TestContract,    // wcspr
+    TestContract,    // factory
+) {
+    deploy_uniswap_router_from("uniswap-v2-router.wasm")
+}
+
+// Same as `deploy_uniswap_router` with the router installed from `router_wasm`.
+pub fn deploy_uniswap_router_from(
+    router_wasm: &str,
+) -> (
     TestEnv,         // env
     UniswapInstance, // token
     AccountHash,     // owner
@@ -209,7 +268,7 @@ fn deploy_uniswap_router() -> (
     // Deploy Router Contract
     let router_contract = TestContract::new(
         &env,
-        "uniswap-v2-router.wasm",
+        router_wasm,
         NAME,
         owner,
         runtime_args! {
@@ -302,7 +361,7 @@ fn add_liquidity() {
         env,
         uniswap,
         owner,
-        _router_contract,
+        router_contract,
         flash_swapper,
         _,
         token1,
@@ -318,6 +377,7 @@ fn add_liquidity() {
         Key::Hash(factory.package_hash()),
         Key::Hash(flash_swapper.package_hash()),
     );
+    allow_pair(&router_contract, owner, &pair);
 
     let token_a = Key::Hash(token1.package_hash());
     let token_b = Key::Hash(token2.package_hash());
@@ -344,6 +404,7 @@ fn add_liquidity() {
         uniswap.test_contract_package_hash(),
         deadline.into(),
         Some(Key::Hash(pair.package_hash())),
+        None,
     );
     let (amount_a, amount_b, _): (U256, U256, U256) = uniswap.add_liquidity_result();
 
@@ -351,9 +412,156 @@ fn add_liquidity() {
     more_asserts::assert_ge!(amount_b, amount_b_min);
 }
 
+// With the allowlist off a pair made for the router's factory is taken without the owner
+// allowing it.
+#[test]
+fn add_liquidity_with_factory_pair_not_allowed() {
+    let (env, uniswap, owner, _, flash_swapper, _, token1, token2, _, _, factory) =
+        deploy_uniswap_router();
+    let pair: TestContract = deploy_pair_contract(
+        &env,
+        owner,
+        Key::Hash(factory.package_hash()),
+        Key::Hash(flash_swapper.package_hash()),
+    );
+    let token_a = Key::Hash(token1.package_hash());
+    let token_b = Key::Hash(token2.package_hash());
+    let deadline: u128 = match SystemTime::now().duration_since(UNIX_EPOCH) {
+        Err(_) => 0,
+        Ok(n) => n.as_millis() + (1000 * (30 * 60)), // current epoch time in milisecond + 30 minutes
+    };
+
+    uniswap.add_liquidity(
+        owner,
+        token_a,
+        token_b,
+        10000000.into(),
+        10000000.into(),
+        0.into(),
+        0.into(),
+        uniswap.test_contract_package_hash(),
+        deadline.into(),
+        Some(Key::Hash(pair.package_hash())),
+        None,
+    );
+    let (amount_a, amount_b, _): (U256, U256, U256) = uniswap.add_liquidity_result();
+
+    assert_eq!(amount_a, U256::from(10000000));
+    assert_eq!(amount_b, U256::from(10000000));
+}
+
+#[test]
+fn add_liquidity_with_minimums() {
+    let (env, uniswap, owner, router_contract, flash_swapper, _, token1, token2, _, _, factory) =
+        deploy_uniswap_router();
+    let pair: TestContract = deploy_pair_contract(
+        &env,
+        owner,
+        Key::Hash(factory.package_hash()),
+        Key::Hash(flash_swapper.package_hash()),
+    );
+    allow_pair(&router_contract, owner, &pair);
+    let deadline: u128 = match SystemTime::now().duration_since(UNIX_EPOCH) {
+        Err(_) => 0,
+        Ok(n) => n.as_millis() + (1000 * (30 * 60)), // current epoch time in milisecond + 30 minutes
+    };
+
+    // 10000000 of both tokens mints sqrt(10000000 * 10000000) - 1000 locked by the pair
+    let min_liquidity: U256 = U256::from(9999000);
+    router_contract.call_contract(
+        owner,
+        "set_min_initial_liquidity",
+        runtime_args! {
+            "min_initial_liquidity" => min_liquidity
+        },
+        0,
+    );
+    let min_initial_liquidity: U256 =
+        router_contract.query_named_key("min_initial_liquidity".to_string());
+    assert_eq!(min_initial_liquidity, min_liquidity);
+
+    uniswap.add_liquidity(
+        owner,
+        Key::Hash(token1.package_hash()),
+        Key::Hash(token2.package_hash()),
+        U256::from(10000000),
+        U256::from(10000000),
+        U256::zero(),
+        U256::zero(),
+        uniswap.test_contract_package_hash(),
+        deadline.into(),
+        Some(Key::Hash(pair.package_hash())),
+        Some(min_liquidity),
+    );
+    let (_, _, liquidity): (U256, U256, U256) = uniswap.add_liquidity_result();
+    more_asserts::assert_ge!(liquidity, min_liquidity);
+}
+
+// Deploys built before `min_liquidity_out` existed do not pass it.
+#[test]
+fn add_liquidity_without_min_liquidity_out() {
+    let (env, uniswap, owner, router_contract, flash_swapper, _, token1, token2, _, _, factory) =
+        deploy_uniswap_router();
+    let pair: TestContract = deploy_pair_contract(
+        &env,
+        owner,
+        Key::Hash(factory.package_hash()),
+        Key::Hash(flash_swapper.package_hash()),
+    );
+    allow_pair(&router_contract, owner, &pair);
+    let deadline: u128 = match SystemTime::now().duration_since(UNIX_EPOCH) {
+        Err(_) => 0,
+        Ok(n) => n.as_millis() + (1000 * (30 * 60)), // current epoch time in milisecond + 30 minutes
+    };
+    let amount: U256 = U256::from(10000000);
+    token2.call_contract(
+        owner,
+        "mint",
+        runtime_args! {
+            "to" => Key::from(owner),
+            "amount" => amount
+        },
+        0,
+    );
+    for token in [&token1, &token2] {
+        token.call_contract(
+            owner,
+            "approve",
+            runtime_args! {
+                "spender" => Key::Hash(router_contract.package_hash()),
+                "amount" => amount
+            },
+            0,
+        );
+    }
+
+    let token_a = Key::Hash(token1.package_hash());
+    let token_b = Key::Hash(token2.package_hash());
+    router_contract.call_contract(
+        owner,
+        "add_liquidity",
+        runtime_args! {
+            "token_a" => token_a,
+            "token_b" => token_b,
+            "amount_a_desired" => amount,
+            "amount_b_desired" => amount,
+            "amount_a_min" => U256::zero(),
+            "amount_b_min" => U256::zero(),
+            "to" => Key::from(owner),
+            "deadline" => U256::from(deadline),
+            "pair" => Some(Key::Hash(pair.package_hash()))
+        },
+        0,
+    );
+    uniswap.get_reserves(owner, Key::Hash(factory.package_hash()), token_a, token_b);
+    let (reserve_a, reserve_b): (U128, U128) = uniswap.get_reserves_result();
+    assert_eq!(reserve_a, U128::from(10000000));
+    assert_eq!(reserve_b, U128::from(10000000));
+}
+
 #[test]
 fn add_liquidity_cspr() {
-    let (env, uniswap, owner, router_contract, flash_swapper, _, token1, _token2, _, _, factory) =
+    let (env, _, owner, router_contract, flash_swapper, _, token1, _token2, _, _, factory) =
         deploy_uniswap_router();
     let pair: TestContract = deploy_pair_contract(
         &env,
@@ -361,6 +569,7 @@ fn add_liquidity_cspr() {
         Key::Hash(factory.package_hash()),
         Key::Hash(flash_swapper.package_hash()),
     );
+    allow_pair(&router_contract, owner, &pair);
 
     let token = Key::Hash(token1.package_hash());
 
@@ -398,18 +607,19 @@ fn add_liquidity_cspr() {
         Key::Hash(router_contract.package_hash()),
         deadline.into(),
         Some(Key::Hash(pair.package_hash())),
+        None,
         Key::Hash(router_contract.package_hash()),
-        uniswap.test_contract_package_hash(),
     );
 
-    let (amount_token, amount_cspr, _): (U256, U256, U256) = uniswap.add_liquidity_cspr_result();
+    let amounts = session_result(&env, owner, "add_liquidity_cspr");
+    let (amount_token, amount_cspr) = (amounts[0], amounts[1]);
     more_asserts::assert_ge!(amount_token, amount_token_min);
     more_asserts::assert_ge!(amount_cspr, amount_cspr_min);
 }
 
 #[test]
 fn remove_liquidity() {
-    let (env, uniswap, owner, _router_contract, flash_swapper, _, token1, token2, _, _, factory) =
+    let (env, uniswap, owner, router_contract, flash_swapper, _, token1, token2, _, _, factory) =
         deploy_uniswap_router();
 
     // First Add liquidity
@@ -419,6 +629,7 @@ fn remove_liquidity() {
         Key::Hash(factory.package_hash()),
         Key::Hash(flash_swapper.package_hash()),
     );
+    allow_pair(&router_contract, owner, &pair);
 
     let token_a = Key::Hash(token1.package_hash());
     let token_b = Key::Hash(token2.package_hash());
@@ -445,6 +656,7 @@ fn remove_liquidity() {
         uniswap.test_contract_package_hash(),
         deadline.into(),
         Some(Key::Hash(pair.package_hash())),
+        None,
     );
     let (_, _, liquidity): (U256, U256, U256) = uniswap.add_liquidity_result();
 
@@ -468,8 +680,8 @@ fn remove_liquidity() {
 }
 
 #[test]
-fn remove_liquidity_cspr() {
-    let (env, uniswap, owner, router_contract, flash_swapper, _, token1, _token2, _, _, factory) =
+fn quote_liquidity_matches_add_and_remove() {
+    let (env, uniswap, owner, router_contract, flash_swapper, _, token1, token2, _, _, factory) =
         deploy_uniswap_router();
     let pair: TestContract = deploy_pair_contract(
         &env,
@@ -477,148 +689,714 @@ fn remove_liquidity_cspr() {
         Key::Hash(factory.package_hash()),
         Key::Hash(flash_swapper.package_hash()),
     );
-
-    let token = Key::Hash(token1.package_hash());
-
-    let amount_token_desired: U256 = U256::from(10000000);
-    let amount_cspr_desired: U256 = U256::from(100);
-    let amount_token_min: U256 = U256::from(100000);
-    let amount_cspr_min: U256 = U256::from(10);
-
+    allow_pair(&router_contract, owner, &pair);
+    let token_a = Key::Hash(token1.package_hash());
+    let token_b = Key::Hash(token2.package_hash());
     let deadline: u128 = match SystemTime::now().duration_since(UNIX_EPOCH) {
         Ok(n) => n.as_millis() + (1000 * (30 * 60)), // current epoch time in milisecond + 30 minutes
         Err(_) => 0,
     };
 
-    let amount: U512 = 1000.into();
-    token1.call_contract(
-        owner,
-        "approve",
-        runtime_args! {
-            "spender" => Key::Hash(router_contract.package_hash()),
-            "amount" => amount_token_desired
-        },
-        0,
-    );
+    // the pair is not registered yet, so it is quoted as an empty one
+    uniswap.quote_add_liquidity(owner, token_a, token_b, 10000000.into(), 10000000.into());
+    let quote: (U256, U256, U256) = uniswap.quote_add_liquidity_result();
+    assert_eq!(quote, (10000000.into(), 10000000.into(), 9999000.into()));
 
-    let _ = session_add_liquidity_cspr(
-        &env,
+    uniswap.add_liquidity(
         owner,
-        amount,
-        token,
-        amount_token_desired,
-        amount_cspr_desired,
-        amount_token_min,
-        amount_cspr_min,
-        Key::from(owner),
+        token_a,
+        token_b,
+        10000000.into(),
+        10000000.into(),
+        U256::zero(),
+        U256::zero(),
+        uniswap.test_contract_package_hash(),
         deadline.into(),
         Some(Key::Hash(pair.package_hash())),
-        Key::Hash(router_contract.package_hash()),
-        uniswap.test_contract_package_hash(),
+        None,
     );
-
-    let (_amount_token, _amount_cspr, liquidity): (U256, U256, U256) =
-        uniswap.add_liquidity_cspr_result();
-
-    pair.call_contract(
-        owner,
-        "approve",
-        runtime_args! {
-            "spender" => Key::Hash(router_contract.package_hash()),
-            "amount" => liquidity
-        },
-        0,
+    assert_eq!(uniswap.add_liquidity_result(), quote);
+    let (_, _, liquidity) = quote;
+
+    // only as much of token_b as matches 1000 of token_a at the 1:1 reserves is taken
+    uniswap.quote_add_liquidity(owner, token_a, token_b, 1000.into(), 5000.into());
+    assert_eq!(
+        uniswap.quote_add_liquidity_result(),
+        (1000.into(), 1000.into(), 1000.into())
     );
 
-    // Now Remove liquidity
+    uniswap.quote_remove_liquidity(owner, token_a, token_b, liquidity);
+    let amounts: (U256, U256) = uniswap.quote_remove_liquidity_result();
+    assert_eq!(amounts, (9999000.into(), 9999000.into()));
 
-    let _ = session_remove_liquidity_cspr(
-        &env,
+    uniswap.remove_liquidity(
         owner,
-        token,
+        token_a,
+        token_b,
         liquidity,
-        amount_token_min,
-        amount_cspr_min,
-        Key::from(owner),
+        U256::zero(),
+        U256::zero(),
+        uniswap.test_contract_package_hash(),
         deadline.into(),
-        Key::Hash(router_contract.package_hash()),
+        Key::Hash(pair.package_hash()),
         uniswap.test_contract_package_hash(),
     );
-
-    let (amount_token, amount_cspr): (U256, U256) = uniswap.remove_liquidity_cspr_result();
-    more_asserts::assert_ge!(amount_token, amount_token_min);
-    more_asserts::assert_ge!(amount_cspr, amount_cspr_min);
+    assert_eq!(uniswap.remove_liquidity_result(), amounts);
 }
 
 #[test]
-fn remove_liquidity_with_permit() {
-    let (
-        env,
-        uniswap,
+fn get_pair_info_reports_pair_state() {
+    let (env, uniswap, owner, router_contract, flash_swapper, _, token1, token2, _, _, factory) =
+        deploy_uniswap_router();
+    let pair: TestContract = deploy_pair_contract(
+        &env,
         owner,
-        router_contract,
-        flash_swapper,
-        pair_contract,
-        token1,
-        token2,
-        _,
-        _,
-        factory,
-    ) = deploy_uniswap_router();
+        Key::Hash(factory.package_hash()),
+        Key::Hash(flash_swapper.package_hash()),
+    );
+    allow_pair(&router_contract, owner, &pair);
+    let token_a = Key::Hash(token1.package_hash());
+    let token_b = Key::Hash(token2.package_hash());
+    let deadline: u128 = match SystemTime::now().duration_since(UNIX_EPOCH) {
+        Ok(n) => n.as_millis() + (1000 * (30 * 60)), // current epoch time in milisecond + 30 minutes
+        Err(_) => 0,
+    };
 
-    let router_package_hash: ContractPackageHash =
-        router_contract.query_named_key(String::from("package_hash"));
-    let router_package_hash: Key = router_package_hash.into();
+    uniswap.add_liquidity(
+        owner,
+        token_a,
+        token_b,
+        U256::from(10000000),
+        U256::from(20000000),
+        U256::zero(),
+        U256::zero(),
+        uniswap.test_contract_package_hash(),
+        deadline.into(),
+        Some(Key::Hash(pair.package_hash())),
+        None,
+    );
 
-    // First need to add liquidity
+    // the order the tokens are passed in does not matter
+    uniswap.get_pair_info(owner, token_b, token_a);
+    let ((pair_key, total_supply), (token0, token1), (reserve0, reserve1, block_timestamp_last)) =
+        uniswap.get_pair_info_result();
+    assert_eq!(pair_key, Key::Hash(pair.package_hash()));
+    // sqrt(10000000 * 20000000), including the 1000 the pair locks away
+    assert_eq!(total_supply, U256::from(14142135));
+    let (reserve_a, reserve_b) = if token0 == token_a {
+        assert_eq!(token1, token_b);
+        (reserve0, reserve1)
+    } else {
+        assert_eq!((token0, token1), (token_b, token_a));
+        (reserve1, reserve0)
+    };
+    assert_eq!((reserve_a, reserve_b), (10000000.into(), 20000000.into()));
+    // add_liquidity runs at block time 0
+    assert_eq!(block_timestamp_last, 0);
+}
+
+#[test]
+fn batch_amounts_match_single_quotes() {
+    let (env, uniswap, owner, router_contract, flash_swapper, _, token1, token2, _, _, factory) =
+        deploy_uniswap_router();
     let pair: TestContract = deploy_pair_contract(
         &env,
         owner,
         Key::Hash(factory.package_hash()),
         Key::Hash(flash_swapper.package_hash()),
     );
-
+    allow_pair(&router_contract, owner, &pair);
     let token_a = Key::Hash(token1.package_hash());
     let token_b = Key::Hash(token2.package_hash());
-
-    let amount_a_desired: U256 = U256::from(10000000);
-    let amount_b_desired: U256 = U256::from(10000000);
-    let amount_a_min: U256 = U256::from(100000);
-    let amount_b_min: U256 = U256::from(100000);
-
     let deadline: u128 = match SystemTime::now().duration_since(UNIX_EPOCH) {
         Ok(n) => n.as_millis() + (1000 * (30 * 60)), // current epoch time in milisecond + 30 minutes
         Err(_) => 0,
     };
 
-    // approve the router to spend tokens
     uniswap.add_liquidity(
         owner,
         token_a,
         token_b,
-        amount_a_desired,
-        amount_b_desired,
-        amount_a_min,
-        amount_b_min,
+        U256::from(10000000),
+        U256::from(20000000),
+        U256::zero(),
+        U256::zero(),
         uniswap.test_contract_package_hash(),
         deadline.into(),
         Some(Key::Hash(pair.package_hash())),
+        None,
     );
-    let (_, _, liquidity): (U256, U256, U256) = uniswap.add_liquidity_result();
 
-    // Now remove liquidity
-    let approve_max = false;
-    let blocktime: U256 = deadline.into();
-    let permit_type_hash: String = pair_contract.query_named_key("permit_type_hash".to_string());
-    let domain_separator: String = pair_contract.query_named_key("domain_separator".to_string());
-    let nonces: U256 = pair_contract
-        .query_dictionary("nonces", router_package_hash.to_formatted_string())
-        .unwrap_or_default();
+    let reserves = [(U256::from(10000000), U256::from(20000000))];
+    let amounts: Vec<U256> = vec![1000.into(), 100000.into(), 1000000.into()];
+
+    uniswap.get_amounts_out_batch(owner, amounts.clone(), vec![token_a, token_b]);
+    let expected: Vec<Vec<U256>> = amounts
+        .iter()
+        .map(|amount_in| math::get_amounts_out(*amount_in, &reserves).unwrap())
+        .collect();
+    assert_eq!(uniswap.get_amounts_out_batch_result(), expected);
+
+    uniswap.get_amounts_in_batch(owner, amounts.clone(), vec![token_a, token_b]);
+    let expected: Vec<Vec<U256>> = amounts
+        .iter()
+        .map(|amount_out| math::get_amounts_in(*amount_out, &reserves).unwrap())
+        .collect();
+    assert_eq!(uniswap.get_amounts_in_batch_result(), expected);
+}
 
-    let data: String = format!(
-        "{}{}{}{}{}{}",
-        permit_type_hash,
-        uniswap.test_contract_package_hash(),
+#[test]
+fn flash_swap_repays_pair_with_fee() {
+    let (env, uniswap, owner, router_contract, _, _, token1, token2, _, _, factory) =
+        deploy_uniswap_router();
+    let router = Key::Hash(router_contract.package_hash());
+    // the pair calls back the router during flash swaps
+    let pair: TestContract =
+        deploy_pair_contract(&env, owner, Key::Hash(factory.package_hash()), router);
+    allow_pair(&router_contract, owner, &pair);
+    let token_a = Key::Hash(token1.package_hash());
+    let token_b = Key::Hash(token2.package_hash());
+    let deadline: u128 = match SystemTime::now().duration_since(UNIX_EPOCH) {
+        Ok(n) => n.as_millis() + (1000 * (30 * 60)), // current epoch time in milisecond + 30 minutes
+        Err(_) => 0,
+    };
+
+    uniswap.add_liquidity(
+        owner,
+        token_a,
+        token_b,
+        U256::from(10000000),
+        U256::from(20000000),
+        U256::zero(),
+        U256::zero(),
+        uniswap.test_contract_package_hash(),
+        deadline.into(),
+        Some(Key::Hash(pair.package_hash())),
+        None,
+    );
+
+    // the borrower pays the fee from its own balance
+    let borrower: TestContract = deploy_flash_borrower(&env, owner, router);
+    let borrower_key = Key::Hash(borrower.package_hash());
+    token1.call_contract(
+        owner,
+        "transfer",
+        runtime_args! {
+            "recipient" => borrower_key,
+            "amount" => U256::from(1000)
+        },
+        0,
+    );
+
+    let amount = U256::from(100000);
+    borrower.call_contract(
+        owner,
+        "borrow",
+        runtime_args! {
+            "token" => token_a,
+            "other_token" => token_b,
+            "amount" => amount,
+            "data" => "arbitrage",
+            "deadline" => U256::from(deadline)
+        },
+        0,
+    );
+
+    let fee: U256 = math::flash_swap_fee(amount).unwrap();
+    // 100000 * 3 / 997, rounded up
+    assert_eq!(fee, U256::from(301));
+    let (token, borrowed, paid_fee): (Key, U256, U256) =
+        borrower.query_named_key("last_flash_swap".to_string());
+    assert_eq!((token, borrowed, paid_fee), (token_a, amount, fee));
+    assert_eq!(
+        uniswap.balance_of(&token1, Key::Hash(pair.package_hash())),
+        U256::from(10000000) + fee
+    );
+    assert_eq!(
+        uniswap.balance_of(&token1, borrower_key),
+        U256::from(1000) - fee
+    );
+}
+
+#[test]
+fn swap_cycle_keeps_profit() {
+    let (
+        env,
+        uniswap,
+        owner,
+        router_contract,
+        flash_swapper,
+        _,
+        token1,
+        token2,
+        token3,
+        _,
+        factory,
+    ) = deploy_uniswap_router();
+    let deadline: u128 = match SystemTime::now().duration_since(UNIX_EPOCH) {
+        Ok(n) => n.as_millis() + (1000 * (30 * 60)), // current epoch time in milisecond + 30 minutes
+        Err(_) => 0,
+    };
+    let (token_a, token_b, token_c) = (
+        Key::Hash(token1.package_hash()),
+        Key::Hash(token2.package_hash()),
+        Key::Hash(token3.package_hash()),
+    );
+    // token_c trades at 2 token_a in its pair with token_a but at 1 token_a through token_b
+    let reserves = [
+        (U256::from(10000000), U256::from(10000000)),
+        (U256::from(10000000), U256::from(10000000)),
+        (U256::from(10000000), U256::from(20000000)),
+    ];
+    for (i, (input, output)) in [(token_a, token_b), (token_b, token_c), (token_c, token_a)]
+        .iter()
+        .enumerate()
+    {
+        let pair: TestContract = deploy_named_pair_contract(
+            &env,
+            &format!("pair{}", i),
+            owner,
+            Key::Hash(factory.package_hash()),
+            Key::Hash(flash_swapper.package_hash()),
+        );
+        allow_pair(&router_contract, owner, &pair);
+        uniswap.add_liquidity(
+            owner,
+            *input,
+            *output,
+            reserves[i].0,
+            reserves[i].1,
+            U256::zero(),
+            U256::zero(),
+            uniswap.test_contract_package_hash(),
+            deadline.into(),
+            Some(Key::Hash(pair.package_hash())),
+            None,
+        );
+    }
+
+    let amount_in = U256::from(100000);
+    let amounts: Vec<U256> = math::get_amounts_out(amount_in, &reserves).unwrap();
+    let profit: U256 = amounts[3] - amount_in;
+    uniswap.approve(
+        &token1,
+        owner,
+        Key::Hash(router_contract.package_hash()),
+        amount_in,
+    );
+    let balance: U256 = uniswap.balance_of(&token1, owner);
+    router_contract.call_contract(
+        owner,
+        "swap_cycle",
+        runtime_args! {
+            "amount_in" => amount_in,
+            "min_profit" => profit,
+            "path" => vec![
+                token_a.to_formatted_string(),
+                token_b.to_formatted_string(),
+                token_c.to_formatted_string(),
+                token_a.to_formatted_string()
+            ],
+            "to" => Key::from(owner),
+            "deadline" => U256::from(deadline),
+            "max_price_impact_bps" => None::<u32>
+        },
+        0,
+    );
+    assert_eq!(uniswap.balance_of(&token1, owner), balance + profit);
+    // the intermediate tokens only passed through the pairs
+    assert_eq!(uniswap.balance_of(&token2, owner), U256::from(1000));
+    assert_eq!(uniswap.balance_of(&token3, owner), U256::from(1000));
+}
+
+#[test]
+fn keeper_executes_limit_order() {
+    let (env, uniswap, owner, router_contract, flash_swapper, _, token1, token2, _, _, factory) =
+        deploy_uniswap_router();
+    let pair: TestContract = deploy_pair_contract(
+        &env,
+        owner,
+        Key::Hash(factory.package_hash()),
+        Key::Hash(flash_swapper.package_hash()),
+    );
+    allow_pair(&router_contract, owner, &pair);
+    let router = Key::Hash(router_contract.package_hash());
+    let token_a = Key::Hash(token1.package_hash());
+    let token_b = Key::Hash(token2.package_hash());
+    let deadline: u128 = match SystemTime::now().duration_since(UNIX_EPOCH) {
+        Ok(n) => n.as_millis() + (1000 * (30 * 60)), // current epoch time in milisecond + 30 minutes
+        Err(_) => 0,
+    };
+
+    uniswap.add_liquidity(
+        owner,
+        token_a,
+        token_b,
+        U256::from(10000000),
+        U256::from(10000000),
+        U256::zero(),
+        U256::zero(),
+        uniswap.test_contract_package_hash(),
+        deadline.into(),
+        Some(Key::Hash(pair.package_hash())),
+        None,
+    );
+
+    // the pair pays 98715 token_b for 100000 token_a, short of the limit
+    let (amount_in, amount_out_min, tip) =
+        (U256::from(100000), U256::from(100000), U256::from(500));
+    uniswap.approve(&token1, owner, router, amount_in + tip);
+    let balance_a: U256 = uniswap.balance_of(&token1, owner);
+    let balance_b: U256 = uniswap.balance_of(&token2, owner);
+    router_contract.call_contract(
+        owner,
+        "place_order",
+        runtime_args! {
+            "path" => vec![token_a, token_b],
+            "amount_in" => amount_in,
+            "amount_out_min" => amount_out_min,
+            "tip" => tip
+        },
+        0,
+    );
+    let orders_length: u64 = router_contract.query_named_key("orders_length".to_string());
+    let order_id: u64 = orders_length - 1;
+    let order: Option<Option<(Key, Vec<Key>, (U256, U256, U256))>> =
+        router_contract.query_dictionary("orders", order_id.to_string());
+    assert_eq!(
+        order,
+        Some(Some((
+            Key::from(owner),
+            vec![token_a, token_b],
+            (amount_in, amount_out_min, tip)
+        )))
+    );
+    assert_eq!(
+        uniswap.balance_of(&token1, owner),
+        balance_a - amount_in - tip
+    );
+    assert_eq!(uniswap.balance_of(&token1, router), amount_in + tip);
+    let escrow: Option<U256> = router_contract.query_dictionary("escrows", escrow_key(token_a));
+    assert_eq!(escrow, Some(amount_in + tip));
+
+    // selling token_b into the pair makes token_a worth more than the limit
+    uniswap.swap_exact_tokens_for_tokens(
+        owner,
+        U256::from(1000000),
+        U256::from(1),
+        vec![token_b.to_formatted_string(), token_a.to_formatted_string()],
+        uniswap.test_contract_package_hash(),
+        deadline.into(),
+        None,
+    );
+
+    let keeper: AccountHash = env.next_user();
+    router_contract.call_contract(
+        keeper,
+        "execute_order",
+        runtime_args! {
+            "order_id" => order_id
+        },
+        0,
+    );
+    more_asserts::assert_ge!(
+        uniswap.balance_of(&token2, owner),
+        balance_b + amount_out_min
+    );
+    assert_eq!(uniswap.balance_of(&token1, keeper), tip);
+    assert_eq!(uniswap.balance_of(&token1, router), U256::zero());
+    let escrow: Option<U256> = router_contract.query_dictionary("escrows", escrow_key(token_a));
+    assert_eq!(escrow, Some(U256::zero()));
+    let order: Option<Option<(Key, Vec<Key>, (U256, U256, U256))>> =
+        router_contract.query_dictionary("orders", order_id.to_string());
+    assert_eq!(order, Some(None));
+}
+
+#[test]
+fn owner_cancels_limit_order() {
+    let (_, uniswap, owner, router_contract, _, _, token1, token2, ..) = deploy_uniswap_router();
+    let router = Key::Hash(router_contract.package_hash());
+    let token_a = Key::Hash(token1.package_hash());
+    let token_b = Key::Hash(token2.package_hash());
+
+    // an order needs no pair until it is executed
+    let (amount_in, amount_out_min, tip) =
+        (U256::from(100000), U256::from(100000), U256::from(500));
+    uniswap.approve(&token1, owner, router, amount_in + tip);
+    let balance_a: U256 = uniswap.balance_of(&token1, owner);
+    router_contract.call_contract(
+        owner,
+        "place_order",
+        runtime_args! {
+            "path" => vec![token_a, token_b],
+            "amount_in" => amount_in,
+            "amount_out_min" => amount_out_min,
+            "tip" => tip
+        },
+        0,
+    );
+    assert_eq!(
+        uniswap.balance_of(&token1, owner),
+        balance_a - amount_in - tip
+    );
+
+    router_contract.call_contract(
+        owner,
+        "cancel_order",
+        runtime_args! {
+            "order_id" => 0u64
+        },
+        0,
+    );
+    assert_eq!(uniswap.balance_of(&token1, owner), balance_a);
+    assert_eq!(uniswap.balance_of(&token1, router), U256::zero());
+    let escrow: Option<U256> = router_contract.query_dictionary("escrows", escrow_key(token_a));
+    assert_eq!(escrow, Some(U256::zero()));
+    let order: Option<Option<(Key, Vec<Key>, (U256, U256, U256))>> =
+        router_contract.query_dictionary("orders", 0.to_string());
+    assert_eq!(order, Some(None));
+}
+
+// A router installed with escrow tracking has no orders placed before it to account for.
+#[test]
+fn migrate_escrows_with_nothing_left() {
+    let (_, uniswap, owner, router_contract, _, _, token1, token2, ..) = deploy_uniswap_router();
+    let router = Key::Hash(router_contract.package_hash());
+    let token_a = Key::Hash(token1.package_hash());
+    let (amount_in, tip) = (U256::from(100000), U256::from(500));
+    uniswap.approve(&token1, owner, router, amount_in + tip);
+    router_contract.call_contract(
+        owner,
+        "place_order",
+        runtime_args! {
+            "path" => vec![token_a, Key::Hash(token2.package_hash())],
+            "amount_in" => amount_in,
+            "amount_out_min" => U256::from(100000),
+            "tip" => tip
+        },
+        0,
+    );
+
+    let escrow_migration: (u64, u64) =
+        router_contract.query_named_key("escrow_migration".to_string());
+    assert_eq!(escrow_migration, (0, 0));
+    router_contract.call_contract(
+        owner,
+        "migrate_escrows",
+        runtime_args! {
+            "limit" => 10u64
+        },
+        0,
+    );
+    // the order placed above is not counted twice
+    let escrow: Option<U256> = router_contract.query_dictionary("escrows", escrow_key(token_a));
+    assert_eq!(escrow, Some(amount_in + tip));
+    let escrow_migration: (u64, u64) =
+        router_contract.query_named_key("escrow_migration".to_string());
+    assert_eq!(escrow_migration, (0, 0));
+}
+
+#[test]
+fn limit_order_results_are_recorded() {
+    let (_, uniswap, owner, router_contract, _, _, token1, token2, ..) = deploy_uniswap_router();
+    let router = Key::Hash(router_contract.package_hash());
+    let token_a = Key::Hash(token1.package_hash());
+    let token_b = Key::Hash(token2.package_hash());
+    let (amount_in, amount_out_min, tip) =
+        (U256::from(100000), U256::from(100000), U256::from(500));
+    uniswap.approve(&token1, owner, router, (amount_in + tip) * 2);
+
+    // the second order gets id 1, which is recorded as the second result of the owner
+    for _ in 0..2 {
+        router_contract.call_contract(
+            owner,
+            "place_order",
+            runtime_args! {
+                "path" => vec![token_a, token_b],
+                "amount_in" => amount_in,
+                "amount_out_min" => amount_out_min,
+                "tip" => tip
+            },
+            0,
+        );
+    }
+    let result: Option<(String, Vec<U256>)> =
+        router_contract.query_dictionary("results", result_key(Key::from(owner), 1));
+    assert_eq!(result, Some(("place_order".to_string(), vec![U256::one()])));
+
+    router_contract.call_contract(
+        owner,
+        "cancel_order",
+        runtime_args! {
+            "order_id" => 1u64
+        },
+        0,
+    );
+    let nonce: Option<u64> =
+        router_contract.query_dictionary("result_nonces", result_nonce_key(Key::from(owner)));
+    assert_eq!(nonce, Some(3));
+    let result: Option<(String, Vec<U256>)> =
+        router_contract.query_dictionary("results", result_key(Key::from(owner), 2));
+    assert_eq!(
+        result,
+        Some(("cancel_order".to_string(), vec![amount_in, tip]))
+    );
+}
+
+#[test]
+fn remove_liquidity_cspr() {
+    let (env, _, owner, router_contract, flash_swapper, _, token1, _token2, _, _, factory) =
+        deploy_uniswap_router();
+    let pair: TestContract = deploy_pair_contract(
+        &env,
+        owner,
+        Key::Hash(factory.package_hash()),
+        Key::Hash(flash_swapper.package_hash()),
+    );
+    allow_pair(&router_contract, owner, &pair);
+
+    let token = Key::Hash(token1.package_hash());
+
+    let amount_token_desired: U256 = U256::from(10000000);
+    let amount_cspr_desired: U256 = U256::from(100);
+    let amount_token_min: U256 = U256::from(100000);
+    let amount_cspr_min: U256 = U256::from(10);
+
+    let deadline: u128 = match SystemTime::now().duration_since(UNIX_EPOCH) {
+        Ok(n) => n.as_millis() + (1000 * (30 * 60)), // current epoch time in milisecond + 30 minutes
+        Err(_) => 0,
+    };
+
+    let amount: U512 = 1000.into();
+    token1.call_contract(
+        owner,
+        "approve",
+        runtime_args! {
+            "spender" => Key::Hash(router_contract.package_hash()),
+            "amount" => amount_token_desired
+        },
+        0,
+    );
+
+    let _ = session_add_liquidity_cspr(
+        &env,
+        owner,
+        amount,
+        token,
+        amount_token_desired,
+        amount_cspr_desired,
+        amount_token_min,
+        amount_cspr_min,
+        Key::from(owner),
+        deadline.into(),
+        Some(Key::Hash(pair.package_hash())),
+        None,
+        Key::Hash(router_contract.package_hash()),
+    );
+
+    let liquidity: U256 = session_result(&env, owner, "add_liquidity_cspr")[2];
+
+    pair.call_contract(
+        owner,
+        "approve",
+        runtime_args! {
+            "spender" => Key::Hash(router_contract.package_hash()),
+            "amount" => liquidity
+        },
+        0,
+    );
+
+    // Now Remove liquidity
+
+    let _ = session_remove_liquidity_cspr(
+        &env,
+        owner,
+        token,
+        liquidity,
+        amount_token_min,
+        amount_cspr_min,
+        Key::from(owner),
+        deadline.into(),
+        Key::Hash(router_contract.package_hash()),
+    );
+
+    let amounts = session_result(&env, owner, "remove_liquidity_cspr");
+    let (amount_token, amount_cspr) = (amounts[0], amounts[1]);
+    more_asserts::assert_ge!(amount_token, amount_token_min);
+    more_asserts::assert_ge!(amount_cspr, amount_cspr_min);
+}
+
+#[test]
+fn remove_liquidity_with_permit() {
+    let (
+        env,
+        uniswap,
+        owner,
+        router_contract,
+        flash_swapper,
+        pair_contract,
+        token1,
+        token2,
+        _,
+        _,
+        factory,
+    ) = deploy_uniswap_router();
+
+    let router_package_hash: ContractPackageHash =
+        router_contract.query_named_key(String::from("package_hash"));
+    let router_package_hash: Key = router_package_hash.into();
+
+    // First need to add liquidity
+    let pair: TestContract = deploy_pair_contract(
+        &env,
+        owner,
+        Key::Hash(factory.package_hash()),
+        Key::Hash(flash_swapper.package_hash()),
+    );
+    allow_pair(&router_contract, owner, &pair);
+
+    let token_a = Key::Hash(token1.package_hash());
+    let token_b = Key::Hash(token2.package_hash());
+
+    let amount_a_desired: U256 = U256::from(10000000);
+    let amount_b_desired: U256 = U256::from(10000000);
+    let amount_a_min: U256 = U256::from(100000);
+    let amount_b_min: U256 = U256::from(100000);
+
+    let deadline: u128 = match SystemTime::now().duration_since(UNIX_EPOCH) {
+        Ok(n) => n.as_millis() + (1000 * (30 * 60)), // current epoch time in milisecond + 30 minutes
+        Err(_) => 0,
+    };
+
+    // approve the router to spend tokens
+    uniswap.add_liquidity(
+        owner,
+        token_a,
+        token_b,
+        amount_a_desired,
+        amount_b_desired,
+        amount_a_min,
+        amount_b_min,
+        uniswap.test_contract_package_hash(),
+        deadline.into(),
+        Some(Key::Hash(pair.package_hash())),
+        None,
+    );
+    let (_, _, liquidity): (U256, U256, U256) = uniswap.add_liquidity_result();
+
+    // Now remove liquidity
+    let approve_max = false;
+    let blocktime: U256 = deadline.into();
+    let permit_type_hash: String = pair_contract.query_named_key("permit_type_hash".to_string());
+    let domain_separator: String = pair_contract.query_named_key("domain_separator".to_string());
+    let nonces: U256 = pair_contract
+        .query_dictionary("nonces", router_package_hash.to_formatted_string())
+        .unwrap_or_default();
+
+    let data: String = format!(
+        "{}{}{}{}{}{}",
+        permit_type_hash,
+        uniswap.test_contract_package_hash(),
         router_package_hash,
         liquidity,
         nonces,
@@ -652,7 +1430,7 @@ fn swap_exact_tokens_for_tokens() {
         env,
         uniswap,
         owner,
-        _router_contract,
+        router_contract,
         flash_swapper,
         _,
         token1,
@@ -669,6 +1447,7 @@ fn swap_exact_tokens_for_tokens() {
         Key::Hash(factory.package_hash()),
         Key::Hash(flash_swapper.package_hash()),
     );
+    allow_pair(&router_contract, owner, &pair);
 
     let token_a = Key::Hash(token1.package_hash());
 
@@ -696,6 +1475,7 @@ fn swap_exact_tokens_for_tokens() {
         to,
         deadline.into(),
         Some(Key::Hash(pair.package_hash())),
+        None,
     );
 
     // SWAP
@@ -716,7 +1496,201 @@ fn swap_exact_tokens_for_tokens() {
         path,
         to,
         deadline.into(),
+        None,
+    );
+}
+
+#[test]
+fn approve_and_swap_exact_tokens_for_tokens() {
+    let (env, uniswap, owner, router_contract, flash_swapper, _, token1, token2, _, _, factory) =
+        deploy_uniswap_router();
+    let pair: TestContract = deploy_pair_contract(
+        &env,
+        owner,
+        Key::Hash(factory.package_hash()),
+        Key::Hash(flash_swapper.package_hash()),
+    );
+    allow_pair(&router_contract, owner, &pair);
+
+    let token_a = Key::Hash(token1.package_hash());
+    let token_b = Key::Hash(token2.package_hash());
+    let deadline: u128 = match SystemTime::now().duration_since(UNIX_EPOCH) {
+        Ok(n) => n.as_millis() + (1000 * (30 * 60)), // current epoch time in milisecond + 30 minutes
+        Err(_) => 0,
+    };
+
+    uniswap.add_liquidity(
+        owner,
+        token_a,
+        token_b,
+        U256::from(10000000),
+        U256::from(10000000),
+        U256::from(100000),
+        U256::from(100000),
+        uniswap.test_contract_package_hash(),
+        deadline.into(),
+        Some(Key::Hash(pair.package_hash())),
+        None,
+    );
+
+    // No approve deploy, the session approves the router for amount_in itself.
+    let amount_in: U256 = 100000.into();
+    let balance: U256 = uniswap.balance_of(&token1, owner);
+    let _ = session_approve_and_swap(
+        &env,
+        owner,
+        Key::Hash(router_contract.package_hash()),
+        "swap_exact_tokens_for_tokens",
+        runtime_args! {
+            "amount_in" => amount_in,
+            "amount_out_min" => U256::from(1000),
+            "path" => vec![token_a.to_formatted_string(), token_b.to_formatted_string()],
+            "to" => Key::from(owner),
+            "deadline" => U256::from(deadline),
+            "max_price_impact_bps" => None::<u32>
+        },
+    );
+
+    let amounts = session_result(&env, owner, "swap_exact_tokens_for_tokens");
+    assert_eq!(amounts[0], amount_in);
+    more_asserts::assert_ge!(amounts[1], U256::from(1000));
+    assert_eq!(uniswap.balance_of(&token1, owner), balance - amount_in);
+}
+
+// Deploys may leave out min_liquidity_out and max_price_impact_bps, as with direct calls.
+#[test]
+fn approve_and_swap_leaves_out_optional_args() {
+    let (env, _, owner, router_contract, flash_swapper, _, token1, token2, _, _, factory) =
+        deploy_uniswap_router();
+    let pair: TestContract = deploy_pair_contract(
+        &env,
+        owner,
+        Key::Hash(factory.package_hash()),
+        Key::Hash(flash_swapper.package_hash()),
+    );
+    allow_pair(&router_contract, owner, &pair);
+
+    let router = Key::Hash(router_contract.package_hash());
+    let token_a = Key::Hash(token1.package_hash());
+    let token_b = Key::Hash(token2.package_hash());
+    let deadline: u128 = match SystemTime::now().duration_since(UNIX_EPOCH) {
+        Ok(n) => n.as_millis() + (1000 * (30 * 60)), // current epoch time in milisecond + 30 minutes
+        Err(_) => 0,
+    };
+
+    let _ = session_approve_and_swap(
+        &env,
+        owner,
+        router,
+        "add_liquidity",
+        runtime_args! {
+            "token_a" => token_a,
+            "token_b" => token_b,
+            "amount_a_desired" => U256::from(10000000),
+            "amount_b_desired" => U256::from(10000000),
+            "amount_a_min" => U256::from(100000),
+            "amount_b_min" => U256::from(100000),
+            "to" => Key::from(owner),
+            "deadline" => U256::from(deadline),
+            "pair" => Some(Key::Hash(pair.package_hash()))
+        },
+    );
+    let amounts = session_result(&env, owner, "add_liquidity");
+    assert_eq!(amounts[0], U256::from(10000000));
+    assert_eq!(amounts[1], U256::from(10000000));
+
+    let amount_in: U256 = 100000.into();
+    let _ = session_approve_and_swap(
+        &env,
+        owner,
+        router,
+        "swap_exact_tokens_for_tokens",
+        runtime_args! {
+            "amount_in" => amount_in,
+            "amount_out_min" => U256::from(1000),
+            "path" => vec![token_a.to_formatted_string(), token_b.to_formatted_string()],
+            "to" => Key::from(owner),
+            "deadline" => U256::from(deadline)
+        },
+    );
+    let amounts = session_result(&env, owner, "swap_exact_tokens_for_tokens");
+    assert_eq!(amounts[0], amount_in);
+    more_asserts::assert_ge!(amounts[1], U256::from(1000));
+}
+
+#[test]
+fn js_client_results_are_recorded() {
+    let (env, uniswap, owner, router_contract, flash_swapper, _, token1, token2, _, _, factory) =
+        deploy_uniswap_router();
+    let pair: TestContract = deploy_pair_contract(
+        &env,
+        owner,
+        Key::Hash(factory.package_hash()),
+        Key::Hash(flash_swapper.package_hash()),
+    );
+    allow_pair(&router_contract, owner, &pair);
+
+    let token_a = Key::Hash(token1.package_hash());
+    let token_b = Key::Hash(token2.package_hash());
+    let deadline: u128 = match SystemTime::now().duration_since(UNIX_EPOCH) {
+        Ok(n) => n.as_millis() + (1000 * (30 * 60)), // current epoch time in milisecond + 30 minutes
+        Err(_) => 0,
+    };
+
+    uniswap.add_liquidity(
+        owner,
+        token_a,
+        token_b,
+        U256::from(10000000),
+        U256::from(10000000),
+        U256::from(100000),
+        U256::from(100000),
+        uniswap.test_contract_package_hash(),
+        deadline.into(),
+        Some(Key::Hash(pair.package_hash())),
+        None,
+    );
+    // results are kept per caller, here the test contract
+    let (amount_a, amount_b, liquidity) = uniswap.add_liquidity_result();
+    let result: Option<(String, Vec<U256>)> = router_contract.query_dictionary(
+        "results",
+        result_key(uniswap.test_contract_package_hash(), 0),
+    );
+    assert_eq!(
+        result,
+        Some((
+            "add_liquidity".to_string(),
+            vec![amount_a, amount_b, liquidity]
+        ))
+    );
+
+    let amount_in: U256 = 100000.into();
+    let router = Key::Hash(router_contract.package_hash());
+    uniswap.approve(&token1, owner, router, amount_in);
+    router_contract.call_contract(
+        owner,
+        "swap_exact_tokens_for_tokens_js_client",
+        runtime_args! {
+            "amount_in" => amount_in,
+            "amount_out_min" => U256::from(1000),
+            "path" => vec![token_a.to_formatted_string(), token_b.to_formatted_string()],
+            "to" => Key::from(owner),
+            "deadline" => U256::from(deadline),
+            "max_price_impact_bps" => None::<u32>
+        },
+        0,
     );
+
+    let nonce: Option<u64> =
+        router_contract.query_dictionary("result_nonces", result_nonce_key(Key::from(owner)));
+    assert_eq!(nonce, Some(1));
+    let (entry_point, amounts): (String, Vec<U256>) = router_contract
+        .query_dictionary("results", result_key(Key::from(owner), 0))
+        .unwrap();
+    assert_eq!(entry_point, "swap_exact_tokens_for_tokens_js_client");
+    assert_eq!(amounts[0], amount_in);
+    more_asserts::assert_ge!(amounts[1], U256::from(1000));
+    assert_eq!(uniswap.balance_of(&token2, owner), amounts[1]);
 }
 
 #[test]
@@ -725,7 +1699,7 @@ fn swap_tokens_for_exact_tokens() {
         env,
         uniswap,
         owner,
-        _router_contract,
+        router_contract,
         flash_swapper,
         _,
         token1,
@@ -742,16 +1716,142 @@ fn swap_tokens_for_exact_tokens() {
         Key::Hash(factory.package_hash()),
         Key::Hash(flash_swapper.package_hash()),
     );
+    allow_pair(&router_contract, owner, &pair);
+
+    let token_a = Key::Hash(token1.package_hash());
+    let token_b = Key::Hash(token2.package_hash());
+    let to = Key::Hash(token3.package_hash());
+
+    let amount_a_desired: U256 = U256::from(10000000);
+    let amount_b_desired: U256 = U256::from(10000000);
+    let amount_a_min: U256 = U256::from(100000);
+    let amount_b_min: U256 = U256::from(100000);
+
+    let deadline: u128 = match SystemTime::now().duration_since(UNIX_EPOCH) {
+        Ok(n) => n.as_millis() + (1000 * (30 * 60)), // current epoch time in milisecond + 30 minutes
+        Err(_) => 0,
+    };
+
+    uniswap.add_liquidity(
+        owner,
+        token_a,
+        token_b,
+        amount_a_desired,
+        amount_b_desired,
+        amount_a_min,
+        amount_b_min,
+        to,
+        deadline.into(),
+        Some(Key::Hash(pair.package_hash())),
+        None,
+    );
+
+    // Swap
+    let amount_in_max: U256 = 1000000.into();
+    let amount_out: U256 = 10000.into();
+    let path: Vec<String> = vec![token_a.to_formatted_string(), token_b.to_formatted_string()];
+    let to: Key = Key::Hash(token3.package_hash());
+    let deadline: u128 = match SystemTime::now().duration_since(UNIX_EPOCH) {
+        Ok(n) => n.as_millis() + (1000 * (30 * 60)), // current epoch time in milisecond + 30 minutes
+        Err(_) => 0,
+    };
+
+    uniswap.swap_tokens_for_exact_tokens(
+        owner,
+        amount_out,
+        amount_in_max,
+        path,
+        to,
+        deadline.into(),
+        None,
+    );
+}
+
+#[test]
+fn swap_exact_tokens_for_tokens_within_max_price_impact() {
+    let (
+        env,
+        uniswap,
+        owner,
+        router_contract,
+        flash_swapper,
+        _,
+        token1,
+        token2,
+        token3,
+        _,
+        factory,
+    ) = deploy_uniswap_router();
+    let pair: TestContract = deploy_pair_contract(
+        &env,
+        owner,
+        Key::Hash(factory.package_hash()),
+        Key::Hash(flash_swapper.package_hash()),
+    );
+    allow_pair(&router_contract, owner, &pair);
+
+    let token_a = Key::Hash(token1.package_hash());
+    let token_b = Key::Hash(token2.package_hash());
+    let to = Key::Hash(token3.package_hash());
+    let deadline: u128 = match SystemTime::now().duration_since(UNIX_EPOCH) {
+        Ok(n) => n.as_millis() + (1000 * (30 * 60)), // current epoch time in milisecond + 30 minutes
+        Err(_) => 0,
+    };
+
+    uniswap.add_liquidity(
+        owner,
+        token_a,
+        token_b,
+        U256::from(10000000),
+        U256::from(10000000),
+        U256::from(100000),
+        U256::from(100000),
+        to,
+        deadline.into(),
+        Some(Key::Hash(pair.package_hash())),
+        None,
+    );
+
+    // 1% of the reserve plus the 0.3% fee stays under 5%
+    uniswap.swap_exact_tokens_for_tokens(
+        owner,
+        100000.into(),
+        1000.into(),
+        vec![token_a.to_formatted_string(), token_b.to_formatted_string()],
+        to,
+        deadline.into(),
+        Some(500),
+    );
+    more_asserts::assert_ge!(uniswap.balance_of(&token2, to), U256::from(1000));
+}
+
+// Deploys built before `max_price_impact_bps` existed do not pass it.
+#[test]
+fn swap_exact_tokens_for_tokens_without_max_price_impact() {
+    let (
+        env,
+        uniswap,
+        owner,
+        router_contract,
+        flash_swapper,
+        _,
+        token1,
+        token2,
+        token3,
+        _,
+        factory,
+    ) = deploy_uniswap_router();
+    let pair: TestContract = deploy_pair_contract(
+        &env,
+        owner,
+        Key::Hash(factory.package_hash()),
+        Key::Hash(flash_swapper.package_hash()),
+    );
+    allow_pair(&router_contract, owner, &pair);
 
     let token_a = Key::Hash(token1.package_hash());
     let token_b = Key::Hash(token2.package_hash());
     let to = Key::Hash(token3.package_hash());
-
-    let amount_a_desired: U256 = U256::from(10000000);
-    let amount_b_desired: U256 = U256::from(10000000);
-    let amount_a_min: U256 = U256::from(100000);
-    let amount_b_min: U256 = U256::from(100000);
-
     let deadline: u128 = match SystemTime::now().duration_since(UNIX_EPOCH) {
         Ok(n) => n.as_millis() + (1000 * (30 * 60)), // current epoch time in milisecond + 30 minutes
         Err(_) => 0,
@@ -761,38 +1861,44 @@ fn swap_tokens_for_exact_tokens() {
         owner,
         token_a,
         token_b,
-        amount_a_desired,
-        amount_b_desired,
-        amount_a_min,
-        amount_b_min,
+        U256::from(10000000),
+        U256::from(10000000),
+        U256::from(100000),
+        U256::from(100000),
         to,
         deadline.into(),
         Some(Key::Hash(pair.package_hash())),
+        None,
     );
 
-    // Swap
-    let amount_in_max: U256 = 1000000.into();
-    let amount_out: U256 = 10000.into();
-    let path: Vec<String> = vec![token_a.to_formatted_string(), token_b.to_formatted_string()];
-    let to: Key = Key::Hash(token3.package_hash());
-    let deadline: u128 = match SystemTime::now().duration_since(UNIX_EPOCH) {
-        Ok(n) => n.as_millis() + (1000 * (30 * 60)), // current epoch time in milisecond + 30 minutes
-        Err(_) => 0,
-    };
-
-    uniswap.swap_tokens_for_exact_tokens(
+    let amount_in: U256 = U256::from(100000);
+    token1.call_contract(
         owner,
-        amount_out,
-        amount_in_max,
-        path,
-        to,
-        deadline.into(),
+        "approve",
+        runtime_args! {
+            "spender" => Key::Hash(router_contract.package_hash()),
+            "amount" => amount_in
+        },
+        0,
+    );
+    router_contract.call_contract(
+        owner,
+        "swap_exact_tokens_for_tokens",
+        runtime_args! {
+            "amount_in" => amount_in,
+            "amount_out_min" => U256::from(1000),
+            "path" => vec![token_a.to_formatted_string(), token_b.to_formatted_string()],
+            "to" => to,
+            "deadline" => U256::from(deadline)
+        },
+        0,
     );
+    more_asserts::assert_ge!(uniswap.balance_of(&token2, to), U256::from(1000));
 }
 
 #[test]
 fn swap_exact_cspr_for_tokens() {
-    let (env, uniswap, owner, router_contract, flash_swapper, _, token1, token2, _, wcspr, factory) =
+    let (env, _, owner, router_contract, flash_swapper, _, token1, token2, _, wcspr, factory) =
         deploy_uniswap_router();
     let pair: TestContract = deploy_pair_contract(
         &env,
@@ -800,6 +1906,7 @@ fn swap_exact_cspr_for_tokens() {
         Key::Hash(factory.package_hash()),
         Key::Hash(flash_swapper.package_hash()),
     );
+    allow_pair(&router_contract, owner, &pair);
 
     let token = Key::Hash(token1.package_hash());
 
@@ -835,11 +1942,12 @@ fn swap_exact_cspr_for_tokens() {
         Key::Hash(router_contract.package_hash()),
         deadline.into(),
         Some(Key::Hash(pair.package_hash())),
+        None,
         Key::Hash(router_contract.package_hash()),
-        uniswap.test_contract_package_hash(),
     );
 
-    let (amount_token, amount_cspr, _): (U256, U256, U256) = uniswap.add_liquidity_cspr_result();
+    let amounts = session_result(&env, owner, "add_liquidity_cspr");
+    let (amount_token, amount_cspr) = (amounts[0], amounts[1]);
     more_asserts::assert_ge!(amount_token, amount_token_min);
     more_asserts::assert_ge!(amount_cspr, amount_cspr_min);
 
@@ -861,31 +1969,102 @@ fn swap_exact_cspr_for_tokens() {
         path,
         to,
         deadline.into(),
+        None,
         Key::Hash(router_contract.package_hash()),
     );
 }
 
+// Deploys may leave out the optional arguments of the CSPR entry points, as with direct calls.
 #[test]
-fn swap_tokens_for_exact_cspr() {
-    let (
-        env,
-        uniswap,
+fn purse_proxy_leaves_out_optional_args() {
+    let (env, _, owner, router_contract, flash_swapper, _, token1, _, _, wcspr, factory) =
+        deploy_uniswap_router();
+    let pair: TestContract = deploy_pair_contract(
+        &env,
         owner,
-        router_contract,
-        flash_swapper,
-        _,
-        token1,
-        _token2,
-        _,
-        wcspr,
-        factory,
-    ) = deploy_uniswap_router();
+        Key::Hash(factory.package_hash()),
+        Key::Hash(flash_swapper.package_hash()),
+    );
+    allow_pair(&router_contract, owner, &pair);
+
+    let router = Key::Hash(router_contract.package_hash());
+    let token = Key::Hash(token1.package_hash());
+    let deadline: u128 = match SystemTime::now().duration_since(UNIX_EPOCH) {
+        Ok(n) => n.as_millis() + (1000 * (30 * 60)), // current epoch time in milisecond + 30 minutes
+        Err(_) => 0,
+    };
+    let add_liquidity_cspr = |pair: Option<Key>| {
+        token1.call_contract(
+            owner,
+            "approve",
+            runtime_args! {
+                "spender" => router,
+                "amount" => U256::from(10000000)
+            },
+            0,
+        );
+        let mut args: RuntimeArgs = runtime_args! {
+            "amount" => U512::from(1000),
+            "destination_entrypoint" => "add_liquidity_cspr",
+            "token" => token,
+            "amount_token_desired" => U256::from(10000000),
+            "amount_cspr_desired" => U256::from(100),
+            "amount_token_min" => U256::from(100000),
+            "amount_cspr_min" => U256::from(10),
+            "to" => router,
+            "deadline" => U256::from(deadline),
+            "router_hash" => router
+        };
+        if let Some(pair) = pair {
+            args.insert("pair", Some(pair)).unwrap();
+        }
+        TestContract::new(&env, PURSE_PROXY_WASM_SRC, "purse-proxy", owner, args, 0);
+        session_result(&env, owner, "add_liquidity_cspr")
+    };
+
+    // no min_liquidity_out, then no pair either once the factory knows it
+    let amounts = add_liquidity_cspr(Some(Key::Hash(pair.package_hash())));
+    more_asserts::assert_ge!(amounts[0], U256::from(100000));
+    let amounts = add_liquidity_cspr(None);
+    more_asserts::assert_ge!(amounts[0], U256::from(100000));
+
+    // no max_price_impact_bps
+    TestContract::new(
+        &env,
+        PURSE_PROXY_WASM_SRC,
+        "purse-proxy",
+        owner,
+        runtime_args! {
+            "amount" => U512::from(10),
+            "destination_entrypoint" => "swap_exact_cspr_for_tokens",
+            "amount_out_min" => U256::from(10),
+            "amount_in" => U256::from(10),
+            "path" => vec![
+                Key::Hash(wcspr.package_hash()).to_formatted_string(),
+                token.to_formatted_string(),
+            ],
+            "to" => Key::from(owner),
+            "deadline" => U256::from(deadline),
+            "router_hash" => router
+        },
+        0,
+    );
+    let amounts = session_result(&env, owner, "swap_exact_cspr_for_tokens");
+    assert_eq!(amounts[0], U256::from(10));
+    more_asserts::assert_ge!(amounts[1], U256::from(10));
+}
+
+#[test]
+fn swap_tokens_for_exact_cspr() {
+    let (env, _, owner, router_contract, flash_swapper, _, token1, _token2, _, wcspr, factory) =
+        deploy_uniswap_router();
     let pair: TestContract = deploy_pair_contract(
         &env,
         owner,
         Key::Hash(factory.package_hash()),
         Key::Hash(flash_swapper.package_hash()),
     );
+    allow_pair(&router_contract, owner, &pair);
 
     let token = Key::Hash(token1.package_hash());
 
@@ -921,11 +2100,12 @@ fn swap_tokens_for_exact_cspr() {
         Key::Hash(router_contract.package_hash()),
         deadline.into(),
         Some(Key::Hash(pair.package_hash())),
+        None,
         Key::Hash(router_contract.package_hash()),
-        uniswap.test_contract_package_hash(),
     );
 
-    let (amount_token, amount_cspr, _): (U256, U256, U256) = uniswap.add_liquidity_cspr_result();
+    let amounts = session_result(&env, owner, "add_liquidity_cspr");
+    let (amount_token, amount_cspr) = (amounts[0], amounts[1]);
     more_asserts::assert_ge!(amount_token, amount_token_min);
     more_asserts::assert_ge!(amount_cspr, amount_cspr_min);
 
@@ -954,31 +2134,22 @@ fn swap_tokens_for_exact_cspr() {
         amount_in_max,
         path,
         deadline.into(),
+        None,
         Key::Hash(router_contract.package_hash()),
     );
 }
 
 #[test]
 fn swap_exact_tokens_for_cspr() {
-    let (
-        env,
-        uniswap,
-        owner,
-        router_contract,
-        flash_swapper,
-        _,
-        token1,
-        _token2,
-        _,
-        wcspr,
-        factory,
-    ) = deploy_uniswap_router();
+    let (env, _, owner, router_contract, flash_swapper, _, token1, _token2, _, wcspr, factory) =
+        deploy_uniswap_router();
     let pair: TestContract = deploy_pair_contract(
         &env,
         owner,
         Key::Hash(factory.package_hash()),
         Key::Hash(flash_swapper.package_hash()),
     );
+    allow_pair(&router_contract, owner, &pair);
 
     let token = Key::Hash(token1.package_hash());
 
@@ -1014,11 +2185,12 @@ fn swap_exact_tokens_for_cspr() {
         Key::Hash(router_contract.package_hash()),
         deadline.into(),
         Some(Key::Hash(pair.package_hash())),
+        None,
         Key::Hash(router_contract.package_hash()),
-        uniswap.test_contract_package_hash(),
     );
 
-    let (amount_token, amount_cspr, _): (U256, U256, U256) = uniswap.add_liquidity_cspr_result();
+    let amounts = session_result(&env, owner, "add_liquidity_cspr");
+    let (amount_token, amount_cspr) = (amounts[0], amounts[1]);
     more_asserts::assert_ge!(amount_token, amount_token_min);
     more_asserts::assert_ge!(amount_cspr, amount_cspr_min);
 
@@ -1050,13 +2222,79 @@ fn swap_exact_tokens_for_cspr() {
         amount_out_min,
         path,
         deadline.into(),
+        None,
         Key::Hash(router_contract.package_hash()),
     );
 }
 
+#[test]
+fn swap_exact_tokens_for_cspr_to_account() {
+    let (env, uniswap, owner, router_contract, flash_swapper, _, token1, _, _, wcspr, factory) =
+        deploy_uniswap_router();
+    let pair: TestContract = deploy_pair_contract(
+        &env,
+        owner,
+        Key::Hash(factory.package_hash()),
+        Key::Hash(flash_swapper.package_hash()),
+    );
+    allow_pair(&router_contract, owner, &pair);
+    let token = Key::Hash(token1.package_hash());
+    let router = Key::Hash(router_contract.package_hash());
+    let deadline: u128 = match SystemTime::now().duration_since(UNIX_EPOCH) {
+        Ok(n) => n.as_millis() + (1000 * (30 * 60)), // current epoch time in milisecond + 30 minutes
+        Err(_) => 0,
+    };
+
+    let amount_token_desired: U256 = U256::from(10000000);
+    uniswap.approve(&token1, owner, router, amount_token_desired);
+    let _ = session_add_liquidity_cspr(
+        &env,
+        owner,
+        1000.into(),
+        token,
+        amount_token_desired,
+        U256::from(1000),
+        U256::from(100000),
+        U256::from(100),
+        router,
+        deadline.into(),
+        Some(Key::Hash(pair.package_hash())),
+        None,
+        router,
+    );
+
+    // The owner calls the router directly and pays the cspr to another account, no purse needed.
+    let recipient: AccountHash = env.next_user();
+    let amount_in: U256 = 100000.into();
+    let balance: U256 = uniswap.balance_of(&token1, owner);
+    uniswap.approve(&token1, owner, router, amount_in);
+    router_contract.call_contract(
+        owner,
+        "swap_exact_tokens_for_cspr_to_key",
+        runtime_args! {
+            "amount_in" => amount_in,
+            "amount_out_min" => U256::from(1),
+            "path" => vec![token.to_formatted_string(), Key::Hash(wcspr.package_hash()).to_formatted_string()],
+            "to" => Key::from(recipient),
+            "deadline" => U256::from(deadline),
+            "max_price_impact_bps" => None::<u32>
+        },
+        0,
+    );
+
+    // the owner's first result is the liquidity added through the purse-proxy
+    let (entry_point, amounts): (String, Vec<U256>) = router_contract
+        .query_dictionary("results", result_key(Key::from(owner), 1))
+        .unwrap();
+    assert_eq!(entry_point, "swap_exact_tokens_for_cspr_to_key");
+    assert_eq!(amounts[0], amount_in);
+    more_asserts::assert_ge!(amounts[1], U256::from(1));
+    assert_eq!(uniswap.balance_of(&token1, owner), balance - amount_in);
+}
+
 #[test]
 fn swap_cspr_for_exact_tokens() {
-    let (env, uniswap, owner, router_contract, flash_swapper, _, token1, token2, _, wcspr, factory) =
+    let (env, _, owner, router_contract, flash_swapper, _, token1, token2, _, wcspr, factory) =
         deploy_uniswap_router();
     let pair: TestContract = deploy_pair_contract(
         &env,
@@ -1064,6 +2302,7 @@ fn swap_cspr_for_exact_tokens() {
         Key::Hash(factory.package_hash()),
         Key::Hash(flash_swapper.package_hash()),
     );
+    allow_pair(&router_contract, owner, &pair);
 
     let token = Key::Hash(token1.package_hash());
 
@@ -1099,11 +2338,12 @@ fn swap_cspr_for_exact_tokens() {
         Key::Hash(router_contract.package_hash()),
         deadline.into(),
         Some(Key::Hash(pair.package_hash())),
+        None,
         Key::Hash(router_contract.package_hash()),
-        uniswap.test_contract_package_hash(),
     );
 
-    let (amount_token, amount_cspr, _): (U256, U256, U256) = uniswap.add_liquidity_cspr_result();
+    let amounts = session_result(&env, owner, "add_liquidity_cspr");
+    let (amount_token, amount_cspr) = (amounts[0], amounts[1]);
     more_asserts::assert_ge!(amount_token, amount_token_min);
     more_asserts::assert_ge!(amount_cspr, amount_cspr_min);
     // calling swap now
@@ -1133,6 +2373,229 @@ fn swap_cspr_for_exact_tokens() {
         path,
         to,
         deadline.into(),
+        None,
         Key::Hash(router_contract.package_hash()),
     );
 }
+
+#[test]
+fn consult_returns_time_weighted_price() {
+    let (env, uniswap, owner, router_contract, flash_swapper, _, token1, token2, _, _, factory) =
+        deploy_uniswap_router();
+    let pair: TestContract = deploy_pair_contract(
+        &env,
+        owner,
+        Key::Hash(factory.package_hash()),
+        Key::Hash(flash_swapper.package_hash()),
+    );
+    allow_pair(&router_contract, owner, &pair);
+
+    let token_a = Key::Hash(token1.package_hash());
+    let token_b = Key::Hash(token2.package_hash());
+    let deadline: u128 = match SystemTime::now().duration_since(UNIX_EPOCH) {
+        Ok(n) => n.as_millis() + (1000 * (30 * 60)), // current epoch time in milisecond + 30 minutes
+        Err(_) => 0,
+    };
+
+    // one token_a is worth two token_b from block time 0 on
+    uniswap.add_liquidity(
+        owner,
+        token_a,
+        token_b,
+        U256::from(10000000),
+        U256::from(20000000),
+        U256::from(100000),
+        U256::from(100000),
+        uniswap.test_contract_package_hash(),
+        deadline.into(),
+        Some(Key::Hash(pair.package_hash())),
+        None,
+    );
+    router_contract.call_contract(
+        owner,
+        "update_oracle",
+        runtime_args! {
+            "token_a" => token_a,
+            "token_b" => token_b
+        },
+        ORACLE_PERIOD,
+    );
+
+    uniswap.consult(
+        owner,
+        token_a,
+        1000.into(),
+        token_b,
+        2 * ORACLE_PERIOD,
+        3 * ORACLE_PERIOD,
+    );
+    assert_eq!(uniswap.consult_result(), U256::from(2000));
+    uniswap.consult(
+        owner,
+        token_b,
+        1000.into(),
+        token_a,
+        2 * ORACLE_PERIOD,
+        3 * ORACLE_PERIOD,
+    );
+    assert_eq!(uniswap.consult_result(), U256::from(500));
+}
+
+#[test]
+fn swap_through_oracle_guarded_pair() {
+    let (env, uniswap, owner, router_contract, flash_swapper, _, token1, token2, _, _, factory) =
+        deploy_uniswap_router();
+    let pair: TestContract = deploy_pair_contract(
+        &env,
+        owner,
+        Key::Hash(factory.package_hash()),
+        Key::Hash(flash_swapper.package_hash()),
+    );
+    allow_pair(&router_contract, owner, &pair);
+
+    let token_a = Key::Hash(token1.package_hash());
+    let token_b = Key::Hash(token2.package_hash());
+    let deadline: u128 = match SystemTime::now().duration_since(UNIX_EPOCH) {
+        Ok(n) => n.as_millis() + (1000 * (30 * 60)), // current epoch time in milisecond + 30 minutes
+        Err(_) => 0,
+    };
+
+    uniswap.add_liquidity(
+        owner,
+        token_a,
+        token_b,
+        U256::from(10000000),
+        U256::from(10000000),
+        U256::from(100000),
+        U256::from(100000),
+        uniswap.test_contract_package_hash(),
+        deadline.into(),
+        Some(Key::Hash(pair.package_hash())),
+        None,
+    );
+    router_contract.call_contract(
+        owner,
+        "set_oracle_guard",
+        runtime_args! {
+            "token_a" => token_a,
+            "token_b" => token_b,
+            "max_deviation_bps" => Some(100u32),
+            "window" => ORACLE_PERIOD
+        },
+        0,
+    );
+    router_contract.call_contract(
+        owner,
+        "update_oracle",
+        runtime_args! {
+            "token_a" => token_a,
+            "token_b" => token_b
+        },
+        ORACLE_PERIOD,
+    );
+
+    // the price has not moved since the observation
+    let amount_in: U256 = 100000.into();
+    let router = Key::Hash(router_contract.package_hash());
+    uniswap.approve(&token1, owner, router, amount_in);
+    router_contract.call_contract(
+        owner,
+        "swap_exact_tokens_for_tokens",
+        runtime_args! {
+            "amount_in" => amount_in,
+            "amount_out_min" => U256::from(1000),
+            "path" => vec![token_a.to_formatted_string(), token_b.to_formatted_string()],
+            "to" => Key::from(owner),
+            "deadline" => U256::from(deadline),
+            "max_price_impact_bps" => None::<u32>
+        },
+        2 * ORACLE_PERIOD,
+    );
+    more_asserts::assert_ge!(uniswap.balance_of(&token2, owner), U256::from(1000));
+}
+
+// A guarded pair whose keeper went quiet keeps trading against the last observation older than
+// the window and records the observations its guard needs from then on.
+#[test]
+fn swap_through_oracle_guarded_pair_with_stale_observation() {
+    let (env, uniswap, owner, router_contract, flash_swapper, _, token1, token2, _, _, factory) =
+        deploy_uniswap_router();
+    let pair: TestContract = deploy_pair_contract(
+        &env,
+        owner,
+        Key::Hash(factory.package_hash()),
+        Key::Hash(flash_swapper.package_hash()),
+    );
+    allow_pair(&router_contract, owner, &pair);
+
+    let token_a = Key::Hash(token1.package_hash());
+    let token_b = Key::Hash(token2.package_hash());
+    let deadline: u128 = match SystemTime::now().duration_since(UNIX_EPOCH) {
+        Ok(n) => n.as_millis() + (1000 * (30 * 60)), // current epoch time in milisecond + 30 minutes
+        Err(_) => 0,
+    };
+
+    uniswap.add_liquidity(
+        owner,
+        token_a,
+        token_b,
+        U256::from(10000000),
+        U256::from(10000000),
+        U256::from(100000),
+        U256::from(100000),
+        uniswap.test_contract_package_hash(),
+        deadline.into(),
+        Some(Key::Hash(pair.package_hash())),
+        None,
+    );
+    router_contract.call_contract(
+        owner,
+        "set_oracle_guard",
+        runtime_args! {
+            "token_a" => token_a,
+            "token_b" => token_b,
+            "max_deviation_bps" => Some(100u32),
+            "window" => ORACLE_PERIOD
+        },
+        0,
+    );
+    router_contract.call_contract(
+        owner,
+        "update_oracle",
+        runtime_args! {
+            "token_a" => token_a,
+            "token_b" => token_b
+        },
+        ORACLE_PERIOD,
+    );
+
+    // nobody observed the pair in the period the window starts in
+    let amount_in: U256 = 100000.into();
+    let router = Key::Hash(router_contract.package_hash());
+    uniswap.approve(&token1, owner, router, amount_in);
+    router_contract.call_contract(
+        owner,
+        "swap_exact_tokens_for_tokens",
+        runtime_args! {
+            "amount_in" => amount_in,
+            "amount_out_min" => U256::from(1000),
+            "path" => vec![token_a.to_formatted_string(), token_b.to_formatted_string()],
+            "to" => Key::from(owner),
+            "deadline" => U256::from(deadline),
+            "max_price_impact_bps" => None::<u32>
+        },
+        5 * ORACLE_PERIOD,
+    );
+    more_asserts::assert_ge!(uniswap.balance_of(&token2, owner), U256::from(1000));
+
+    // the swap recorded the observation a window starting in its period needs
+    uniswap.consult(
+        owner,
+        token_a,
+        1000.into(),
+        token_b,
+        ORACLE_PERIOD,
+        6 * ORACLE_PERIOD,
+    );
+    more_asserts::assert_gt!(uniswap.consult_result(), U256::zero());
+}
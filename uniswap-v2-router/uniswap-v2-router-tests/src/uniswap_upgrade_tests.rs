@@ -0,0 +1,224 @@
+use casper_types::{
+    account::AccountHash, runtime_args, ContractPackageHash, Key, RuntimeArgs, U128, U256,
+};
+use casperlabs_test_env::TestContract;
+use std::collections::BTreeMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::uniswap_instance::{allowed_pair_key, result_nonce_key, UniswapInstance};
+use crate::uniswap_tests::{
+    allow_pair, deploy_pair_contract, deploy_uniswap_router, deploy_uniswap_router_from, NAME,
+};
+
+fn add_liquidity(
+    uniswap: &UniswapInstance,
+    owner: AccountHash,
+    token_a: Key,
+    token_b: Key,
+    pair: &TestContract,
+) {
+    let deadline: u128 = match SystemTime::now().duration_since(UNIX_EPOCH) {
+        Ok(n) => n.as_millis() + (1000 * (30 * 60)), // current epoch time in milisecond + 30 minutes
+        Err(_) => 0,
+    };
+    uniswap.add_liquidity(
+        owner,
+        token_a,
+        token_b,
+        U256::from(10000000),
+        U256::from(10000000),
+        U256::from(100000),
+        U256::from(100000),
+        uniswap.test_contract_package_hash(),
+        deadline.into(),
+        Some(Key::Hash(pair.package_hash())),
+        None,
+    );
+}
+
+fn reserves(
+    uniswap: &UniswapInstance,
+    owner: AccountHash,
+    factory: &TestContract,
+    token_a: Key,
+    token_b: Key,
+) -> (U128, U128) {
+    uniswap.get_reserves(owner, Key::Hash(factory.package_hash()), token_a, token_b);
+    uniswap.get_reserves_result()
+}
+
+#[test]
+fn upgrade_keeps_state() {
+    let (env, uniswap, owner, router_contract, flash_swapper, _, token1, token2, _, wcspr, factory) =
+        deploy_uniswap_router();
+    let pair: TestContract = deploy_pair_contract(
+        &env,
+        owner,
+        Key::Hash(factory.package_hash()),
+        Key::Hash(flash_swapper.package_hash()),
+    );
+    allow_pair(&router_contract, owner, &pair);
+    router_contract.call_contract(
+        owner,
+        "set_pair_allowlist",
+        runtime_args! {
+            "enabled" => true
+        },
+        0,
+    );
+    let token_a = Key::Hash(token1.package_hash());
+    let token_b = Key::Hash(token2.package_hash());
+
+    add_liquidity(&uniswap, owner, token_a, token_b, &pair);
+    let reserves_before: (U128, U128) = reserves(&uniswap, owner, &factory, token_a, token_b);
+    let library_hash: ContractPackageHash =
+        router_contract.query_named_key("library_hash".to_string());
+    let self_hash: Key = router_contract.query_named_key("self_hash".to_string());
+
+    // installing under the same name takes the upgrade branch of the installer
+    let upgraded: TestContract = TestContract::new(
+        &env,
+        "uniswap-v2-router.wasm",
+        NAME,
+        owner,
+        runtime_args! {},
+        0,
+    );
+    assert_eq!(upgraded.package_hash(), router_contract.package_hash());
+
+    // config keys
+    let factory_hash: ContractPackageHash = upgraded.query_named_key("factory".to_string());
+    let wcspr_hash: ContractPackageHash = upgraded.query_named_key("wcspr".to_string());
+    let package_hash: ContractPackageHash = upgraded.query_named_key("package_hash".to_string());
+    let upgraded_library_hash: ContractPackageHash =
+        upgraded.query_named_key("library_hash".to_string());
+    let upgraded_self_hash: Key = upgraded.query_named_key("self_hash".to_string());
+    let schema_version: u32 = upgraded.query_named_key("schema_version".to_string());
+    assert_eq!(Key::from(factory_hash), Key::Hash(factory.package_hash()));
+    assert_eq!(Key::from(wcspr_hash), Key::Hash(wcspr.package_hash()));
+    assert_eq!(
+        Key::from(package_hash),
+        Key::Hash(router_contract.package_hash())
+    );
+    assert_eq!(upgraded_library_hash, library_hash);
+    assert_ne!(upgraded_self_hash, self_hash);
+    assert_eq!(schema_version, 10);
+    let router_owner: Key = upgraded.query_named_key("owner".to_string());
+    let min_initial_liquidity: U256 = upgraded.query_named_key("min_initial_liquidity".to_string());
+    assert_eq!(router_owner, Key::from(owner));
+    assert_eq!(min_initial_liquidity, U256::zero());
+    let flash_swap: Option<(Key, Key, Key)> = upgraded.query_named_key("flash_swap".to_string());
+    assert_eq!(flash_swap, None);
+    let orders_length: u64 = upgraded.query_named_key("orders_length".to_string());
+    assert_eq!(orders_length, 0);
+    let pair_allowed: Option<bool> = upgraded.query_dictionary(
+        "allowed_pairs",
+        allowed_pair_key(Key::Hash(pair.package_hash())),
+    );
+    assert_eq!(pair_allowed, Some(true));
+    let pair_allowlist: bool = upgraded.query_named_key("pair_allowlist".to_string());
+    assert!(pair_allowlist);
+
+    // reserves
+    assert_eq!(
+        reserves(&uniswap, owner, &factory, token_a, token_b),
+        reserves_before
+    );
+
+    // event history
+    let events_length: u32 = upgraded.query_named_key("events_length".to_string());
+    assert_eq!(events_length, 1);
+    let event: BTreeMap<String, String> = upgraded
+        .query_dictionary("events", 0.to_string())
+        .unwrap_or_default();
+    assert_eq!(event["event_type"], "addreserves");
+
+    // the package now resolves to the new version, which keeps appending to the same history
+    add_liquidity(&uniswap, owner, token_a, token_b, &pair);
+    let events_length: u32 = upgraded.query_named_key("events_length".to_string());
+    assert_eq!(events_length, 2);
+    let results: Option<u64> = upgraded.query_dictionary(
+        "result_nonces",
+        result_nonce_key(uniswap.test_contract_package_hash()),
+    );
+    assert_eq!(results, Some(2));
+    let (reserve_a, reserve_b): (U128, U128) =
+        reserves(&uniswap, owner, &factory, token_a, token_b);
+    assert!(reserve_a > reserves_before.0);
+    assert!(reserve_b > reserves_before.1);
+}
+
+// `uniswap-v2-router-baseline.wasm` is the router before upgrades were supported, built with
+// `make build-baseline-contract`. Its package has no migrate group and its contract no
+// `export_state`, so the installer rebuilds its config from the install arguments.
+#[test]
+fn upgrade_from_baseline_keeps_config() {
+    let (env, uniswap, owner, router_contract, flash_swapper, _, token1, token2, _, wcspr, factory) =
+        deploy_uniswap_router_from("uniswap-v2-router-baseline.wasm");
+    let pair: TestContract = deploy_pair_contract(
+        &env,
+        owner,
+        Key::Hash(factory.package_hash()),
+        Key::Hash(flash_swapper.package_hash()),
+    );
+    let token_a = Key::Hash(token1.package_hash());
+    let token_b = Key::Hash(token2.package_hash());
+
+    add_liquidity(&uniswap, owner, token_a, token_b, &pair);
+    let reserves_before: (U128, U128) = reserves(&uniswap, owner, &factory, token_a, token_b);
+    let library_hash: ContractPackageHash =
+        router_contract.query_named_key("library_hash".to_string());
+
+    let upgraded: TestContract = TestContract::new(
+        &env,
+        "uniswap-v2-router.wasm",
+        NAME,
+        owner,
+        runtime_args! {
+            "factory" => Key::Hash(factory.package_hash()),
+            "wcspr" => Key::Hash(wcspr.package_hash()),
+            "library" => Key::from(library_hash)
+        },
+        0,
+    );
+    assert_eq!(upgraded.package_hash(), router_contract.package_hash());
+
+    let factory_hash: ContractPackageHash = upgraded.query_named_key("factory".to_string());
+    let wcspr_hash: ContractPackageHash = upgraded.query_named_key("wcspr".to_string());
+    let package_hash: ContractPackageHash = upgraded.query_named_key("package_hash".to_string());
+    let upgraded_library_hash: ContractPackageHash =
+        upgraded.query_named_key("library_hash".to_string());
+    let schema_version: u32 = upgraded.query_named_key("schema_version".to_string());
+    let router_owner: Key = upgraded.query_named_key("owner".to_string());
+    assert_eq!(Key::from(factory_hash), Key::Hash(factory.package_hash()));
+    assert_eq!(Key::from(wcspr_hash), Key::Hash(wcspr.package_hash()));
+    assert_eq!(
+        Key::from(package_hash),
+        Key::Hash(router_contract.package_hash())
+    );
+    assert_eq!(upgraded_library_hash, library_hash);
+    assert_eq!(schema_version, 10);
+    assert_eq!(router_owner, Key::from(owner));
+    // the baseline router took any pair, so the upgrade leaves the allowlist off
+    let pair_allowlist: bool = upgraded.query_named_key("pair_allowlist".to_string());
+    assert!(!pair_allowlist);
+    // nor any order whose escrow would be left to account for
+    let escrow_migration: (u64, u64) = upgraded.query_named_key("escrow_migration".to_string());
+    assert_eq!(escrow_migration, (0, 0));
+
+    // the pair holds the liquidity, the baseline router kept no history to carry over
+    assert_eq!(
+        reserves(&uniswap, owner, &factory, token_a, token_b),
+        reserves_before
+    );
+    let events_length: u32 = upgraded.query_named_key("events_length".to_string());
+    assert_eq!(events_length, 0);
+
+    add_liquidity(&uniswap, owner, token_a, token_b, &pair);
+    let events_length: u32 = upgraded.query_named_key("events_length".to_string());
+    assert_eq!(events_length, 1);
+    let (reserve_a, reserve_b): (U128, U128) =
+        reserves(&uniswap, owner, &factory, token_a, token_b);
+    assert!(reserve_a > reserves_before.0);
+    assert!(reserve_b > reserves_before.1);
+}
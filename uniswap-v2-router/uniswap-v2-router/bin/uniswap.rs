@@ -2,18 +2,19 @@
 #![no_std]
 
 extern crate alloc;
-use alloc::{boxed::Box, collections::BTreeSet, format, string::String, vec, vec::Vec};
+use alloc::{collections::BTreeSet, format, string::String, vec, vec::Vec};
 use casper_contract::{
     contract_api::{runtime, storage},
     unwrap_or_revert::UnwrapOrRevert,
 };
 use casper_types::{
-    contracts::{ContractHash, ContractPackageHash},
-    runtime_args, ApiError, CLType, CLTyped, CLValue, EntryPoint, EntryPointAccess, EntryPointType,
-    EntryPoints, Group, Key, Parameter, RuntimeArgs, URef, U256,
+    contracts::{ContractHash, ContractPackageHash, NamedKeys},
+    runtime_args, ApiError, CLValue, Key, RuntimeArgs, URef, U128, U256,
 };
 use casperlabs_contract_utils::{ContractContext, OnChainContractStorage};
 use uniswap_v2_router::config::*;
+use uniswap_v2_router::data;
+use uniswap_v2_router::entry_points::{self, get_entry_points};
 use uniswap_v2_router::{self, UniswapV2Router};
 
 #[derive(Default)]
@@ -56,33 +57,36 @@ impl Uniswap {
 /// Constructor to initialize required key pairs
 #[no_mangle]
 fn constructor() {
-    let factory: Key = runtime::get_named_arg("factory");
-    let wcspr: Key = runtime::get_named_arg("wcspr");
-    let library_hash: Key = runtime::get_named_arg("library_hash");
-    let contract_hash: ContractHash = runtime::get_named_arg("contract_hash");
-    let package_hash: ContractPackageHash = runtime::get_named_arg("package_hash");
+    let entry_points::constructor::Args {
+        factory,
+        wcspr,
+        library_hash,
+        contract_hash,
+        package_hash,
+    } = entry_points::constructor::Args::get();
+
     Uniswap::default().constructor(factory, wcspr, library_hash, contract_hash, package_hash);
 }
 
 #[no_mangle]
 /// Add tokens to liquidity pool.
-///
-/// Parameters-> token_a:Key, token_b:Key, amount_a_desired:U256, amount_b_desired:U256, amount_a_min:U256, amount_b_min:U256, to:Key, deadline: U256, pair:Option<Key> , purse:URef
 fn add_liquidity() {
-    let deadline: U256 = runtime::get_named_arg("deadline");
+    let entry_points::add_liquidity::Args {
+        token_a,
+        token_b,
+        amount_a_desired,
+        amount_b_desired,
+        amount_a_min,
+        amount_b_min,
+        to,
+        deadline,
+        pair,
+        min_liquidity_out,
+    } = entry_points::add_liquidity::Args::get();
     if !(Uniswap::default().ensure(deadline)) {
         runtime::revert(ApiError::User(ErrorCodes::UniswapV2RouterTimedOut1 as u16));
     }
 
-    let token_a: Key = runtime::get_named_arg("token_a");
-    let token_b: Key = runtime::get_named_arg("token_b");
-    let amount_a_desired: U256 = runtime::get_named_arg("amount_a_desired");
-    let amount_b_desired: U256 = runtime::get_named_arg("amount_b_desired");
-    let amount_a_min: U256 = runtime::get_named_arg("amount_a_min");
-    let amount_b_min: U256 = runtime::get_named_arg("amount_b_min");
-    let to: Key = runtime::get_named_arg("to");
-    let pair: Option<Key> = runtime::get_named_arg("pair");
-
     let _token_a = ContractPackageHash::from(token_a.into_hash().unwrap_or_default());
     let _token_b = ContractPackageHash::from(token_b.into_hash().unwrap_or_default());
 
@@ -95,33 +99,38 @@ fn add_liquidity() {
         amount_b_min,
         to,
         pair,
+        min_liquidity_out,
+    );
+    Uniswap::default().record_result(
+        entry_points::add_liquidity::NAME,
+        vec![amount_a, amount_b, liquidity],
     );
     runtime::ret(CLValue::from_t((amount_a, amount_b, liquidity)).unwrap_or_revert());
 }
 
 #[no_mangle]
 /// Add tokens to liquidity pool.
-///
-/// Parameters-> token_a:Key, token_b:Key, amount_a_desired:U256, amount_b_desired:U256, amount_a_min:U256, amount_b_min:U256, to:Key, deadline: U256, pair:Option<Key> , purse:URef
 fn add_liquidity_js_client() {
-    let deadline: U256 = runtime::get_named_arg("deadline");
+    let entry_points::add_liquidity_js_client::Args {
+        token_a,
+        token_b,
+        amount_a_desired,
+        amount_b_desired,
+        amount_a_min,
+        amount_b_min,
+        to,
+        deadline,
+        pair,
+        min_liquidity_out,
+    } = entry_points::add_liquidity_js_client::Args::get();
     if !(Uniswap::default().ensure(deadline)) {
         runtime::revert(ApiError::User(ErrorCodes::UniswapV2RouterTimedOut2 as u16));
     }
 
-    let token_a: Key = runtime::get_named_arg("token_a");
-    let token_b: Key = runtime::get_named_arg("token_b");
-    let amount_a_desired: U256 = runtime::get_named_arg("amount_a_desired");
-    let amount_b_desired: U256 = runtime::get_named_arg("amount_b_desired");
-    let amount_a_min: U256 = runtime::get_named_arg("amount_a_min");
-    let amount_b_min: U256 = runtime::get_named_arg("amount_b_min");
-    let to: Key = runtime::get_named_arg("to");
-    let pair: Option<Key> = runtime::get_named_arg("pair");
-
     let _token_a = ContractPackageHash::from(token_a.into_hash().unwrap_or_default());
     let _token_b = ContractPackageHash::from(token_b.into_hash().unwrap_or_default());
 
-    let (_amount_a, _amount_b, _liquidity): (U256, U256, U256) = Uniswap::default().add_liquidity(
+    let (amount_a, amount_b, liquidity): (U256, U256, U256) = Uniswap::default().add_liquidity(
         _token_a,
         _token_b,
         amount_a_desired,
@@ -130,28 +139,33 @@ fn add_liquidity_js_client() {
         amount_b_min,
         to,
         pair,
+        min_liquidity_out,
+    );
+    Uniswap::default().record_result(
+        entry_points::add_liquidity_js_client::NAME,
+        vec![amount_a, amount_b, liquidity],
     );
 }
 
 #[no_mangle]
 /// Add cspr to liquidity pool.
-///
-/// Parameters-> token:Key, amount_token_desired:U256, amount_cspr_desired:U256, amount_token_min:U256, amount_cspr_min:U256, to:Key, deadline:U256, pair:Option<Key> , purse:URef
 fn add_liquidity_cspr() {
-    let deadline: U256 = runtime::get_named_arg("deadline");
+    let entry_points::add_liquidity_cspr::Args {
+        token,
+        amount_token_desired,
+        amount_cspr_desired,
+        amount_token_min,
+        amount_cspr_min,
+        to,
+        deadline,
+        pair,
+        min_liquidity_out,
+        purse,
+    } = entry_points::add_liquidity_cspr::Args::get();
     if !(Uniswap::default().ensure(deadline)) {
         runtime::revert(ApiError::User(ErrorCodes::UniswapV2RouterTimedOut3 as u16));
     }
 
-    let token: Key = runtime::get_named_arg("token");
-    let amount_token_desired: U256 = runtime::get_named_arg("amount_token_desired");
-    let amount_cspr_desired: U256 = runtime::get_named_arg("amount_cspr_desired");
-    let amount_token_min: U256 = runtime::get_named_arg("amount_token_min");
-    let amount_cspr_min: U256 = runtime::get_named_arg("amount_cspr_min");
-    let to: Key = runtime::get_named_arg("to");
-    let pair: Option<Key> = runtime::get_named_arg("pair");
-    let purse: URef = runtime::get_named_arg("purse");
-
     let _token = ContractPackageHash::from(token.into_hash().unwrap_or_default());
     let (amount_token, amount_cspr, liquidity): (U256, U256, U256) = Uniswap::default()
         .add_liquidity_cspr(
@@ -162,32 +176,37 @@ fn add_liquidity_cspr() {
             amount_cspr_min,
             to,
             pair,
+            min_liquidity_out,
             purse,
         );
+    Uniswap::default().record_result(
+        entry_points::add_liquidity_cspr::NAME,
+        vec![amount_token, amount_cspr, liquidity],
+    );
     runtime::ret(CLValue::from_t((amount_token, amount_cspr, liquidity)).unwrap_or_revert());
 }
 
 #[no_mangle]
 /// Add cspr to liquidity pool.
-///
-/// Parameters-> token:Key, amount_token_desired:U256, amount_cspr_desired:U256, amount_token_min:U256, amount_cspr_min:U256, to:Key, deadline:U256, pair:Option<Key> , purse:URef
 fn add_liquidity_cspr_js_client() {
-    let deadline: U256 = runtime::get_named_arg("deadline");
+    let entry_points::add_liquidity_cspr_js_client::Args {
+        token,
+        amount_token_desired,
+        amount_cspr_desired,
+        amount_token_min,
+        amount_cspr_min,
+        to,
+        deadline,
+        pair,
+        min_liquidity_out,
+        purse,
+    } = entry_points::add_liquidity_cspr_js_client::Args::get();
     if !(Uniswap::default().ensure(deadline)) {
         runtime::revert(ApiError::User(ErrorCodes::UniswapV2RouterTimedOut4 as u16));
     }
 
-    let token: Key = runtime::get_named_arg("token");
-    let amount_token_desired: U256 = runtime::get_named_arg("amount_token_desired");
-    let amount_cspr_desired: U256 = runtime::get_named_arg("amount_cspr_desired");
-    let amount_token_min: U256 = runtime::get_named_arg("amount_token_min");
-    let amount_cspr_min: U256 = runtime::get_named_arg("amount_cspr_min");
-    let to: Key = runtime::get_named_arg("to");
-    let pair: Option<Key> = runtime::get_named_arg("pair");
-    let purse: URef = runtime::get_named_arg("purse");
-
     let _token = ContractPackageHash::from(token.into_hash().unwrap_or_default());
-    let (_amount_token, _amount_cspr, _liquidity): (U256, U256, U256) = Uniswap::default()
+    let (amount_token, amount_cspr, liquidity): (U256, U256, U256) = Uniswap::default()
         .add_liquidity_cspr(
             _token,
             amount_token_desired,
@@ -196,27 +215,31 @@ fn add_liquidity_cspr_js_client() {
             amount_cspr_min,
             to,
             pair,
+            min_liquidity_out,
             purse,
         );
+    Uniswap::default().record_result(
+        entry_points::add_liquidity_cspr_js_client::NAME,
+        vec![amount_token, amount_cspr, liquidity],
+    );
 }
 
 #[no_mangle]
 /// Remove from liquidity pool.
-///
-/// Parameters-> token_a:Key, token_b:Key, liquidity:U256, amount_a_min:U256, amount_b_min:U256, to:Key, deadline:U256
 fn remove_liquidity() {
-    let deadline: U256 = runtime::get_named_arg("deadline");
+    let entry_points::remove_liquidity::Args {
+        token_a,
+        token_b,
+        liquidity,
+        amount_a_min,
+        amount_b_min,
+        to,
+        deadline,
+    } = entry_points::remove_liquidity::Args::get();
     if !(Uniswap::default().ensure(deadline)) {
         runtime::revert(ApiError::User(ErrorCodes::UniswapV2RouterTimedOut5 as u16));
     }
 
-    let token_a: Key = runtime::get_named_arg("token_a");
-    let token_b: Key = runtime::get_named_arg("token_b");
-    let liquidity: U256 = runtime::get_named_arg("liquidity");
-    let amount_a_min: U256 = runtime::get_named_arg("amount_a_min");
-    let amount_b_min: U256 = runtime::get_named_arg("amount_b_min");
-    let to: Key = runtime::get_named_arg("to");
-
     let _token_a = ContractPackageHash::from(token_a.into_hash().unwrap_or_default());
     let _token_b = ContractPackageHash::from(token_b.into_hash().unwrap_or_default());
 
@@ -228,30 +251,33 @@ fn remove_liquidity() {
         amount_b_min,
         to,
     );
+    Uniswap::default().record_result(
+        entry_points::remove_liquidity::NAME,
+        vec![amount_a, amount_b],
+    );
     runtime::ret(CLValue::from_t((amount_a, amount_b)).unwrap_or_revert());
 }
 
 #[no_mangle]
 /// Remove from liquidity pool.
-///
-/// Parameters-> token_a:Key, token_b:Key, liquidity:U256, amount_a_min:U256, amount_b_min:U256, to:Key, deadline:U256
 fn remove_liquidity_js_client() {
-    let deadline: U256 = runtime::get_named_arg("deadline");
+    let entry_points::remove_liquidity_js_client::Args {
+        token_a,
+        token_b,
+        liquidity,
+        amount_a_min,
+        amount_b_min,
+        to,
+        deadline,
+    } = entry_points::remove_liquidity_js_client::Args::get();
     if !(Uniswap::default().ensure(deadline)) {
         runtime::revert(ApiError::User(ErrorCodes::UniswapV2RouterTimedOut6 as u16));
     }
 
-    let token_a: Key = runtime::get_named_arg("token_a");
-    let token_b: Key = runtime::get_named_arg("token_b");
-    let liquidity: U256 = runtime::get_named_arg("liquidity");
-    let amount_a_min: U256 = runtime::get_named_arg("amount_a_min");
-    let amount_b_min: U256 = runtime::get_named_arg("amount_b_min");
-    let to: Key = runtime::get_named_arg("to");
-
     let _token_a = ContractPackageHash::from(token_a.into_hash().unwrap_or_default());
     let _token_b = ContractPackageHash::from(token_b.into_hash().unwrap_or_default());
 
-    let (_amount_a, _amount_b): (U256, U256) = Uniswap::default().remove_liquidity(
+    let (amount_a, amount_b): (U256, U256) = Uniswap::default().remove_liquidity(
         _token_a,
         _token_b,
         liquidity,
@@ -259,25 +285,28 @@ fn remove_liquidity_js_client() {
         amount_b_min,
         to,
     );
+    Uniswap::default().record_result(
+        entry_points::remove_liquidity_js_client::NAME,
+        vec![amount_a, amount_b],
+    );
 }
 
 #[no_mangle]
 /// Remove cspr from liquidity pool.
-///
-/// Parameters-> token:Key, liquidity:U256, amount_token_min:U256, amount_cspr_min:U256, to:Key, deadline:U256
 fn remove_liquidity_cspr() {
-    let deadline: U256 = runtime::get_named_arg("deadline");
+    let entry_points::remove_liquidity_cspr::Args {
+        token,
+        liquidity,
+        amount_token_min,
+        amount_cspr_min,
+        to,
+        deadline,
+        to_purse,
+    } = entry_points::remove_liquidity_cspr::Args::get();
     if !(Uniswap::default().ensure(deadline)) {
         runtime::revert(ApiError::User(ErrorCodes::UniswapV2RouterTimedOut7 as u16));
     }
 
-    let token: Key = runtime::get_named_arg("token");
-    let liquidity: U256 = runtime::get_named_arg("liquidity");
-    let amount_token_min: U256 = runtime::get_named_arg("amount_token_min");
-    let amount_cspr_min: U256 = runtime::get_named_arg("amount_cspr_min");
-    let to: Key = runtime::get_named_arg("to");
-    let to_purse: URef = runtime::get_named_arg("to_purse");
-
     let _token = ContractPackageHash::from(token.into_hash().unwrap_or_default());
     let (amount_token, amount_cspr): (U256, U256) = Uniswap::default().remove_liquidity_cspr(
         _token,
@@ -285,55 +314,97 @@ fn remove_liquidity_cspr() {
         amount_token_min,
         amount_cspr_min,
         to,
-        to_purse,
+        Key::from(to_purse),
+    );
+    Uniswap::default().record_result(
+        entry_points::remove_liquidity_cspr::NAME,
+        vec![amount_token, amount_cspr],
     );
     runtime::ret(CLValue::from_t((amount_token, amount_cspr)).unwrap_or_revert());
 }
 
 #[no_mangle]
 /// Remove cspr from liquidity pool.
-///
-/// Parameters-> token:Key, liquidity:U256, amount_token_min:U256, amount_cspr_min:U256, to:Key, deadline:U256
 fn remove_liquidity_cspr_js_client() {
-    let deadline: U256 = runtime::get_named_arg("deadline");
+    let entry_points::remove_liquidity_cspr_js_client::Args {
+        token,
+        liquidity,
+        amount_token_min,
+        amount_cspr_min,
+        to,
+        deadline,
+        to_purse,
+    } = entry_points::remove_liquidity_cspr_js_client::Args::get();
     if !(Uniswap::default().ensure(deadline)) {
         runtime::revert(ApiError::User(ErrorCodes::UniswapV2RouterTimedOut8 as u16));
     }
 
-    let token: Key = runtime::get_named_arg("token");
-    let liquidity: U256 = runtime::get_named_arg("liquidity");
-    let amount_token_min: U256 = runtime::get_named_arg("amount_token_min");
-    let amount_cspr_min: U256 = runtime::get_named_arg("amount_cspr_min");
-    let to: Key = runtime::get_named_arg("to");
-    let to_purse: URef = runtime::get_named_arg("to_purse");
+    let _token = ContractPackageHash::from(token.into_hash().unwrap_or_default());
+    let (amount_token, amount_cspr): (U256, U256) = Uniswap::default().remove_liquidity_cspr(
+        _token,
+        liquidity,
+        amount_token_min,
+        amount_cspr_min,
+        to,
+        Key::from(to_purse),
+    );
+    Uniswap::default().record_result(
+        entry_points::remove_liquidity_cspr_js_client::NAME,
+        vec![amount_token, amount_cspr],
+    );
+}
+
+#[no_mangle]
+/// Remove cspr from liquidity pool, paying the token and the cspr to an account.
+fn remove_liquidity_cspr_to_key() {
+    let entry_points::remove_liquidity_cspr_to_key::Args {
+        token,
+        liquidity,
+        amount_token_min,
+        amount_cspr_min,
+        to,
+        deadline,
+    } = entry_points::remove_liquidity_cspr_to_key::Args::get();
+    if !(Uniswap::default().ensure(deadline)) {
+        runtime::revert(ApiError::User(ErrorCodes::UniswapV2RouterTimedOut21 as u16));
+    }
+    if to.into_account().is_none() {
+        runtime::revert(ApiError::User(
+            ErrorCodes::UniswapV2RouterInvalidCsprRecipient as u16,
+        ));
+    }
 
     let _token = ContractPackageHash::from(token.into_hash().unwrap_or_default());
-    let (_amount_token, _amount_cspr): (U256, U256) = Uniswap::default().remove_liquidity_cspr(
+    let (amount_token, amount_cspr): (U256, U256) = Uniswap::default().remove_liquidity_cspr(
         _token,
         liquidity,
         amount_token_min,
         amount_cspr_min,
         to,
-        to_purse,
+        to,
     );
+    Uniswap::default().record_result(
+        entry_points::remove_liquidity_cspr_to_key::NAME,
+        vec![amount_token, amount_cspr],
+    );
+    runtime::ret(CLValue::from_t((amount_token, amount_cspr)).unwrap_or_revert());
 }
 
 #[no_mangle]
 /// Remove from liquidity pool with permit.
-///
-/// Parameters-> token_a:Key, token_b:Key, liquidity:U256, amount_a_min:U256, amount_b_min:U256, to:Key, approve_max:bool
-/// public_key:String, signature: String, deadline:U256,  to_purse: URef
 fn remove_liquidity_with_permit() {
-    let token_a: Key = runtime::get_named_arg("token_a");
-    let token_b: Key = runtime::get_named_arg("token_b");
-    let liquidity: U256 = runtime::get_named_arg("liquidity");
-    let amount_a_min: U256 = runtime::get_named_arg("amount_a_min");
-    let amount_b_min: U256 = runtime::get_named_arg("amount_b_min");
-    let to: Key = runtime::get_named_arg("to");
-    let approve_max: bool = runtime::get_named_arg("approve_max");
-    let public_key: String = runtime::get_named_arg("public_key");
-    let signature: String = runtime::get_named_arg("signature");
-    let deadline: U256 = runtime::get_named_arg("deadline");
+    let entry_points::remove_liquidity_with_permit::Args {
+        token_a,
+        token_b,
+        liquidity,
+        amount_a_min,
+        amount_b_min,
+        to,
+        deadline,
+        approve_max,
+        public_key,
+        signature,
+    } = entry_points::remove_liquidity_with_permit::Args::get();
 
     let _token_a = ContractPackageHash::from(token_a.into_hash().unwrap_or_default());
     let _token_b = ContractPackageHash::from(token_b.into_hash().unwrap_or_default());
@@ -350,30 +421,33 @@ fn remove_liquidity_with_permit() {
         signature,
         deadline,
     );
+    Uniswap::default().record_result(
+        entry_points::remove_liquidity_with_permit::NAME,
+        vec![amount_a, amount_b],
+    );
     runtime::ret(CLValue::from_t((amount_a, amount_b)).unwrap_or_revert());
 }
 
 #[no_mangle]
 /// Remove from liquidity pool with permit.
-///
-/// Parameters-> token_a:Key, token_b:Key, liquidity:U256, amount_a_min:U256, amount_b_min:U256, to:Key, approve_max:bool
-/// public_key:String, signature: String, deadline:U256
 fn remove_liquidity_with_permit_js_client() {
-    let token_a: Key = runtime::get_named_arg("token_a");
-    let token_b: Key = runtime::get_named_arg("token_b");
-    let liquidity: U256 = runtime::get_named_arg("liquidity");
-    let amount_a_min: U256 = runtime::get_named_arg("amount_a_min");
-    let amount_b_min: U256 = runtime::get_named_arg("amount_b_min");
-    let to: Key = runtime::get_named_arg("to");
-    let approve_max: bool = runtime::get_named_arg("approve_max");
-    let public_key: String = runtime::get_named_arg("public_key");
-    let signature: String = runtime::get_named_arg("signature");
-    let deadline: U256 = runtime::get_named_arg("deadline");
+    let entry_points::remove_liquidity_with_permit_js_client::Args {
+        token_a,
+        token_b,
+        liquidity,
+        amount_a_min,
+        amount_b_min,
+        to,
+        deadline,
+        approve_max,
+        public_key,
+        signature,
+    } = entry_points::remove_liquidity_with_permit_js_client::Args::get();
 
     let _token_a = ContractPackageHash::from(token_a.into_hash().unwrap_or_default());
     let _token_b = ContractPackageHash::from(token_b.into_hash().unwrap_or_default());
 
-    let (_amount_a, _amount_b): (U256, U256) = Uniswap::default().remove_liquidity_with_permit(
+    let (amount_a, amount_b): (U256, U256) = Uniswap::default().remove_liquidity_with_permit(
         _token_a,
         _token_b,
         liquidity,
@@ -385,25 +459,27 @@ fn remove_liquidity_with_permit_js_client() {
         signature,
         deadline,
     );
+    Uniswap::default().record_result(
+        entry_points::remove_liquidity_with_permit_js_client::NAME,
+        vec![amount_a, amount_b],
+    );
 }
 
 #[no_mangle]
 /// Remove cspr from liquidity pool with permit.
-///
-/// Parameters-> token:ContractPackageHash, liquidity:U256, amount_token_min:U256, amount_cspr_min:U256, to:Key, approve_max:bool,
-/// deadline:U256, public_key:String, signature: String, to_purse: URef
-
 fn remove_liquidity_cspr_with_permit() {
-    let token: Key = runtime::get_named_arg("token");
-    let liquidity: U256 = runtime::get_named_arg("liquidity");
-    let amount_token_min: U256 = runtime::get_named_arg("amount_token_min");
-    let amount_cspr_min: U256 = runtime::get_named_arg("amount_cspr_min");
-    let to: Key = runtime::get_named_arg("to");
-    let approve_max: bool = runtime::get_named_arg("approve_max");
-    let public_key: String = runtime::get_named_arg("public_key");
-    let signature: String = runtime::get_named_arg("signature");
-    let deadline: U256 = runtime::get_named_arg("deadline");
-    let to_purse: URef = runtime::get_named_arg("to_purse");
+    let entry_points::remove_liquidity_cspr_with_permit::Args {
+        token,
+        liquidity,
+        amount_token_min,
+        amount_cspr_min,
+        to,
+        deadline,
+        approve_max,
+        public_key,
+        signature,
+        to_purse,
+    } = entry_points::remove_liquidity_cspr_with_permit::Args::get();
 
     let _token = ContractPackageHash::from(token.into_hash().unwrap_or_default());
     let (amount_token, amount_cspr): (U256, U256) = Uniswap::default()
@@ -419,29 +495,31 @@ fn remove_liquidity_cspr_with_permit() {
             deadline,
             to_purse,
         );
+    Uniswap::default().record_result(
+        entry_points::remove_liquidity_cspr_with_permit::NAME,
+        vec![amount_token, amount_cspr],
+    );
     runtime::ret(CLValue::from_t((amount_token, amount_cspr)).unwrap_or_revert());
 }
 
 #[no_mangle]
 /// Remove cspr from liquidity pool with permit.
-///
-/// Parameters-> token:ContractPackageHash, liquidity:U256, amount_token_min:U256, amount_cspr_min:U256, to:Key, approve_max:bool,
-/// deadline:U256, public_key:String, signature: String
-
 fn remove_liquidity_cspr_with_permit_js_client() {
-    let token: Key = runtime::get_named_arg("token");
-    let liquidity: U256 = runtime::get_named_arg("liquidity");
-    let amount_token_min: U256 = runtime::get_named_arg("amount_token_min");
-    let amount_cspr_min: U256 = runtime::get_named_arg("amount_cspr_min");
-    let to: Key = runtime::get_named_arg("to");
-    let approve_max: bool = runtime::get_named_arg("approve_max");
-    let public_key: String = runtime::get_named_arg("public_key");
-    let signature: String = runtime::get_named_arg("signature");
-    let deadline: U256 = runtime::get_named_arg("deadline");
-    let to_purse: URef = runtime::get_named_arg("to_purse");
+    let entry_points::remove_liquidity_cspr_with_permit_js_client::Args {
+        token,
+        liquidity,
+        amount_token_min,
+        amount_cspr_min,
+        to,
+        deadline,
+        approve_max,
+        public_key,
+        signature,
+        to_purse,
+    } = entry_points::remove_liquidity_cspr_with_permit_js_client::Args::get();
 
     let _token = ContractPackageHash::from(token.into_hash().unwrap_or_default());
-    let (_amount_token, _amount_cspr): (U256, U256) = Uniswap::default()
+    let (amount_token, amount_cspr): (U256, U256) = Uniswap::default()
         .remove_liquidity_cspr_with_permit(
             _token,
             liquidity,
@@ -454,250 +532,428 @@ fn remove_liquidity_cspr_with_permit_js_client() {
             deadline,
             to_purse,
         );
+    Uniswap::default().record_result(
+        entry_points::remove_liquidity_cspr_with_permit_js_client::NAME,
+        vec![amount_token, amount_cspr],
+    );
 }
 
 #[no_mangle]
 /// Swap exact tokens for tokens.
-///
-/// Parameters-> amount_in:U256, amount_out_min:U256, path:Vec<Key>, to:Key, deadline:U256
 fn swap_exact_tokens_for_tokens() {
-    let deadline: U256 = runtime::get_named_arg("deadline");
+    let entry_points::swap_exact_tokens_for_tokens::Args {
+        amount_in,
+        amount_out_min,
+        path,
+        to,
+        deadline,
+        max_price_impact_bps,
+    } = entry_points::swap_exact_tokens_for_tokens::Args::get();
     if !(Uniswap::default().ensure(deadline)) {
         runtime::revert(ApiError::User(ErrorCodes::UniswapV2RouterTimedOut9 as u16));
     }
 
-    let amount_in: U256 = runtime::get_named_arg("amount_in");
-    let amount_out_min: U256 = runtime::get_named_arg("amount_out_min");
-    let path: Vec<String> = runtime::get_named_arg("path");
-    let to: Key = runtime::get_named_arg("to");
-
-    let amounts: Vec<U256> =
-        Uniswap::default().swap_exact_tokens_for_tokens(amount_in, amount_out_min, path, to);
+    let amounts: Vec<U256> = Uniswap::default().swap_exact_tokens_for_tokens(
+        amount_in,
+        amount_out_min,
+        path,
+        to,
+        max_price_impact_bps,
+    );
+    Uniswap::default().record_result(
+        entry_points::swap_exact_tokens_for_tokens::NAME,
+        amounts.clone(),
+    );
     runtime::ret(CLValue::from_t(amounts).unwrap_or_revert());
 }
 
 #[no_mangle]
 /// Swap exact tokens for tokens.
-///
-/// Parameters-> amount_in:U256, amount_out_min:U256, path:Vec<Key>, to:Key, deadline:U256
 fn swap_exact_tokens_for_tokens_js_client() {
-    let deadline: U256 = runtime::get_named_arg("deadline");
+    let entry_points::swap_exact_tokens_for_tokens_js_client::Args {
+        amount_in,
+        amount_out_min,
+        path,
+        to,
+        deadline,
+        max_price_impact_bps,
+    } = entry_points::swap_exact_tokens_for_tokens_js_client::Args::get();
     if !(Uniswap::default().ensure(deadline)) {
         runtime::revert(ApiError::User(ErrorCodes::UniswapV2RouterTimedOut10 as u16));
     }
 
-    let amount_in: U256 = runtime::get_named_arg("amount_in");
-    let amount_out_min: U256 = runtime::get_named_arg("amount_out_min");
-    let path: Vec<String> = runtime::get_named_arg("path");
-    let to: Key = runtime::get_named_arg("to");
-
-    let _amounts: Vec<U256> =
-        Uniswap::default().swap_exact_tokens_for_tokens(amount_in, amount_out_min, path, to);
+    let amounts: Vec<U256> = Uniswap::default().swap_exact_tokens_for_tokens(
+        amount_in,
+        amount_out_min,
+        path,
+        to,
+        max_price_impact_bps,
+    );
+    Uniswap::default().record_result(
+        entry_points::swap_exact_tokens_for_tokens_js_client::NAME,
+        amounts,
+    );
 }
 
 #[no_mangle]
 /// Swap tokens for exact tokens.
-///
-/// Parameters-> amount_out:U256, amount_in_max:U256, path:Vec<Key>, to:Key, deadline:U256
 fn swap_tokens_for_exact_tokens() {
-    let deadline: U256 = runtime::get_named_arg("deadline");
+    let entry_points::swap_tokens_for_exact_tokens::Args {
+        amount_out,
+        amount_in_max,
+        path,
+        to,
+        deadline,
+        max_price_impact_bps,
+    } = entry_points::swap_tokens_for_exact_tokens::Args::get();
     if !(Uniswap::default().ensure(deadline)) {
         runtime::revert(ApiError::User(ErrorCodes::UniswapV2RouterTimedOut11 as u16));
     }
 
-    let amount_out: U256 = runtime::get_named_arg("amount_out");
-    let amount_in_max: U256 = runtime::get_named_arg("amount_in_max");
-    let path: Vec<String> = runtime::get_named_arg("path");
-    let to: Key = runtime::get_named_arg("to");
-
-    let amounts: Vec<U256> =
-        Uniswap::default().swap_tokens_for_exact_tokens(amount_out, amount_in_max, path, to);
+    let amounts: Vec<U256> = Uniswap::default().swap_tokens_for_exact_tokens(
+        amount_out,
+        amount_in_max,
+        path,
+        to,
+        max_price_impact_bps,
+    );
+    Uniswap::default().record_result(
+        entry_points::swap_tokens_for_exact_tokens::NAME,
+        amounts.clone(),
+    );
     runtime::ret(CLValue::from_t(amounts).unwrap_or_revert());
 }
 
 #[no_mangle]
 /// Swap tokens for exact tokens.
-///
-/// Parameters-> amount_out:U256, amount_in_max:U256, path:Vec<Key>, to:Key, deadline:U256
 fn swap_tokens_for_exact_tokens_js_client() {
-    let deadline: U256 = runtime::get_named_arg("deadline");
+    let entry_points::swap_tokens_for_exact_tokens_js_client::Args {
+        amount_out,
+        amount_in_max,
+        path,
+        to,
+        deadline,
+        max_price_impact_bps,
+    } = entry_points::swap_tokens_for_exact_tokens_js_client::Args::get();
     if !(Uniswap::default().ensure(deadline)) {
         runtime::revert(ApiError::User(ErrorCodes::UniswapV2RouterTimedOut12 as u16));
     }
 
-    let amount_out: U256 = runtime::get_named_arg("amount_out");
-    let amount_in_max: U256 = runtime::get_named_arg("amount_in_max");
-    let path: Vec<String> = runtime::get_named_arg("path");
-    let to: Key = runtime::get_named_arg("to");
-
-    let _amounts: Vec<U256> =
-        Uniswap::default().swap_tokens_for_exact_tokens(amount_out, amount_in_max, path, to);
+    let amounts: Vec<U256> = Uniswap::default().swap_tokens_for_exact_tokens(
+        amount_out,
+        amount_in_max,
+        path,
+        to,
+        max_price_impact_bps,
+    );
+    Uniswap::default().record_result(
+        entry_points::swap_tokens_for_exact_tokens_js_client::NAME,
+        amounts,
+    );
 }
 
 #[no_mangle]
 /// Swap exact cspr for tokens.
-///
-/// Parameters-> amount_out_min:U256, amount_in:U256, path:Vec<Key>, to:Key, deadline:U256, purse:URef
 fn swap_exact_cspr_for_tokens() {
-    let deadline: U256 = runtime::get_named_arg("deadline");
+    let entry_points::swap_exact_cspr_for_tokens::Args {
+        amount_out_min,
+        amount_in,
+        path,
+        to,
+        deadline,
+        max_price_impact_bps,
+        purse,
+    } = entry_points::swap_exact_cspr_for_tokens::Args::get();
     if !(Uniswap::default().ensure(deadline)) {
         runtime::revert(ApiError::User(ErrorCodes::UniswapV2RouterTimedOut13 as u16));
     }
 
-    let amount_out_min: U256 = runtime::get_named_arg("amount_out_min");
-    let amount_in: U256 = runtime::get_named_arg("amount_in");
-    let path: Vec<String> = runtime::get_named_arg("path");
-    let to: Key = runtime::get_named_arg("to");
-    let purse: URef = runtime::get_named_arg("purse");
-
-    let amounts: Vec<U256> =
-        Uniswap::default().swap_exact_cspr_for_tokens(amount_out_min, amount_in, path, to, purse);
+    let amounts: Vec<U256> = Uniswap::default().swap_exact_cspr_for_tokens(
+        amount_out_min,
+        amount_in,
+        path,
+        to,
+        max_price_impact_bps,
+        purse,
+    );
+    Uniswap::default().record_result(
+        entry_points::swap_exact_cspr_for_tokens::NAME,
+        amounts.clone(),
+    );
     runtime::ret(CLValue::from_t(amounts).unwrap_or_revert());
 }
 
 #[no_mangle]
 /// Swap exact cspr for tokens.
-///
-/// Parameters-> amount_out_min:U256, amount_in:U256, path:Vec<Key>, to:Key, deadline:U256, purse:URef
 fn swap_exact_cspr_for_tokens_js_client() {
-    let deadline: U256 = runtime::get_named_arg("deadline");
+    let entry_points::swap_exact_cspr_for_tokens_js_client::Args {
+        amount_out_min,
+        amount_in,
+        path,
+        to,
+        deadline,
+        max_price_impact_bps,
+        purse,
+    } = entry_points::swap_exact_cspr_for_tokens_js_client::Args::get();
     if !(Uniswap::default().ensure(deadline)) {
         runtime::revert(ApiError::User(ErrorCodes::UniswapV2RouterTimedOut14 as u16));
     }
 
-    let amount_out_min: U256 = runtime::get_named_arg("amount_out_min");
-    let amount_in: U256 = runtime::get_named_arg("amount_in");
-    let path: Vec<String> = runtime::get_named_arg("path");
-    let to: Key = runtime::get_named_arg("to");
-    let purse: URef = runtime::get_named_arg("purse");
-
-    let _amounts: Vec<U256> =
-        Uniswap::default().swap_exact_cspr_for_tokens(amount_out_min, amount_in, path, to, purse);
+    let amounts: Vec<U256> = Uniswap::default().swap_exact_cspr_for_tokens(
+        amount_out_min,
+        amount_in,
+        path,
+        to,
+        max_price_impact_bps,
+        purse,
+    );
+    Uniswap::default().record_result(
+        entry_points::swap_exact_cspr_for_tokens_js_client::NAME,
+        amounts,
+    );
 }
 
 #[no_mangle]
 /// Swap tokens for exact cspr.
-///
-/// Parameters-> amount_out:U256, amount_in_max:U256, path:Vec<Key>, to:Key, deadline:U256
 fn swap_tokens_for_exact_cspr() {
-    let deadline: U256 = runtime::get_named_arg("deadline");
+    let entry_points::swap_tokens_for_exact_cspr::Args {
+        amount_out,
+        amount_in_max,
+        path,
+        to,
+        deadline,
+        max_price_impact_bps,
+    } = entry_points::swap_tokens_for_exact_cspr::Args::get();
     if !(Uniswap::default().ensure(deadline)) {
         runtime::revert(ApiError::User(ErrorCodes::UniswapV2RouterTimedOut15 as u16));
     }
 
-    let amount_out: U256 = runtime::get_named_arg("amount_out");
-    let amount_in_max: U256 = runtime::get_named_arg("amount_in_max");
-    let path: Vec<String> = runtime::get_named_arg("path");
-    let to: URef = runtime::get_named_arg("to");
-
-    let amounts: Vec<U256> =
-        Uniswap::default().swap_tokens_for_exact_cspr(amount_out, amount_in_max, path, to);
+    let amounts: Vec<U256> = Uniswap::default().swap_tokens_for_exact_cspr(
+        amount_out,
+        amount_in_max,
+        path,
+        Key::from(to),
+        max_price_impact_bps,
+    );
+    Uniswap::default().record_result(
+        entry_points::swap_tokens_for_exact_cspr::NAME,
+        amounts.clone(),
+    );
     runtime::ret(CLValue::from_t(amounts).unwrap_or_revert());
 }
 
 #[no_mangle]
 /// Swap tokens for exact cspr.
-///
-/// Parameters-> amount_out:U256, amount_in_max:U256, path:Vec<Key>, to:Key, deadline:U256
 fn swap_tokens_for_exact_cspr_js_client() {
-    let deadline: U256 = runtime::get_named_arg("deadline");
+    let entry_points::swap_tokens_for_exact_cspr_js_client::Args {
+        amount_out,
+        amount_in_max,
+        path,
+        to,
+        deadline,
+        max_price_impact_bps,
+    } = entry_points::swap_tokens_for_exact_cspr_js_client::Args::get();
     if !(Uniswap::default().ensure(deadline)) {
         runtime::revert(ApiError::User(ErrorCodes::UniswapV2RouterTimedOut16 as u16));
     }
 
-    let amount_out: U256 = runtime::get_named_arg("amount_out");
-    let amount_in_max: U256 = runtime::get_named_arg("amount_in_max");
-    let path: Vec<String> = runtime::get_named_arg("path");
-    let to: URef = runtime::get_named_arg("to");
+    let amounts: Vec<U256> = Uniswap::default().swap_tokens_for_exact_cspr(
+        amount_out,
+        amount_in_max,
+        path,
+        Key::from(to),
+        max_price_impact_bps,
+    );
+    Uniswap::default().record_result(
+        entry_points::swap_tokens_for_exact_cspr_js_client::NAME,
+        amounts,
+    );
+}
+
+#[no_mangle]
+/// Swap tokens for exact cspr, paid to an account or a purse.
+fn swap_tokens_for_exact_cspr_to_key() {
+    let entry_points::swap_tokens_for_exact_cspr_to_key::Args {
+        amount_out,
+        amount_in_max,
+        path,
+        to,
+        deadline,
+        max_price_impact_bps,
+    } = entry_points::swap_tokens_for_exact_cspr_to_key::Args::get();
+    if !(Uniswap::default().ensure(deadline)) {
+        runtime::revert(ApiError::User(ErrorCodes::UniswapV2RouterTimedOut22 as u16));
+    }
 
-    let _amounts: Vec<U256> =
-        Uniswap::default().swap_tokens_for_exact_cspr(amount_out, amount_in_max, path, to);
+    let amounts: Vec<U256> = Uniswap::default().swap_tokens_for_exact_cspr(
+        amount_out,
+        amount_in_max,
+        path,
+        to,
+        max_price_impact_bps,
+    );
+    Uniswap::default().record_result(
+        entry_points::swap_tokens_for_exact_cspr_to_key::NAME,
+        amounts.clone(),
+    );
+    runtime::ret(CLValue::from_t(amounts).unwrap_or_revert());
 }
 
 #[no_mangle]
 /// Swap exact tokens for cspr.
-///
-/// Parameters-> amount_in:U256, amount_out_min:U256, path:Vec<Key>, to:Key, deadline:U256
 fn swap_exact_tokens_for_cspr() {
-    let deadline: U256 = runtime::get_named_arg("deadline");
+    let entry_points::swap_exact_tokens_for_cspr::Args {
+        amount_in,
+        amount_out_min,
+        path,
+        to,
+        deadline,
+        max_price_impact_bps,
+    } = entry_points::swap_exact_tokens_for_cspr::Args::get();
     if !(Uniswap::default().ensure(deadline)) {
         runtime::revert(ApiError::User(ErrorCodes::UniswapV2RouterTimedOut17 as u16));
     }
 
-    let amount_in: U256 = runtime::get_named_arg("amount_in");
-    let amount_out_min: U256 = runtime::get_named_arg("amount_out_min");
-    let path: Vec<String> = runtime::get_named_arg("path");
-    let to: URef = runtime::get_named_arg("to");
-
-    let amounts: Vec<U256> =
-        Uniswap::default().swap_exact_tokens_for_cspr(amount_in, amount_out_min, path, to);
+    let amounts: Vec<U256> = Uniswap::default().swap_exact_tokens_for_cspr(
+        amount_in,
+        amount_out_min,
+        path,
+        Key::from(to),
+        max_price_impact_bps,
+    );
+    Uniswap::default().record_result(
+        entry_points::swap_exact_tokens_for_cspr::NAME,
+        amounts.clone(),
+    );
     runtime::ret(CLValue::from_t(amounts).unwrap_or_revert());
 }
 
 #[no_mangle]
 /// Swap exact tokens for cspr.
-///
-/// Parameters-> amount_in:U256, amount_out_min:U256, path:Vec<Key>, to:Key, deadline:U256
 fn swap_exact_tokens_for_cspr_js_client() {
-    let deadline: U256 = runtime::get_named_arg("deadline");
+    let entry_points::swap_exact_tokens_for_cspr_js_client::Args {
+        amount_in,
+        amount_out_min,
+        path,
+        to,
+        deadline,
+        max_price_impact_bps,
+    } = entry_points::swap_exact_tokens_for_cspr_js_client::Args::get();
     if !(Uniswap::default().ensure(deadline)) {
         runtime::revert(ApiError::User(ErrorCodes::UniswapV2RouterTimedOut18 as u16));
     }
 
-    let amount_in: U256 = runtime::get_named_arg("amount_in");
-    let amount_out_min: U256 = runtime::get_named_arg("amount_out_min");
-    let path: Vec<String> = runtime::get_named_arg("path");
-    let to: URef = runtime::get_named_arg("to");
+    let amounts: Vec<U256> = Uniswap::default().swap_exact_tokens_for_cspr(
+        amount_in,
+        amount_out_min,
+        path,
+        Key::from(to),
+        max_price_impact_bps,
+    );
+    Uniswap::default().record_result(
+        entry_points::swap_exact_tokens_for_cspr_js_client::NAME,
+        amounts,
+    );
+}
+
+#[no_mangle]
+/// Swap exact tokens for cspr, paid to an account or a purse.
+fn swap_exact_tokens_for_cspr_to_key() {
+    let entry_points::swap_exact_tokens_for_cspr_to_key::Args {
+        amount_in,
+        amount_out_min,
+        path,
+        to,
+        deadline,
+        max_price_impact_bps,
+    } = entry_points::swap_exact_tokens_for_cspr_to_key::Args::get();
+    if !(Uniswap::default().ensure(deadline)) {
+        runtime::revert(ApiError::User(ErrorCodes::UniswapV2RouterTimedOut23 as u16));
+    }
 
-    let _amounts: Vec<U256> =
-        Uniswap::default().swap_exact_tokens_for_cspr(amount_in, amount_out_min, path, to);
+    let amounts: Vec<U256> = Uniswap::default().swap_exact_tokens_for_cspr(
+        amount_in,
+        amount_out_min,
+        path,
+        to,
+        max_price_impact_bps,
+    );
+    Uniswap::default().record_result(
+        entry_points::swap_exact_tokens_for_cspr_to_key::NAME,
+        amounts.clone(),
+    );
+    runtime::ret(CLValue::from_t(amounts).unwrap_or_revert());
 }
 
 /// Swap cspr for exact tokens
-///
-/// Parameters-> amount_out:U256, amount_in_max:U256, path:Vec<Key>, to:Key, deadline:U256, purse:URef
 
 #[no_mangle]
 fn swap_cspr_for_exact_tokens() {
-    let deadline: U256 = runtime::get_named_arg("deadline");
+    let entry_points::swap_cspr_for_exact_tokens::Args {
+        amount_out,
+        amount_in_max,
+        path,
+        to,
+        deadline,
+        max_price_impact_bps,
+        purse,
+    } = entry_points::swap_cspr_for_exact_tokens::Args::get();
     if !(Uniswap::default().ensure(deadline)) {
         runtime::revert(ApiError::User(ErrorCodes::UniswapV2RouterTimedOut19 as u16));
     }
 
-    let amount_out: U256 = runtime::get_named_arg("amount_out");
-    let amount_in_max: U256 = runtime::get_named_arg("amount_in_max");
-    let path: Vec<String> = runtime::get_named_arg("path");
-    let to: Key = runtime::get_named_arg("to");
-    let purse: URef = runtime::get_named_arg("purse");
-
-    let amounts: Vec<U256> =
-        Uniswap::default().swap_cspr_for_exact_tokens(amount_out, amount_in_max, path, to, purse);
+    let amounts: Vec<U256> = Uniswap::default().swap_cspr_for_exact_tokens(
+        amount_out,
+        amount_in_max,
+        path,
+        to,
+        max_price_impact_bps,
+        purse,
+    );
+    Uniswap::default().record_result(
+        entry_points::swap_cspr_for_exact_tokens::NAME,
+        amounts.clone(),
+    );
     runtime::ret(CLValue::from_t(amounts).unwrap_or_revert());
 }
 
 #[no_mangle]
 fn swap_cspr_for_exact_tokens_js_client() {
-    let deadline: U256 = runtime::get_named_arg("deadline");
+    let entry_points::swap_cspr_for_exact_tokens_js_client::Args {
+        amount_out,
+        amount_in_max,
+        path,
+        to,
+        deadline,
+        max_price_impact_bps,
+        purse,
+    } = entry_points::swap_cspr_for_exact_tokens_js_client::Args::get();
     if !(Uniswap::default().ensure(deadline)) {
         runtime::revert(ApiError::User(ErrorCodes::UniswapV2RouterTimedOut20 as u16));
     }
 
-    let amount_out: U256 = runtime::get_named_arg("amount_out");
-    let amount_in_max: U256 = runtime::get_named_arg("amount_in_max");
-    let path: Vec<String> = runtime::get_named_arg("path");
-    let to: Key = runtime::get_named_arg("to");
-    let purse: URef = runtime::get_named_arg("purse");
-
-    let _amounts: Vec<U256> =
-        Uniswap::default().swap_cspr_for_exact_tokens(amount_out, amount_in_max, path, to, purse);
+    let amounts: Vec<U256> = Uniswap::default().swap_cspr_for_exact_tokens(
+        amount_out,
+        amount_in_max,
+        path,
+        to,
+        max_price_impact_bps,
+        purse,
+    );
+    Uniswap::default().record_result(
+        entry_points::swap_cspr_for_exact_tokens_js_client::NAME,
+        amounts,
+    );
 }
 
 #[no_mangle]
 // given some amount of an asset and pair reserves, returns an equivalent amount of the other asset
 fn quote() {
-    let amount_a: U256 = runtime::get_named_arg("amount_a");
-    let reserve_a: U256 = runtime::get_named_arg("reserve_a");
-    let reserve_b: U256 = runtime::get_named_arg("reserve_b");
+    let entry_points::quote::Args {
+        amount_a,
+        reserve_a,
+        reserve_b,
+    } = entry_points::quote::Args::get();
 
     let amount_b: U256 = Uniswap::quote(amount_a, reserve_a, reserve_b);
     runtime::ret(CLValue::from_t(amount_b).unwrap_or_revert())
@@ -706,9 +962,11 @@ fn quote() {
 #[no_mangle]
 // given an input amount of an asset and pair reserves, returns the maximum output amount of the other asset
 fn get_amount_out() {
-    let amount_in: U256 = runtime::get_named_arg("amount_in");
-    let reserve_in: U256 = runtime::get_named_arg("reserve_in");
-    let reserve_out: U256 = runtime::get_named_arg("reserve_out");
+    let entry_points::get_amount_out::Args {
+        amount_in,
+        reserve_in,
+        reserve_out,
+    } = entry_points::get_amount_out::Args::get();
 
     let amount_out: U256 = Uniswap::get_amount_out(amount_in, reserve_in, reserve_out);
     runtime::ret(CLValue::from_t(amount_out).unwrap_or_revert())
@@ -717,9 +975,11 @@ fn get_amount_out() {
 #[no_mangle]
 // given an output amount of an asset and pair reserves, returns a required input amount of the other asset
 fn get_amount_in() {
-    let amount_out: U256 = runtime::get_named_arg("amount_out");
-    let reserve_in: U256 = runtime::get_named_arg("reserve_in");
-    let reserve_out: U256 = runtime::get_named_arg("reserve_out");
+    let entry_points::get_amount_in::Args {
+        amount_out,
+        reserve_in,
+        reserve_out,
+    } = entry_points::get_amount_in::Args::get();
 
     let amount_in: U256 = Uniswap::get_amount_in(amount_out, reserve_in, reserve_out);
     runtime::ret(CLValue::from_t(amount_in).unwrap_or_revert())
@@ -728,8 +988,8 @@ fn get_amount_in() {
 #[no_mangle]
 // performs chained getAmountOut calculations on any number of pairs
 fn get_amounts_out() {
-    let amount_in: U256 = runtime::get_named_arg("amount_in");
-    let path: Vec<Key> = runtime::get_named_arg("path");
+    let entry_points::get_amounts_out::Args { amount_in, path } =
+        entry_points::get_amounts_out::Args::get();
 
     let amounts: Vec<U256> = Uniswap::get_amounts_out(amount_in, path);
     runtime::ret(CLValue::from_t(amounts).unwrap_or_revert())
@@ -738,492 +998,310 @@ fn get_amounts_out() {
 #[no_mangle]
 // performs chained getAmountIn calculations on any number of pairs
 fn get_amounts_in() {
-    let amount_out: U256 = runtime::get_named_arg("amount_out");
-    let path: Vec<Key> = runtime::get_named_arg("path");
+    let entry_points::get_amounts_in::Args { amount_out, path } =
+        entry_points::get_amounts_in::Args::get();
 
     let amounts: Vec<U256> = Uniswap::get_amounts_in(amount_out, path);
     runtime::ret(CLValue::from_t(amounts).unwrap_or_revert())
 }
 
+#[no_mangle]
+// performs chained getAmountOut calculations for a batch of input amounts
+fn get_amounts_out_batch() {
+    let entry_points::get_amounts_out_batch::Args { amounts_in, path } =
+        entry_points::get_amounts_out_batch::Args::get();
+
+    let amounts: Vec<Vec<U256>> = Uniswap::get_amounts_out_batch(amounts_in, path);
+    runtime::ret(CLValue::from_t(amounts).unwrap_or_revert())
+}
+
+#[no_mangle]
+// performs chained getAmountIn calculations for a batch of output amounts
+fn get_amounts_in_batch() {
+    let entry_points::get_amounts_in_batch::Args { amounts_out, path } =
+        entry_points::get_amounts_in_batch::Args::get();
+
+    let amounts: Vec<Vec<U256>> = Uniswap::get_amounts_in_batch(amounts_out, path);
+    runtime::ret(CLValue::from_t(amounts).unwrap_or_revert())
+}
+
+/// Results recorded for `owner` by the state-changing entry points, newest first.
+#[no_mangle]
+fn latest_results() {
+    let entry_points::latest_results::Args { owner, count } =
+        entry_points::latest_results::Args::get();
+
+    let results: Vec<(u64, String, Vec<U256>)> = Uniswap::latest_results(owner, count);
+    runtime::ret(CLValue::from_t(results).unwrap_or_revert())
+}
+
+/// Record the cumulative prices of a pair for the current oracle period.
+#[no_mangle]
+fn update_oracle() {
+    let entry_points::update_oracle::Args { token_a, token_b } =
+        entry_points::update_oracle::Args::get();
+    Uniswap::default().update_oracle(token_a, token_b);
+}
+
+/// Time-weighted average price of a pair over the last `window` milliseconds.
+#[no_mangle]
+fn consult() {
+    let entry_points::consult::Args {
+        token_in,
+        amount_in,
+        token_out,
+        window,
+    } = entry_points::consult::Args::get();
+
+    let amount_out: U256 = Uniswap::consult(token_in, amount_in, token_out, window);
+    runtime::ret(CLValue::from_t(amount_out).unwrap_or_revert())
+}
+
+/// Guard swaps through a pair against spot prices away from its time-weighted price. Only
+/// callable by the owner.
+#[no_mangle]
+fn set_oracle_guard() {
+    let entry_points::set_oracle_guard::Args {
+        token_a,
+        token_b,
+        max_deviation_bps,
+        window,
+    } = entry_points::set_oracle_guard::Args::get();
+    Uniswap::default().set_oracle_guard(token_a, token_b, max_deviation_bps, window);
+}
+
+/// Preview the amounts and LP tokens of adding liquidity.
+#[no_mangle]
+fn quote_add_liquidity() {
+    let entry_points::quote_add_liquidity::Args {
+        token_a,
+        token_b,
+        amount_a_desired,
+        amount_b_desired,
+    } = entry_points::quote_add_liquidity::Args::get();
+
+    let quote: (U256, U256, U256) =
+        Uniswap::quote_add_liquidity(token_a, token_b, amount_a_desired, amount_b_desired);
+    runtime::ret(CLValue::from_t(quote).unwrap_or_revert())
+}
+
+/// Preview the amounts paid out for removing liquidity.
+#[no_mangle]
+fn quote_remove_liquidity() {
+    let entry_points::quote_remove_liquidity::Args {
+        token_a,
+        token_b,
+        liquidity,
+    } = entry_points::quote_remove_liquidity::Args::get();
+
+    let amounts: (U256, U256) = Uniswap::quote_remove_liquidity(token_a, token_b, liquidity);
+    runtime::ret(CLValue::from_t(amounts).unwrap_or_revert())
+}
+
+/// Return the reserves of a pair in the order of the tokens passed.
+#[no_mangle]
+fn get_reserves() {
+    let entry_points::get_reserves::Args { token_a, token_b } =
+        entry_points::get_reserves::Args::get();
+
+    let reserves: (U128, U128) = Uniswap::get_reserves(token_a, token_b);
+    runtime::ret(CLValue::from_t(reserves).unwrap_or_revert())
+}
+
+/// Return the pair of two tokens.
+#[no_mangle]
+fn pair_for() {
+    let entry_points::pair_for::Args { token_a, token_b } = entry_points::pair_for::Args::get();
+
+    let pair: Key = Uniswap::pair_for(token_a, token_b);
+    runtime::ret(CLValue::from_t(pair).unwrap_or_revert())
+}
+
+/// Return a pair with its total supply, sorted tokens, reserves and last update.
+#[no_mangle]
+fn get_pair_info() {
+    let entry_points::get_pair_info::Args { token_a, token_b } =
+        entry_points::get_pair_info::Args::get();
+
+    let info: ((Key, U256), (Key, Key), (U128, U128, u64)) =
+        Uniswap::get_pair_info(token_a, token_b);
+    runtime::ret(CLValue::from_t(info).unwrap_or_revert())
+}
+
+/// Lend tokens of a pair to a callback contract that repays them with the fee in the same call.
+#[no_mangle]
+fn flash_swap() {
+    let entry_points::flash_swap::Args {
+        token,
+        other_token,
+        amount,
+        callback,
+        data,
+        deadline,
+    } = entry_points::flash_swap::Args::get();
+    if !(Uniswap::default().ensure(deadline)) {
+        runtime::revert(ApiError::User(ErrorCodes::UniswapV2RouterTimedOut24 as u16));
+    }
+
+    let fee: U256 = Uniswap::default().flash_swap(token, other_token, amount, callback, data);
+    Uniswap::default().record_result(entry_points::flash_swap::NAME, vec![amount, fee]);
+    runtime::ret(CLValue::from_t(fee).unwrap_or_revert())
+}
+
+/// Hand the loan of the flash swap in progress to its callback contract and check the repayment.
+#[no_mangle]
+fn uniswap_v2_call() {
+    let entry_points::uniswap_v2_call::Args {
+        sender,
+        amount0,
+        amount1,
+        data,
+    } = entry_points::uniswap_v2_call::Args::get();
+    Uniswap::default().uniswap_v2_call(sender, amount0, amount1, data);
+}
+
+/// Swap tokens around a cycle back into the token swapped in, for at least a minimum profit.
+#[no_mangle]
+fn swap_cycle() {
+    let entry_points::swap_cycle::Args {
+        amount_in,
+        min_profit,
+        path,
+        to,
+        deadline,
+        max_price_impact_bps,
+    } = entry_points::swap_cycle::Args::get();
+    if !(Uniswap::default().ensure(deadline)) {
+        runtime::revert(ApiError::User(ErrorCodes::UniswapV2RouterTimedOut25 as u16));
+    }
+
+    let amounts: Vec<U256> =
+        Uniswap::default().swap_cycle(amount_in, min_profit, path, to, max_price_impact_bps);
+    Uniswap::default().record_result(entry_points::swap_cycle::NAME, amounts.clone());
+    runtime::ret(CLValue::from_t(amounts).unwrap_or_revert())
+}
+
+/// Open a limit order, escrowing its input amount and tip.
+#[no_mangle]
+fn place_order() {
+    let entry_points::place_order::Args {
+        path,
+        amount_in,
+        amount_out_min,
+        tip,
+    } = entry_points::place_order::Args::get();
+
+    let order_id: u64 = Uniswap::default().place_order(path, amount_in, amount_out_min, tip);
+    Uniswap::default().record_result(entry_points::place_order::NAME, vec![order_id.into()]);
+    runtime::ret(CLValue::from_t(order_id).unwrap_or_revert())
+}
+
+/// Cancel a limit order and refund its escrow. Only callable by the order's owner.
+#[no_mangle]
+fn cancel_order() {
+    let entry_points::cancel_order::Args { order_id } = entry_points::cancel_order::Args::get();
+
+    let amounts: Vec<U256> = Uniswap::default().cancel_order(order_id);
+    Uniswap::default().record_result(entry_points::cancel_order::NAME, amounts);
+}
+
+/// Execute a limit order once the pairs meet its limit, earning its tip.
+#[no_mangle]
+fn execute_order() {
+    let entry_points::execute_order::Args { order_id } = entry_points::execute_order::Args::get();
+
+    let amounts: Vec<U256> = Uniswap::default().execute_order(order_id);
+    Uniswap::default().record_result(entry_points::execute_order::NAME, amounts.clone());
+    runtime::ret(CLValue::from_t(amounts).unwrap_or_revert())
+}
+
+/// Account for the escrow of a batch of the limit orders placed before escrows were tracked.
+#[no_mangle]
+fn migrate_escrows() {
+    let entry_points::migrate_escrows::Args { limit } = entry_points::migrate_escrows::Args::get();
+
+    let left: u64 = Uniswap::default().migrate_escrows(limit);
+    runtime::ret(CLValue::from_t(left).unwrap_or_revert())
+}
+
+/// Return an open limit order.
+#[no_mangle]
+fn get_order() {
+    let entry_points::get_order::Args { order_id } = entry_points::get_order::Args::get();
+
+    let order: Option<(Key, Vec<Key>, (U256, U256, U256))> = Uniswap::get_order(order_id);
+    runtime::ret(CLValue::from_t(order).unwrap_or_revert())
+}
+
+/// Migrate the state carried over from the previous contract version.
+#[no_mangle]
+fn migrate() {
+    let entry_points::migrate::Args { contract_hash } = entry_points::migrate::Args::get();
+
+    Uniswap::default().migrate(Key::from(contract_hash));
+}
+
+/// Return the named keys of this version so the installer can hand them to the next version.
+#[no_mangle]
+fn export_state() {
+    let named_keys: NamedKeys = runtime::list_named_keys();
+    runtime::ret(CLValue::from_t(named_keys).unwrap_or_revert());
+}
+
 #[no_mangle]
 fn package_hash() {
     let ret: ContractPackageHash = Uniswap::default().get_package_hash();
     runtime::ret(CLValue::from_t(ret).unwrap_or_revert());
 }
 
-fn get_entry_points() -> EntryPoints {
-    let mut entry_points = EntryPoints::new();
-
-    entry_points.add_entry_point(EntryPoint::new(
-        "constructor",
-        vec![
-            Parameter::new("factory", CLType::Key),
-            Parameter::new("wcspr", CLType::Key),
-            Parameter::new("library_hash", CLType::Key),
-            Parameter::new("contract_hash", ContractHash::cl_type()),
-            Parameter::new("package_hash", ContractPackageHash::cl_type()),
-        ],
-        <()>::cl_type(),
-        EntryPointAccess::Groups(vec![Group::new("constructor")]),
-        EntryPointType::Contract,
-    ));
-
-    entry_points.add_entry_point(EntryPoint::new(
-        String::from("add_liquidity"),
-        vec![
-            Parameter::new("token_a", CLType::Key),
-            Parameter::new("token_b", CLType::Key),
-            Parameter::new("amount_a_desired", CLType::U256),
-            Parameter::new("amount_b_desired", CLType::U256),
-            Parameter::new("amount_a_min", CLType::U256),
-            Parameter::new("amount_b_min", CLType::U256),
-            Parameter::new("to", CLType::Key),
-            Parameter::new("deadline", CLType::U256),
-            Parameter::new("pair", CLType::Option(Box::new(CLType::Key))),
-        ],
-        CLType::Tuple3([
-            Box::new(CLType::U256),
-            Box::new(CLType::U256),
-            Box::new(CLType::U256),
-        ]),
-        EntryPointAccess::Public,
-        EntryPointType::Contract,
-    ));
-    entry_points.add_entry_point(EntryPoint::new(
-        String::from("add_liquidity_js_client"),
-        vec![
-            Parameter::new("token_a", CLType::Key),
-            Parameter::new("token_b", CLType::Key),
-            Parameter::new("amount_a_desired", CLType::U256),
-            Parameter::new("amount_b_desired", CLType::U256),
-            Parameter::new("amount_a_min", CLType::U256),
-            Parameter::new("amount_b_min", CLType::U256),
-            Parameter::new("to", CLType::Key),
-            Parameter::new("deadline", CLType::U256),
-            Parameter::new("pair", CLType::Option(Box::new(CLType::Key))),
-        ],
-        <()>::cl_type(),
-        EntryPointAccess::Public,
-        EntryPointType::Contract,
-    ));
-
-    entry_points.add_entry_point(EntryPoint::new(
-        String::from("add_liquidity_cspr"),
-        vec![
-            Parameter::new("token", Key::cl_type()),
-            Parameter::new("amount_token_desired", CLType::U256),
-            Parameter::new("amount_cspr_desired", CLType::U256),
-            Parameter::new("amount_token_min", CLType::U256),
-            Parameter::new("amount_cspr_min", CLType::U256),
-            Parameter::new("to", Key::cl_type()),
-            Parameter::new("deadline", CLType::U256),
-            Parameter::new("pair", CLType::Option(Box::new(CLType::Key))),
-            Parameter::new("purse", CLType::URef),
-        ],
-        CLType::Tuple3([
-            Box::new(CLType::U256),
-            Box::new(CLType::U256),
-            Box::new(CLType::U256),
-        ]),
-        EntryPointAccess::Public,
-        EntryPointType::Contract,
-    ));
-    entry_points.add_entry_point(EntryPoint::new(
-        String::from("add_liquidity_cspr_js_client"),
-        vec![
-            Parameter::new("token", Key::cl_type()),
-            Parameter::new("amount_token_desired", CLType::U256),
-            Parameter::new("amount_cspr_desired", CLType::U256),
-            Parameter::new("amount_token_min", CLType::U256),
-            Parameter::new("amount_cspr_min", CLType::U256),
-            Parameter::new("to", Key::cl_type()),
-            Parameter::new("deadline", CLType::U256),
-            Parameter::new("pair", CLType::Option(Box::new(CLType::Key))),
-            Parameter::new("purse", CLType::URef),
-        ],
-        <()>::cl_type(),
-        EntryPointAccess::Public,
-        EntryPointType::Contract,
-    ));
-
-    entry_points.add_entry_point(EntryPoint::new(
-        String::from("remove_liquidity"),
-        vec![
-            Parameter::new("token_a", Key::cl_type()),
-            Parameter::new("token_b", Key::cl_type()),
-            Parameter::new("liquidity", CLType::U256),
-            Parameter::new("amount_a_min", CLType::U256),
-            Parameter::new("amount_b_min", CLType::U256),
-            Parameter::new("to", Key::cl_type()),
-            Parameter::new("deadline", CLType::U256),
-        ],
-        CLType::Tuple2([Box::new(CLType::U256), Box::new(CLType::U256)]),
-        EntryPointAccess::Public,
-        EntryPointType::Contract,
-    ));
-
-    entry_points.add_entry_point(EntryPoint::new(
-        String::from("remove_liquidity_js_client"),
-        vec![
-            Parameter::new("token_a", Key::cl_type()),
-            Parameter::new("token_b", Key::cl_type()),
-            Parameter::new("liquidity", CLType::U256),
-            Parameter::new("amount_a_min", CLType::U256),
-            Parameter::new("amount_b_min", CLType::U256),
-            Parameter::new("to", Key::cl_type()),
-            Parameter::new("deadline", CLType::U256),
-        ],
-        <()>::cl_type(),
-        EntryPointAccess::Public,
-        EntryPointType::Contract,
-    ));
-
-    entry_points.add_entry_point(EntryPoint::new(
-        String::from("remove_liquidity_cspr"),
-        vec![
-            Parameter::new("token", Key::cl_type()),
-            Parameter::new("liquidity", CLType::U256),
-            Parameter::new("amount_token_min", CLType::U256),
-            Parameter::new("amount_cspr_min", CLType::U256),
-            Parameter::new("to", Key::cl_type()),
-            Parameter::new("deadline", CLType::U256),
-            Parameter::new("to_purse", CLType::URef),
-        ],
-        CLType::Tuple2([Box::new(CLType::U256), Box::new(CLType::U256)]),
-        EntryPointAccess::Public,
-        EntryPointType::Contract,
-    ));
-
-    entry_points.add_entry_point(EntryPoint::new(
-        String::from("remove_liquidity_cspr_js_client"),
-        vec![
-            Parameter::new("token", Key::cl_type()),
-            Parameter::new("liquidity", CLType::U256),
-            Parameter::new("amount_token_min", CLType::U256),
-            Parameter::new("amount_cspr_min", CLType::U256),
-            Parameter::new("to", Key::cl_type()),
-            Parameter::new("deadline", CLType::U256),
-            Parameter::new("to_purse", CLType::URef),
-        ],
-        <()>::cl_type(),
-        EntryPointAccess::Public,
-        EntryPointType::Contract,
-    ));
-
-    entry_points.add_entry_point(EntryPoint::new(
-        String::from("remove_liquidity_with_permit"),
-        vec![
-            Parameter::new("token_a", Key::cl_type()),
-            Parameter::new("token_b", Key::cl_type()),
-            Parameter::new("liquidity", CLType::U256),
-            Parameter::new("amount_a_min", CLType::U256),
-            Parameter::new("amount_b_min", CLType::U256),
-            Parameter::new("to", Key::cl_type()),
-            Parameter::new("deadline", CLType::U256),
-            Parameter::new("approve_max", CLType::Bool),
-            Parameter::new("public_key", CLType::String),
-            Parameter::new("signature", CLType::String),
-        ],
-        CLType::Tuple2([Box::new(CLType::U256), Box::new(CLType::U256)]),
-        EntryPointAccess::Public,
-        EntryPointType::Contract,
-    ));
-    entry_points.add_entry_point(EntryPoint::new(
-        String::from("remove_liquidity_with_permit_js_client"),
-        vec![
-            Parameter::new("token_a", Key::cl_type()),
-            Parameter::new("token_b", Key::cl_type()),
-            Parameter::new("liquidity", CLType::U256),
-            Parameter::new("amount_a_min", CLType::U256),
-            Parameter::new("amount_b_min", CLType::U256),
-            Parameter::new("to", Key::cl_type()),
-            Parameter::new("deadline", CLType::U256),
-            Parameter::new("approve_max", CLType::Bool),
-            Parameter::new("public_key", CLType::String),
-            Parameter::new("signature", CLType::String),
-        ],
-        <()>::cl_type(),
-        EntryPointAccess::Public,
-        EntryPointType::Contract,
-    ));
-
-    entry_points.add_entry_point(EntryPoint::new(
-        String::from("remove_liquidity_cspr_with_permit"),
-        vec![
-            Parameter::new("token", Key::cl_type()),
-            Parameter::new("liquidity", CLType::U256),
-            Parameter::new("amount_token_min", CLType::U256),
-            Parameter::new("amount_cspr_min", CLType::U256),
-            Parameter::new("to", Key::cl_type()),
-            Parameter::new("deadline", CLType::U256),
-            Parameter::new("approve_max", CLType::Bool),
-            Parameter::new("public_key", CLType::String),
-            Parameter::new("signature", CLType::String),
-            Parameter::new("to_purse", CLType::URef),
-        ],
-        CLType::Tuple2([Box::new(CLType::U256), Box::new(CLType::U256)]),
-        EntryPointAccess::Public,
-        EntryPointType::Contract,
-    ));
-
-    entry_points.add_entry_point(EntryPoint::new(
-        String::from("remove_liquidity_cspr_with_permit_js_client"),
-        vec![
-            Parameter::new("token", Key::cl_type()),
-            Parameter::new("liquidity", CLType::U256),
-            Parameter::new("amount_token_min", CLType::U256),
-            Parameter::new("amount_cspr_min", CLType::U256),
-            Parameter::new("to", Key::cl_type()),
-            Parameter::new("deadline", CLType::U256),
-            Parameter::new("approve_max", CLType::Bool),
-            Parameter::new("public_key", CLType::String),
-            Parameter::new("signature", CLType::String),
-            Parameter::new("to_purse", CLType::URef),
-        ],
-        <()>::cl_type(),
-        EntryPointAccess::Public,
-        EntryPointType::Contract,
-    ));
-
-    entry_points.add_entry_point(EntryPoint::new(
-        String::from("swap_exact_tokens_for_tokens"),
-        vec![
-            Parameter::new("amount_in", CLType::U256),
-            Parameter::new("amount_out_min", CLType::U256),
-            Parameter::new("path", CLType::List(Box::new(String::cl_type()))),
-            Parameter::new("to", CLType::Key),
-            Parameter::new("deadline", CLType::U256),
-        ],
-        CLType::List(Box::new(CLType::U256)),
-        EntryPointAccess::Public,
-        EntryPointType::Contract,
-    ));
-
-    entry_points.add_entry_point(EntryPoint::new(
-        String::from("swap_exact_tokens_for_tokens_js_client"),
-        vec![
-            Parameter::new("amount_in", CLType::U256),
-            Parameter::new("amount_out_min", CLType::U256),
-            Parameter::new("path", CLType::List(Box::new(String::cl_type()))),
-            Parameter::new("to", CLType::Key),
-            Parameter::new("deadline", CLType::U256),
-        ],
-        <()>::cl_type(),
-        EntryPointAccess::Public,
-        EntryPointType::Contract,
-    ));
-
-    entry_points.add_entry_point(EntryPoint::new(
-        String::from("swap_tokens_for_exact_tokens"),
-        vec![
-            Parameter::new("amount_out", CLType::U256),
-            Parameter::new("amount_in_max", CLType::U256),
-            Parameter::new("path", CLType::List(Box::new(String::cl_type()))),
-            Parameter::new("to", CLType::Key),
-            Parameter::new("deadline", CLType::U256),
-        ],
-        CLType::List(Box::new(CLType::U256)),
-        EntryPointAccess::Public,
-        EntryPointType::Contract,
-    ));
-
-    entry_points.add_entry_point(EntryPoint::new(
-        String::from("swap_tokens_for_exact_tokens_js_client"),
-        vec![
-            Parameter::new("amount_out", CLType::U256),
-            Parameter::new("amount_in_max", CLType::U256),
-            Parameter::new("path", CLType::List(Box::new(String::cl_type()))),
-            Parameter::new("to", CLType::Key),
-            Parameter::new("deadline", CLType::U256),
-        ],
-        <()>::cl_type(),
-        EntryPointAccess::Public,
-        EntryPointType::Contract,
-    ));
-
-    entry_points.add_entry_point(EntryPoint::new(
-        String::from("swap_exact_cspr_for_tokens"),
-        vec![
-            Parameter::new("amount_out_min", CLType::U256),
-            Parameter::new("amount_in", CLType::U256),
-            Parameter::new("path", CLType::List(Box::new(String::cl_type()))),
-            Parameter::new("to", CLType::Key),
-            Parameter::new("deadline", CLType::U256),
-            Parameter::new("purse", CLType::URef),
-        ],
-        CLType::List(Box::new(CLType::U256)),
-        EntryPointAccess::Public,
-        EntryPointType::Contract,
-    ));
-
-    entry_points.add_entry_point(EntryPoint::new(
-        String::from("swap_exact_cspr_for_tokens_js_client"),
-        vec![
-            Parameter::new("amount_out_min", CLType::U256),
-            Parameter::new("amount_in", CLType::U256),
-            Parameter::new("path", CLType::List(Box::new(String::cl_type()))),
-            Parameter::new("to", CLType::Key),
-            Parameter::new("deadline", CLType::U256),
-            Parameter::new("purse", CLType::URef),
-        ],
-        <()>::cl_type(),
-        EntryPointAccess::Public,
-        EntryPointType::Contract,
-    ));
-
-    entry_points.add_entry_point(EntryPoint::new(
-        String::from("swap_tokens_for_exact_cspr"),
-        vec![
-            Parameter::new("amount_out", CLType::U256),
-            Parameter::new("amount_in_max", CLType::U256),
-            Parameter::new("path", CLType::List(Box::new(String::cl_type()))),
-            Parameter::new("to", CLType::URef),
-            Parameter::new("deadline", CLType::U256),
-        ],
-        CLType::List(Box::new(CLType::U256)),
-        EntryPointAccess::Public,
-        EntryPointType::Contract,
-    ));
-
-    entry_points.add_entry_point(EntryPoint::new(
-        String::from("swap_tokens_for_exact_cspr_js_client"),
-        vec![
-            Parameter::new("amount_out", CLType::U256),
-            Parameter::new("amount_in_max", CLType::U256),
-            Parameter::new("path", CLType::List(Box::new(String::cl_type()))),
-            Parameter::new("to", CLType::URef),
-            Parameter::new("deadline", CLType::U256),
-        ],
-        <()>::cl_type(),
-        EntryPointAccess::Public,
-        EntryPointType::Contract,
-    ));
-
-    entry_points.add_entry_point(EntryPoint::new(
-        String::from("swap_exact_tokens_for_cspr"),
-        vec![
-            Parameter::new("amount_in", CLType::U256),
-            Parameter::new("amount_out_min", CLType::U256),
-            Parameter::new("path", CLType::List(Box::new(String::cl_type()))),
-            Parameter::new("to", CLType::URef), // purse to transfer cspr to
-            Parameter::new("deadline", CLType::U256),
-        ],
-        CLType::List(Box::new(CLType::U256)),
-        EntryPointAccess::Public,
-        EntryPointType::Contract,
-    ));
-
-    entry_points.add_entry_point(EntryPoint::new(
-        String::from("swap_exact_tokens_for_cspr_js_client"),
-        vec![
-            Parameter::new("amount_in", CLType::U256),
-            Parameter::new("amount_out_min", CLType::U256),
-            Parameter::new("path", CLType::List(Box::new(String::cl_type()))),
-            Parameter::new("to", CLType::URef), // purse to transfer cspr to
-            Parameter::new("deadline", CLType::U256),
-        ],
-        <()>::cl_type(),
-        EntryPointAccess::Public,
-        EntryPointType::Contract,
-    ));
-
-    entry_points.add_entry_point(EntryPoint::new(
-        String::from("swap_cspr_for_exact_tokens"),
-        vec![
-            Parameter::new("amount_out", CLType::U256),
-            Parameter::new("amount_in_max", CLType::U256),
-            Parameter::new("path", CLType::List(Box::new(String::cl_type()))),
-            Parameter::new("to", CLType::Key),
-            Parameter::new("deadline", CLType::U256),
-            Parameter::new("purse", CLType::URef),
-        ],
-        CLType::List(Box::new(CLType::U256)),
-        EntryPointAccess::Public,
-        EntryPointType::Contract,
-    ));
-
-    entry_points.add_entry_point(EntryPoint::new(
-        String::from("swap_cspr_for_exact_tokens_js_client"),
-        vec![
-            Parameter::new("amount_out", CLType::U256),
-            Parameter::new("amount_in_max", CLType::U256),
-            Parameter::new("path", CLType::List(Box::new(String::cl_type()))),
-            Parameter::new("to", CLType::Key),
-            Parameter::new("deadline", CLType::U256),
-            Parameter::new("purse", CLType::URef),
-        ],
-        <()>::cl_type(),
-        EntryPointAccess::Public,
-        EntryPointType::Contract,
-    ));
-
-    entry_points.add_entry_point(EntryPoint::new(
-        "quote",
-        vec![
-            Parameter::new("amount_a", U256::cl_type()),
-            Parameter::new("reserve_a", U256::cl_type()),
-            Parameter::new("reserve_b", U256::cl_type()),
-        ],
-        U256::cl_type(),
-        EntryPointAccess::Public,
-        EntryPointType::Contract,
-    ));
-
-    entry_points.add_entry_point(EntryPoint::new(
-        String::from("get_amount_out"),
-        vec![
-            Parameter::new("amount_in", U256::cl_type()),
-            Parameter::new("reserve_in", U256::cl_type()),
-            Parameter::new("reserve_out", U256::cl_type()),
-        ],
-        U256::cl_type(),
-        EntryPointAccess::Public,
-        EntryPointType::Contract,
-    ));
-
-    entry_points.add_entry_point(EntryPoint::new(
-        String::from("get_amount_in"),
-        vec![
-            Parameter::new("amount_out", U256::cl_type()),
-            Parameter::new("reserve_in", U256::cl_type()),
-            Parameter::new("reserve_out", U256::cl_type()),
-        ],
-        U256::cl_type(),
-        EntryPointAccess::Public,
-        EntryPointType::Contract,
-    ));
-
-    entry_points.add_entry_point(EntryPoint::new(
-        String::from("get_amounts_out"),
-        vec![
-            Parameter::new("amount_in", U256::cl_type()),
-            Parameter::new("path", CLType::List(Box::new(Key::cl_type()))),
-        ],
-        CLType::List(Box::new(U256::cl_type())),
-        EntryPointAccess::Public,
-        EntryPointType::Contract,
-    ));
-
-    entry_points.add_entry_point(EntryPoint::new(
-        String::from("get_amounts_in"),
-        vec![
-            Parameter::new("amount_out", U256::cl_type()),
-            Parameter::new("path", CLType::List(Box::new(Key::cl_type()))),
-        ],
-        CLType::List(Box::new(U256::cl_type())),
-        EntryPointAccess::Public,
-        EntryPointType::Contract,
-    ));
-
-    entry_points.add_entry_point(EntryPoint::new(
-        "package_hash",
-        vec![],
-        ContractPackageHash::cl_type(),
-        EntryPointAccess::Public,
-        EntryPointType::Contract,
-    ));
-
-    entry_points
+/// Set the liquidity the first deposit into a pair has to mint. Only callable by the owner.
+#[no_mangle]
+fn set_min_initial_liquidity() {
+    let entry_points::set_min_initial_liquidity::Args {
+        min_initial_liquidity,
+    } = entry_points::set_min_initial_liquidity::Args::get();
+    Uniswap::default().set_min_initial_liquidity(min_initial_liquidity);
+}
+
+/// Allow or disallow the pair contract package `pair` to be registered through `add_liquidity`.
+/// Only callable by the owner.
+#[no_mangle]
+fn set_pair_allowed() {
+    let entry_points::set_pair_allowed::Args { pair, allowed } =
+        entry_points::set_pair_allowed::Args::get();
+    Uniswap::default().set_pair_allowed(pair, allowed);
+}
+
+/// Turn the pair allowlist on or off.
+/// Only callable by the owner.
+#[no_mangle]
+fn set_pair_allowlist() {
+    let entry_points::set_pair_allowlist::Args { enabled } =
+        entry_points::set_pair_allowlist::Args::get();
+    Uniswap::default().set_pair_allowlist(enabled);
+}
+
+/// Named keys for a version installed before `export_state` existed. Session code cannot read the
+/// named keys of a contract, but the only state of those versions was the config the installer
+/// received, so the upgrade takes the same `factory`, `wcspr` and `library` arguments again and
+/// `migrate` initializes the rest as schema 0.
+fn legacy_state(package_hash: ContractPackageHash) -> NamedKeys {
+    let mut named_keys = NamedKeys::new();
+    for (name, arg) in [
+        (data::FACTORY, "factory"),
+        (data::WCSPR, "wcspr"),
+        (data::LIBRARY_HASH, "library"),
+    ] {
+        let key: Key = runtime::get_named_arg(arg);
+        let hash: ContractPackageHash =
+            ContractPackageHash::from(key.into_hash().unwrap_or_revert());
+        named_keys.insert(name.into(), storage::new_uref(hash).into());
+    }
+    named_keys.insert(
+        data::PACKAGE_HASH.into(),
+        storage::new_uref(package_hash).into(),
+    );
+    named_keys
 }
 
 // All session code must have a `call` entrypoint.
@@ -1269,6 +1347,14 @@ fn call() {
         storage::remove_contract_user_group_urefs(package_hash, "constructor", urefs)
             .unwrap_or_revert();
 
+        // Empty migrate group, only the holder of the package access token can provision it.
+        storage::create_contract_user_group(package_hash, "migrate", 0, Default::default())
+            .unwrap_or_revert();
+        runtime::put_key(
+            &format!("{}_exports_state", contract_name),
+            storage::new_uref(true).into(),
+        );
+
         runtime::put_key(
             &format!("{}_package_hash", contract_name),
             package_hash.into(),
@@ -1299,8 +1385,46 @@ fn call() {
                 .unwrap()
                 .into();
 
+        let previous_contract_hash: ContractHash =
+            runtime::get_key(&format!("{}_contract_hash", contract_name))
+                .unwrap_or_revert()
+                .into_hash()
+                .unwrap_or_revert()
+                .into();
+
+        // Versions installed before the migrate entry point existed have neither the migrate
+        // group nor `export_state`.
+        let exports_state: bool = runtime::has_key(&format!("{}_exports_state", contract_name));
+        if !exports_state {
+            storage::create_contract_user_group(package_hash, "migrate", 0, Default::default())
+                .unwrap_or_revert();
+        }
+
+        // Provisioning the migrate group needs the package access token held by this account.
+        let migrate_access: URef =
+            storage::provision_contract_user_group_uref(package_hash, "migrate").unwrap_or_revert();
+
+        // Named keys are per contract version, carry the previous ones over so no state is lost.
+        let named_keys: NamedKeys = if exports_state {
+            runtime::call_contract(previous_contract_hash, "export_state", runtime_args! {})
+        } else {
+            legacy_state(package_hash)
+        };
         let (contract_hash, _): (ContractHash, _) =
-            storage::add_contract_version(package_hash, get_entry_points(), Default::default());
+            storage::add_contract_version(package_hash, get_entry_points(), named_keys);
+
+        let _: () = runtime::call_contract(
+            contract_hash,
+            "migrate",
+            runtime_args! {
+                "contract_hash" => contract_hash
+            },
+        );
+
+        let mut urefs = BTreeSet::new();
+        urefs.insert(migrate_access);
+        storage::remove_contract_user_group_urefs(package_hash, "migrate", urefs)
+            .unwrap_or_revert();
 
         // update contract hash
         runtime::put_key(
@@ -1311,5 +1435,9 @@ fn call() {
             &format!("{}_contract_hash_wrapped", contract_name),
             storage::new_uref(contract_hash).into(),
         );
+        runtime::put_key(
+            &format!("{}_exports_state", contract_name),
+            storage::new_uref(true).into(),
+        );
     }
 }
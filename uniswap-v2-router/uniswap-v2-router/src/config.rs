@@ -1,125 +1,199 @@
-#[repr(u16)]
-pub enum ErrorCodes {
-    /// 65,603 for (UniswapV2 Router Amount A Desired Is Zero)
-    UniswapV2RouterAmountADesiredIsZero = 67,
-    /// 65,604 for (UniswapV2 Router Amount B Desired Is Zero)
-    UniswapV2RouterAmountBDesiredIsZero = 68,
-    /// 65,605 for (UniswapV2 Router Amount Token Is Zero)
-    UniswapV2RouterAmountTokenIsZero = 69,
-    /// 65,606 for (UniswapV2 Router Abort1)
-    UniswapV2RouterAbort1 = 70,
-    /// 65,607 for (UniswapV2 Router Abort2)
-    UniswapV2RouterAbort2 = 71,
-    /// 65,608 for (UniswapV2 Router Abort3)
-    UniswapV2RouterAbort3 = 72,
-    /// 65,609 for (UniswapV2 Router Abort4)
-    UniswapV2RouterAbort4 = 73,
-    /// 65,610 for (UniswapV2 Router Abort5)
-    UniswapV2RouterAbort5 = 74,
-    /// 65,611 for (UniswapV2 Router Abort6)
-    UniswapV2RouterAbort6 = 75,
-    /// 65,612 for (UniswapV2 Router Abort7)
-    UniswapV2RouterAbort7 = 76,
-    /// 65,613 for (UniswapV2 Router Abort8)
-    UniswapV2RouterAbort8 = 77,
-    /// 65,614 for (UniswapV2 Router Abort9)
-    UniswapV2RouterAbort9 = 78,
-    /// 65,615 for (UniswapV2 Router Abort10)
-    UniswapV2RouterAbort10 = 79,
-    /// 65,616 for (UniswapV2 Router Abort11)
-    UniswapV2RouterAbort11 = 80,
-    /// 65,617 for (UniswapV2 Router Abort12)
-    UniswapV2RouterAbort12 = 81,
-    /// 65,618 for (UniswapV2 Router Abort13)
-    UniswapV2RouterAbort13 = 82,
-    /// 65,619 for (UniswapV2 Router TransferFailed1)
-    UniswapV2RouterTransferFailed1 = 83,
-    /// 65,620 for (UniswapV2 Router TransferFailed2)
-    UniswapV2RouterTransferFailed2 = 84,
-    /// 65,621 for (UniswapV2 Router TransferFailed3)
-    UniswapV2RouterTransferFailed3 = 85,
-    /// 65,622 for (UniswapV2 Router TransferFailed4)
-    UniswapV2RouterTransferFailed4 = 86,
-    /// 65,623 for (UniswapV2 Router TransferFailed5)
-    UniswapV2RouterTransferFailed5 = 87,
-    /// 65,624 for (UniswapV2 Router TransferFailed6)
-    UniswapV2RouterTransferFailed6 = 88,
-    /// 65,625 for (UniswapV2 Router TransferFailed7)
-    UniswapV2RouterTransferFailed7 = 89,
-    /// 65,626 for (UniswapV2 Router TransferFailed8)
-    UniswapV2RouterTransferFailed8 = 90,
-    /// 65,627 for (UniswapV2 Router TransferFailed9)
-    UniswapV2RouterTransferFailed9 = 91,
-    /// 65,628 for (UniswapV2 Router TransferFailed10)
-    UniswapV2RouterTransferFailed10 = 92,
-    /// 65,629 for (UniswapV2 Router TransferFailed11)
-    UniswapV2RouterTransferFailed11 = 93,
-    /// 65,630 for (UniswapV2 Router TransferFailed12)
-    UniswapV2RouterTransferFailed12 = 94,
-    /// 65,631 for (UniswapV2 Router TransferFailed13)
-    UniswapV2RouterTransferFailed13 = 95,
-    /// 65,632 for (UniswapV2 Router TransferFailed14)
-    UniswapV2RouterTransferFailed14 = 96,
-    /// 65,633 for (UniswapV2 Router TransferFailed15)
-    UniswapV2RouterTransferFailed15 = 97,
-    /// 65,634 for (UniswapV2 Router TransferFailed16)
-    UniswapV2RouterTransferFailed16 = 98,
-    /// 65,635 for (UniswapV2 Router TransferFailed17)
-    UniswapV2RouterTransferFailed17 = 99,
-    /// 65,636 for (UniswapV2 Router TransferFailed18)
-    UniswapV2RouterTransferFailed18 = 100,
-    /// 65,637 for (UniswapV2 Router TransferFailed19)
-    UniswapV2RouterTransferFailed19 = 101,
-    /// 65,638 for (UniswapV2 Router TransferFailed20)
-    UniswapV2RouterTransferFailed20 = 102,
-    /// 65,639 for (UniswapV2 Router TransferFailed21)
-    UniswapV2RouterTransferFailed21 = 103,
-    /// 65,640 for (UniswapV2 Router Zero Addr)
-    UniswapV2RouterZeroAddr = 104,
-    /// 65,641 for (UniswapV2 Router Invalid Arguments)
-    UniswapV2RouterInvalidArguments = 105,
-    /// 65,641 for (UniswapV2 Router Timed Out1)
-    UniswapV2RouterTimedOut1 = 106,
-    /// 65,641 for (UniswapV2 Router Timed Out2)
-    UniswapV2RouterTimedOut2 = 107,
-    /// 65,641 for (UniswapV2 Router Timed Out3)
-    UniswapV2RouterTimedOut3 = 108,
-    /// 65,641 for (UniswapV2 Router Timed Out4)
-    UniswapV2RouterTimedOut4 = 109,
-    /// 65,641 for (UniswapV2 Router Timed Out5)
-    UniswapV2RouterTimedOut5 = 110,
-    /// 65,641 for (UniswapV2 Router Timed Out6)
-    UniswapV2RouterTimedOut6 = 111,
-    /// 65,641 for (UniswapV2 Router Timed Out7)
-    UniswapV2RouterTimedOut7 = 112,
-    /// 65,641 for (UniswapV2 Router Timed Out8)
-    UniswapV2RouterTimedOut8 = 113,
-    /// 65,641 for (UniswapV2 Router Timed Out9)
-    UniswapV2RouterTimedOut9 = 114,
-    /// 65,641 for (UniswapV2 Router Timed Out10)
-    UniswapV2RouterTimedOut10 = 115,
-    /// 65,641 for (UniswapV2 Router Timed Out11)
-    UniswapV2RouterTimedOut11 = 116,
-    /// 65,641 for (UniswapV2 Router Timed Out12)
-    UniswapV2RouterTimedOut12 = 117,
-    /// 65,641 for (UniswapV2 Router Timed Out13)
-    UniswapV2RouterTimedOut13 = 118,
-    /// 65,641 for (UniswapV2 Router Timed Out14)
-    UniswapV2RouterTimedOut14 = 119,
-    /// 65,641 for (UniswapV2 Router Timed Out15)
-    UniswapV2RouterTimedOut15 = 120,
-    /// 65,641 for (UniswapV2 Router Timed Out16)
-    UniswapV2RouterTimedOut16 = 121,
-    /// 65,641 for (UniswapV2 Router Timed Out17)
-    UniswapV2RouterTimedOut17 = 122,
-    /// 65,641 for (UniswapV2 Router Timed Out18)
-    UniswapV2RouterTimedOut18 = 123,
-    /// 65,641 for (UniswapV2 Router Timed Out19)
-    UniswapV2RouterTimedOut19 = 124,
-    /// 65,641 for (UniswapV2 Router Timed Out20)
-    UniswapV2RouterTimedOut20 = 125,
+uniswap_v2_library::error_codes! {
+    pub enum ErrorCodes {
+        /// 65,603 for (UniswapV2 Router Amount A Desired Is Zero)
+        UniswapV2RouterAmountADesiredIsZero = 67,
+        /// 65,604 for (UniswapV2 Router Amount B Desired Is Zero)
+        UniswapV2RouterAmountBDesiredIsZero = 68,
+        /// 65,605 for (UniswapV2 Router Amount Token Is Zero)
+        UniswapV2RouterAmountTokenIsZero = 69,
+        /// 65,606 for (UniswapV2 Router Abort1)
+        UniswapV2RouterAbort1 = 70,
+        /// 65,607 for (UniswapV2 Router Abort2)
+        UniswapV2RouterAbort2 = 71,
+        /// 65,608 for (UniswapV2 Router Abort3)
+        UniswapV2RouterAbort3 = 72,
+        /// 65,609 for (UniswapV2 Router Abort4)
+        UniswapV2RouterAbort4 = 73,
+        /// 65,610 for (UniswapV2 Router Abort5)
+        UniswapV2RouterAbort5 = 74,
+        /// 65,611 for (UniswapV2 Router Abort6)
+        UniswapV2RouterAbort6 = 75,
+        /// 65,612 for (UniswapV2 Router Abort7)
+        UniswapV2RouterAbort7 = 76,
+        /// 65,613 for (UniswapV2 Router Abort8)
+        UniswapV2RouterAbort8 = 77,
+        /// 65,614 for (UniswapV2 Router Abort9)
+        UniswapV2RouterAbort9 = 78,
+        /// 65,615 for (UniswapV2 Router Abort10)
+        UniswapV2RouterAbort10 = 79,
+        /// 65,616 for (UniswapV2 Router Abort11)
+        UniswapV2RouterAbort11 = 80,
+        /// 65,617 for (UniswapV2 Router Abort12)
+        UniswapV2RouterAbort12 = 81,
+        /// 65,618 for (UniswapV2 Router Abort13)
+        UniswapV2RouterAbort13 = 82,
+        /// 65,619 for (UniswapV2 Router TransferFailed1)
+        UniswapV2RouterTransferFailed1 = 83,
+        /// 65,620 for (UniswapV2 Router TransferFailed2)
+        UniswapV2RouterTransferFailed2 = 84,
+        /// 65,621 for (UniswapV2 Router TransferFailed3)
+        UniswapV2RouterTransferFailed3 = 85,
+        /// 65,622 for (UniswapV2 Router TransferFailed4)
+        UniswapV2RouterTransferFailed4 = 86,
+        /// 65,623 for (UniswapV2 Router TransferFailed5)
+        UniswapV2RouterTransferFailed5 = 87,
+        /// 65,624 for (UniswapV2 Router TransferFailed6)
+        UniswapV2RouterTransferFailed6 = 88,
+        /// 65,625 for (UniswapV2 Router TransferFailed7)
+        UniswapV2RouterTransferFailed7 = 89,
+        /// 65,626 for (UniswapV2 Router TransferFailed8)
+        UniswapV2RouterTransferFailed8 = 90,
+        /// 65,627 for (UniswapV2 Router TransferFailed9)
+        UniswapV2RouterTransferFailed9 = 91,
+        /// 65,628 for (UniswapV2 Router TransferFailed10)
+        UniswapV2RouterTransferFailed10 = 92,
+        /// 65,629 for (UniswapV2 Router TransferFailed11)
+        UniswapV2RouterTransferFailed11 = 93,
+        /// 65,630 for (UniswapV2 Router TransferFailed12)
+        UniswapV2RouterTransferFailed12 = 94,
+        /// 65,631 for (UniswapV2 Router TransferFailed13)
+        UniswapV2RouterTransferFailed13 = 95,
+        /// 65,632 for (UniswapV2 Router TransferFailed14)
+        UniswapV2RouterTransferFailed14 = 96,
+        /// 65,633 for (UniswapV2 Router TransferFailed15)
+        UniswapV2RouterTransferFailed15 = 97,
+        /// 65,634 for (UniswapV2 Router TransferFailed16)
+        UniswapV2RouterTransferFailed16 = 98,
+        /// 65,635 for (UniswapV2 Router TransferFailed17)
+        UniswapV2RouterTransferFailed17 = 99,
+        /// 65,636 for (UniswapV2 Router TransferFailed18)
+        UniswapV2RouterTransferFailed18 = 100,
+        /// 65,637 for (UniswapV2 Router TransferFailed19)
+        UniswapV2RouterTransferFailed19 = 101,
+        /// 65,638 for (UniswapV2 Router TransferFailed20)
+        UniswapV2RouterTransferFailed20 = 102,
+        /// 65,639 for (UniswapV2 Router TransferFailed21)
+        UniswapV2RouterTransferFailed21 = 103,
+        /// 65,640 for (UniswapV2 Router Zero Addr)
+        UniswapV2RouterZeroAddr = 104,
+        /// 65,641 for (UniswapV2 Router Invalid Arguments)
+        UniswapV2RouterInvalidArguments = 105,
+        /// 65,641 for (UniswapV2 Router Timed Out1)
+        UniswapV2RouterTimedOut1 = 106,
+        /// 65,641 for (UniswapV2 Router Timed Out2)
+        UniswapV2RouterTimedOut2 = 107,
+        /// 65,641 for (UniswapV2 Router Timed Out3)
+        UniswapV2RouterTimedOut3 = 108,
+        /// 65,641 for (UniswapV2 Router Timed Out4)
+        UniswapV2RouterTimedOut4 = 109,
+        /// 65,641 for (UniswapV2 Router Timed Out5)
+        UniswapV2RouterTimedOut5 = 110,
+        /// 65,641 for (UniswapV2 Router Timed Out6)
+        UniswapV2RouterTimedOut6 = 111,
+        /// 65,641 for (UniswapV2 Router Timed Out7)
+        UniswapV2RouterTimedOut7 = 112,
+        /// 65,641 for (UniswapV2 Router Timed Out8)
+        UniswapV2RouterTimedOut8 = 113,
+        /// 65,641 for (UniswapV2 Router Timed Out9)
+        UniswapV2RouterTimedOut9 = 114,
+        /// 65,641 for (UniswapV2 Router Timed Out10)
+        UniswapV2RouterTimedOut10 = 115,
+        /// 65,641 for (UniswapV2 Router Timed Out11)
+        UniswapV2RouterTimedOut11 = 116,
+        /// 65,641 for (UniswapV2 Router Timed Out12)
+        UniswapV2RouterTimedOut12 = 117,
+        /// 65,641 for (UniswapV2 Router Timed Out13)
+        UniswapV2RouterTimedOut13 = 118,
+        /// 65,641 for (UniswapV2 Router Timed Out14)
+        UniswapV2RouterTimedOut14 = 119,
+        /// 65,641 for (UniswapV2 Router Timed Out15)
+        UniswapV2RouterTimedOut15 = 120,
+        /// 65,641 for (UniswapV2 Router Timed Out16)
+        UniswapV2RouterTimedOut16 = 121,
+        /// 65,641 for (UniswapV2 Router Timed Out17)
+        UniswapV2RouterTimedOut17 = 122,
+        /// 65,641 for (UniswapV2 Router Timed Out18)
+        UniswapV2RouterTimedOut18 = 123,
+        /// 65,641 for (UniswapV2 Router Timed Out19)
+        UniswapV2RouterTimedOut19 = 124,
+        /// 65,641 for (UniswapV2 Router Timed Out20)
+        UniswapV2RouterTimedOut20 = 125,
+        /// 65,662 for (UniswapV2 Router Schema Downgrade)
+        UniswapV2RouterSchemaDowngrade = 126,
+        /// 65,663 for (UniswapV2 Router Invalid Cspr Recipient)
+        UniswapV2RouterInvalidCsprRecipient = 127,
+        /// 65,664 for (UniswapV2 Router Timed Out21)
+        UniswapV2RouterTimedOut21 = 128,
+        /// 65,665 for (UniswapV2 Router Timed Out22)
+        UniswapV2RouterTimedOut22 = 129,
+        /// 65,666 for (UniswapV2 Router Timed Out23)
+        UniswapV2RouterTimedOut23 = 130,
+        /// 65,667 for (UniswapV2 Router Untrusted Pair)
+        UniswapV2RouterUntrustedPair = 131,
+        /// 65,668 for (UniswapV2 Router Not Owner)
+        UniswapV2RouterNotOwner = 132,
+        /// 65,669 for (UniswapV2 Router Insufficient Liquidity Minted)
+        UniswapV2RouterInsufficientLiquidityMinted = 133,
+        /// 65,670 for (UniswapV2 Router Insufficient Initial Liquidity)
+        UniswapV2RouterInsufficientInitialLiquidity = 134,
+        /// 65,671 for (UniswapV2 Router Excessive Price Impact)
+        UniswapV2RouterExcessivePriceImpact = 135,
+        /// 65,672 for (UniswapV2 Router Invalid Oracle Window)
+        UniswapV2RouterInvalidOracleWindow = 136,
+        /// 65,673 for (UniswapV2 Router Missing Observation)
+        UniswapV2RouterMissingObservation = 137,
+        /// 65,674 for (UniswapV2 Router Price Deviation)
+        UniswapV2RouterPriceDeviation = 138,
+        /// 65,675 for (UniswapV2 Router Timed Out24)
+        UniswapV2RouterTimedOut24 = 139,
+        /// 65,676 for (UniswapV2 Router Invalid Flash Swap)
+        UniswapV2RouterInvalidFlashSwap = 140,
+        /// 65,677 for (UniswapV2 Router Flash Swap Not Repaid)
+        UniswapV2RouterFlashSwapNotRepaid = 141,
+        /// 65,678 for (UniswapV2 Router Timed Out25)
+        UniswapV2RouterTimedOut25 = 142,
+        /// 65,679 for (UniswapV2 Router TransferFailed22)
+        UniswapV2RouterTransferFailed22 = 143,
+        /// 65,680 for (UniswapV2 Router Invalid Cycle)
+        UniswapV2RouterInvalidCycle = 144,
+        /// 65,681 for (UniswapV2 Router Insufficient Profit)
+        UniswapV2RouterInsufficientProfit = 145,
+        /// 65,682 for (UniswapV2 Router TransferFailed23)
+        UniswapV2RouterTransferFailed23 = 146,
+        /// 65,683 for (UniswapV2 Router TransferFailed24)
+        UniswapV2RouterTransferFailed24 = 147,
+        /// 65,684 for (UniswapV2 Router TransferFailed25)
+        UniswapV2RouterTransferFailed25 = 148,
+        /// 65,685 for (UniswapV2 Router TransferFailed26)
+        UniswapV2RouterTransferFailed26 = 149,
+        /// 65,686 for (UniswapV2 Router Invalid Order)
+        UniswapV2RouterInvalidOrder = 150,
+        /// 65,687 for (UniswapV2 Router Unknown Order)
+        UniswapV2RouterUnknownOrder = 151,
+        /// 65,688 for (UniswapV2 Router Not Order Owner)
+        UniswapV2RouterNotOrderOwner = 152,
+        /// 65,689 for (UniswapV2 Router Order Limit Not Met)
+        UniswapV2RouterOrderLimitNotMet = 153,
+        /// 65,690 for (UniswapV2 Router Escrow Shortfall)
+        UniswapV2RouterEscrowShortfall = 154,
+        /// 65,691 for (UniswapV2 Router Escrows Not Migrated)
+        UniswapV2RouterEscrowsNotMigrated = 155,
+    }
 }
 
+/// Basis points in one, the unit of `max_price_impact_bps`.
+pub const BPS: u32 = 10_000;
+
+/// Block time in milliseconds between two price observations the oracle keeps for a pair.
+pub const ORACLE_PERIOD: u64 = 30 * 60 * 1000;
+
+/// Number of price observations the oracle keeps per pair, one per period. Each observation
+/// overwrites the one taken `ORACLE_OBSERVATIONS` periods earlier.
+pub const ORACLE_OBSERVATIONS: u64 = 48;
+
+/// Layout version of the router's named keys, bumped whenever `migrate` has to rewrite state.
+pub const CURRENT_SCHEMA_VERSION: u32 = 10;
+
 pub mod uniswapv2_contract_methods {
 
     // FACTORY Contract Methods
@@ -134,6 +208,8 @@ pub mod uniswapv2_contract_methods {
     pub const LIBRARY_SORT_TOKENS: &str = "sort_tokens";
     pub const LIBRARY_GET_AMOUNTS_OUT: &str = "get_amounts_out";
     pub const LIBRARY_GET_AMOUNTS_IN: &str = "get_amounts_in";
+    pub const LIBRARY_GET_AMOUNTS_OUT_BATCH: &str = "get_amounts_out_batch";
+    pub const LIBRARY_GET_AMOUNTS_IN_BATCH: &str = "get_amounts_in_batch";
     pub const LIBRARY_GET_AMOUNT_OUT: &str = "get_amount_out";
     pub const LIBRARY_GET_AMOUNT_IN: &str = "get_amount_in";
 
@@ -143,10 +219,23 @@ pub mod uniswapv2_contract_methods {
     pub const PAIR_BURN: &str = "burn";
     pub const PAIR_PERMIT: &str = "permit";
     pub const PAIR_SWAP: &str = "swap";
+    pub const PAIR_TOKEN0: &str = "token0";
+    pub const PAIR_TOKEN1: &str = "token1";
+    pub const PAIR_FACTORY: &str = "factory";
+    pub const PAIR_GET_RESERVES: &str = "get_reserves";
+    pub const PAIR_PRICE0_CUMULATIVE_LAST: &str = "price0_cumulative_last";
+    pub const PAIR_PRICE1_CUMULATIVE_LAST: &str = "price1_cumulative_last";
+    pub const PAIR_TOTAL_SUPPLY: &str = "total_supply";
 
     // IWETH Contract methods
     pub const WCSPR_DEPOSIT: &str = "deposit";
     pub const WCSPR_TRANSFER: &str = "transfer";
     pub const WCSPR_TRANSFER_FROM: &str = "transfer_from";
     pub const WCSPR_WITHDRAW: &str = "withdraw";
+
+    // ERC20 Contract methods
+    pub const TOKEN_BALANCE_OF: &str = "balance_of";
+
+    // Flash swap callback methods
+    pub const FLASH_SWAP_CALL: &str = "flash_swap_call";
 }
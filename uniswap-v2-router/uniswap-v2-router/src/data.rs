@@ -1,7 +1,13 @@
-use casper_contract::unwrap_or_revert::UnwrapOrRevert;
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use casper_contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
 use casper_types::{
-    contracts::{ContractPackageHash},
-    Key,
+    bytesrepr::ToBytes, contracts::ContractPackageHash, ApiError, CLTyped, Key, URef, U256,
 };
 use casperlabs_contract_utils::{get_key, set_key};
 
@@ -10,6 +16,22 @@ pub const FACTORY: &str = "factory";
 pub const SELF_HASH: &str = "self_hash";
 pub const PACKAGE_HASH: &str = "package_hash";
 pub const LIBRARY_HASH: &str = "library_hash";
+pub const SCHEMA_VERSION: &str = "schema_version";
+pub const EVENTS: &str = "events";
+pub const EVENTS_LENGTH: &str = "events_length";
+pub const RESULTS: &str = "results";
+pub const RESULT_NONCES: &str = "result_nonces";
+pub const OWNER: &str = "owner";
+pub const MIN_INITIAL_LIQUIDITY: &str = "min_initial_liquidity";
+pub const OBSERVATIONS: &str = "observations";
+pub const ORACLE_GUARDS: &str = "oracle_guards";
+pub const FLASH_SWAP: &str = "flash_swap";
+pub const ORDERS: &str = "orders";
+pub const ORDERS_LENGTH: &str = "orders_length";
+pub const ESCROWS: &str = "escrows";
+pub const ESCROW_MIGRATION: &str = "escrow_migration";
+pub const ALLOWED_PAIRS: &str = "allowed_pairs";
+pub const PAIR_ALLOWLIST: &str = "pair_allowlist";
 
 pub fn wcspr() -> ContractPackageHash {
     get_key(WCSPR).unwrap_or_revert()
@@ -45,3 +67,234 @@ pub fn package_hash() -> ContractPackageHash {
 pub fn set_package_hash(package_hash: ContractPackageHash) {
     set_key(PACKAGE_HASH, package_hash);
 }
+
+pub fn owner() -> Key {
+    get_key(OWNER).unwrap_or_revert()
+}
+pub fn set_owner(owner: Key) {
+    set_key(OWNER, owner);
+}
+
+pub fn min_initial_liquidity() -> U256 {
+    get_key(MIN_INITIAL_LIQUIDITY).unwrap_or_default()
+}
+pub fn set_min_initial_liquidity(min_initial_liquidity: U256) {
+    set_key(MIN_INITIAL_LIQUIDITY, min_initial_liquidity);
+}
+
+pub fn schema_version() -> u32 {
+    get_key(SCHEMA_VERSION).unwrap_or_default()
+}
+pub fn set_schema_version(schema_version: u32) {
+    set_key(SCHEMA_VERSION, schema_version);
+}
+
+pub fn init_events() {
+    storage::new_dictionary(EVENTS).unwrap_or_revert();
+    set_key(EVENTS_LENGTH, 0u32);
+}
+
+pub fn events_length() -> u32 {
+    get_key(EVENTS_LENGTH).unwrap_or_default()
+}
+
+fn dictionary(name: &str) -> URef {
+    *runtime::get_key(name)
+        .unwrap_or_revert()
+        .as_uref()
+        .unwrap_or_revert_with(ApiError::UnexpectedKeyVariant)
+}
+
+// Appends `event` to the events dictionary under its index.
+pub fn record_event<T: CLTyped + ToBytes>(event: T) {
+    let index: u32 = events_length();
+    storage::dictionary_put(dictionary(EVENTS), &index.to_string(), event);
+    set_key(EVENTS_LENGTH, index + 1);
+}
+
+pub fn init_results() {
+    storage::new_dictionary(RESULTS).unwrap_or_revert();
+    storage::new_dictionary(RESULT_NONCES).unwrap_or_revert();
+}
+
+// Dictionary item keys are limited to 64 bytes, so owners and nonces are hashed into them.
+fn hashed_key<T: ToBytes>(value: T) -> String {
+    hex::encode(runtime::blake2b(value.to_bytes().unwrap_or_revert()))
+}
+
+// Item key of the number of results recorded for `owner` in the result nonces dictionary.
+pub fn result_nonce_key(owner: Key) -> String {
+    hashed_key(owner)
+}
+
+// Item key of the result `nonce` of `owner` in the results dictionary.
+pub fn result_key(owner: Key, nonce: u64) -> String {
+    hashed_key((owner, nonce))
+}
+
+pub fn result_nonce(owner: Key) -> u64 {
+    storage::dictionary_get(dictionary(RESULT_NONCES), &result_nonce_key(owner))
+        .unwrap_or_revert()
+        .unwrap_or_default()
+}
+
+pub fn result(owner: Key, nonce: u64) -> Option<(String, Vec<U256>)> {
+    storage::dictionary_get(dictionary(RESULTS), &result_key(owner, nonce)).unwrap_or_revert()
+}
+
+// Stores what `entry_point` returned to `owner` under the owner's next nonce.
+pub fn record_result(owner: Key, entry_point: &str, amounts: Vec<U256>) {
+    let nonce: u64 = result_nonce(owner);
+    storage::dictionary_put(
+        dictionary(RESULTS),
+        &result_key(owner, nonce),
+        (entry_point.to_string(), amounts),
+    );
+    storage::dictionary_put(
+        dictionary(RESULT_NONCES),
+        &result_nonce_key(owner),
+        nonce + 1,
+    );
+}
+
+pub fn init_observations() {
+    storage::new_dictionary(OBSERVATIONS).unwrap_or_revert();
+}
+
+// Item key of the observation in ring slot `slot` of `pair` in the observations dictionary.
+pub fn observation_key(pair: Key, slot: u64) -> String {
+    hashed_key((pair, slot))
+}
+
+// Block time and cumulative prices of `pair` stored in ring slot `slot`, if any.
+pub fn observation(pair: Key, slot: u64) -> Option<(u64, U256, U256)> {
+    storage::dictionary_get(dictionary(OBSERVATIONS), &observation_key(pair, slot))
+        .unwrap_or_revert()
+}
+
+pub fn set_observation(pair: Key, slot: u64, observation: (u64, U256, U256)) {
+    storage::dictionary_put(
+        dictionary(OBSERVATIONS),
+        &observation_key(pair, slot),
+        observation,
+    );
+}
+
+pub fn init_oracle_guards() {
+    storage::new_dictionary(ORACLE_GUARDS).unwrap_or_revert();
+}
+
+// Item key of the guard of `pair` in the oracle guards dictionary.
+pub fn oracle_guard_key(pair: Key) -> String {
+    hashed_key(pair)
+}
+
+// Deviation in basis points the spot price of `pair` may have from its time-weighted price over
+// the window, if the pair is guarded.
+pub fn oracle_guard(pair: Key) -> Option<(u32, u64)> {
+    storage::dictionary_get(dictionary(ORACLE_GUARDS), &oracle_guard_key(pair))
+        .unwrap_or_revert()
+        .flatten()
+}
+
+pub fn set_oracle_guard(pair: Key, guard: Option<(u32, u64)>) {
+    storage::dictionary_put(dictionary(ORACLE_GUARDS), &oracle_guard_key(pair), guard);
+}
+
+// Pair, callback and initiator of the flash swap waiting for its pair to call back, if any.
+pub fn flash_swap() -> Option<(Key, Key, Key)> {
+    get_key(FLASH_SWAP).unwrap_or_default()
+}
+pub fn set_flash_swap(flash_swap: Option<(Key, Key, Key)>) {
+    set_key(FLASH_SWAP, flash_swap);
+}
+
+pub fn init_orders() {
+    storage::new_dictionary(ORDERS).unwrap_or_revert();
+    set_key(ORDERS_LENGTH, 0u64);
+}
+
+pub fn orders_length() -> u64 {
+    get_key(ORDERS_LENGTH).unwrap_or_default()
+}
+
+// Owner, path and `(amount_in, amount_out_min, tip)` of the open order `order_id`, if any.
+pub fn order(order_id: u64) -> Option<(Key, Vec<Key>, (U256, U256, U256))> {
+    storage::dictionary_get(dictionary(ORDERS), &order_id.to_string())
+        .unwrap_or_revert()
+        .flatten()
+}
+
+// Stores `order` under the next order id and returns the id.
+pub fn add_order(order: (Key, Vec<Key>, (U256, U256, U256))) -> u64 {
+    let order_id: u64 = orders_length();
+    storage::dictionary_put(dictionary(ORDERS), &order_id.to_string(), Some(order));
+    set_key(ORDERS_LENGTH, order_id + 1);
+    order_id
+}
+
+// Closes the order `order_id`, its id is never reused.
+pub fn remove_order(order_id: u64) {
+    storage::dictionary_put(
+        dictionary(ORDERS),
+        &order_id.to_string(),
+        None::<(Key, Vec<Key>, (U256, U256, U256))>,
+    );
+}
+
+pub fn init_escrows() {
+    storage::new_dictionary(ESCROWS).unwrap_or_revert();
+}
+
+// Item key of `token` in the escrows dictionary.
+pub fn escrow_key(token: Key) -> String {
+    hashed_key(token)
+}
+
+// Amount of `token` the router holds for its open orders, amounts and tips together.
+pub fn escrow(token: Key) -> U256 {
+    storage::dictionary_get(dictionary(ESCROWS), &escrow_key(token))
+        .unwrap_or_revert()
+        .unwrap_or_default()
+}
+
+pub fn set_escrow(token: Key, escrow: U256) {
+    storage::dictionary_put(dictionary(ESCROWS), &escrow_key(token), escrow);
+}
+
+// Next order id whose escrow still has to be accounted for and the end of the orders placed
+// before the escrows were tracked. Equal once all of them are accounted for.
+pub fn escrow_migration() -> (u64, u64) {
+    get_key(ESCROW_MIGRATION).unwrap_or_default()
+}
+pub fn set_escrow_migration(escrow_migration: (u64, u64)) {
+    set_key(ESCROW_MIGRATION, escrow_migration);
+}
+
+pub fn init_allowed_pairs() {
+    storage::new_dictionary(ALLOWED_PAIRS).unwrap_or_revert();
+}
+
+// Item key of `pair` in the allowed pairs dictionary.
+pub fn allowed_pair_key(pair: Key) -> String {
+    hashed_key(pair)
+}
+
+// Whether the owner allowed `pair` to be registered through the router.
+pub fn pair_allowed(pair: Key) -> bool {
+    storage::dictionary_get(dictionary(ALLOWED_PAIRS), &allowed_pair_key(pair))
+        .unwrap_or_revert()
+        .unwrap_or_default()
+}
+
+pub fn set_pair_allowed(pair: Key, allowed: bool) {
+    storage::dictionary_put(dictionary(ALLOWED_PAIRS), &allowed_pair_key(pair), allowed);
+}
+
+// Whether new pairs also have to be allowed by the owner.
+pub fn pair_allowlist() -> bool {
+    get_key(PAIR_ALLOWLIST).unwrap_or_default()
+}
+pub fn set_pair_allowlist(enabled: bool) {
+    set_key(PAIR_ALLOWLIST, enabled);
+}
@@ -0,0 +1,479 @@
+//! Entry points of the router contract.
+
+use alloc::{string::String, vec::Vec};
+use casper_types::{
+    contracts::{ContractHash, ContractPackageHash, NamedKeys},
+    Key, URef, U128, U256,
+};
+
+uniswap_v2_library::entry_points! {
+    #[group = "constructor"]
+    fn constructor(
+        factory: Key,
+        wcspr: Key,
+        library_hash: Key,
+        contract_hash: ContractHash,
+        package_hash: ContractPackageHash,
+    ) -> ();
+
+    /// Add tokens to liquidity pool.
+    fn add_liquidity(
+        token_a: Key,
+        token_b: Key,
+        amount_a_desired: U256,
+        amount_b_desired: U256,
+        amount_a_min: U256,
+        amount_b_min: U256,
+        to: Key,
+        deadline: U256,
+        pair: Option<Key>,
+        min_liquidity_out: Option<U256>,
+    ) -> (U256, U256, U256);
+
+    /// Add tokens to liquidity pool.
+    fn add_liquidity_js_client(
+        token_a: Key,
+        token_b: Key,
+        amount_a_desired: U256,
+        amount_b_desired: U256,
+        amount_a_min: U256,
+        amount_b_min: U256,
+        to: Key,
+        deadline: U256,
+        pair: Option<Key>,
+        min_liquidity_out: Option<U256>,
+    ) -> ();
+
+    /// Add cspr to liquidity pool.
+    fn add_liquidity_cspr(
+        token: Key,
+        amount_token_desired: U256,
+        amount_cspr_desired: U256,
+        amount_token_min: U256,
+        amount_cspr_min: U256,
+        to: Key,
+        deadline: U256,
+        pair: Option<Key>,
+        min_liquidity_out: Option<U256>,
+        purse: URef,
+    ) -> (U256, U256, U256);
+
+    /// Add cspr to liquidity pool.
+    fn add_liquidity_cspr_js_client(
+        token: Key,
+        amount_token_desired: U256,
+        amount_cspr_desired: U256,
+        amount_token_min: U256,
+        amount_cspr_min: U256,
+        to: Key,
+        deadline: U256,
+        pair: Option<Key>,
+        min_liquidity_out: Option<U256>,
+        purse: URef,
+    ) -> ();
+
+    /// Remove from liquidity pool.
+    fn remove_liquidity(
+        token_a: Key,
+        token_b: Key,
+        liquidity: U256,
+        amount_a_min: U256,
+        amount_b_min: U256,
+        to: Key,
+        deadline: U256,
+    ) -> (U256, U256);
+
+    /// Remove from liquidity pool.
+    fn remove_liquidity_js_client(
+        token_a: Key,
+        token_b: Key,
+        liquidity: U256,
+        amount_a_min: U256,
+        amount_b_min: U256,
+        to: Key,
+        deadline: U256,
+    ) -> ();
+
+    /// Remove cspr from liquidity pool.
+    fn remove_liquidity_cspr(
+        token: Key,
+        liquidity: U256,
+        amount_token_min: U256,
+        amount_cspr_min: U256,
+        to: Key,
+        deadline: U256,
+        to_purse: URef,
+    ) -> (U256, U256);
+
+    /// Remove cspr from liquidity pool.
+    fn remove_liquidity_cspr_js_client(
+        token: Key,
+        liquidity: U256,
+        amount_token_min: U256,
+        amount_cspr_min: U256,
+        to: Key,
+        deadline: U256,
+        to_purse: URef,
+    ) -> ();
+
+    /// Remove cspr from liquidity pool, paying both the token and the cspr to the `to` account.
+    fn remove_liquidity_cspr_to_key(
+        token: Key,
+        liquidity: U256,
+        amount_token_min: U256,
+        amount_cspr_min: U256,
+        to: Key,
+        deadline: U256,
+    ) -> (U256, U256);
+
+    /// Remove from liquidity pool with permit.
+    fn remove_liquidity_with_permit(
+        token_a: Key,
+        token_b: Key,
+        liquidity: U256,
+        amount_a_min: U256,
+        amount_b_min: U256,
+        to: Key,
+        deadline: U256,
+        approve_max: bool,
+        public_key: String,
+        signature: String,
+    ) -> (U256, U256);
+
+    /// Remove from liquidity pool with permit.
+    fn remove_liquidity_with_permit_js_client(
+        token_a: Key,
+        token_b: Key,
+        liquidity: U256,
+        amount_a_min: U256,
+        amount_b_min: U256,
+        to: Key,
+        deadline: U256,
+        approve_max: bool,
+        public_key: String,
+        signature: String,
+    ) -> ();
+
+    /// Remove cspr from liquidity pool with permit.
+    fn remove_liquidity_cspr_with_permit(
+        token: Key,
+        liquidity: U256,
+        amount_token_min: U256,
+        amount_cspr_min: U256,
+        to: Key,
+        deadline: U256,
+        approve_max: bool,
+        public_key: String,
+        signature: String,
+        to_purse: URef,
+    ) -> (U256, U256);
+
+    /// Remove cspr from liquidity pool with permit.
+    fn remove_liquidity_cspr_with_permit_js_client(
+        token: Key,
+        liquidity: U256,
+        amount_token_min: U256,
+        amount_cspr_min: U256,
+        to: Key,
+        deadline: U256,
+        approve_max: bool,
+        public_key: String,
+        signature: String,
+        to_purse: URef,
+    ) -> ();
+
+    /// Swap exact tokens for tokens. `path` holds the tokens as formatted `hash-…` strings.
+    fn swap_exact_tokens_for_tokens(
+        amount_in: U256,
+        amount_out_min: U256,
+        path: Vec<String>,
+        to: Key,
+        deadline: U256,
+        max_price_impact_bps: Option<u32>,
+    ) -> Vec<U256>;
+
+    /// Swap exact tokens for tokens. `path` holds the tokens as formatted `hash-…` strings.
+    fn swap_exact_tokens_for_tokens_js_client(
+        amount_in: U256,
+        amount_out_min: U256,
+        path: Vec<String>,
+        to: Key,
+        deadline: U256,
+        max_price_impact_bps: Option<u32>,
+    ) -> ();
+
+    /// Swap tokens for exact tokens. `path` holds the tokens as formatted `hash-…` strings.
+    fn swap_tokens_for_exact_tokens(
+        amount_out: U256,
+        amount_in_max: U256,
+        path: Vec<String>,
+        to: Key,
+        deadline: U256,
+        max_price_impact_bps: Option<u32>,
+    ) -> Vec<U256>;
+
+    /// Swap tokens for exact tokens. `path` holds the tokens as formatted `hash-…` strings.
+    fn swap_tokens_for_exact_tokens_js_client(
+        amount_out: U256,
+        amount_in_max: U256,
+        path: Vec<String>,
+        to: Key,
+        deadline: U256,
+        max_price_impact_bps: Option<u32>,
+    ) -> ();
+
+    /// Swap exact cspr for tokens. `path` holds the tokens as formatted `hash-…` strings.
+    fn swap_exact_cspr_for_tokens(
+        amount_out_min: U256,
+        amount_in: U256,
+        path: Vec<String>,
+        to: Key,
+        deadline: U256,
+        max_price_impact_bps: Option<u32>,
+        purse: URef,
+    ) -> Vec<U256>;
+
+    /// Swap exact cspr for tokens. `path` holds the tokens as formatted `hash-…` strings.
+    fn swap_exact_cspr_for_tokens_js_client(
+        amount_out_min: U256,
+        amount_in: U256,
+        path: Vec<String>,
+        to: Key,
+        deadline: U256,
+        max_price_impact_bps: Option<u32>,
+        purse: URef,
+    ) -> ();
+
+    /// Swap tokens for exact cspr, paid to the `to` purse. `path` holds the tokens as formatted
+    /// `hash-…` strings.
+    fn swap_tokens_for_exact_cspr(
+        amount_out: U256,
+        amount_in_max: U256,
+        path: Vec<String>,
+        to: URef,
+        deadline: U256,
+        max_price_impact_bps: Option<u32>,
+    ) -> Vec<U256>;
+
+    /// Swap tokens for exact cspr, paid to the `to` purse. `path` holds the tokens as formatted
+    /// `hash-…` strings.
+    fn swap_tokens_for_exact_cspr_js_client(
+        amount_out: U256,
+        amount_in_max: U256,
+        path: Vec<String>,
+        to: URef,
+        deadline: U256,
+        max_price_impact_bps: Option<u32>,
+    ) -> ();
+
+    /// Swap tokens for exact cspr, paid to the main purse of a `Key::Account` or to a `Key::URef`
+    /// purse. `path` holds the tokens as formatted `hash-…` strings.
+    fn swap_tokens_for_exact_cspr_to_key(
+        amount_out: U256,
+        amount_in_max: U256,
+        path: Vec<String>,
+        to: Key,
+        deadline: U256,
+        max_price_impact_bps: Option<u32>,
+    ) -> Vec<U256>;
+
+    /// Swap exact tokens for cspr, paid to the `to` purse. `path` holds the tokens as formatted
+    /// `hash-…` strings.
+    fn swap_exact_tokens_for_cspr(
+        amount_in: U256,
+        amount_out_min: U256,
+        path: Vec<String>,
+        to: URef,
+        deadline: U256,
+        max_price_impact_bps: Option<u32>,
+    ) -> Vec<U256>;
+
+    /// Swap exact tokens for cspr, paid to the `to` purse. `path` holds the tokens as formatted
+    /// `hash-…` strings.
+    fn swap_exact_tokens_for_cspr_js_client(
+        amount_in: U256,
+        amount_out_min: U256,
+        path: Vec<String>,
+        to: URef,
+        deadline: U256,
+        max_price_impact_bps: Option<u32>,
+    ) -> ();
+
+    /// Swap exact tokens for cspr, paid to the main purse of a `Key::Account` or to a `Key::URef`
+    /// purse. `path` holds the tokens as formatted `hash-…` strings.
+    fn swap_exact_tokens_for_cspr_to_key(
+        amount_in: U256,
+        amount_out_min: U256,
+        path: Vec<String>,
+        to: Key,
+        deadline: U256,
+        max_price_impact_bps: Option<u32>,
+    ) -> Vec<U256>;
+
+    /// Swap cspr for exact tokens. `path` holds the tokens as formatted `hash-…` strings.
+    fn swap_cspr_for_exact_tokens(
+        amount_out: U256,
+        amount_in_max: U256,
+        path: Vec<String>,
+        to: Key,
+        deadline: U256,
+        max_price_impact_bps: Option<u32>,
+        purse: URef,
+    ) -> Vec<U256>;
+
+    /// Swap cspr for exact tokens. `path` holds the tokens as formatted `hash-…` strings.
+    fn swap_cspr_for_exact_tokens_js_client(
+        amount_out: U256,
+        amount_in_max: U256,
+        path: Vec<String>,
+        to: Key,
+        deadline: U256,
+        max_price_impact_bps: Option<u32>,
+        purse: URef,
+    ) -> ();
+
+    /// Given some amount of an asset and pair reserves, returns an equivalent amount of the other
+    /// asset.
+    fn quote(amount_a: U256, reserve_a: U256, reserve_b: U256) -> U256;
+
+    /// Given an input amount of an asset and pair reserves, returns the maximum output amount of
+    /// the other asset.
+    fn get_amount_out(amount_in: U256, reserve_in: U256, reserve_out: U256) -> U256;
+
+    /// Given an output amount of an asset and pair reserves, returns a required input amount of
+    /// the other asset.
+    fn get_amount_in(amount_out: U256, reserve_in: U256, reserve_out: U256) -> U256;
+
+    /// Performs chained `get_amount_out` calculations on any number of pairs.
+    fn get_amounts_out(amount_in: U256, path: Vec<Key>) -> Vec<U256>;
+
+    /// Performs chained `get_amount_in` calculations on any number of pairs.
+    fn get_amounts_in(amount_out: U256, path: Vec<Key>) -> Vec<U256>;
+
+    /// Performs chained `get_amount_out` calculations for every amount in `amounts_in`, reading
+    /// the reserves of each pair once. Row `i` holds the amounts along `path` for `amounts_in[i]`.
+    fn get_amounts_out_batch(amounts_in: Vec<U256>, path: Vec<Key>) -> Vec<Vec<U256>>;
+
+    /// Performs chained `get_amount_in` calculations for every amount in `amounts_out`, reading
+    /// the reserves of each pair once. Row `i` holds the amounts along `path` for `amounts_out[i]`.
+    fn get_amounts_in_batch(amounts_out: Vec<U256>, path: Vec<Key>) -> Vec<Vec<U256>>;
+
+    fn package_hash() -> ContractPackageHash;
+
+    /// Set the liquidity the first deposit into a pair has to mint. Only callable by the owner.
+    fn set_min_initial_liquidity(min_initial_liquidity: U256) -> ();
+
+    /// Allow or disallow the pair contract package `pair` to be registered through
+    /// `add_liquidity` while the pair allowlist is on. Only callable by the owner.
+    fn set_pair_allowed(pair: Key, allowed: bool) -> ();
+
+    /// Turn on or off the requirement that new pairs are allowed with `set_pair_allowed` on top
+    /// of reporting the router's factory. Only callable by the owner.
+    fn set_pair_allowlist(enabled: bool) -> ();
+
+    /// Results of the state-changing entry points called by `owner`, newest first, as
+    /// `(nonce, entry point, amounts)`. At most `count` results are returned.
+    fn latest_results(owner: Key, count: u32) -> Vec<(u64, String, Vec<U256>)>;
+
+    /// Record the cumulative prices of the pair of `token_a` and `token_b` for the current oracle
+    /// period. Anyone can call it, at most one observation per period is kept.
+    fn update_oracle(token_a: Key, token_b: Key) -> ();
+
+    /// Amount of `token_out` that `amount_in` of `token_in` is worth at the pair's time-weighted
+    /// average price over the last `window` milliseconds, give or take one oracle period.
+    fn consult(token_in: Key, amount_in: U256, token_out: Key, window: u64) -> U256;
+
+    /// Refuse swaps through the pair of `token_a` and `token_b` while its spot price is more than
+    /// `max_deviation_bps` away from its average price since the newest observation at least
+    /// `window` old, or while there is no such observation. `None` lifts the guard. Only callable
+    /// by the owner.
+    fn set_oracle_guard(
+        token_a: Key,
+        token_b: Key,
+        max_deviation_bps: Option<u32>,
+        window: u64,
+    ) -> ();
+
+    /// Amounts of `token_a` and `token_b` that `add_liquidity` would take for the desired amounts
+    /// at the current reserves and the LP tokens it would mint, as
+    /// `(amount_a, amount_b, liquidity)`.
+    fn quote_add_liquidity(
+        token_a: Key,
+        token_b: Key,
+        amount_a_desired: U256,
+        amount_b_desired: U256,
+    ) -> (U256, U256, U256);
+
+    /// Amounts of `token_a` and `token_b` that burning `liquidity` of their pair would pay out at
+    /// the current reserves, as `(amount_a, amount_b)`. Reverts if the tokens have no pair.
+    fn quote_remove_liquidity(token_a: Key, token_b: Key, liquidity: U256) -> (U256, U256);
+
+    /// Reserves of the pair of `token_a` and `token_b`, in the order of the tokens passed. Reverts
+    /// if the tokens have no pair.
+    fn get_reserves(token_a: Key, token_b: Key) -> (U128, U128);
+
+    /// Pair of `token_a` and `token_b` registered with the factory. Reverts if there is none.
+    fn pair_for(token_a: Key, token_b: Key) -> Key;
+
+    /// Pair of `token_a` and `token_b` with its LP total supply, its sorted tokens and their
+    /// reserves with the block time of their last update, as
+    /// `((pair, total_supply), (token0, token1), (reserve0, reserve1, block_timestamp_last))`.
+    fn get_pair_info(token_a: Key, token_b: Key) -> ((Key, U256), (Key, Key), (U128, U128, u64));
+
+    /// Lend `amount` of `token` from its pair with `other_token` to the `callback` contract, which
+    /// is called back with `data` and has to repay the amount plus the returned fee to the pair.
+    /// Only pairs deployed with the router package as their callee can lend, flash swaps through
+    /// pairs deployed with the flash swapper revert.
+    fn flash_swap(
+        token: Key,
+        other_token: Key,
+        amount: U256,
+        callback: Key,
+        data: String,
+        deadline: U256,
+    ) -> U256;
+
+    /// Flash swap callback of pairs deployed with the router as callee.
+    fn uniswap_v2_call(sender: Key, amount0: U256, amount1: U256, data: String) -> ();
+
+    /// Swap `amount_in` around `path`, which starts and ends at the same token, reverting unless
+    /// it returns at least `min_profit` more than `amount_in`. `path` holds the tokens as
+    /// formatted `hash-…` strings.
+    fn swap_cycle(
+        amount_in: U256,
+        min_profit: U256,
+        path: Vec<String>,
+        to: Key,
+        deadline: U256,
+        max_price_impact_bps: Option<u32>,
+    ) -> Vec<U256>;
+
+    /// Open a limit order selling `amount_in` of the first token of `path` for at least
+    /// `amount_out_min` of its last token. The amount and the keeper's `tip` are escrowed in the
+    /// first token, and methods paying out of the router's balance revert rather than touch it.
+    /// Returns the order id, which is also recorded for the caller.
+    fn place_order(path: Vec<Key>, amount_in: U256, amount_out_min: U256, tip: U256) -> u64;
+
+    /// Cancel an open limit order and refund its escrow. Only callable by the order's owner. The
+    /// refunded `amount_in` and `tip` are recorded for the caller.
+    fn cancel_order(order_id: u64) -> ();
+
+    /// Execute an open limit order whose limit the pairs meet, paying its tip to the caller.
+    fn execute_order(order_id: u64) -> Vec<U256>;
+
+    /// Account for the escrow of up to `limit` of the limit orders placed before the router
+    /// tracked escrows, continuing where the last call stopped. Anyone can call it. Until none
+    /// are left, methods relying on the escrows revert. Returns the number of orders left.
+    fn migrate_escrows(limit: u64) -> u64;
+
+    /// Owner, path and `(amount_in, amount_out_min, tip)` of an open limit order.
+    fn get_order(order_id: u64) -> Option<(Key, Vec<Key>, (U256, U256, U256))>;
+
+    /// Migrate the state carried over from the previous contract version.
+    #[group = "migrate"]
+    fn migrate(contract_hash: ContractHash) -> ();
+
+    /// Return the named keys of this version so the installer can hand them to the next version.
+    #[group = "migrate"]
+    fn export_state() -> NamedKeys;
+}
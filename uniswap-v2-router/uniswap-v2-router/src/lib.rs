@@ -4,6 +4,8 @@ extern crate alloc;
 
 pub mod config;
 pub mod data;
+pub mod entry_points;
+pub mod session;
 pub mod transfer_helper;
 pub mod uniswap_v2_router;
 
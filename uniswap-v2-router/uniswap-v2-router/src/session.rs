@@ -0,0 +1,102 @@
+//! Building blocks for the session wasm accounts call the router through.
+//!
+//! A session reads the router's entry point table to forward the arguments of a destination
+//! entry point without knowing them in advance, and stores the amounts the router returns in the
+//! account's named keys.
+
+use alloc::{boxed::Box, format, vec, vec::Vec};
+
+use casper_contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{
+    bytesrepr::{self, FromBytes, OPTION_NONE_TAG},
+    ApiError, CLType, CLTyped, CLValue, Key, U256,
+};
+use uniswap_v2_library::abi::{self, EntryPointAbi};
+
+use crate::entry_points;
+
+pub const RESULT_KEY_SUFFIX: &str = "_result";
+
+/// A serialized value whose type is only known at runtime, from the router's entry point table.
+pub struct Raw(pub Vec<u8>);
+
+impl CLTyped for Raw {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
+impl FromBytes for Raw {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        Ok((Raw(bytes.to_vec()), &[]))
+    }
+}
+
+/// The router entry point called `name`.
+pub fn destination(name: &str) -> Option<EntryPointAbi> {
+    entry_points::abi()
+        .into_iter()
+        .find(|entry_point| entry_point.name == name)
+}
+
+/// Reads the runtime argument `name` of the session as is, to be forwarded with type `cl_type`.
+/// An `Option` argument the caller left out is forwarded as `None`, the way the router reads it
+/// when called directly.
+pub fn forward_arg(name: &str, cl_type: CLType) -> CLValue {
+    if let CLType::Option(_) = cl_type {
+        if !abi::has_named_arg(name) {
+            return CLValue::from_components(cl_type, vec![OPTION_NONE_TAG]);
+        }
+    }
+    let Raw(bytes) = runtime::get_named_arg(name);
+    CLValue::from_components(cl_type, bytes)
+}
+
+/// Reads the amounts out of a router result, `None` for results that are not made of `U256`s.
+pub fn amounts(cl_type: &CLType, bytes: Vec<u8>) -> Option<Vec<U256>> {
+    let fields: &[Box<CLType>] = match cl_type {
+        CLType::Unit => &[],
+        CLType::U256 => {
+            return bytesrepr::deserialize(bytes)
+                .ok()
+                .map(|amount| vec![amount])
+        }
+        CLType::List(inner) if **inner == CLType::U256 => {
+            return bytesrepr::deserialize(bytes).ok()
+        }
+        CLType::Tuple1(fields) => &fields[..],
+        CLType::Tuple2(fields) => &fields[..],
+        CLType::Tuple3(fields) => &fields[..],
+        _ => return None,
+    };
+    let mut amounts: Vec<U256> = Vec::new();
+    let mut remainder: &[u8] = &bytes;
+    for field in fields {
+        if **field != CLType::U256 {
+            return None;
+        }
+        let (amount, rest) = U256::from_bytes(remainder).ok()?;
+        amounts.push(amount);
+        remainder = rest;
+    }
+    Some(amounts)
+}
+
+/// Writes `amounts` to the `<destination>_result` named key of the account, reusing the URef of
+/// an earlier call.
+pub fn store_result(destination: &str, amounts: Vec<U256>) {
+    let name = format!("{}{}", destination, RESULT_KEY_SUFFIX);
+    match runtime::get_key(&name) {
+        Some(Key::URef(uref)) => storage::write(uref, amounts),
+        _ => runtime::put_key(&name, storage::new_uref(amounts).into()),
+    }
+}
+
+/// Reverts with `error` unless `result` holds amounts, then stores them for `destination`.
+pub fn store_amounts<E: Into<ApiError>>(destination: &EntryPointAbi, result: Vec<u8>, error: E) {
+    let amounts = amounts(&destination.ret, result).unwrap_or_revert_with(error);
+    store_result(destination.name, amounts);
+}
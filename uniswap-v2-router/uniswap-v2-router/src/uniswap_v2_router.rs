@@ -17,6 +17,14 @@ use crate::data::{self};
 use crate::transfer_helper::transfer_helper;
 use alloc::collections::BTreeMap;
 use casper_contract::contract_api::storage;
+use uniswap_v2_library::{math, oracle};
+
+// U256 products of amounts and reserves can overflow, so price checks multiply in U512.
+fn u512(value: U256) -> U512 {
+    let mut bytes = [0u8; 32];
+    value.to_little_endian(&mut bytes);
+    U512::from_little_endian(&bytes)
+}
 
 pub enum ROUTEREvent {
     AddReserves {
@@ -68,6 +76,75 @@ pub trait UniswapV2Router<Storage: ContractStorage>: ContractContext<Storage> {
         data::set_library_hash(library_hash);
         data::set_self_hash(contract_hash);
         data::set_package_hash(package_hash);
+        data::init_events();
+        data::init_results();
+        data::set_owner(self.get_caller());
+        data::set_min_initial_liquidity(0.into());
+        data::init_observations();
+        data::init_oracle_guards();
+        data::set_flash_swap(None);
+        data::init_orders();
+        data::init_allowed_pairs();
+        data::init_escrows();
+        data::set_escrow_migration((0, 0));
+        data::set_pair_allowlist(false);
+        data::set_schema_version(CURRENT_SCHEMA_VERSION);
+    }
+
+    // Will be called by the installer right after a new contract version is added, with the
+    // named keys of the previous version already carried over.
+    fn migrate(&mut self, contract_hash: Key) {
+        let schema_version: u32 = data::schema_version();
+        if schema_version > CURRENT_SCHEMA_VERSION {
+            runtime::revert(ApiError::User(
+                ErrorCodes::UniswapV2RouterSchemaDowngrade as u16,
+            ));
+        }
+        // schema 0 had no event history
+        if schema_version < 1 {
+            data::init_events();
+        }
+        // schema 1 did not keep the results of state-changing entry points
+        if schema_version < 2 {
+            data::init_results();
+        }
+        // schema 2 had no owner and no minimum for the first deposit into a pair
+        if schema_version < 3 {
+            data::set_owner(self.get_caller());
+            data::set_min_initial_liquidity(0.into());
+        }
+        // schema 3 had no price oracle
+        if schema_version < 4 {
+            data::init_observations();
+        }
+        // schema 4 did not guard swaps with the oracle
+        if schema_version < 5 {
+            data::init_oracle_guards();
+        }
+        // schema 5 had no flash swaps
+        if schema_version < 6 {
+            data::set_flash_swap(None);
+        }
+        // schema 6 had no limit orders
+        if schema_version < 7 {
+            data::init_orders();
+        }
+        // schema 7 accepted any new pair the factory registered
+        if schema_version < 8 {
+            data::init_allowed_pairs();
+        }
+        // schema 8 held the escrow of open orders without accounting for it, the orders placed so
+        // far are accounted for in batches by `migrate_escrows`
+        if schema_version < 9 {
+            data::init_escrows();
+            data::set_escrow_migration((0, data::orders_length()));
+        }
+        // schema 8 and 9 only accepted new pairs the owner allowed, older schemas any pair
+        if schema_version < 10 {
+            data::set_pair_allowlist(schema_version >= 8);
+        }
+        data::set_self_hash(contract_hash);
+        data::set_schema_version(CURRENT_SCHEMA_VERSION);
     }
 
     fn add_liquidity(
@@ -80,6 +157,7 @@ pub trait UniswapV2Router<Storage: ContractStorage>: ContractContext<Storage> {
         amount_b_min: U256,
         to: Key,
         pair: Option<Key>,
+        min_liquidity_out: Option<U256>,
     ) -> (U256, U256, U256) {
         let factory: ContractPackageHash = data::factory();
 
@@ -94,7 +172,7 @@ pub trait UniswapV2Router<Storage: ContractStorage>: ContractContext<Storage> {
             ));
         }
 
-        let (amount_a, amount_b): (U256, U256) = Self::_add_liquidity(
+        let (amount_a, amount_b, initial): (U256, U256, bool) = Self::_add_liquidity(
             token_a,
             token_b,
             amount_a_desired,
@@ -159,6 +237,7 @@ pub trait UniswapV2Router<Storage: ContractStorage>: ContractContext<Storage> {
             uniswapv2_contract_methods::PAIR_MINT,
             args,
         );
+        Self::check_liquidity(liquidity, initial, min_liquidity_out);
         self.emit(&ROUTEREvent::AddReserves {
             user: to,
             reserve0: amount_a,
@@ -177,12 +256,13 @@ pub trait UniswapV2Router<Storage: ContractStorage>: ContractContext<Storage> {
         amount_cspr_min: U256,
         to: Key,
         pair: Option<Key>,
+        min_liquidity_out: Option<U256>,
         caller_purse: URef,
     ) -> (U256, U256, U256) {
         let wcspr: ContractPackageHash = data::wcspr();
         let factory: ContractPackageHash = data::factory();
 
-        let (amount_token, amount_cspr): (U256, U256) = Self::_add_liquidity(
+        let (amount_token, amount_cspr, initial): (U256, U256, bool) = Self::_add_liquidity(
             token,
             wcspr,
             amount_token_desired,
@@ -286,6 +366,7 @@ pub trait UniswapV2Router<Storage: ContractStorage>: ContractContext<Storage> {
             uniswapv2_contract_methods::PAIR_MINT,
             args,
         );
+        Self::check_liquidity(liquidity, initial, min_liquidity_out);
         self.emit(&ROUTEREvent::AddReserves {
             user: to,
             reserve0: amount_token,
@@ -388,8 +469,8 @@ pub trait UniswapV2Router<Storage: ContractStorage>: ContractContext<Storage> {
         liquidity: U256,
         amount_token_min: U256,
         amount_cspr_min: U256,
-        to: Key,        // to's key to transfer back token
-        to_purse: URef, // to's purse to transfer back cspr
+        to: Key,      // to's key to transfer back token
+        cspr_to: Key, // to's purse or account to transfer back cspr
     ) -> (U256, U256) {
         // calling self contract's removeLiquidity
         let package_hash = data::package_hash();
@@ -414,25 +495,14 @@ pub trait UniswapV2Router<Storage: ContractStorage>: ContractContext<Storage> {
                 ErrorCodes::UniswapV2RouterTransferFailed8 as u16,
             ));
         }
+        Self::check_escrow(Key::from(token));
 
-        // call withdraw and transfer cspr to 'to'
-        let args: RuntimeArgs = runtime_args! {
-            "to_purse" => to_purse,
-            "amount" => U512::from(amount_cspr.as_u128())
-        };
-
-        let result: Result<(), u32> = Self::call_versioned_contract(
-            &wcspr.to_formatted_string(),
-            uniswapv2_contract_methods::WCSPR_WITHDRAW,
-            args,
+        // call withdraw and transfer cspr to 'cspr_to'
+        Self::pay_cspr(
+            cspr_to,
+            amount_cspr,
+            ErrorCodes::UniswapV2RouterTransferFailed9,
         );
-        if result.is_err()
-        // wcspr_withdraw failed
-        {
-            runtime::revert(ApiError::User(
-                ErrorCodes::UniswapV2RouterTransferFailed9 as u16,
-            ));
-        }
         (amount_token, amount_cspr)
     }
 
@@ -562,7 +632,7 @@ pub trait UniswapV2Router<Storage: ContractStorage>: ContractContext<Storage> {
             amount_token_min,
             amount_cspr_min,
             to,
-            to_purse,
+            Key::from(to_purse),
         );
         (amount_token, amount_cspr)
     }
@@ -573,6 +643,7 @@ pub trait UniswapV2Router<Storage: ContractStorage>: ContractContext<Storage> {
         amount_out_min: U256,
         _path: Vec<String>,
         to: Key,
+        max_price_impact_bps: Option<u32>,
     ) -> Vec<U256> {
         let factory: ContractPackageHash = data::factory();
         let mut path: Vec<Key> = Vec::new();
@@ -595,6 +666,7 @@ pub trait UniswapV2Router<Storage: ContractStorage>: ContractContext<Storage> {
         if amounts[amounts.len() - 1] < amount_out_min {
             runtime::revert(ApiError::User(ErrorCodes::UniswapV2RouterAbort4 as u16));
         }
+        Self::check_price_impact(&amounts, &path, max_price_impact_bps);
 
         // get pair
         let args: RuntimeArgs = runtime_args! {
@@ -636,6 +708,7 @@ pub trait UniswapV2Router<Storage: ContractStorage>: ContractContext<Storage> {
         amount_in_max: U256,
         _path: Vec<String>,
         to: Key,
+        max_price_impact_bps: Option<u32>,
     ) -> Vec<U256> {
         let factory: ContractPackageHash = data::factory();
 
@@ -660,6 +733,7 @@ pub trait UniswapV2Router<Storage: ContractStorage>: ContractContext<Storage> {
         if amounts[0] > amount_in_max {
             runtime::revert(ApiError::User(ErrorCodes::UniswapV2RouterAbort5 as u16));
         }
+        Self::check_price_impact(&amounts, &path, max_price_impact_bps);
 
         // Get pair
         let args: RuntimeArgs = runtime_args! {
@@ -702,6 +776,7 @@ pub trait UniswapV2Router<Storage: ContractStorage>: ContractContext<Storage> {
         amount_in: U256,
         _path: Vec<String>,
         to: Key,
+        max_price_impact_bps: Option<u32>,
         caller_purse: URef,
     ) -> Vec<U256> {
         let wcspr: ContractPackageHash = data::wcspr();
@@ -730,6 +805,7 @@ pub trait UniswapV2Router<Storage: ContractStorage>: ContractContext<Storage> {
         if amounts[amounts.len() - 1] < amount_out_min {
             runtime::revert(ApiError::User(ErrorCodes::UniswapV2RouterAbort7 as u16));
         }
+        Self::check_price_impact(&amounts, &path, max_price_impact_bps);
 
         let self_purse = system::create_purse(); // create new temporary purse and transfer cspr from caller purse to this
         let _: () = system::transfer_from_purse_to_purse(
@@ -803,7 +879,8 @@ pub trait UniswapV2Router<Storage: ContractStorage>: ContractContext<Storage> {
         amount_out: U256,
         amount_in_max: U256,
         _path: Vec<String>,
-        to: URef, // recipient of cspr, must be a purse
+        to: Key, // recipient of cspr, a purse or an account
+        max_price_impact_bps: Option<u32>,
     ) -> Vec<U256> {
         let wcspr: ContractPackageHash = data::wcspr();
         let factory: ContractPackageHash = data::factory();
@@ -833,6 +910,7 @@ pub trait UniswapV2Router<Storage: ContractStorage>: ContractContext<Storage> {
         if amounts[0] > amount_in_max {
             runtime::revert(ApiError::User(ErrorCodes::UniswapV2RouterAbort9 as u16));
         }
+        Self::check_price_impact(&amounts, &path, max_price_impact_bps);
 
         // call safeTransferFrom from TransferHelper
 
@@ -871,22 +949,11 @@ pub trait UniswapV2Router<Storage: ContractStorage>: ContractContext<Storage> {
         Self::_swap(&amounts, &path, self_addr);
 
         // call withdraw from WCSPR and transfer cspr to 'to'
-        let args: RuntimeArgs = runtime_args! {
-            "to_purse" => to,
-            "amount" => U512::from(amounts[amounts.len() - 1].as_u128())
-        };
-        let result: Result<(), u32> = Self::call_versioned_contract(
-            &wcspr.to_formatted_string(),
-            uniswapv2_contract_methods::WCSPR_WITHDRAW,
-            args,
+        Self::pay_cspr(
+            to,
+            amounts[amounts.len() - 1],
+            ErrorCodes::UniswapV2RouterTransferFailed16,
         );
-        if result.is_err()
-        // transfer_from failed
-        {
-            runtime::revert(ApiError::User(
-                ErrorCodes::UniswapV2RouterTransferFailed16 as u16,
-            ));
-        }
 
         amounts
     }
@@ -896,7 +963,8 @@ pub trait UniswapV2Router<Storage: ContractStorage>: ContractContext<Storage> {
         amount_in: U256,
         amount_out_min: U256,
         _path: Vec<String>,
-        to: URef, // recipient of cspr, must be a purse
+        to: Key, // recipient of cspr, a purse or an account
+        max_price_impact_bps: Option<u32>,
     ) -> Vec<U256> {
         let wcspr: ContractPackageHash = data::wcspr();
         let factory: ContractPackageHash = data::factory();
@@ -926,6 +994,7 @@ pub trait UniswapV2Router<Storage: ContractStorage>: ContractContext<Storage> {
         if amounts[amounts.len() - 1] < amount_out_min {
             runtime::revert(ApiError::User(ErrorCodes::UniswapV2RouterAbort11 as u16));
         }
+        Self::check_price_impact(&amounts, &path, max_price_impact_bps);
 
         // call safeTransferFrom from TransferHelper
         // first need to get the pair
@@ -961,22 +1030,11 @@ pub trait UniswapV2Router<Storage: ContractStorage>: ContractContext<Storage> {
         Self::_swap(&amounts, &path, self_addr);
 
         // call withdraw from WCSPR and transfer cspr to 'to'
-        let args: RuntimeArgs = runtime_args! {
-            "to_purse" => to,
-            "amount" => U512::from(amounts[amounts.len() - 1].as_u128())
-        };
-        let result: Result<(), u32> = Self::call_versioned_contract(
-            &wcspr.to_formatted_string(),
-            uniswapv2_contract_methods::WCSPR_WITHDRAW,
-            args,
+        Self::pay_cspr(
+            to,
+            amounts[amounts.len() - 1],
+            ErrorCodes::UniswapV2RouterTransferFailed18,
         );
-        if result.is_err()
-        // transfer_from failed
-        {
-            runtime::revert(ApiError::User(
-                ErrorCodes::UniswapV2RouterTransferFailed18 as u16,
-            ));
-        }
 
         amounts
     }
@@ -987,6 +1045,7 @@ pub trait UniswapV2Router<Storage: ContractStorage>: ContractContext<Storage> {
         amount_in_max: U256,
         _path: Vec<String>,
         to: Key,
+        max_price_impact_bps: Option<u32>,
         caller_purse: URef,
     ) -> Vec<U256> {
         let wcspr: ContractPackageHash = data::wcspr();
@@ -1015,6 +1074,7 @@ pub trait UniswapV2Router<Storage: ContractStorage>: ContractContext<Storage> {
         if amounts[0] > amount_in_max {
             runtime::revert(ApiError::User(ErrorCodes::UniswapV2RouterAbort13 as u16));
         }
+        Self::check_price_impact(&amounts, &path, max_price_impact_bps);
 
         let self_purse = system::create_purse(); // create new temporary purse and transfer cspr from caller purse to this
         let _: () = system::transfer_from_purse_to_purse(
@@ -1084,6 +1144,296 @@ pub trait UniswapV2Router<Storage: ContractStorage>: ContractContext<Storage> {
         amounts
     }
 
+    // Swaps `amount_in` of the first token of `path` around the cycle back into the same token, in
+    // one call so a later hop cannot leave the caller holding an intermediate token. Reverts
+    // unless the cycle returns more than `amount_in` and at least `min_profit` on top of it.
+    fn swap_cycle(
+        &mut self,
+        amount_in: U256,
+        min_profit: U256,
+        _path: Vec<String>,
+        to: Key,
+        max_price_impact_bps: Option<u32>,
+    ) -> Vec<U256> {
+        let mut path: Vec<Key> = Vec::new();
+        for i in 0..(_path.len()) {
+            path.push(Key::from_formatted_str(&_path[i]).unwrap());
+        }
+        if path.len() < 3 || path[0] != path[path.len() - 1] {
+            runtime::revert(ApiError::User(
+                ErrorCodes::UniswapV2RouterInvalidCycle as u16,
+            ));
+        }
+        let amounts: Vec<U256> = Self::get_amounts_out(amount_in, path.clone());
+
+        let amount_out: U256 = amounts[amounts.len() - 1];
+        if amount_out <= amount_in || amount_out - amount_in < min_profit {
+            runtime::revert(ApiError::User(
+                ErrorCodes::UniswapV2RouterInsufficientProfit as u16,
+            ));
+        }
+        Self::check_price_impact(&amounts, &path, max_price_impact_bps);
+
+        let pair: Key = Self::pair_for(path[0], path[1]);
+        let result: Result<(), u32> =
+            transfer_helper::safe_transfer_from(path[0], self.get_caller(), pair, amounts[0]);
+        if result.is_err()
+        // transfer_from failed
+        {
+            runtime::revert(ApiError::User(
+                ErrorCodes::UniswapV2RouterTransferFailed22 as u16,
+            ));
+        }
+
+        Self::_swap(&amounts, &path, to);
+        amounts
+    }
+
+    // Lends `amount` of `token` from its pair with `other_token` to the `callback` contract, which
+    // the pair calls back through the router with `data` and which has to pay the amount plus the
+    // fee back to the pair before the swap completes. Returns the fee. Pairs call back the callee
+    // they were deployed with, so only pairs deployed with the router as callee can lend this way.
+    fn flash_swap(
+        &mut self,
+        token: Key,
+        other_token: Key,
+        amount: U256,
+        callback: Key,
+        data: String,
+    ) -> U256 {
+        // pairs skip the callback when there is no data
+        if data.is_empty() || callback.into_hash().is_none() {
+            runtime::revert(ApiError::User(
+                ErrorCodes::UniswapV2RouterInvalidArguments as u16,
+            ));
+        }
+        let pair: Key = Self::pair_for(token, other_token);
+        let fee: U256 = math::flash_swap_fee(amount).unwrap_or_revert();
+
+        let (token0, _): (ContractPackageHash, ContractPackageHash) = Self::call_versioned_contract(
+            &data::library_hash().to_formatted_string(),
+            uniswapv2_contract_methods::LIBRARY_SORT_TOKENS,
+            runtime_args! {
+                "token_a" => token,
+                "token_b" => other_token
+            },
+        );
+        let (amount0_out, amount1_out): (U256, U256) = if token == Key::from(token0) {
+            (amount, 0.into())
+        } else {
+            (0.into(), amount)
+        };
+
+        data::set_flash_swap(Some((pair, callback, self.get_caller())));
+        let () = Self::call_versioned_contract(
+            &ContractPackageHash::from(pair.into_hash().unwrap_or_default()).to_formatted_string(),
+            uniswapv2_contract_methods::PAIR_SWAP,
+            runtime_args! {
+                "amount0_out" => amount0_out,
+                "amount1_out" => amount1_out,
+                "to" => callback,
+                "data" => data,
+            },
+        );
+        // a pair deployed with another callee never calls `uniswap_v2_call` of the router
+        if data::flash_swap().is_some() {
+            runtime::revert(ApiError::User(
+                ErrorCodes::UniswapV2RouterInvalidFlashSwap as u16,
+            ));
+        }
+        fee
+    }
+
+    // Called back by the pair of the flash swap in progress once it sent the loan to the callback
+    // contract. Hands the loan over to the callback contract and checks that the pair got the
+    // borrowed amount back with the fee.
+    fn uniswap_v2_call(&mut self, sender: Key, amount0: U256, amount1: U256, data: String) {
+        let invalid: ApiError = ApiError::User(ErrorCodes::UniswapV2RouterInvalidFlashSwap as u16);
+        let (pair, callback, initiator): (Key, Key, Key) = match data::flash_swap() {
+            Some(flash_swap) => flash_swap,
+            None => runtime::revert(invalid),
+        };
+        if self.get_caller() != pair || sender != Key::from(data::package_hash()) {
+            runtime::revert(invalid);
+        }
+        data::set_flash_swap(None);
+
+        let pair_hash: String =
+            ContractPackageHash::from(pair.into_hash().unwrap_or_default()).to_formatted_string();
+        // the pair updates its reserves only after the callback returns
+        let (reserve0, reserve1, _): (U128, U128, u64) = Self::call_versioned_contract(
+            &pair_hash,
+            uniswapv2_contract_methods::PAIR_GET_RESERVES,
+            runtime_args! {},
+        );
+        let (token_method, amount, reserve): (&str, U256, U128) = if amount1.is_zero() {
+            (uniswapv2_contract_methods::PAIR_TOKEN0, amount0, reserve0)
+        } else {
+            (uniswapv2_contract_methods::PAIR_TOKEN1, amount1, reserve1)
+        };
+        let token: Key = Self::call_versioned_contract(&pair_hash, token_method, runtime_args! {});
+        let fee: U256 = math::flash_swap_fee(amount).unwrap_or_revert();
+
+        let () = Self::call_versioned_contract(
+            &ContractPackageHash::from(callback.into_hash().unwrap_or_default())
+                .to_formatted_string(),
+            uniswapv2_contract_methods::FLASH_SWAP_CALL,
+            runtime_args! {
+                "initiator" => initiator,
+                "token" => token,
+                "amount" => amount,
+                "fee" => fee,
+                "pair" => pair,
+                "data" => data
+            },
+        );
+
+        let balance: U256 = Self::call_versioned_contract(
+            &ContractPackageHash::from(token.into_hash().unwrap_or_default()).to_formatted_string(),
+            uniswapv2_contract_methods::TOKEN_BALANCE_OF,
+            runtime_args! {
+                "owner" => pair
+            },
+        );
+        if balance < U256::from(reserve.as_u128()) + fee {
+            runtime::revert(ApiError::User(
+                ErrorCodes::UniswapV2RouterFlashSwapNotRepaid as u16,
+            ));
+        }
+    }
+
+    // Opens an order selling `amount_in` of the first token of `path` for at least
+    // `amount_out_min` of its last token. The router escrows the amount together with the `tip`
+    // the keeper executing the order earns, both in the first token. Returns the order id.
+    fn place_order(
+        &mut self,
+        path: Vec<Key>,
+        amount_in: U256,
+        amount_out_min: U256,
+        tip: U256,
+    ) -> u64 {
+        let invalid: ApiError = ApiError::User(ErrorCodes::UniswapV2RouterInvalidOrder as u16);
+        if path.len() < 2 || amount_in.is_zero() || amount_out_min.is_zero() {
+            runtime::revert(invalid);
+        }
+        let escrow: U256 = amount_in.checked_add(tip).unwrap_or_revert_with(invalid);
+        let owner: Key = self.get_caller();
+        let balance: U256 = Self::router_balance(path[0]);
+        let result: Result<(), u32> = transfer_helper::safe_transfer_from(
+            path[0],
+            owner,
+            Key::from(data::package_hash()),
+            escrow,
+        );
+        // the escrow is only accounted for as far as the router received it
+        if result.is_err() || Self::router_balance(path[0]).saturating_sub(balance) < escrow {
+            runtime::revert(ApiError::User(
+                ErrorCodes::UniswapV2RouterTransferFailed23 as u16,
+            ));
+        }
+        data::set_escrow(path[0], data::escrow(path[0]) + escrow);
+        data::add_order((owner, path, (amount_in, amount_out_min, tip)))
+    }
+
+    // Swaps the escrowed amount of the order `order_id` along its path to its owner once the
+    // pairs pay at least its limit, and pays the tip to the caller. Anyone can execute an order.
+    fn execute_order(&mut self, order_id: u64) -> Vec<U256> {
+        let (owner, path, (amount_in, amount_out_min, tip)): (Key, Vec<Key>, (U256, U256, U256)) =
+            match data::order(order_id) {
+                Some(order) => order,
+                None => runtime::revert(ApiError::User(
+                    ErrorCodes::UniswapV2RouterUnknownOrder as u16,
+                )),
+            };
+        let amounts: Vec<U256> = Self::get_amounts_out(amount_in, path.clone());
+        if amounts[amounts.len() - 1] < amount_out_min {
+            runtime::revert(ApiError::User(
+                ErrorCodes::UniswapV2RouterOrderLimitNotMet as u16,
+            ));
+        }
+        Self::check_escrows_migrated();
+        data::remove_order(order_id);
+        data::set_escrow(path[0], data::escrow(path[0]) - amount_in - tip);
+
+        let pair: Key = Self::pair_for(path[0], path[1]);
+        let result: Result<(), u32> = transfer_helper::safe_transfer(path[0], pair, amounts[0]);
+        if result.is_err()
+        // transfer failed
+        {
+            runtime::revert(ApiError::User(
+                ErrorCodes::UniswapV2RouterTransferFailed24 as u16,
+            ));
+        }
+        Self::_swap(&amounts, &path, owner);
+
+        if !tip.is_zero() {
+            let result: Result<(), u32> =
+                transfer_helper::safe_transfer(path[0], self.get_caller(), tip);
+            if result.is_err()
+            // transfer failed
+            {
+                runtime::revert(ApiError::User(
+                    ErrorCodes::UniswapV2RouterTransferFailed25 as u16,
+                ));
+            }
+        }
+        Self::check_escrow(path[0]);
+        amounts
+    }
+
+    // Closes the order `order_id` and pays the escrowed amount and tip back to its owner, which
+    // are returned as `[amount_in, tip]`. Only callable by the owner of the order.
+    fn cancel_order(&mut self, order_id: u64) -> Vec<U256> {
+        let (owner, path, (amount_in, _, tip)): (Key, Vec<Key>, (U256, U256, U256)) =
+            match data::order(order_id) {
+                Some(order) => order,
+                None => runtime::revert(ApiError::User(
+                    ErrorCodes::UniswapV2RouterUnknownOrder as u16,
+                )),
+            };
+        if self.get_caller() != owner {
+            runtime::revert(ApiError::User(
+                ErrorCodes::UniswapV2RouterNotOrderOwner as u16,
+            ));
+        }
+        Self::check_escrows_migrated();
+        data::remove_order(order_id);
+        data::set_escrow(path[0], data::escrow(path[0]) - amount_in - tip);
+
+        let result: Result<(), u32> =
+            transfer_helper::safe_transfer(path[0], owner, amount_in + tip);
+        if result.is_err()
+        // transfer failed
+        {
+            runtime::revert(ApiError::User(
+                ErrorCodes::UniswapV2RouterTransferFailed26 as u16,
+            ));
+        }
+        Self::check_escrow(path[0]);
+        vec![amount_in, tip]
+    }
+
+    // Accounts for the escrow of up to `limit` of the orders placed before the router tracked
+    // escrows, continuing where the last call stopped. Anyone can call it. Returns the number of
+    // those orders left.
+    fn migrate_escrows(&mut self, limit: u64) -> u64 {
+        let (next, end): (u64, u64) = data::escrow_migration();
+        let stop: u64 = end.min(next.saturating_add(limit));
+        for order_id in next..stop {
+            if let Some((_, path, (amount_in, _, tip))) = data::order(order_id) {
+                data::set_escrow(path[0], data::escrow(path[0]) + amount_in + tip);
+            }
+        }
+        data::set_escrow_migration((stop, end));
+        end - stop
+    }
+
+    // Owner, path and `(amount_in, amount_out_min, tip)` of the open order `order_id`, `None` once
+    // it was executed or cancelled.
+    fn get_order(order_id: u64) -> Option<(Key, Vec<Key>, (U256, U256, U256))> {
+        data::order(order_id)
+    }
+
     fn quote(amount_a: U256, reserve_a: U256, reserve_b: U256) -> U256 {
         let uniswapv2_library_contract_hash = data::library_hash().to_formatted_string();
         let args: RuntimeArgs = runtime_args! {
@@ -1168,12 +1518,310 @@ pub trait UniswapV2Router<Storage: ContractStorage>: ContractContext<Storage> {
         amounts_in
     }
 
+    fn get_amounts_out_batch(amounts_in: Vec<U256>, path: Vec<Key>) -> Vec<Vec<U256>> {
+        Self::call_versioned_contract(
+            &data::library_hash().to_formatted_string(),
+            uniswapv2_contract_methods::LIBRARY_GET_AMOUNTS_OUT_BATCH,
+            runtime_args! {
+                "factory" => Key::from(data::factory()),
+                "amounts_in" => amounts_in,
+                "path" => path
+            },
+        )
+    }
+
+    fn get_amounts_in_batch(amounts_out: Vec<U256>, path: Vec<Key>) -> Vec<Vec<U256>> {
+        Self::call_versioned_contract(
+            &data::library_hash().to_formatted_string(),
+            uniswapv2_contract_methods::LIBRARY_GET_AMOUNTS_IN_BATCH,
+            runtime_args! {
+                "factory" => Key::from(data::factory()),
+                "amounts_out" => amounts_out,
+                "path" => path
+            },
+        )
+    }
+
     fn get_package_hash(&mut self) -> ContractPackageHash {
         data::package_hash()
     }
 
+    // Keeps what `entry_point` returned so callers that cannot read return values can look it up.
+    fn record_result(&mut self, entry_point: &str, amounts: Vec<U256>) {
+        data::record_result(self.get_caller(), entry_point, amounts);
+    }
+
+    // Results of `owner`, newest first, together with their nonce.
+    fn latest_results(owner: Key, count: u32) -> Vec<(u64, String, Vec<U256>)> {
+        let nonce: u64 = data::result_nonce(owner);
+        (nonce.saturating_sub(u64::from(count))..nonce)
+            .rev()
+            .map(|nonce| {
+                let (entry_point, amounts): (String, Vec<U256>) =
+                    data::result(owner, nonce).unwrap_or_revert();
+                (nonce, entry_point, amounts)
+            })
+            .collect()
+    }
+
+    fn set_min_initial_liquidity(&mut self, min_initial_liquidity: U256) {
+        if self.get_caller() != data::owner() {
+            runtime::revert(ApiError::User(ErrorCodes::UniswapV2RouterNotOwner as u16));
+        }
+        data::set_min_initial_liquidity(min_initial_liquidity);
+    }
+
+    fn set_pair_allowed(&mut self, pair: Key, allowed: bool) {
+        if self.get_caller() != data::owner() {
+            runtime::revert(ApiError::User(ErrorCodes::UniswapV2RouterNotOwner as u16));
+        }
+        data::set_pair_allowed(pair, allowed);
+    }
+
+    fn set_pair_allowlist(&mut self, enabled: bool) {
+        if self.get_caller() != data::owner() {
+            runtime::revert(ApiError::User(ErrorCodes::UniswapV2RouterNotOwner as u16));
+        }
+        data::set_pair_allowlist(enabled);
+    }
+
+    // Records an observation of the pair of `token_a` and `token_b` for the current oracle period.
+    fn update_oracle(&mut self, token_a: Key, token_b: Key) {
+        let pair: Key = Self::call_versioned_contract(
+            &data::library_hash().to_formatted_string(),
+            uniswapv2_contract_methods::LIBRARY_PAIR_FOR,
+            runtime_args! {
+                "factory" => Key::from(data::factory()),
+                "token_a" => token_a,
+                "token_b" => token_b
+            },
+        );
+        Self::record_observation(pair);
+    }
+
+    // Amount of `token_out` that `amount_in` of `token_in` is worth at the average price of their
+    // pair since the observation taken in the oracle period `window` before now.
+    fn consult(token_in: Key, amount_in: U256, token_out: Key, window: u64) -> U256 {
+        Self::check_oracle_window(window);
+        let pair: Key = Self::call_versioned_contract(
+            &data::library_hash().to_formatted_string(),
+            uniswapv2_contract_methods::LIBRARY_PAIR_FOR,
+            runtime_args! {
+                "factory" => Key::from(data::factory()),
+                "token_a" => token_in,
+                "token_b" => token_out
+            },
+        );
+        let observation: (u64, U256, U256) = Self::window_observation(pair, window)
+            .unwrap_or_revert_with(ApiError::User(
+                ErrorCodes::UniswapV2RouterMissingObservation as u16,
+            ));
+        Self::average_amount_out(pair, token_in, amount_in, token_out, observation)
+    }
+
+    // Amount of `token_out` that `amount_in` of `token_in` is worth at the average price of their
+    // pair since `observation`.
+    fn average_amount_out(
+        pair: Key,
+        token_in: Key,
+        amount_in: U256,
+        token_out: Key,
+        observation: (u64, U256, U256),
+    ) -> U256 {
+        let (observed_at, price0_start, price1_start): (u64, U256, U256) = observation;
+        let timestamp: u64 = runtime::get_blocktime().into();
+        let (price0_end, price1_end): (U256, U256) =
+            Self::current_cumulative_prices(pair, timestamp);
+
+        let (token0, _): (ContractPackageHash, ContractPackageHash) = Self::call_versioned_contract(
+            &data::library_hash().to_formatted_string(),
+            uniswapv2_contract_methods::LIBRARY_SORT_TOKENS,
+            runtime_args! {
+                "token_a" => token_in,
+                "token_b" => token_out
+            },
+        );
+        let (price_start, price_end): (U256, U256) = if token_in == Key::from(token0) {
+            (price0_start, price0_end)
+        } else {
+            (price1_start, price1_end)
+        };
+        oracle::compute_amount_out(price_start, price_end, timestamp - observed_at, amount_in)
+            .unwrap_or_revert()
+    }
+
+    // Guards swaps through the pair of `token_a` and `token_b` against spot prices more than
+    // `max_deviation_bps` away from the pair's average price over `window`, or lifts the guard.
+    fn set_oracle_guard(
+        &mut self,
+        token_a: Key,
+        token_b: Key,
+        max_deviation_bps: Option<u32>,
+        window: u64,
+    ) {
+        if self.get_caller() != data::owner() {
+            runtime::revert(ApiError::User(ErrorCodes::UniswapV2RouterNotOwner as u16));
+        }
+        if max_deviation_bps.is_some() {
+            Self::check_oracle_window(window);
+        }
+        let pair: Key = Self::call_versioned_contract(
+            &data::library_hash().to_formatted_string(),
+            uniswapv2_contract_methods::LIBRARY_PAIR_FOR,
+            runtime_args! {
+                "factory" => Key::from(data::factory()),
+                "token_a" => token_a,
+                "token_b" => token_b
+            },
+        );
+        data::set_oracle_guard(
+            pair,
+            max_deviation_bps.map(|max_deviation_bps| (max_deviation_bps, window)),
+        );
+    }
+
+    // Amounts of `token_a` and `token_b` that `add_liquidity` would take for the desired amounts,
+    // together with the LP tokens the pair would mint for them. A pair that does not exist yet is
+    // quoted as an empty one.
+    fn quote_add_liquidity(
+        token_a: Key,
+        token_b: Key,
+        amount_a_desired: U256,
+        amount_b_desired: U256,
+    ) -> (U256, U256, U256) {
+        let pair: Key = Self::call_versioned_contract(
+            &data::factory().to_formatted_string(),
+            uniswapv2_contract_methods::FACTORY_GET_PAIR,
+            runtime_args! {
+                "token0" => token_a,
+                "token1" => token_b
+            },
+        );
+        let zero_addr: Key = Key::from_formatted_str(
+            "hash-0000000000000000000000000000000000000000000000000000000000000000",
+        )
+        .unwrap();
+        let (reserve_a, reserve_b, total_supply): (U128, U128, U256) = if pair == zero_addr {
+            (0.into(), 0.into(), 0.into())
+        } else {
+            let (reserve_a, reserve_b) = Self::get_reserves(token_a, token_b);
+            (reserve_a, reserve_b, Self::pair_total_supply(pair))
+        };
+
+        let (amount_a, amount_b): (U256, U256) = if reserve_a.is_zero() && reserve_b.is_zero() {
+            (amount_a_desired, amount_b_desired)
+        } else {
+            let reserve_a: U256 = U256::from(reserve_a.as_u128());
+            let reserve_b: U256 = U256::from(reserve_b.as_u128());
+            let amount_b_optimal: U256 = Self::quote(amount_a_desired, reserve_a, reserve_b);
+            if amount_b_optimal <= amount_b_desired {
+                (amount_a_desired, amount_b_optimal)
+            } else {
+                (
+                    Self::quote(amount_b_desired, reserve_b, reserve_a),
+                    amount_b_desired,
+                )
+            }
+        };
+        let liquidity: U256 =
+            math::liquidity_minted(amount_a, amount_b, reserve_a, reserve_b, total_supply)
+                .unwrap_or_revert();
+        (amount_a, amount_b, liquidity)
+    }
+
+    // Amounts of `token_a` and `token_b` that burning `liquidity` of their pair would pay out at
+    // its current reserves.
+    fn quote_remove_liquidity(token_a: Key, token_b: Key, liquidity: U256) -> (U256, U256) {
+        let pair: Key = Self::pair_for(token_a, token_b);
+        let (reserve_a, reserve_b): (U128, U128) = Self::get_reserves(token_a, token_b);
+        math::liquidity_amounts(
+            liquidity,
+            reserve_a,
+            reserve_b,
+            Self::pair_total_supply(pair),
+        )
+        .unwrap_or_revert()
+    }
+
+    // Reserves of the pair of `token_a` and `token_b`, in the order of the tokens passed.
+    fn get_reserves(token_a: Key, token_b: Key) -> (U128, U128) {
+        // the library would ask the zero hash for its reserves
+        Self::pair_for(token_a, token_b);
+        Self::call_versioned_contract(
+            &data::library_hash().to_formatted_string(),
+            uniswapv2_contract_methods::LIBRARY_GET_RESERVES,
+            runtime_args! {
+                "factory" => Key::from(data::factory()),
+                "token_a" => token_a,
+                "token_b" => token_b
+            },
+        )
+    }
+
+    // Pair of `token_a` and `token_b` registered with the factory, reverting if there is none.
+    fn pair_for(token_a: Key, token_b: Key) -> Key {
+        let pair: Key = Self::call_versioned_contract(
+            &data::library_hash().to_formatted_string(),
+            uniswapv2_contract_methods::LIBRARY_PAIR_FOR,
+            runtime_args! {
+                "factory" => Key::from(data::factory()),
+                "token_a" => token_a,
+                "token_b" => token_b
+            },
+        );
+        let zero_addr: Key = Key::from_formatted_str(
+            "hash-0000000000000000000000000000000000000000000000000000000000000000",
+        )
+        .unwrap();
+        if pair == zero_addr {
+            runtime::revert(ApiError::User(ErrorCodes::UniswapV2RouterZeroAddr as u16));
+        }
+        pair
+    }
+
+    // Everything a client needs to price the pair of `token_a` and `token_b`, as
+    // `((pair, total_supply), (token0, token1), (reserve0, reserve1, block_timestamp_last))` with
+    // the reserves in the order of the sorted tokens.
+    fn get_pair_info(token_a: Key, token_b: Key) -> ((Key, U256), (Key, Key), (U128, U128, u64)) {
+        let pair: Key = Self::pair_for(token_a, token_b);
+        let (token0, token1): (ContractPackageHash, ContractPackageHash) =
+            Self::call_versioned_contract(
+                &data::library_hash().to_formatted_string(),
+                uniswapv2_contract_methods::LIBRARY_SORT_TOKENS,
+                runtime_args! {
+                    "token_a" => token_a,
+                    "token_b" => token_b
+                },
+            );
+        let reserves: (U128, U128, u64) = Self::call_versioned_contract(
+            &ContractPackageHash::from(pair.into_hash().unwrap_or_default()).to_formatted_string(),
+            uniswapv2_contract_methods::PAIR_GET_RESERVES,
+            runtime_args! {},
+        );
+        (
+            (pair, Self::pair_total_supply(pair)),
+            (Key::from(token0), Key::from(token1)),
+            reserves,
+        )
+    }
+
     // *************************************** Helper methods ****************************************
 
+    // The first deposit into a pair sets its price and the value of an LP share, so it has to mint
+    // at least the configured minimum on top of the caller's own `min_liquidity_out`.
+    fn check_liquidity(liquidity: U256, initial: bool, min_liquidity_out: Option<U256>) {
+        if liquidity < min_liquidity_out.unwrap_or_default() {
+            runtime::revert(ApiError::User(
+                ErrorCodes::UniswapV2RouterInsufficientLiquidityMinted as u16,
+            ));
+        }
+        if initial && liquidity < data::min_initial_liquidity() {
+            runtime::revert(ApiError::User(
+                ErrorCodes::UniswapV2RouterInsufficientInitialLiquidity as u16,
+            ));
+        }
+    }
+
     fn _add_liquidity(
         token_a: ContractPackageHash,
         token_b: ContractPackageHash,
@@ -1182,7 +1830,7 @@ pub trait UniswapV2Router<Storage: ContractStorage>: ContractContext<Storage> {
         amount_a_min: U256,
         amount_b_min: U256,
         pair_received: Option<Key>,
-    ) -> (U256, U256) {
+    ) -> (U256, U256, bool) {
         let factory: ContractPackageHash = data::factory();
         let args: RuntimeArgs = runtime_args! {
             "token0" => Key::from(token_a),
@@ -1229,6 +1877,7 @@ pub trait UniswapV2Router<Storage: ContractStorage>: ContractContext<Storage> {
                 uniswapv2_contract_methods::FACTORY_CREATE_PAIR, // this create_pair method DOES NOT create a new pair, instead it initializes the pair passed in
                 args,
             );
+            Self::verify_pair(pair, token_a, token_b);
         }
 
         let uniswapv2_library_contract_hash = data::library_hash().to_formatted_string();
@@ -1244,7 +1893,7 @@ pub trait UniswapV2Router<Storage: ContractStorage>: ContractContext<Storage> {
         );
 
         if reserve_a == 0.into() && reserve_b == 0.into() {
-            return (amount_a_desired, amount_b_desired);
+            return (amount_a_desired, amount_b_desired, true);
         } else {
             let args: RuntimeArgs = runtime_args! {
                 "amount_a" => amount_a_desired,
@@ -1259,7 +1908,7 @@ pub trait UniswapV2Router<Storage: ContractStorage>: ContractContext<Storage> {
             );
 
             if amount_b_optimal <= amount_b_desired && amount_b_optimal >= amount_b_min {
-                return (amount_a_desired, amount_b_optimal);
+                return (amount_a_desired, amount_b_optimal, false);
             } else {
                 let args: RuntimeArgs = runtime_args! {
                     "amount_a" => amount_b_desired,
@@ -1279,9 +1928,9 @@ pub trait UniswapV2Router<Storage: ContractStorage>: ContractContext<Storage> {
                 }
 
                 if amount_a_optimal >= amount_a_min {
-                    return (amount_a_optimal, amount_b_desired);
+                    return (amount_a_optimal, amount_b_desired, false);
                 } else {
-                    return (0.into(), 0.into());
+                    return (0.into(), 0.into(), false);
                 }
             }
         }
@@ -1341,6 +1990,7 @@ pub trait UniswapV2Router<Storage: ContractStorage>: ContractContext<Storage> {
                 uniswapv2_contract_methods::LIBRARY_PAIR_FOR,
                 args,
             );
+            Self::check_oracle_guard(pair, input, output);
             let pair: ContractPackageHash =
                 ContractPackageHash::from(pair.into_hash().unwrap_or_default()); // convert key into ContractPackageHash
 
@@ -1359,6 +2009,288 @@ pub trait UniswapV2Router<Storage: ContractStorage>: ContractContext<Storage> {
         }
     }
 
+    // Compares the price each hop of `amounts` executes at with the mid-price of the hop's pair
+    // before the trade, and reverts when it is worse by more than `max_price_impact_bps`. The 0.3%
+    // pair fee counts towards the impact.
+    fn check_price_impact(amounts: &Vec<U256>, path: &Vec<Key>, max_price_impact_bps: Option<u32>) {
+        let max_price_impact_bps: U512 = match max_price_impact_bps {
+            Some(max_price_impact_bps) => U512::from(max_price_impact_bps),
+            None => return,
+        };
+        let bps: U512 = U512::from(BPS);
+        let factory: ContractPackageHash = data::factory();
+        let uniswapv2_library_contract_hash = data::library_hash().to_formatted_string();
+        for i in 0..(path.len() - 1) {
+            let args: RuntimeArgs = runtime_args! {
+                "factory" => Key::from(factory),
+                "token_a" => path[i],
+                "token_b" => path[i + 1],
+            };
+            let (reserve_in, reserve_out): (U128, U128) = Self::call_versioned_contract(
+                &uniswapv2_library_contract_hash,
+                uniswapv2_contract_methods::LIBRARY_GET_RESERVES,
+                args,
+            );
+            // amount_out / amount_in >= (reserve_out / reserve_in) * (1 - max_price_impact_bps / BPS)
+            let execution: U512 = u512(amounts[i + 1]) * U512::from(reserve_in.as_u128()) * bps;
+            let limit: U512 = u512(amounts[i])
+                * U512::from(reserve_out.as_u128())
+                * bps.saturating_sub(max_price_impact_bps);
+            if execution < limit {
+                runtime::revert(ApiError::User(
+                    ErrorCodes::UniswapV2RouterExcessivePriceImpact as u16,
+                ));
+            }
+        }
+    }
+
+    // The window of an average has to start in one of the oracle periods the ring still holds,
+    // and in an earlier period than the current one.
+    fn check_oracle_window(window: u64) {
+        if window < ORACLE_PERIOD || window > (ORACLE_OBSERVATIONS - 1) * ORACLE_PERIOD {
+            runtime::revert(ApiError::User(
+                ErrorCodes::UniswapV2RouterInvalidOracleWindow as u16,
+            ));
+        }
+    }
+
+    // Reverts when the spot price of a guarded pair is further from its time-weighted price than
+    // the guard allows, or when the oracle holds no observation at least `window` old to average
+    // from, as right after installing the guard.
+    fn check_oracle_guard(pair: Key, input: Key, output: Key) {
+        let (max_deviation_bps, window): (u32, u64) = match data::oracle_guard(pair) {
+            Some(guard) => guard,
+            None => return,
+        };
+        Self::record_observation(pair);
+        let observation: (u64, U256, U256) = Self::guard_observation(pair, window)
+            .unwrap_or_revert_with(ApiError::User(
+                ErrorCodes::UniswapV2RouterMissingObservation as u16,
+            ));
+        let (reserve_in, reserve_out): (U128, U128) = Self::call_versioned_contract(
+            &data::library_hash().to_formatted_string(),
+            uniswapv2_contract_methods::LIBRARY_GET_RESERVES,
+            runtime_args! {
+                "factory" => Key::from(data::factory()),
+                "token_a" => input,
+                "token_b" => output
+            },
+        );
+        // at the spot price reserve_in is worth reserve_out
+        let spot: U256 = U256::from(reserve_out.as_u128());
+        let average: U256 = Self::average_amount_out(
+            pair,
+            input,
+            U256::from(reserve_in.as_u128()),
+            output,
+            observation,
+        );
+        let deviation: U256 = if spot > average {
+            spot - average
+        } else {
+            average - spot
+        };
+        if u512(deviation) * U512::from(BPS) > u512(average) * U512::from(max_deviation_bps) {
+            runtime::revert(ApiError::User(
+                ErrorCodes::UniswapV2RouterPriceDeviation as u16,
+            ));
+        }
+    }
+
+    // Stores the cumulative prices of `pair` in the ring slot of the current oracle period, unless
+    // the slot already holds an observation from this period.
+    fn record_observation(pair: Key) {
+        let timestamp: u64 = runtime::get_blocktime().into();
+        let period: u64 = timestamp / ORACLE_PERIOD;
+        let slot: u64 = period % ORACLE_OBSERVATIONS;
+        if let Some((observed_at, _, _)) = data::observation(pair, slot) {
+            if observed_at / ORACLE_PERIOD == period {
+                return;
+            }
+        }
+        let (price0_cumulative, price1_cumulative): (U256, U256) =
+            Self::current_cumulative_prices(pair, timestamp);
+        data::set_observation(
+            pair,
+            slot,
+            (timestamp, price0_cumulative, price1_cumulative),
+        );
+    }
+
+    // Observation of `pair` taken in the oracle period `window` before now, if the ring holds one.
+    // The slot may be empty or still hold an observation from an earlier lap of the ring.
+    fn window_observation(pair: Key, window: u64) -> Option<(u64, U256, U256)> {
+        let timestamp: u64 = runtime::get_blocktime().into();
+        let period: u64 = timestamp.saturating_sub(window) / ORACLE_PERIOD;
+        data::observation(pair, period % ORACLE_OBSERVATIONS)
+            .filter(|observation| observation.0 / ORACLE_PERIOD == period)
+    }
+
+    // Newest observation of `pair` taken at least `window` before now, if the ring holds one.
+    // Walks back one period at a time from the one the window starts in, so a quiet pair is
+    // averaged over a longer stretch instead of going unguarded.
+    fn guard_observation(pair: Key, window: u64) -> Option<(u64, U256, U256)> {
+        let timestamp: u64 = runtime::get_blocktime().into();
+        let start: u64 = timestamp.saturating_sub(window);
+        let period: u64 = start / ORACLE_PERIOD;
+        (0..ORACLE_OBSERVATIONS.min(period + 1))
+            .map(|lap| period - lap)
+            .find_map(|period| {
+                data::observation(pair, period % ORACLE_OBSERVATIONS).filter(|observation| {
+                    observation.0 / ORACLE_PERIOD == period && observation.0 <= start
+                })
+            })
+    }
+
+    fn pair_total_supply(pair: Key) -> U256 {
+        Self::call_versioned_contract(
+            &ContractPackageHash::from(pair.into_hash().unwrap_or_default()).to_formatted_string(),
+            uniswapv2_contract_methods::PAIR_TOTAL_SUPPLY,
+            runtime_args! {},
+        )
+    }
+
+    // Cumulative prices of `pair` at `timestamp`, read from the pair and brought forward from its
+    // last update with its current reserves.
+    fn current_cumulative_prices(pair: Key, timestamp: u64) -> (U256, U256) {
+        let pair: String =
+            ContractPackageHash::from(pair.into_hash().unwrap_or_default()).to_formatted_string();
+        let (reserve0, reserve1, block_timestamp_last): (U128, U128, u64) =
+            Self::call_versioned_contract(
+                &pair,
+                uniswapv2_contract_methods::PAIR_GET_RESERVES,
+                runtime_args! {},
+            );
+        let price0_cumulative_last: U256 = Self::call_versioned_contract(
+            &pair,
+            uniswapv2_contract_methods::PAIR_PRICE0_CUMULATIVE_LAST,
+            runtime_args! {},
+        );
+        let price1_cumulative_last: U256 = Self::call_versioned_contract(
+            &pair,
+            uniswapv2_contract_methods::PAIR_PRICE1_CUMULATIVE_LAST,
+            runtime_args! {},
+        );
+        oracle::current_cumulative_prices(
+            reserve0,
+            reserve1,
+            block_timestamp_last,
+            price0_cumulative_last,
+            price1_cumulative_last,
+            timestamp,
+        )
+    }
+
+    // A caller-supplied pair is only trusted if it reports the router's factory as its own and the
+    // two tokens in sorted order. Contracts cannot read another contract's wasm, so with the
+    // allowlist on the owner also has to have vetted the code of the pair and allowed it.
+    fn verify_pair(pair: Key, token_a: ContractPackageHash, token_b: ContractPackageHash) {
+        let untrusted: ApiError = ApiError::User(ErrorCodes::UniswapV2RouterUntrustedPair as u16);
+        let pair_hash: ContractPackageHash = match pair {
+            Key::Hash(hash) => ContractPackageHash::new(hash),
+            _ => runtime::revert(untrusted),
+        };
+        if data::pair_allowlist() && !data::pair_allowed(pair) {
+            runtime::revert(untrusted);
+        }
+        let pair_factory: Key = Self::call_versioned_contract(
+            &pair_hash.to_formatted_string(),
+            uniswapv2_contract_methods::PAIR_FACTORY,
+            runtime_args! {},
+        );
+        if pair_factory != Key::from(data::factory()) {
+            runtime::revert(untrusted);
+        }
+
+        let (token0, token1): (ContractPackageHash, ContractPackageHash) =
+            Self::call_versioned_contract(
+                &data::library_hash().to_formatted_string(),
+                uniswapv2_contract_methods::LIBRARY_SORT_TOKENS,
+                runtime_args! {
+                    "token_a" => Key::from(token_a),
+                    "token_b" => Key::from(token_b)
+                },
+            );
+        let pair_token0: Key = Self::call_versioned_contract(
+            &pair_hash.to_formatted_string(),
+            uniswapv2_contract_methods::PAIR_TOKEN0,
+            runtime_args! {},
+        );
+        let pair_token1: Key = Self::call_versioned_contract(
+            &pair_hash.to_formatted_string(),
+            uniswapv2_contract_methods::PAIR_TOKEN1,
+            runtime_args! {},
+        );
+        if pair_token0 != Key::from(token0) || pair_token1 != Key::from(token1) {
+            runtime::revert(untrusted);
+        }
+    }
+
+    // Withdraws `amount` of the router's WCSPR as cspr into the `to` purse, or into a temporary
+    // purse that is then paid to the main purse of the `to` account.
+    fn pay_cspr(to: Key, amount: U256, error: ErrorCodes) {
+        let purse: URef = match to {
+            Key::URef(purse) => purse,
+            Key::Account(_) => system::create_purse(),
+            _ => runtime::revert(ApiError::User(
+                ErrorCodes::UniswapV2RouterInvalidCsprRecipient as u16,
+            )),
+        };
+        let amount: U512 = U512::from(amount.as_u128());
+        let args: RuntimeArgs = runtime_args! {
+            "to_purse" => purse,
+            "amount" => amount
+        };
+        let result: Result<(), u32> = Self::call_versioned_contract(
+            &data::wcspr().to_formatted_string(),
+            uniswapv2_contract_methods::WCSPR_WITHDRAW,
+            args,
+        );
+        if result.is_err()
+        // wcspr_withdraw failed
+        {
+            runtime::revert(ApiError::User(error as u16));
+        }
+        if let Key::Account(account) = to {
+            system::transfer_from_purse_to_account(purse, account, amount, None).unwrap_or_revert();
+        }
+        Self::check_escrow(Key::from(data::wcspr()));
+    }
+
+    // Balance of `token` the router holds itself.
+    fn router_balance(token: Key) -> U256 {
+        Self::call_versioned_contract(
+            &ContractPackageHash::from(token.into_hash().unwrap_or_default()).to_formatted_string(),
+            uniswapv2_contract_methods::TOKEN_BALANCE_OF,
+            runtime_args! {
+                "owner" => Key::from(data::package_hash())
+            },
+        )
+    }
+
+    // The router holds the escrow of open orders but pays out the amounts pairs report, which a
+    // pair it cannot vouch for may inflate. Reverts unless the router still holds the escrow of
+    // `token` after paying it out.
+    fn check_escrow(token: Key) {
+        Self::check_escrows_migrated();
+        if Self::router_balance(token) < data::escrow(token) {
+            runtime::revert(ApiError::User(
+                ErrorCodes::UniswapV2RouterEscrowShortfall as u16,
+            ));
+        }
+    }
+
+    // Escrows miss the orders `migrate_escrows` has not gone through yet, so nothing relying on
+    // them may run before it is done.
+    fn check_escrows_migrated() {
+        let (next, end): (u64, u64) = data::escrow_migration();
+        if next < end {
+            runtime::revert(ApiError::User(
+                ErrorCodes::UniswapV2RouterEscrowsNotMigrated as u16,
+            ));
+        }
+    }
+
     fn ensure(&self, deadline: U256) -> bool {
         // shadowing the variable
         let deadline = BlockTime::new(deadline.as_u64());
@@ -1411,6 +2343,7 @@ pub trait UniswapV2Router<Storage: ContractStorage>: ContractContext<Storage> {
             }
         };
         for event in events {
+            data::record_event(event.clone());
             let _: URef = storage::new_uref(event);
         }
     }
@@ -0,0 +1,53 @@
+//! Router calls wrapped in the approve-and-swap session wasm.
+//!
+//! The session approves the router for the tokens the call may pull, makes the call and resets the
+//! allowances to zero, so a token flow needs a single deploy. The returned amounts are stored
+//! under [`result_key`](crate::purse_proxy::result_key) in the account's named keys, like the
+//! purse-proxy does.
+
+use casper_execution_engine::core::engine_state::ExecutableDeployItem;
+use casper_types::{bytesrepr::Bytes, ContractPackageHash, Key};
+
+use crate::RouterCall;
+
+/// The approve-and-swap wasm bound to a router package.
+#[derive(Clone, Debug)]
+pub struct ApproveAndSwap {
+    pub module_bytes: Bytes,
+    pub router: ContractPackageHash,
+}
+
+impl ApproveAndSwap {
+    pub fn new<B: Into<Bytes>>(module_bytes: B, router: ContractPackageHash) -> ApproveAndSwap {
+        ApproveAndSwap {
+            module_bytes: module_bytes.into(),
+            router,
+        }
+    }
+
+    /// Session item for an `add_liquidity` call or a token swap.
+    pub fn session<C: RouterCall + ?Sized>(&self, call: &C) -> ExecutableDeployItem {
+        let mut args = call.runtime_args();
+        args.insert("destination_entrypoint", call.entry_point())
+            .expect("destination_entrypoint is a string");
+        args.insert("router_hash", Key::from(self.router))
+            .expect("router_hash is a key");
+        ExecutableDeployItem::ModuleBytes {
+            module_bytes: self.module_bytes.clone(),
+            args,
+        }
+    }
+
+    /// Session item for a `remove_liquidity` call, which burns liquidity tokens of `pair`.
+    pub fn remove_liquidity_session<C: RouterCall + ?Sized>(
+        &self,
+        call: &C,
+        pair: ContractPackageHash,
+    ) -> ExecutableDeployItem {
+        let mut session = self.session(call);
+        if let ExecutableDeployItem::ModuleBytes { args, .. } = &mut session {
+            args.insert("pair", Key::from(pair)).expect("pair is a key");
+        }
+        session
+    }
+}
@@ -0,0 +1,66 @@
+use casper_execution_engine::core::engine_state::ExecutableDeployItem;
+use casper_node::types::{Deploy, TimeDiff, Timestamp};
+use casper_types::{bytesrepr::Bytes, runtime_args, RuntimeArgs, SecretKey, U512};
+
+/// Default deploy time to live, 30 minutes.
+pub const DEFAULT_TTL_MILLIS: u64 = 30 * 60 * 1000;
+pub const DEFAULT_GAS_PRICE: u64 = 1;
+
+/// Wraps a session item in a deploy paid with the standard payment and signs it offline.
+#[derive(Clone, Debug)]
+pub struct DeployBuilder {
+    chain_name: String,
+    payment_amount: U512,
+    ttl: TimeDiff,
+    gas_price: u64,
+    timestamp: Option<Timestamp>,
+}
+
+impl DeployBuilder {
+    pub fn new<S: Into<String>>(chain_name: S, payment_amount: U512) -> DeployBuilder {
+        DeployBuilder {
+            chain_name: chain_name.into(),
+            payment_amount,
+            ttl: TimeDiff::from(DEFAULT_TTL_MILLIS),
+            gas_price: DEFAULT_GAS_PRICE,
+            timestamp: None,
+        }
+    }
+
+    pub fn with_ttl(mut self, ttl: TimeDiff) -> DeployBuilder {
+        self.ttl = ttl;
+        self
+    }
+
+    pub fn with_gas_price(mut self, gas_price: u64) -> DeployBuilder {
+        self.gas_price = gas_price;
+        self
+    }
+
+    /// Pin the deploy timestamp, the current time is used otherwise.
+    pub fn with_timestamp(mut self, timestamp: Timestamp) -> DeployBuilder {
+        self.timestamp = Some(timestamp);
+        self
+    }
+
+    pub fn payment(&self) -> ExecutableDeployItem {
+        ExecutableDeployItem::ModuleBytes {
+            module_bytes: Bytes::new(),
+            args: runtime_args! { "amount" => self.payment_amount },
+        }
+    }
+
+    pub fn build(&self, session: ExecutableDeployItem, secret_key: &SecretKey) -> Deploy {
+        Deploy::new(
+            self.timestamp.unwrap_or_else(Timestamp::now),
+            self.ttl,
+            self.gas_price,
+            vec![],
+            self.chain_name.clone(),
+            self.payment(),
+            session,
+            secret_key,
+            None,
+        )
+    }
+}
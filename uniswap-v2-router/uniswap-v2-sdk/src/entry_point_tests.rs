@@ -0,0 +1,514 @@
+use std::collections::BTreeMap;
+
+use casper_execution_engine::core::engine_state::ExecutableDeployItem;
+use casper_node::types::{Deploy, Timestamp};
+use casper_types::{
+    AccessRights, CLType, ContractPackageHash, EntryPointAccess, EntryPoints, Key, RuntimeArgs,
+    SecretKey, URef, U256, U512,
+};
+use uniswap_v2_router::entry_points::get_entry_points;
+
+use crate::approve_and_swap::ApproveAndSwap;
+use crate::purse_proxy::{self, PurseProxy, PurseProxyCall};
+use crate::router::*;
+use crate::DeployBuilder;
+
+fn key(byte: u8) -> Key {
+    Key::Hash([byte; 32])
+}
+
+fn purse() -> URef {
+    URef::new([9; 32], AccessRights::READ_ADD_WRITE)
+}
+
+fn router_calls() -> Vec<Box<dyn RouterCall>> {
+    let amount = U256::from(1000);
+    let deadline = U256::from(1);
+    let path = vec![key(1), key(2), key(3)];
+    let mut calls: Vec<Box<dyn RouterCall>> = Vec::new();
+    for js_client in [false, true] {
+        calls.push(Box::new(AddLiquidity {
+            token_a: key(1),
+            token_b: key(2),
+            amount_a_desired: amount,
+            amount_b_desired: amount,
+            amount_a_min: amount,
+            amount_b_min: amount,
+            to: key(4),
+            deadline,
+            pair: Some(key(5)),
+            min_liquidity_out: Some(amount),
+            js_client,
+        }));
+        calls.push(Box::new(AddLiquidityCspr {
+            token: key(1),
+            amount_token_desired: amount,
+            amount_cspr_desired: amount,
+            amount_token_min: amount,
+            amount_cspr_min: amount,
+            to: key(4),
+            deadline,
+            pair: None,
+            min_liquidity_out: None,
+            purse: purse(),
+            js_client,
+        }));
+        calls.push(Box::new(RemoveLiquidity {
+            token_a: key(1),
+            token_b: key(2),
+            liquidity: amount,
+            amount_a_min: amount,
+            amount_b_min: amount,
+            to: key(4),
+            deadline,
+            js_client,
+        }));
+        calls.push(Box::new(RemoveLiquidityCspr {
+            token: key(1),
+            liquidity: amount,
+            amount_token_min: amount,
+            amount_cspr_min: amount,
+            to: key(4),
+            deadline,
+            to_purse: purse(),
+            js_client,
+        }));
+        calls.push(Box::new(RemoveLiquidityWithPermit {
+            token_a: key(1),
+            token_b: key(2),
+            liquidity: amount,
+            amount_a_min: amount,
+            amount_b_min: amount,
+            to: key(4),
+            deadline,
+            approve_max: false,
+            public_key: "1, 2".to_string(),
+            signature: "3, 4".to_string(),
+            js_client,
+        }));
+        calls.push(Box::new(RemoveLiquidityCsprWithPermit {
+            token: key(1),
+            liquidity: amount,
+            amount_token_min: amount,
+            amount_cspr_min: amount,
+            to: key(4),
+            deadline,
+            approve_max: true,
+            public_key: "1, 2".to_string(),
+            signature: "3, 4".to_string(),
+            to_purse: purse(),
+            js_client,
+        }));
+        calls.push(Box::new(SwapExactTokensForTokens {
+            amount_in: amount,
+            amount_out_min: amount,
+            path: path.clone(),
+            to: key(4),
+            deadline,
+            max_price_impact_bps: Some(100),
+            js_client,
+        }));
+        calls.push(Box::new(SwapTokensForExactTokens {
+            amount_out: amount,
+            amount_in_max: amount,
+            path: path.clone(),
+            to: key(4),
+            deadline,
+            max_price_impact_bps: None,
+            js_client,
+        }));
+        calls.push(Box::new(SwapExactCsprForTokens {
+            amount_out_min: amount,
+            amount_in: amount,
+            path: path.clone(),
+            to: key(4),
+            deadline,
+            max_price_impact_bps: Some(100),
+            purse: purse(),
+            js_client,
+        }));
+        calls.push(Box::new(SwapTokensForExactCspr {
+            amount_out: amount,
+            amount_in_max: amount,
+            path: path.clone(),
+            to: purse(),
+            deadline,
+            max_price_impact_bps: None,
+            js_client,
+        }));
+        calls.push(Box::new(SwapExactTokensForCspr {
+            amount_in: amount,
+            amount_out_min: amount,
+            path: path.clone(),
+            to: purse(),
+            deadline,
+            max_price_impact_bps: Some(100),
+            js_client,
+        }));
+        calls.push(Box::new(SwapCsprForExactTokens {
+            amount_out: amount,
+            amount_in_max: amount,
+            path: path.clone(),
+            to: key(4),
+            deadline,
+            max_price_impact_bps: None,
+            purse: purse(),
+            js_client,
+        }));
+    }
+    calls.push(Box::new(RemoveLiquidityCsprToKey {
+        token: key(1),
+        liquidity: amount,
+        amount_token_min: amount,
+        amount_cspr_min: amount,
+        to: key(4),
+        deadline,
+    }));
+    calls.push(Box::new(SwapTokensForExactCsprToKey {
+        amount_out: amount,
+        amount_in_max: amount,
+        path: path.clone(),
+        to: key(4),
+        deadline,
+        max_price_impact_bps: Some(100),
+    }));
+    calls.push(Box::new(SwapExactTokensForCsprToKey {
+        amount_in: amount,
+        amount_out_min: amount,
+        path: path.clone(),
+        to: key(4),
+        deadline,
+        max_price_impact_bps: None,
+    }));
+    calls.push(Box::new(Quote {
+        amount_a: amount,
+        reserve_a: amount,
+        reserve_b: amount,
+    }));
+    calls.push(Box::new(GetAmountOut {
+        amount_in: amount,
+        reserve_in: amount,
+        reserve_out: amount,
+    }));
+    calls.push(Box::new(GetAmountIn {
+        amount_out: amount,
+        reserve_in: amount,
+        reserve_out: amount,
+    }));
+    calls.push(Box::new(GetAmountsOut {
+        amount_in: amount,
+        path: path.clone(),
+    }));
+    calls.push(Box::new(GetAmountsIn {
+        amount_out: amount,
+        path: path.clone(),
+    }));
+    calls.push(Box::new(GetAmountsOutBatch {
+        amounts_in: vec![amount, amount],
+        path: path.clone(),
+    }));
+    calls.push(Box::new(GetAmountsInBatch {
+        amounts_out: vec![amount, amount],
+        path,
+    }));
+    calls.push(Box::new(PackageHash));
+    calls.push(Box::new(SetMinInitialLiquidity {
+        min_initial_liquidity: amount,
+    }));
+    calls.push(Box::new(SetPairAllowed {
+        pair: key(3),
+        allowed: true,
+    }));
+    calls.push(Box::new(SetPairAllowlist { enabled: true }));
+    calls.push(Box::new(LatestResults {
+        owner: key(4),
+        count: 5,
+    }));
+    calls.push(Box::new(UpdateOracle {
+        token_a: key(1),
+        token_b: key(2),
+    }));
+    calls.push(Box::new(Consult {
+        token_in: key(1),
+        amount_in: amount,
+        token_out: key(2),
+        window: 3_600_000,
+    }));
+    calls.push(Box::new(SetOracleGuard {
+        token_a: key(1),
+        token_b: key(2),
+        max_deviation_bps: Some(200),
+        window: 3_600_000,
+    }));
+    calls.push(Box::new(QuoteAddLiquidity {
+        token_a: key(1),
+        token_b: key(2),
+        amount_a_desired: amount,
+        amount_b_desired: amount,
+    }));
+    calls.push(Box::new(QuoteRemoveLiquidity {
+        token_a: key(1),
+        token_b: key(2),
+        liquidity: amount,
+    }));
+    calls.push(Box::new(GetReserves {
+        token_a: key(1),
+        token_b: key(2),
+    }));
+    calls.push(Box::new(PairFor {
+        token_a: key(1),
+        token_b: key(2),
+    }));
+    calls.push(Box::new(GetPairInfo {
+        token_a: key(1),
+        token_b: key(2),
+    }));
+    calls.push(Box::new(FlashSwap {
+        token: key(1),
+        other_token: key(2),
+        amount,
+        callback: key(6),
+        data: "borrow".to_string(),
+        deadline,
+    }));
+    calls.push(Box::new(UniswapV2Call {
+        sender: key(7),
+        amount0: amount,
+        amount1: U256::zero(),
+        data: "borrow".to_string(),
+    }));
+    calls.push(Box::new(SwapCycle {
+        amount_in: amount,
+        min_profit: amount,
+        path: vec![key(1), key(2), key(3), key(1)],
+        to: key(4),
+        deadline,
+        max_price_impact_bps: Some(100),
+    }));
+    calls.push(Box::new(PlaceOrder {
+        path: vec![key(1), key(2)],
+        amount_in: amount,
+        amount_out_min: amount,
+        tip: amount,
+    }));
+    calls.push(Box::new(CancelOrder { order_id: 0 }));
+    calls.push(Box::new(ExecuteOrder { order_id: 0 }));
+    calls.push(Box::new(MigrateEscrows { limit: 100 }));
+    calls.push(Box::new(GetOrder { order_id: 0 }));
+    calls
+}
+
+fn purse_proxy_calls() -> Vec<Box<dyn PurseProxyCall>> {
+    let amount = U256::from(1000);
+    let deadline = U256::from(1);
+    let path = vec![key(1), key(2)];
+    vec![
+        Box::new(purse_proxy::AddLiquidityCspr {
+            amount: U512::from(1000),
+            token: key(1),
+            amount_token_desired: amount,
+            amount_cspr_desired: amount,
+            amount_token_min: amount,
+            amount_cspr_min: amount,
+            to: key(4),
+            deadline,
+            pair: None,
+            min_liquidity_out: None,
+        }),
+        Box::new(purse_proxy::RemoveLiquidityCspr {
+            token: key(1),
+            liquidity: amount,
+            amount_token_min: amount,
+            amount_cspr_min: amount,
+            to: key(4),
+            deadline,
+        }),
+        Box::new(purse_proxy::SwapExactCsprForTokens {
+            amount: U512::from(1000),
+            amount_out_min: amount,
+            amount_in: amount,
+            path: path.clone(),
+            to: key(4),
+            deadline,
+            max_price_impact_bps: Some(100),
+        }),
+        Box::new(purse_proxy::SwapCsprForExactTokens {
+            amount: U512::from(1000),
+            amount_out: amount,
+            amount_in_max: amount,
+            path: path.clone(),
+            to: key(4),
+            deadline,
+            max_price_impact_bps: None,
+        }),
+        Box::new(purse_proxy::SwapTokensForExactCspr {
+            amount_out: amount,
+            amount_in_max: amount,
+            path: path.clone(),
+            deadline,
+            max_price_impact_bps: Some(100),
+        }),
+        Box::new(purse_proxy::SwapExactTokensForCspr {
+            amount_in: amount,
+            amount_out_min: amount,
+            path,
+            deadline,
+            max_price_impact_bps: None,
+        }),
+    ]
+}
+
+fn arguments(args: &RuntimeArgs) -> BTreeMap<String, CLType> {
+    args.named_args()
+        .map(|arg| (arg.name().to_string(), arg.cl_value().cl_type().clone()))
+        .collect()
+}
+
+fn parameters(entry_points: &EntryPoints, name: &str) -> BTreeMap<String, CLType> {
+    entry_points
+        .get_entry_point(name)
+        .unwrap_or_else(|| panic!("router has no {} entry point", name))
+        .args()
+        .iter()
+        .map(|parameter| (parameter.name().to_string(), parameter.cl_type().clone()))
+        .collect()
+}
+
+#[test]
+fn router_calls_match_entry_points() {
+    let entry_points = get_entry_points();
+    for call in router_calls() {
+        let entry_point = entry_points.get_entry_point(call.entry_point()).unwrap();
+        assert_eq!(entry_point.access(), &EntryPointAccess::Public);
+        assert_eq!(
+            arguments(&call.runtime_args()),
+            parameters(&entry_points, call.entry_point()),
+            "{}",
+            call.entry_point()
+        );
+    }
+}
+
+#[test]
+fn every_public_entry_point_has_a_call() {
+    let covered: Vec<&str> = router_calls()
+        .iter()
+        .map(|call| call.entry_point())
+        .collect();
+    for entry_point in get_entry_points().take_entry_points() {
+        if entry_point.access() == &EntryPointAccess::Public {
+            assert!(
+                covered.contains(&entry_point.name()),
+                "no call for {}",
+                entry_point.name()
+            );
+        }
+    }
+}
+
+#[test]
+fn purse_proxy_forwards_router_arguments() {
+    let entry_points = get_entry_points();
+    let proxy = PurseProxy::new(vec![0u8; 4], ContractPackageHash::new([8; 32]));
+    for call in purse_proxy_calls() {
+        let destination = call.destination_entrypoint();
+        let mut forwarded = arguments(&call.runtime_args());
+        forwarded.remove("amount");
+        // The proxy fills in every purse parameter itself.
+        let mut parameters = parameters(&entry_points, destination);
+        parameters.retain(|_, cl_type| *cl_type != CLType::URef);
+        assert_eq!(forwarded, parameters, "{}", destination);
+
+        match proxy.session(call.as_ref()) {
+            ExecutableDeployItem::ModuleBytes { args, .. } => {
+                assert_eq!(
+                    args.get("destination_entrypoint")
+                        .unwrap()
+                        .clone()
+                        .into_t::<String>()
+                        .unwrap(),
+                    destination
+                );
+                assert_eq!(
+                    args.get("router_hash")
+                        .unwrap()
+                        .clone()
+                        .into_t::<Key>()
+                        .unwrap(),
+                    Key::Hash([8; 32])
+                );
+            }
+            _ => panic!("purse-proxy must run as session code"),
+        }
+    }
+}
+
+#[test]
+fn approve_and_swap_adds_the_pair_of_removals() {
+    let session = ApproveAndSwap::new(vec![0u8; 4], ContractPackageHash::new([8; 32]));
+    let call = RemoveLiquidity {
+        token_a: key(1),
+        token_b: key(2),
+        liquidity: U256::from(1000),
+        amount_a_min: U256::zero(),
+        amount_b_min: U256::zero(),
+        to: key(4),
+        deadline: U256::from(1),
+        js_client: false,
+    };
+    match session.remove_liquidity_session(&call, ContractPackageHash::new([5; 32])) {
+        ExecutableDeployItem::ModuleBytes { args, .. } => {
+            let mut expected = arguments(&call.runtime_args());
+            expected.insert("destination_entrypoint".into(), CLType::String);
+            expected.insert("router_hash".into(), CLType::Key);
+            expected.insert("pair".into(), CLType::Key);
+            assert_eq!(arguments(&args), expected);
+            assert_eq!(
+                args.get("pair").unwrap().clone().into_t::<Key>().unwrap(),
+                key(5)
+            );
+        }
+        _ => panic!("approve-and-swap must run as session code"),
+    }
+}
+
+#[test]
+fn router_session_calls_latest_version() {
+    let router = Router::new(ContractPackageHash::new([8; 32]));
+    let call = PackageHash;
+    match router.session(&call) {
+        ExecutableDeployItem::StoredVersionedContractByHash {
+            hash,
+            version,
+            entry_point,
+            args,
+        } => {
+            assert_eq!(hash, router.package_hash);
+            assert_eq!(version, None);
+            assert_eq!(entry_point, "package_hash");
+            assert!(args.is_empty());
+        }
+        _ => panic!("router calls must target the package"),
+    }
+}
+
+#[test]
+fn signed_deploy_round_trips_through_json() {
+    let secret_key = SecretKey::ed25519_from_bytes([7u8; 32]).unwrap();
+    let router = Router::new(ContractPackageHash::new([8; 32]));
+    let session = router.session(&GetAmountsOut {
+        amount_in: U256::from(1000),
+        path: vec![key(1), key(2)],
+    });
+    let deploy = DeployBuilder::new("casper-test", U512::from(5_000_000_000u64))
+        .with_timestamp(Timestamp::from(1_000))
+        .build(session.clone(), &secret_key);
+
+    assert_eq!(deploy.header().chain_name(), "casper-test");
+    assert_eq!(deploy.session(), &session);
+    assert_eq!(deploy.approvals().len(), 1);
+
+    let json = serde_json::to_string(&deploy).unwrap();
+    let decoded: Deploy = serde_json::from_str(&json).unwrap();
+    assert_eq!(decoded, deploy);
+}
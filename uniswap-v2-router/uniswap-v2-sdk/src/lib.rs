@@ -0,0 +1,26 @@
+//! Off-chain builders for Uniswap V2 Router deploys.
+//!
+//! Every router entry point and purse-proxy destination has a typed call in [`router`] and
+//! [`purse_proxy`] that knows its runtime argument names and types. [`Router`], [`PurseProxy`] and
+//! [`ApproveAndSwap`] turn a call into a session item and [`DeployBuilder`] signs it into a deploy
+//! ready to be sent to a node.
+
+pub mod approve_and_swap;
+pub mod deploy;
+pub mod purse_proxy;
+pub mod router;
+
+#[cfg(test)]
+mod entry_point_tests;
+
+pub use approve_and_swap::ApproveAndSwap;
+pub use deploy::DeployBuilder;
+pub use purse_proxy::{PurseProxy, PurseProxyCall};
+pub use router::{Router, RouterCall};
+
+use casper_types::Key;
+
+/// Swap entry points read the path as formatted key strings.
+pub(crate) fn path_arg(path: &[Key]) -> Vec<String> {
+    path.iter().map(Key::to_formatted_string).collect()
+}
@@ -0,0 +1,226 @@
+//! Typed calls for the purse-proxy session wasm.
+//!
+//! The proxy runs in the account's context, so it can move CSPR out of the main purse into a
+//! fresh purse and hand that to the router. `amount` is the number of motes it moves, whatever the
+//! router leaves unspent goes back to the main purse. CSPR the router pays out lands in the main
+//! purse. The returned amounts are stored under [`result_key`] in the account's named keys.
+
+use casper_execution_engine::core::engine_state::ExecutableDeployItem;
+use casper_types::{
+    bytesrepr::Bytes, runtime_args, ContractPackageHash, Key, RuntimeArgs, U256, U512,
+};
+
+use crate::path_arg;
+
+/// Named key of the account holding the amounts returned by the last call to
+/// `destination_entrypoint`, as a `Vec<U256>`.
+pub fn result_key(destination_entrypoint: &str) -> String {
+    format!("{}_result", destination_entrypoint)
+}
+
+/// A call the purse-proxy forwards to one router entry point.
+pub trait PurseProxyCall {
+    fn destination_entrypoint(&self) -> &'static str;
+    fn runtime_args(&self) -> RuntimeArgs;
+}
+
+/// The purse-proxy wasm bound to a router package.
+#[derive(Clone, Debug)]
+pub struct PurseProxy {
+    pub module_bytes: Bytes,
+    pub router: ContractPackageHash,
+}
+
+impl PurseProxy {
+    pub fn new<B: Into<Bytes>>(module_bytes: B, router: ContractPackageHash) -> PurseProxy {
+        PurseProxy {
+            module_bytes: module_bytes.into(),
+            router,
+        }
+    }
+
+    pub fn session<C: PurseProxyCall + ?Sized>(&self, call: &C) -> ExecutableDeployItem {
+        let mut args = call.runtime_args();
+        args.insert("destination_entrypoint", call.destination_entrypoint())
+            .expect("destination_entrypoint is a string");
+        args.insert("router_hash", Key::from(self.router))
+            .expect("router_hash is a key");
+        ExecutableDeployItem::ModuleBytes {
+            module_bytes: self.module_bytes.clone(),
+            args,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct AddLiquidityCspr {
+    pub amount: U512,
+    pub token: Key,
+    pub amount_token_desired: U256,
+    pub amount_cspr_desired: U256,
+    pub amount_token_min: U256,
+    pub amount_cspr_min: U256,
+    pub to: Key,
+    pub deadline: U256,
+    pub pair: Option<Key>,
+    pub min_liquidity_out: Option<U256>,
+}
+
+impl PurseProxyCall for AddLiquidityCspr {
+    fn destination_entrypoint(&self) -> &'static str {
+        "add_liquidity_cspr"
+    }
+
+    fn runtime_args(&self) -> RuntimeArgs {
+        runtime_args! {
+            "amount" => self.amount,
+            "token" => self.token,
+            "amount_token_desired" => self.amount_token_desired,
+            "amount_cspr_desired" => self.amount_cspr_desired,
+            "amount_token_min" => self.amount_token_min,
+            "amount_cspr_min" => self.amount_cspr_min,
+            "to" => self.to,
+            "deadline" => self.deadline,
+            "pair" => self.pair,
+            "min_liquidity_out" => self.min_liquidity_out
+        }
+    }
+}
+
+/// The CSPR is paid to the account's main purse.
+#[derive(Clone, Debug)]
+pub struct RemoveLiquidityCspr {
+    pub token: Key,
+    pub liquidity: U256,
+    pub amount_token_min: U256,
+    pub amount_cspr_min: U256,
+    pub to: Key,
+    pub deadline: U256,
+}
+
+impl PurseProxyCall for RemoveLiquidityCspr {
+    fn destination_entrypoint(&self) -> &'static str {
+        "remove_liquidity_cspr"
+    }
+
+    fn runtime_args(&self) -> RuntimeArgs {
+        runtime_args! {
+            "token" => self.token,
+            "liquidity" => self.liquidity,
+            "amount_token_min" => self.amount_token_min,
+            "amount_cspr_min" => self.amount_cspr_min,
+            "to" => self.to,
+            "deadline" => self.deadline
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct SwapExactCsprForTokens {
+    pub amount: U512,
+    pub amount_out_min: U256,
+    pub amount_in: U256,
+    pub path: Vec<Key>,
+    pub to: Key,
+    pub deadline: U256,
+    pub max_price_impact_bps: Option<u32>,
+}
+
+impl PurseProxyCall for SwapExactCsprForTokens {
+    fn destination_entrypoint(&self) -> &'static str {
+        "swap_exact_cspr_for_tokens"
+    }
+
+    fn runtime_args(&self) -> RuntimeArgs {
+        runtime_args! {
+            "amount" => self.amount,
+            "amount_out_min" => self.amount_out_min,
+            "amount_in" => self.amount_in,
+            "path" => path_arg(&self.path),
+            "to" => self.to,
+            "deadline" => self.deadline,
+            "max_price_impact_bps" => self.max_price_impact_bps
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct SwapCsprForExactTokens {
+    pub amount: U512,
+    pub amount_out: U256,
+    pub amount_in_max: U256,
+    pub path: Vec<Key>,
+    pub to: Key,
+    pub deadline: U256,
+    pub max_price_impact_bps: Option<u32>,
+}
+
+impl PurseProxyCall for SwapCsprForExactTokens {
+    fn destination_entrypoint(&self) -> &'static str {
+        "swap_cspr_for_exact_tokens"
+    }
+
+    fn runtime_args(&self) -> RuntimeArgs {
+        runtime_args! {
+            "amount" => self.amount,
+            "amount_out" => self.amount_out,
+            "amount_in_max" => self.amount_in_max,
+            "path" => path_arg(&self.path),
+            "to" => self.to,
+            "deadline" => self.deadline,
+            "max_price_impact_bps" => self.max_price_impact_bps
+        }
+    }
+}
+
+/// The CSPR is paid to the account's main purse.
+#[derive(Clone, Debug)]
+pub struct SwapTokensForExactCspr {
+    pub amount_out: U256,
+    pub amount_in_max: U256,
+    pub path: Vec<Key>,
+    pub deadline: U256,
+    pub max_price_impact_bps: Option<u32>,
+}
+
+impl PurseProxyCall for SwapTokensForExactCspr {
+    fn destination_entrypoint(&self) -> &'static str {
+        "swap_tokens_for_exact_cspr"
+    }
+
+    fn runtime_args(&self) -> RuntimeArgs {
+        runtime_args! {
+            "amount_out" => self.amount_out,
+            "amount_in_max" => self.amount_in_max,
+            "path" => path_arg(&self.path),
+            "deadline" => self.deadline,
+            "max_price_impact_bps" => self.max_price_impact_bps
+        }
+    }
+}
+
+/// The CSPR is paid to the account's main purse.
+#[derive(Clone, Debug)]
+pub struct SwapExactTokensForCspr {
+    pub amount_in: U256,
+    pub amount_out_min: U256,
+    pub path: Vec<Key>,
+    pub deadline: U256,
+    pub max_price_impact_bps: Option<u32>,
+}
+
+impl PurseProxyCall for SwapExactTokensForCspr {
+    fn destination_entrypoint(&self) -> &'static str {
+        "swap_exact_tokens_for_cspr"
+    }
+
+    fn runtime_args(&self) -> RuntimeArgs {
+        runtime_args! {
+            "amount_in" => self.amount_in,
+            "amount_out_min" => self.amount_out_min,
+            "path" => path_arg(&self.path),
+            "deadline" => self.deadline,
+            "max_price_impact_bps" => self.max_price_impact_bps
+        }
+    }
+}
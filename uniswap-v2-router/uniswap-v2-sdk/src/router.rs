@@ -0,0 +1,1127 @@
+//! Typed calls for the router entry points.
+//!
+//! Calls with a `js_client` flag target the `_js_client` variant of the entry point when it is
+//! set. Those variants take the same arguments but return nothing, what they would have returned
+//! is recorded for the caller and read back with [`LatestResults`].
+
+use casper_execution_engine::core::engine_state::ExecutableDeployItem;
+use casper_types::{runtime_args, ContractPackageHash, Key, RuntimeArgs, URef, U256};
+
+use crate::path_arg;
+
+/// A call to one router entry point.
+pub trait RouterCall {
+    fn entry_point(&self) -> &'static str;
+    fn runtime_args(&self) -> RuntimeArgs;
+}
+
+/// An installed router package.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Router {
+    pub package_hash: ContractPackageHash,
+}
+
+impl Router {
+    pub fn new(package_hash: ContractPackageHash) -> Router {
+        Router { package_hash }
+    }
+
+    /// Session item calling the latest version of the router.
+    pub fn session<C: RouterCall + ?Sized>(&self, call: &C) -> ExecutableDeployItem {
+        ExecutableDeployItem::StoredVersionedContractByHash {
+            hash: self.package_hash,
+            version: None,
+            entry_point: call.entry_point().to_string(),
+            args: call.runtime_args(),
+        }
+    }
+}
+
+fn select(
+    js_client: bool,
+    entry_point: &'static str,
+    js_entry_point: &'static str,
+) -> &'static str {
+    if js_client {
+        js_entry_point
+    } else {
+        entry_point
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct AddLiquidity {
+    pub token_a: Key,
+    pub token_b: Key,
+    pub amount_a_desired: U256,
+    pub amount_b_desired: U256,
+    pub amount_a_min: U256,
+    pub amount_b_min: U256,
+    pub to: Key,
+    pub deadline: U256,
+    pub pair: Option<Key>,
+    pub min_liquidity_out: Option<U256>,
+    pub js_client: bool,
+}
+
+impl RouterCall for AddLiquidity {
+    fn entry_point(&self) -> &'static str {
+        select(self.js_client, "add_liquidity", "add_liquidity_js_client")
+    }
+
+    fn runtime_args(&self) -> RuntimeArgs {
+        runtime_args! {
+            "token_a" => self.token_a,
+            "token_b" => self.token_b,
+            "amount_a_desired" => self.amount_a_desired,
+            "amount_b_desired" => self.amount_b_desired,
+            "amount_a_min" => self.amount_a_min,
+            "amount_b_min" => self.amount_b_min,
+            "to" => self.to,
+            "deadline" => self.deadline,
+            "pair" => self.pair,
+            "min_liquidity_out" => self.min_liquidity_out
+        }
+    }
+}
+
+/// Spends CSPR from `purse`, which the caller must own. Accounts go through the purse-proxy.
+#[derive(Clone, Debug)]
+pub struct AddLiquidityCspr {
+    pub token: Key,
+    pub amount_token_desired: U256,
+    pub amount_cspr_desired: U256,
+    pub amount_token_min: U256,
+    pub amount_cspr_min: U256,
+    pub to: Key,
+    pub deadline: U256,
+    pub pair: Option<Key>,
+    pub min_liquidity_out: Option<U256>,
+    pub purse: URef,
+    pub js_client: bool,
+}
+
+impl RouterCall for AddLiquidityCspr {
+    fn entry_point(&self) -> &'static str {
+        select(
+            self.js_client,
+            "add_liquidity_cspr",
+            "add_liquidity_cspr_js_client",
+        )
+    }
+
+    fn runtime_args(&self) -> RuntimeArgs {
+        runtime_args! {
+            "token" => self.token,
+            "amount_token_desired" => self.amount_token_desired,
+            "amount_cspr_desired" => self.amount_cspr_desired,
+            "amount_token_min" => self.amount_token_min,
+            "amount_cspr_min" => self.amount_cspr_min,
+            "to" => self.to,
+            "deadline" => self.deadline,
+            "pair" => self.pair,
+            "min_liquidity_out" => self.min_liquidity_out,
+            "purse" => self.purse
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct RemoveLiquidity {
+    pub token_a: Key,
+    pub token_b: Key,
+    pub liquidity: U256,
+    pub amount_a_min: U256,
+    pub amount_b_min: U256,
+    pub to: Key,
+    pub deadline: U256,
+    pub js_client: bool,
+}
+
+impl RouterCall for RemoveLiquidity {
+    fn entry_point(&self) -> &'static str {
+        select(
+            self.js_client,
+            "remove_liquidity",
+            "remove_liquidity_js_client",
+        )
+    }
+
+    fn runtime_args(&self) -> RuntimeArgs {
+        runtime_args! {
+            "token_a" => self.token_a,
+            "token_b" => self.token_b,
+            "liquidity" => self.liquidity,
+            "amount_a_min" => self.amount_a_min,
+            "amount_b_min" => self.amount_b_min,
+            "to" => self.to,
+            "deadline" => self.deadline
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct RemoveLiquidityCspr {
+    pub token: Key,
+    pub liquidity: U256,
+    pub amount_token_min: U256,
+    pub amount_cspr_min: U256,
+    pub to: Key,
+    pub deadline: U256,
+    pub to_purse: URef,
+    pub js_client: bool,
+}
+
+impl RouterCall for RemoveLiquidityCspr {
+    fn entry_point(&self) -> &'static str {
+        select(
+            self.js_client,
+            "remove_liquidity_cspr",
+            "remove_liquidity_cspr_js_client",
+        )
+    }
+
+    fn runtime_args(&self) -> RuntimeArgs {
+        runtime_args! {
+            "token" => self.token,
+            "liquidity" => self.liquidity,
+            "amount_token_min" => self.amount_token_min,
+            "amount_cspr_min" => self.amount_cspr_min,
+            "to" => self.to,
+            "deadline" => self.deadline,
+            "to_purse" => self.to_purse
+        }
+    }
+}
+
+/// Pays the token and the CSPR out to the `to` account.
+#[derive(Clone, Debug)]
+pub struct RemoveLiquidityCsprToKey {
+    pub token: Key,
+    pub liquidity: U256,
+    pub amount_token_min: U256,
+    pub amount_cspr_min: U256,
+    pub to: Key,
+    pub deadline: U256,
+}
+
+impl RouterCall for RemoveLiquidityCsprToKey {
+    fn entry_point(&self) -> &'static str {
+        "remove_liquidity_cspr_to_key"
+    }
+
+    fn runtime_args(&self) -> RuntimeArgs {
+        runtime_args! {
+            "token" => self.token,
+            "liquidity" => self.liquidity,
+            "amount_token_min" => self.amount_token_min,
+            "amount_cspr_min" => self.amount_cspr_min,
+            "to" => self.to,
+            "deadline" => self.deadline
+        }
+    }
+}
+
+/// `public_key` and `signature` are the comma separated byte lists the pair's `permit` expects.
+#[derive(Clone, Debug)]
+pub struct RemoveLiquidityWithPermit {
+    pub token_a: Key,
+    pub token_b: Key,
+    pub liquidity: U256,
+    pub amount_a_min: U256,
+    pub amount_b_min: U256,
+    pub to: Key,
+    pub deadline: U256,
+    pub approve_max: bool,
+    pub public_key: String,
+    pub signature: String,
+    pub js_client: bool,
+}
+
+impl RouterCall for RemoveLiquidityWithPermit {
+    fn entry_point(&self) -> &'static str {
+        select(
+            self.js_client,
+            "remove_liquidity_with_permit",
+            "remove_liquidity_with_permit_js_client",
+        )
+    }
+
+    fn runtime_args(&self) -> RuntimeArgs {
+        runtime_args! {
+            "token_a" => self.token_a,
+            "token_b" => self.token_b,
+            "liquidity" => self.liquidity,
+            "amount_a_min" => self.amount_a_min,
+            "amount_b_min" => self.amount_b_min,
+            "to" => self.to,
+            "deadline" => self.deadline,
+            "approve_max" => self.approve_max,
+            "public_key" => self.public_key.clone(),
+            "signature" => self.signature.clone()
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct RemoveLiquidityCsprWithPermit {
+    pub token: Key,
+    pub liquidity: U256,
+    pub amount_token_min: U256,
+    pub amount_cspr_min: U256,
+    pub to: Key,
+    pub deadline: U256,
+    pub approve_max: bool,
+    pub public_key: String,
+    pub signature: String,
+    pub to_purse: URef,
+    pub js_client: bool,
+}
+
+impl RouterCall for RemoveLiquidityCsprWithPermit {
+    fn entry_point(&self) -> &'static str {
+        select(
+            self.js_client,
+            "remove_liquidity_cspr_with_permit",
+            "remove_liquidity_cspr_with_permit_js_client",
+        )
+    }
+
+    fn runtime_args(&self) -> RuntimeArgs {
+        runtime_args! {
+            "token" => self.token,
+            "liquidity" => self.liquidity,
+            "amount_token_min" => self.amount_token_min,
+            "amount_cspr_min" => self.amount_cspr_min,
+            "to" => self.to,
+            "deadline" => self.deadline,
+            "approve_max" => self.approve_max,
+            "public_key" => self.public_key.clone(),
+            "signature" => self.signature.clone(),
+            "to_purse" => self.to_purse
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct SwapExactTokensForTokens {
+    pub amount_in: U256,
+    pub amount_out_min: U256,
+    pub path: Vec<Key>,
+    pub to: Key,
+    pub deadline: U256,
+    pub max_price_impact_bps: Option<u32>,
+    pub js_client: bool,
+}
+
+impl RouterCall for SwapExactTokensForTokens {
+    fn entry_point(&self) -> &'static str {
+        select(
+            self.js_client,
+            "swap_exact_tokens_for_tokens",
+            "swap_exact_tokens_for_tokens_js_client",
+        )
+    }
+
+    fn runtime_args(&self) -> RuntimeArgs {
+        runtime_args! {
+            "amount_in" => self.amount_in,
+            "amount_out_min" => self.amount_out_min,
+            "path" => path_arg(&self.path),
+            "to" => self.to,
+            "deadline" => self.deadline,
+            "max_price_impact_bps" => self.max_price_impact_bps
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct SwapTokensForExactTokens {
+    pub amount_out: U256,
+    pub amount_in_max: U256,
+    pub path: Vec<Key>,
+    pub to: Key,
+    pub deadline: U256,
+    pub max_price_impact_bps: Option<u32>,
+    pub js_client: bool,
+}
+
+impl RouterCall for SwapTokensForExactTokens {
+    fn entry_point(&self) -> &'static str {
+        select(
+            self.js_client,
+            "swap_tokens_for_exact_tokens",
+            "swap_tokens_for_exact_tokens_js_client",
+        )
+    }
+
+    fn runtime_args(&self) -> RuntimeArgs {
+        runtime_args! {
+            "amount_out" => self.amount_out,
+            "amount_in_max" => self.amount_in_max,
+            "path" => path_arg(&self.path),
+            "to" => self.to,
+            "deadline" => self.deadline,
+            "max_price_impact_bps" => self.max_price_impact_bps
+        }
+    }
+}
+
+/// Spends CSPR from `purse`, which the caller must own. Accounts go through the purse-proxy.
+#[derive(Clone, Debug)]
+pub struct SwapExactCsprForTokens {
+    pub amount_out_min: U256,
+    pub amount_in: U256,
+    pub path: Vec<Key>,
+    pub to: Key,
+    pub deadline: U256,
+    pub max_price_impact_bps: Option<u32>,
+    pub purse: URef,
+    pub js_client: bool,
+}
+
+impl RouterCall for SwapExactCsprForTokens {
+    fn entry_point(&self) -> &'static str {
+        select(
+            self.js_client,
+            "swap_exact_cspr_for_tokens",
+            "swap_exact_cspr_for_tokens_js_client",
+        )
+    }
+
+    fn runtime_args(&self) -> RuntimeArgs {
+        runtime_args! {
+            "amount_out_min" => self.amount_out_min,
+            "amount_in" => self.amount_in,
+            "path" => path_arg(&self.path),
+            "to" => self.to,
+            "deadline" => self.deadline,
+            "max_price_impact_bps" => self.max_price_impact_bps,
+            "purse" => self.purse
+        }
+    }
+}
+
+/// Pays the CSPR out to the `to` purse.
+#[derive(Clone, Debug)]
+pub struct SwapTokensForExactCspr {
+    pub amount_out: U256,
+    pub amount_in_max: U256,
+    pub path: Vec<Key>,
+    pub to: URef,
+    pub deadline: U256,
+    pub max_price_impact_bps: Option<u32>,
+    pub js_client: bool,
+}
+
+impl RouterCall for SwapTokensForExactCspr {
+    fn entry_point(&self) -> &'static str {
+        select(
+            self.js_client,
+            "swap_tokens_for_exact_cspr",
+            "swap_tokens_for_exact_cspr_js_client",
+        )
+    }
+
+    fn runtime_args(&self) -> RuntimeArgs {
+        runtime_args! {
+            "amount_out" => self.amount_out,
+            "amount_in_max" => self.amount_in_max,
+            "path" => path_arg(&self.path),
+            "to" => self.to,
+            "deadline" => self.deadline,
+            "max_price_impact_bps" => self.max_price_impact_bps
+        }
+    }
+}
+
+/// Pays the CSPR out to the main purse of a `Key::Account` or to a `Key::URef` purse.
+#[derive(Clone, Debug)]
+pub struct SwapTokensForExactCsprToKey {
+    pub amount_out: U256,
+    pub amount_in_max: U256,
+    pub path: Vec<Key>,
+    pub to: Key,
+    pub deadline: U256,
+    pub max_price_impact_bps: Option<u32>,
+}
+
+impl RouterCall for SwapTokensForExactCsprToKey {
+    fn entry_point(&self) -> &'static str {
+        "swap_tokens_for_exact_cspr_to_key"
+    }
+
+    fn runtime_args(&self) -> RuntimeArgs {
+        runtime_args! {
+            "amount_out" => self.amount_out,
+            "amount_in_max" => self.amount_in_max,
+            "path" => path_arg(&self.path),
+            "to" => self.to,
+            "deadline" => self.deadline,
+            "max_price_impact_bps" => self.max_price_impact_bps
+        }
+    }
+}
+
+/// Pays the CSPR out to the `to` purse.
+#[derive(Clone, Debug)]
+pub struct SwapExactTokensForCspr {
+    pub amount_in: U256,
+    pub amount_out_min: U256,
+    pub path: Vec<Key>,
+    pub to: URef,
+    pub deadline: U256,
+    pub max_price_impact_bps: Option<u32>,
+    pub js_client: bool,
+}
+
+impl RouterCall for SwapExactTokensForCspr {
+    fn entry_point(&self) -> &'static str {
+        select(
+            self.js_client,
+            "swap_exact_tokens_for_cspr",
+            "swap_exact_tokens_for_cspr_js_client",
+        )
+    }
+
+    fn runtime_args(&self) -> RuntimeArgs {
+        runtime_args! {
+            "amount_in" => self.amount_in,
+            "amount_out_min" => self.amount_out_min,
+            "path" => path_arg(&self.path),
+            "to" => self.to,
+            "deadline" => self.deadline,
+            "max_price_impact_bps" => self.max_price_impact_bps
+        }
+    }
+}
+
+/// Pays the CSPR out to the main purse of a `Key::Account` or to a `Key::URef` purse.
+#[derive(Clone, Debug)]
+pub struct SwapExactTokensForCsprToKey {
+    pub amount_in: U256,
+    pub amount_out_min: U256,
+    pub path: Vec<Key>,
+    pub to: Key,
+    pub deadline: U256,
+    pub max_price_impact_bps: Option<u32>,
+}
+
+impl RouterCall for SwapExactTokensForCsprToKey {
+    fn entry_point(&self) -> &'static str {
+        "swap_exact_tokens_for_cspr_to_key"
+    }
+
+    fn runtime_args(&self) -> RuntimeArgs {
+        runtime_args! {
+            "amount_in" => self.amount_in,
+            "amount_out_min" => self.amount_out_min,
+            "path" => path_arg(&self.path),
+            "to" => self.to,
+            "deadline" => self.deadline,
+            "max_price_impact_bps" => self.max_price_impact_bps
+        }
+    }
+}
+
+/// Spends CSPR from `purse`, which the caller must own. Accounts go through the purse-proxy.
+#[derive(Clone, Debug)]
+pub struct SwapCsprForExactTokens {
+    pub amount_out: U256,
+    pub amount_in_max: U256,
+    pub path: Vec<Key>,
+    pub to: Key,
+    pub deadline: U256,
+    pub max_price_impact_bps: Option<u32>,
+    pub purse: URef,
+    pub js_client: bool,
+}
+
+impl RouterCall for SwapCsprForExactTokens {
+    fn entry_point(&self) -> &'static str {
+        select(
+            self.js_client,
+            "swap_cspr_for_exact_tokens",
+            "swap_cspr_for_exact_tokens_js_client",
+        )
+    }
+
+    fn runtime_args(&self) -> RuntimeArgs {
+        runtime_args! {
+            "amount_out" => self.amount_out,
+            "amount_in_max" => self.amount_in_max,
+            "path" => path_arg(&self.path),
+            "to" => self.to,
+            "deadline" => self.deadline,
+            "max_price_impact_bps" => self.max_price_impact_bps,
+            "purse" => self.purse
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Quote {
+    pub amount_a: U256,
+    pub reserve_a: U256,
+    pub reserve_b: U256,
+}
+
+impl RouterCall for Quote {
+    fn entry_point(&self) -> &'static str {
+        "quote"
+    }
+
+    fn runtime_args(&self) -> RuntimeArgs {
+        runtime_args! {
+            "amount_a" => self.amount_a,
+            "reserve_a" => self.reserve_a,
+            "reserve_b" => self.reserve_b
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct GetAmountOut {
+    pub amount_in: U256,
+    pub reserve_in: U256,
+    pub reserve_out: U256,
+}
+
+impl RouterCall for GetAmountOut {
+    fn entry_point(&self) -> &'static str {
+        "get_amount_out"
+    }
+
+    fn runtime_args(&self) -> RuntimeArgs {
+        runtime_args! {
+            "amount_in" => self.amount_in,
+            "reserve_in" => self.reserve_in,
+            "reserve_out" => self.reserve_out
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct GetAmountIn {
+    pub amount_out: U256,
+    pub reserve_in: U256,
+    pub reserve_out: U256,
+}
+
+impl RouterCall for GetAmountIn {
+    fn entry_point(&self) -> &'static str {
+        "get_amount_in"
+    }
+
+    fn runtime_args(&self) -> RuntimeArgs {
+        runtime_args! {
+            "amount_out" => self.amount_out,
+            "reserve_in" => self.reserve_in,
+            "reserve_out" => self.reserve_out
+        }
+    }
+}
+
+/// Unlike the swaps, the amount views take the path as keys.
+#[derive(Clone, Debug)]
+pub struct GetAmountsOut {
+    pub amount_in: U256,
+    pub path: Vec<Key>,
+}
+
+impl RouterCall for GetAmountsOut {
+    fn entry_point(&self) -> &'static str {
+        "get_amounts_out"
+    }
+
+    fn runtime_args(&self) -> RuntimeArgs {
+        runtime_args! {
+            "amount_in" => self.amount_in,
+            "path" => self.path.clone()
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct GetAmountsIn {
+    pub amount_out: U256,
+    pub path: Vec<Key>,
+}
+
+impl RouterCall for GetAmountsIn {
+    fn entry_point(&self) -> &'static str {
+        "get_amounts_in"
+    }
+
+    fn runtime_args(&self) -> RuntimeArgs {
+        runtime_args! {
+            "amount_out" => self.amount_out,
+            "path" => self.path.clone()
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct GetAmountsOutBatch {
+    pub amounts_in: Vec<U256>,
+    pub path: Vec<Key>,
+}
+
+impl RouterCall for GetAmountsOutBatch {
+    fn entry_point(&self) -> &'static str {
+        "get_amounts_out_batch"
+    }
+
+    fn runtime_args(&self) -> RuntimeArgs {
+        runtime_args! {
+            "amounts_in" => self.amounts_in.clone(),
+            "path" => self.path.clone()
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct GetAmountsInBatch {
+    pub amounts_out: Vec<U256>,
+    pub path: Vec<Key>,
+}
+
+impl RouterCall for GetAmountsInBatch {
+    fn entry_point(&self) -> &'static str {
+        "get_amounts_in_batch"
+    }
+
+    fn runtime_args(&self) -> RuntimeArgs {
+        runtime_args! {
+            "amounts_out" => self.amounts_out.clone(),
+            "path" => self.path.clone()
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct PackageHash;
+
+impl RouterCall for PackageHash {
+    fn entry_point(&self) -> &'static str {
+        "package_hash"
+    }
+
+    fn runtime_args(&self) -> RuntimeArgs {
+        runtime_args! {}
+    }
+}
+
+/// Only the router's owner may call it.
+#[derive(Clone, Debug)]
+pub struct SetMinInitialLiquidity {
+    pub min_initial_liquidity: U256,
+}
+
+impl RouterCall for SetMinInitialLiquidity {
+    fn entry_point(&self) -> &'static str {
+        "set_min_initial_liquidity"
+    }
+
+    fn runtime_args(&self) -> RuntimeArgs {
+        runtime_args! {
+            "min_initial_liquidity" => self.min_initial_liquidity
+        }
+    }
+}
+
+/// Only the router's owner may call it.
+#[derive(Clone, Debug)]
+pub struct SetPairAllowed {
+    pub pair: Key,
+    pub allowed: bool,
+}
+
+impl RouterCall for SetPairAllowed {
+    fn entry_point(&self) -> &'static str {
+        "set_pair_allowed"
+    }
+
+    fn runtime_args(&self) -> RuntimeArgs {
+        runtime_args! {
+            "pair" => self.pair,
+            "allowed" => self.allowed
+        }
+    }
+}
+
+/// Turns on or off the requirement that new pairs are allowed with [`SetPairAllowed`]. Only the
+/// router's owner may call it.
+#[derive(Clone, Debug)]
+pub struct SetPairAllowlist {
+    pub enabled: bool,
+}
+
+impl RouterCall for SetPairAllowlist {
+    fn entry_point(&self) -> &'static str {
+        "set_pair_allowlist"
+    }
+
+    fn runtime_args(&self) -> RuntimeArgs {
+        runtime_args! {
+            "enabled" => self.enabled
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct LatestResults {
+    pub owner: Key,
+    pub count: u32,
+}
+
+impl RouterCall for LatestResults {
+    fn entry_point(&self) -> &'static str {
+        "latest_results"
+    }
+
+    fn runtime_args(&self) -> RuntimeArgs {
+        runtime_args! {
+            "owner" => self.owner,
+            "count" => self.count
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct UpdateOracle {
+    pub token_a: Key,
+    pub token_b: Key,
+}
+
+impl RouterCall for UpdateOracle {
+    fn entry_point(&self) -> &'static str {
+        "update_oracle"
+    }
+
+    fn runtime_args(&self) -> RuntimeArgs {
+        runtime_args! {
+            "token_a" => self.token_a,
+            "token_b" => self.token_b
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Consult {
+    pub token_in: Key,
+    pub amount_in: U256,
+    pub token_out: Key,
+    pub window: u64,
+}
+
+impl RouterCall for Consult {
+    fn entry_point(&self) -> &'static str {
+        "consult"
+    }
+
+    fn runtime_args(&self) -> RuntimeArgs {
+        runtime_args! {
+            "token_in" => self.token_in,
+            "amount_in" => self.amount_in,
+            "token_out" => self.token_out,
+            "window" => self.window
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct SetOracleGuard {
+    pub token_a: Key,
+    pub token_b: Key,
+    pub max_deviation_bps: Option<u32>,
+    pub window: u64,
+}
+
+impl RouterCall for SetOracleGuard {
+    fn entry_point(&self) -> &'static str {
+        "set_oracle_guard"
+    }
+
+    fn runtime_args(&self) -> RuntimeArgs {
+        runtime_args! {
+            "token_a" => self.token_a,
+            "token_b" => self.token_b,
+            "max_deviation_bps" => self.max_deviation_bps,
+            "window" => self.window
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct QuoteAddLiquidity {
+    pub token_a: Key,
+    pub token_b: Key,
+    pub amount_a_desired: U256,
+    pub amount_b_desired: U256,
+}
+
+impl RouterCall for QuoteAddLiquidity {
+    fn entry_point(&self) -> &'static str {
+        "quote_add_liquidity"
+    }
+
+    fn runtime_args(&self) -> RuntimeArgs {
+        runtime_args! {
+            "token_a" => self.token_a,
+            "token_b" => self.token_b,
+            "amount_a_desired" => self.amount_a_desired,
+            "amount_b_desired" => self.amount_b_desired
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct QuoteRemoveLiquidity {
+    pub token_a: Key,
+    pub token_b: Key,
+    pub liquidity: U256,
+}
+
+impl RouterCall for QuoteRemoveLiquidity {
+    fn entry_point(&self) -> &'static str {
+        "quote_remove_liquidity"
+    }
+
+    fn runtime_args(&self) -> RuntimeArgs {
+        runtime_args! {
+            "token_a" => self.token_a,
+            "token_b" => self.token_b,
+            "liquidity" => self.liquidity
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct GetReserves {
+    pub token_a: Key,
+    pub token_b: Key,
+}
+
+impl RouterCall for GetReserves {
+    fn entry_point(&self) -> &'static str {
+        "get_reserves"
+    }
+
+    fn runtime_args(&self) -> RuntimeArgs {
+        runtime_args! {
+            "token_a" => self.token_a,
+            "token_b" => self.token_b
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct PairFor {
+    pub token_a: Key,
+    pub token_b: Key,
+}
+
+impl RouterCall for PairFor {
+    fn entry_point(&self) -> &'static str {
+        "pair_for"
+    }
+
+    fn runtime_args(&self) -> RuntimeArgs {
+        runtime_args! {
+            "token_a" => self.token_a,
+            "token_b" => self.token_b
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct GetPairInfo {
+    pub token_a: Key,
+    pub token_b: Key,
+}
+
+impl RouterCall for GetPairInfo {
+    fn entry_point(&self) -> &'static str {
+        "get_pair_info"
+    }
+
+    fn runtime_args(&self) -> RuntimeArgs {
+        runtime_args! {
+            "token_a" => self.token_a,
+            "token_b" => self.token_b
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct FlashSwap {
+    pub token: Key,
+    pub other_token: Key,
+    pub amount: U256,
+    pub callback: Key,
+    pub data: String,
+    pub deadline: U256,
+}
+
+impl RouterCall for FlashSwap {
+    fn entry_point(&self) -> &'static str {
+        "flash_swap"
+    }
+
+    fn runtime_args(&self) -> RuntimeArgs {
+        runtime_args! {
+            "token" => self.token,
+            "other_token" => self.other_token,
+            "amount" => self.amount,
+            "callback" => self.callback,
+            "data" => self.data.clone(),
+            "deadline" => self.deadline
+        }
+    }
+}
+
+/// The callback pairs make during a flash swap. The router rejects it from any other caller.
+#[derive(Clone, Debug)]
+pub struct UniswapV2Call {
+    pub sender: Key,
+    pub amount0: U256,
+    pub amount1: U256,
+    pub data: String,
+}
+
+impl RouterCall for UniswapV2Call {
+    fn entry_point(&self) -> &'static str {
+        "uniswap_v2_call"
+    }
+
+    fn runtime_args(&self) -> RuntimeArgs {
+        runtime_args! {
+            "sender" => self.sender,
+            "amount0" => self.amount0,
+            "amount1" => self.amount1,
+            "data" => self.data.clone()
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct SwapCycle {
+    pub amount_in: U256,
+    pub min_profit: U256,
+    pub path: Vec<Key>,
+    pub to: Key,
+    pub deadline: U256,
+    pub max_price_impact_bps: Option<u32>,
+}
+
+impl RouterCall for SwapCycle {
+    fn entry_point(&self) -> &'static str {
+        "swap_cycle"
+    }
+
+    fn runtime_args(&self) -> RuntimeArgs {
+        runtime_args! {
+            "amount_in" => self.amount_in,
+            "min_profit" => self.min_profit,
+            "path" => path_arg(&self.path),
+            "to" => self.to,
+            "deadline" => self.deadline,
+            "max_price_impact_bps" => self.max_price_impact_bps
+        }
+    }
+}
+
+/// Like the amount views, limit orders take the path as keys.
+#[derive(Clone, Debug)]
+pub struct PlaceOrder {
+    pub path: Vec<Key>,
+    pub amount_in: U256,
+    pub amount_out_min: U256,
+    pub tip: U256,
+}
+
+impl RouterCall for PlaceOrder {
+    fn entry_point(&self) -> &'static str {
+        "place_order"
+    }
+
+    fn runtime_args(&self) -> RuntimeArgs {
+        runtime_args! {
+            "path" => self.path.clone(),
+            "amount_in" => self.amount_in,
+            "amount_out_min" => self.amount_out_min,
+            "tip" => self.tip
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct CancelOrder {
+    pub order_id: u64,
+}
+
+impl RouterCall for CancelOrder {
+    fn entry_point(&self) -> &'static str {
+        "cancel_order"
+    }
+
+    fn runtime_args(&self) -> RuntimeArgs {
+        runtime_args! {
+            "order_id" => self.order_id
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ExecuteOrder {
+    pub order_id: u64,
+}
+
+impl RouterCall for ExecuteOrder {
+    fn entry_point(&self) -> &'static str {
+        "execute_order"
+    }
+
+    fn runtime_args(&self) -> RuntimeArgs {
+        runtime_args! {
+            "order_id" => self.order_id
+        }
+    }
+}
+
+/// Accounts for the escrow of up to `limit` of the orders placed before the router tracked
+/// escrows. Anyone may call it.
+#[derive(Clone, Debug)]
+pub struct MigrateEscrows {
+    pub limit: u64,
+}
+
+impl RouterCall for MigrateEscrows {
+    fn entry_point(&self) -> &'static str {
+        "migrate_escrows"
+    }
+
+    fn runtime_args(&self) -> RuntimeArgs {
+        runtime_args! {
+            "limit" => self.limit
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct GetOrder {
+    pub order_id: u64,
+}
+
+impl RouterCall for GetOrder {
+    fn entry_point(&self) -> &'static str {
+        "get_order"
+    }
+
+    fn runtime_args(&self) -> RuntimeArgs {
+        runtime_args! {
+            "order_id" => self.order_id
+        }
+    }
+}